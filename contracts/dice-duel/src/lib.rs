@@ -2,18 +2,43 @@
 
 //! # Dice Duel
 //!
-//! A two-player dice game where each player rolls two dice.
-//! The player with the highest total wins (ties go to player 1).
+//! A two-player dice game: each round both players roll their dice and the
+//! better roll wins (ties go to player 1).
+//!
+//! `DiceRules` (see `rules.rs`), chosen per session at `start_game`, set how
+//! many dice each player rolls, how many sides they have and how a roll is
+//! scored (`Scoring`); the default is two six-sided dice scored by their total.
+//!
+//! `Scoring::Pig` turns a session into a press-your-luck game instead: players
+//! take turns, every roll is resolved on its own and adds to the turn total,
+//! a 1 loses it, and `bank` keeps it. The first to bank `target` points wins.
+//! Each roll mixes in a secret from both players, committed with `commit_roll`
+//! before either is opened with `reveal_roll`, so nobody can tell how a roll
+//! lands before deciding to take it. A player who does not reveal within
+//! `REVEAL_TIMEOUT_LEDGERS` of the other forfeits the match.
+//!
+//! `best_of` sets the match length: with more than one round, both players
+//! roll again each round and the first to win a majority of the rounds wins
+//! the session. The default is a single round.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
+use game_kit::rematch;
+use game_kit::session_keys::{self, authorize_move};
+use game_kit::settlement::{self, credit_keeper, settlement_config};
+use game_kit::stakes;
+use game_kit::ttl::{self, extend_game_ttl, extend_instance_ttl, ttl_config, HISTORY_TTL_LEDGERS};
+use matchmaking::registry;
+use provably_fair::{dice_duel, Session, MAX_STRKEY_LEN};
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl,
-    contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Vec, contract, contractclient,
+    contracterror, contractimpl, contracttype, vec, xdr::ToXdr
 };
 
+mod rules;
+
 // Import GameHub contract interface
 // This allows us to call into the GameHub contract
 #[contractclient(name = "GameHubClient")]
@@ -39,21 +64,125 @@ pub trait GameHub {
 // Errors
 // ============================================================================
 
+/// Errors returned by the contract.
+///
+/// Codes are stable: clients match on them, so existing variants are never
+/// renumbered or reused and new failures get the next free code.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
+    /// No game exists for the session (never started, or expired)
     GameNotFound = 1,
+    /// The address is not one of the two players, or may not settle this game
+    /// (keepers only settle games whose stakes a matchmaker escrows)
     NotPlayer = 2,
+    /// The player already rolled this round
     AlreadyRolled = 3,
+    /// `reveal_winner` was called before both players rolled
     BothPlayersNotRolled = 4,
+    /// The game already has a winner
     GameAlreadyEnded = 5,
+    /// A TTL threshold exceeds its extension target, or a target exceeds the
+    /// network's maximum TTL
+    InvalidTtlConfig = 6,
+    /// No archived result exists for the session
+    MatchNotFound = 7,
+    /// A keeper tried to settle before `keeper_delay` ledgers passed
+    SettlementTooEarly = 8,
+    /// The keeper reward exceeds 500 basis points
+    InvalidSettlementConfig = 9,
+    /// `best_of` is even or above 15
+    InvalidBestOf = 10,
+    /// Dice count, sides or `Pig` target out of range
+    InvalidRules = 11,
+    /// It is the other player's turn in a `Pig` session
+    NotYourTurn = 12,
+    /// `bank` was called with no points rolled this turn
+    NothingToBank = 13,
+    /// The entry point does not apply to the session's scoring mode
+    WrongGameMode = 14,
+    /// Player 1 and player 2 are the same address
+    SelfPlay = 15,
+    /// The GameHub address is missing from instance storage
+    GameHubNotSet = 16,
+    /// The admin address is missing from instance storage
+    AdminNotSet = 17,
+    /// The caller of `start_matched_game` is not a registered matchmaker
+    NotMatchmaker = 18,
+    /// A session key is a player of the session, expires in the past, is
+    /// scoped to an unknown entry point or already signs for the other player
+    InvalidSessionKey = 19,
+    /// The session key's `expires_at` ledger has been reached
+    SessionKeyExpired = 20,
+    /// The session key is not scoped to this entry point
+    SessionKeyNotAllowed = 21,
+    /// The player has no session key registered for the session
+    SessionKeyNotFound = 22,
+    /// A rematch was proposed before the game ended
+    GameNotEnded = 23,
+    /// No rematch was offered to the player for the session
+    RematchNotFound = 24,
+    /// The session was already rematched; only the latest session of a series can be
+    RematchAlreadyPlayed = 25,
+    /// The session ID is already used by another game
+    SessionIdTaken = 26,
+    /// Doubling the stakes overflows
+    StakeOverflow = 27,
+    /// A player committed zero or negative points
+    InvalidStake = 28,
+    /// A player's points are outside the admin's stake limits
+    StakeOutOfRange = 29,
+    /// The minimum stake is below 1 or above the maximum, or the maximum is
+    /// above `i128::MAX / 2`
+    InvalidStakeLimits = 30,
+    /// The player already committed to the pending `Pig` roll
+    RollAlreadyCommitted = 31,
+    /// `reveal_roll` was called before both players committed to the roll
+    RollNotCommitted = 32,
+    /// The secret does not open the player's roll commitment
+    SecretMismatch = 33,
+    /// The player already revealed their secret for the pending roll
+    RollAlreadyRevealed = 34,
+    /// `bank` was called after the player committed to another roll
+    RollPending = 35,
+    /// The pending roll's reveal deadline has passed
+    RevealDeadlinePassed = 36,
+    /// `reveal_winner` was called on a `Pig` roll before its reveal deadline
+    RevealDeadlineNotReached = 37,
+}
+
+impl From<game_kit::Error> for Error {
+    fn from(error: game_kit::Error) -> Self {
+        match error {
+            game_kit::Error::InvalidTtlConfig => Error::InvalidTtlConfig,
+            game_kit::Error::InvalidSessionKey => Error::InvalidSessionKey,
+            game_kit::Error::SessionKeyExpired => Error::SessionKeyExpired,
+            game_kit::Error::SessionKeyNotAllowed => Error::SessionKeyNotAllowed,
+            game_kit::Error::RematchNotFound => Error::RematchNotFound,
+            game_kit::Error::RematchAlreadyPlayed => Error::RematchAlreadyPlayed,
+            game_kit::Error::StakeOverflow => Error::StakeOverflow,
+            game_kit::Error::InvalidStake => Error::InvalidStake,
+            game_kit::Error::StakeOutOfRange => Error::StakeOutOfRange,
+            game_kit::Error::InvalidStakeLimits => Error::InvalidStakeLimits,
+            game_kit::Error::NotPlayer => Error::NotPlayer,
+            game_kit::Error::SettlementTooEarly => Error::SettlementTooEarly,
+            game_kit::Error::InvalidSettlementConfig => Error::InvalidSettlementConfig,
+        }
+    }
 }
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
+pub use game_kit::rematch::{RematchOffer, Series};
+pub use game_kit::session_keys::SessionKey;
+pub use game_kit::settlement::{SettlementConfig, SettlementMode};
+pub use game_kit::stakes::StakeLimits;
+pub use game_kit::ttl::TtlConfig;
+pub use matchmaking::{KeeperReward, SessionInfo, Settlement};
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
@@ -63,11 +192,134 @@ pub struct Game {
     pub player2_points: i128,
     pub player1_rolled: bool,
     pub player2_rolled: bool,
-    pub player1_die1: Option<u32>,
-    pub player1_die2: Option<u32>,
-    pub player2_die1: Option<u32>,
-    pub player2_die2: Option<u32>,
+    /// Dice of the last revealed round (empty before the first reveal)
+    pub player1_dice: Vec<u32>,
+    pub player2_dice: Vec<u32>,
     pub winner: Option<Address>,
+    /// Ledger of the last start or roll (keepers wait `keeper_delay` after it)
+    pub last_move_ledger: u32,
+    /// Whether a matchmaker (lobby or tournament) escrows the stakes and pays
+    /// keeper rewards; keepers may only settle such games
+    pub escrowed: bool,
+    /// Match length; the first player to win a majority of rounds wins (1 = single roll)
+    pub best_of: u32,
+    /// Current round, starting at 1
+    pub round: u32,
+    pub player1_wins: u32,
+    pub player2_wins: u32,
+    /// Every revealed round, oldest first
+    pub rounds: Vec<DiceRound>,
+    /// Keeper rewards paid out of the stake so far
+    pub keeper_rewards: i128,
+    /// Dice count, sides and scoring agreed at `start_game`
+    pub rules: DiceRules,
+    /// Points banked this round by each player (`Scoring::Pig` only)
+    pub player1_banked: u32,
+    pub player2_banked: u32,
+    /// Unbanked points of the current turn, lost when a 1 is rolled
+    pub turn_total: u32,
+    /// Whose turn it is (`Scoring::Pig` only; player 1 opens the match)
+    pub player1_turn: bool,
+    /// Rolls made so far this round, mixed into each roll's seed
+    pub rolls: u32,
+    /// Commitments (`keccak256` of a secret) to the pending roll (`Scoring::Pig` only)
+    pub player1_roll_hash: Option<BytesN<32>>,
+    pub player2_roll_hash: Option<BytesN<32>>,
+    /// Secrets revealed so far for the pending roll
+    pub player1_roll_secret: Option<BytesN<32>>,
+    pub player2_roll_secret: Option<BytesN<32>>,
+    /// Ledger after which a player who has not revealed the pending roll
+    /// forfeits the match (set by its first reveal)
+    pub reveal_deadline: Option<u32>,
+}
+
+/// Dice and result of one revealed round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiceRound {
+    pub round: u32,
+    pub player1_dice: Vec<u32>,
+    pub player2_dice: Vec<u32>,
+    /// Scores under the session's `Scoring` (points banked for `Pig`); ties go to player 1
+    pub player1_score: u32,
+    pub player2_score: u32,
+    pub player1_won: bool,
+}
+
+/// How a roll is scored; the higher score wins the round
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Scoring {
+    /// Total of all dice
+    Sum = 0,
+    /// Highest single die, ties broken by the total
+    Highest = 1,
+    /// Poker-dice hands (pair up to five of a kind), ties broken by the total
+    PokerDice = 2,
+    /// Total of all dice, doubled when every die shows the same face
+    DoublesBonus = 3,
+    /// Press your luck: roll as often as you dare on your turn and `bank` the
+    /// total; a roll with any 1 loses it. The first to bank `target` points wins
+    Pig = 4,
+}
+
+/// Dice rules for a session, chosen at `start_game`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DiceRules {
+    /// Dice rolled by each player per round (1-10)
+    pub dice: u32,
+    /// Sides per die (2-100)
+    pub sides: u32,
+    pub scoring: Scoring,
+    /// Points to bank to win a round of `Pig` (1-1000; ignored by other modes)
+    pub target: u32,
+}
+
+/// Archived result of a finished game, kept in persistent storage
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchResult {
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_won: bool,
+    pub winner: Address,
+    /// Points paid back to each player under the matched-stakes rule, less the
+    /// keeper rewards; with them they add up to both stakes. Informational only:
+    /// the Game Hub is told just the winner and settles the points itself.
+    pub player1_payout: i128,
+    pub player2_payout: i128,
+    /// Match length and rounds won by each player
+    pub best_of: u32,
+    pub player1_wins: u32,
+    pub player2_wins: u32,
+    /// Keccak-256 of the final game state (XDR encoded)
+    pub state_hash: BytesN<32>,
+    /// Ledger sequence at which the game ended
+    pub end_ledger: u32,
+    /// Third party that settled the final round, if it was not one of the players
+    pub keeper: Option<Address>,
+    /// Points credited to keepers over the whole match
+    pub keeper_reward: i128,
+}
+
+/// Public inputs a session's dice are derived from, for recomputing them with
+/// the `provably-fair` crate (see `get_seed_material`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeedMaterial {
+    pub session_id: u32,
+    /// Player addresses as the strkey text the seeds hash
+    pub player1: String,
+    pub player2: String,
+    /// Current round; revealed rounds are listed in `Game::rounds`
+    pub round: u32,
+    /// Rolls made so far this round (`Scoring::Pig` only)
+    pub rolls: u32,
 }
 
 #[contracttype]
@@ -76,26 +328,465 @@ pub enum DataKey {
     Game(u32),
     GameHubAddress,
     Admin,
+    MatchResult(u32),              // session_id -> MatchResult
+    PlayerMatchCount(Address),     // player -> number of archived matches
+    PlayerMatch(Address, u32),     // (player, index) -> session_id
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+/// Longest match `start_game` accepts
+const MAX_BEST_OF: u32 = 15;
+
+/// Rounds a player must win to take a best-of-`best_of` match
+fn majority(best_of: u32) -> u32 {
+    best_of / 2 + 1
+}
+
+/// Customary `Pig` target, used by the default rules
+const PIG_TARGET: u32 = 100;
+
+/// Ledgers a player has to reveal a `Pig` roll after the other revealed
+/// (1 day = 24 * 60 * 60 / 5 = 17,280 ledgers)
+pub const REVEAL_TIMEOUT_LEDGERS: u32 = 17_280;
+
+/// Classic rules, used when no rules were given: two six-sided dice, highest total
+fn default_dice_rules() -> DiceRules {
+    DiceRules {
+        dice: 2,
+        sides: 6,
+        scoring: Scoring::Sum,
+        target: PIG_TARGET,
+    }
+}
+
+/// Dice are drawn from public seed material, so every roll of a session can be
+/// computed before the first move (see `SessionInfo::predetermined`); only
+/// `Pig` rolls also depend on secrets the players commit to
+fn outcome_predetermined(rules: &DiceRules) -> bool {
+    rules.scoring != Scoring::Pig
+}
+
+/// keccak256 on the host, for the outcome derivations of `provably_fair`
+struct HostKeccak<'a>(&'a Env);
+
+impl provably_fair::Keccak256 for HostKeccak<'_> {
+    fn hash(&self, parts: &[&[u8]]) -> [u8; 32] {
+        let mut preimage = Bytes::new(self.0);
+        for part in parts {
+            preimage.extend_from_slice(part);
+        }
+        self.0.crypto().keccak256(&preimage).to_array()
+    }
+}
+
+/// Seed of the current round, or of its latest roll in a `Pig` session, which
+/// passes the secrets both players revealed for it (see `provably_fair::dice_duel`).
+///
+/// Seed components (all deterministic and identical between sim/submit):
+/// 1. Session ID - unique per game
+/// 2. Player addresses - both players contribute
+/// 3. Round number - fresh dice every round of a match
+/// 4. Roll count (`Pig` only) - fresh dice every roll of a round
+/// 5. Both players' secrets (`Pig` only) - unknown until both committed
+///
+/// Note: We do NOT include ledger sequence or timestamp because those differ
+/// between simulation and submission, which would cause different winners.
+fn round_seed(
+    env: &Env,
+    session_id: u32,
+    game: &Game,
+    pig_secrets: Option<[&[u8; 32]; 2]>,
+) -> [u8; 32] {
+    let player1 = game.player1.to_string().to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let player2 = game.player2.to_string().to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let session = Session {
+        session_id,
+        player1: player1.as_slice(),
+        player2: player2.as_slice(),
+    };
+    match pig_secrets {
+        Some(secrets) => {
+            dice_duel::pig_roll_seed(&HostKeccak(env), &session, game.round, game.rolls, secrets)
+        }
+        None => dice_duel::round_seed(&HostKeccak(env), &session, game.round),
+    }
+}
+
+/// Roll one player's dice from a round seed
+fn roll_dice(env: &Env, seed: &[u8; 32], player: u8, rules: &DiceRules) -> Vec<u32> {
+    let mut dice = Vec::new(env);
+    for face in dice_duel::roll(&HostKeccak(env), seed, player, rules.dice, rules.sides) {
+        dice.push_back(face);
+    }
+    dice
+}
+
+/// Record a decided round, then either set up the next round or, once a player
+/// has a majority of `best_of`, end the session in GameHub and archive it.
+/// Returns the round's winner.
+fn finish_round(
+    env: &Env,
+    session_id: u32,
+    mut game: Game,
+    round: DiceRound,
+    keeper: Option<Address>,
+) -> Result<Address, Error> {
+    let key = DataKey::Game(session_id);
+    let winner = if round.player1_won {
+        game.player1_wins += 1;
+        game.player1.clone()
+    } else {
+        game.player2_wins += 1;
+        game.player2.clone()
+    };
+    game.rounds.push_back(round);
+
+    // Nobody has a majority yet: both players roll again, and in `Pig` the
+    // round's loser opens the next one
+    let needed = majority(game.best_of);
+    if game.player1_wins < needed && game.player2_wins < needed {
+        game.round += 1;
+        game.player1_rolled = false;
+        game.player2_rolled = false;
+        game.player1_banked = 0;
+        game.player2_banked = 0;
+        game.turn_total = 0;
+        game.player1_turn = winner != game.player1;
+        game.rolls = 0;
+        env.storage().temporary().set(&key, &game);
+        extend_game_ttl(env, &key);
+        return Ok(winner);
+    }
+
+    // Update game with winner (this marks the game as ended)
+    game.winner = Some(winner.clone());
+    env.storage().temporary().set(&key, &game);
+    extend_game_ttl(env, &key);
+
+    // Get GameHub address
+    let game_hub_addr: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .ok_or(Error::GameHubNotSet)?;
+
+    // Create GameHub client
+    let game_hub = GameHubClient::new(env, &game_hub_addr);
+
+    // Call GameHub to end the session
+    // This unlocks points and updates standings
+    // Event emitted by the Game Hub contract (GameEnded)
+    let player1_won = winner == game.player1; // true if player1 won, false if player2 won
+    game_hub.end_game(&session_id, &player1_won);
+
+    // Archive the result so it outlives the temporary game entry
+    archive_match(env, session_id, &game, player1_won, keeper);
+
+    Ok(winner)
+}
+
+/// Score a roll under the session's rules (see `rules::score`)
+fn score_dice(rules: &DiceRules, dice: &Vec<u32>) -> u32 {
+    let mut faces = [0u32; rules::MAX_DICE as usize];
+    for (slot, face) in faces.iter_mut().zip(dice.iter()) {
+        *slot = face;
+    }
+    rules::score(rules, &faces[..dice.len() as usize])
+}
+
+/// Roll the dice of the player whose turn it is in a `Pig` session, once both
+/// players revealed their secrets for the roll: the dice are added to the turn
+/// total, or a 1 loses it and passes the turn. Clears the roll's commitments.
+fn resolve_pig_roll(env: &Env, session_id: u32, game: &mut Game, secrets: [&[u8; 32]; 2]) {
+    // Each roll of the round gets its own seed
+    game.rolls += 1;
+    let player1_turn = game.player1_turn;
+    let seed = round_seed(env, session_id, game, Some(secrets));
+    let dice = roll_dice(env, &seed, if player1_turn { 1 } else { 2 }, &game.rules);
+
+    // A 1 scores nothing: the turn total is lost and the turn passes
+    let points = score_dice(&game.rules, &dice);
+    if points == 0 {
+        game.turn_total = 0;
+        game.player1_turn = !player1_turn;
+    } else {
+        game.turn_total += points;
+    }
+    if player1_turn {
+        game.player1_dice = dice;
+    } else {
+        game.player2_dice = dice;
+    }
+
+    game.player1_roll_hash = None;
+    game.player2_roll_hash = None;
+    game.player1_roll_secret = None;
+    game.player2_roll_secret = None;
+    game.reveal_deadline = None;
 }
 
 // ============================================================================
-// Storage TTL Management
+// Match History
 // ============================================================================
-// TTL (Time To Live) ensures game data doesn't expire unexpectedly
-// Games are stored in temporary storage with a minimum 30-day retention
+// Finished games are archived in persistent storage so results outlive the
+// temporary game entry (disputes, statistics)
 
-/// TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
-/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
-const GAME_TTL_LEDGERS: u32 = 518_400;
+/// Maximum number of results returned by one `get_player_matches` call
+const MAX_HISTORY_PAGE: u32 = 50;
+
+/// Archive the final state of a game and index it under both players.
+/// Must be called once, after the winner has been set.
+fn archive_match(
+    env: &Env,
+    session_id: u32,
+    game: &Game,
+    player1_won: bool,
+    keeper: Option<Address>,
+) {
+    let winner = if player1_won {
+        game.player1.clone()
+    } else {
+        game.player2.clone()
+    };
+    let (player1_payout, player2_payout) = stakes::settle_stakes(
+        game.player1_points,
+        game.player2_points,
+        player1_won,
+        game.keeper_rewards,
+    );
+    let result = MatchResult {
+        session_id,
+        player1: game.player1.clone(),
+        player2: game.player2.clone(),
+        player1_points: game.player1_points,
+        player2_points: game.player2_points,
+        player1_won,
+        winner,
+        player1_payout,
+        player2_payout,
+        best_of: game.best_of,
+        player1_wins: game.player1_wins,
+        player2_wins: game.player2_wins,
+        state_hash: env.crypto().keccak256(&game.clone().to_xdr(env)).into(),
+        end_ledger: env.ledger().sequence(),
+        keeper,
+        keeper_reward: game.keeper_rewards,
+    };
+
+    let key = DataKey::MatchResult(session_id);
+    env.storage().persistent().set(&key, &result);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+
+    index_player_match(env, &game.player1, session_id);
+    index_player_match(env, &game.player2, session_id);
+    rematch::record_series_result(env, session_id, player1_won);
+}
+
+/// Append a session to a player's match index
+fn index_player_match(env: &Env, player: &Address, session_id: u32) {
+    let count_key = DataKey::PlayerMatchCount(player.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let entry_key = DataKey::PlayerMatch(player.clone(), count);
+    env.storage().persistent().set(&entry_key, &session_id);
+    env.storage().persistent().set(&count_key, &(count + 1));
+
+    env.storage()
+        .persistent()
+        .extend_ttl(&entry_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+}
 
 // ============================================================================
-// Helper Functions
+// Settlement
+// ============================================================================
+// Players can always settle their own game. In keeper mode anyone else can
+// settle it too, once the players have had `keeper_delay` ledgers to do it
+// themselves, and earns a share of the pot that the contract escrowing the
+// stakes pays out (see `game_kit::settlement`).
+
+/// Check that `caller` may settle `game` now.
+/// Returns the keeper to reward, or `None` when one of the players settles.
+fn authorize_settlement(
+    env: &Env,
+    config: &SettlementConfig,
+    game: &Game,
+    caller: &Address,
+) -> Result<Option<Address>, Error> {
+    let is_player = *caller == game.player1 || *caller == game.player2;
+    settlement::authorize_settlement(
+        env,
+        config,
+        caller,
+        is_player,
+        game.escrowed,
+        game.last_move_ledger,
+    )
+    .map_err(Error::from)
+}
+
+/// Keeper reward for settling one round: the configured share of the
+/// matched pot, spread over the match length
+fn keeper_reward(config: &SettlementConfig, game: &Game) -> i128 {
+    let pot = 2 * game.player1_points.min(game.player2_points);
+    settlement::keeper_reward(config, pot, game.best_of)
+}
+
+// ============================================================================
+// Stakes
+// ============================================================================
+// Both players must commit a positive number of points within the admin's
+// limits. Unequal stakes are matched at the smaller one: the winner takes that
+// much from the loser less any keeper rewards, and the rest of the larger stake
+// goes back to its owner (see `game_kit::stakes`).
+
+/// Largest stake the admin can allow, so that two stakes always add up
+const MAX_STAKE: i128 = i128::MAX / 2;
+
+// ============================================================================
+// Matchmaking
+// ============================================================================
+// Contracts registered by the admin (a lobby, a tournament) may start games
+// for players who gave their consent to the matchmaker instead of signing the
+// start themselves. Both entry points share `open_game` once checks pass.
+
+/// Register the session with the GameHub and store the new game.
+/// Players, match length and rules must already be validated and authorized;
+/// the stakes are checked against the stake limits here. `escrowed` marks
+/// games a registered matchmaker started.
+#[allow(clippy::too_many_arguments)]
+fn open_game(
+    env: &Env,
+    session_id: u32,
+    player1: Address,
+    player2: Address,
+    player1_points: i128,
+    player2_points: i128,
+    best_of: u32,
+    rules: DiceRules,
+    escrowed: bool,
+) -> Result<(), Error> {
+    if session_id_taken(env, session_id) {
+        return Err(Error::SessionIdTaken);
+    }
+    let limits = stakes::stake_limits(env, MAX_STAKE);
+    stakes::validate_stake(&limits, player1_points)?;
+    stakes::validate_stake(&limits, player2_points)?;
+
+    // Get GameHub address
+    let game_hub_addr: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .ok_or(Error::GameHubNotSet)?;
+
+    // Create GameHub client
+    let game_hub = GameHubClient::new(env, &game_hub_addr);
+
+    // Call the Game Hub to start the session and lock points
+    // This requires THIS contract's authorization (env.current_contract_address())
+    game_hub.start_game(
+        &env.current_contract_address(),
+        &session_id,
+        &player1,
+        &player2,
+        &player1_points,
+        &player2_points,
+    );
+
+    // Create game (dice not rolled yet - will be generated in reveal_winner)
+    let game = Game {
+        player1: player1.clone(),
+        player2: player2.clone(),
+        player1_points,
+        player2_points,
+        player1_rolled: false,
+        player2_rolled: false,
+        player1_dice: Vec::new(env),
+        player2_dice: Vec::new(env),
+        winner: None,
+        last_move_ledger: env.ledger().sequence(),
+        escrowed,
+        best_of,
+        round: 1,
+        player1_wins: 0,
+        player2_wins: 0,
+        rounds: Vec::new(env),
+        keeper_rewards: 0,
+        rules,
+        player1_banked: 0,
+        player2_banked: 0,
+        turn_total: 0,
+        player1_turn: true,
+        rolls: 0,
+        player1_roll_hash: None,
+        player2_roll_hash: None,
+        player1_roll_secret: None,
+        player2_roll_secret: None,
+        reveal_deadline: None,
+    };
+
+    // Store game in temporary storage with 30-day TTL
+    let game_key = DataKey::Game(session_id);
+    env.storage().temporary().set(&game_key, &game);
+
+    // Set TTL to ensure game is retained for at least 30 days
+    extend_game_ttl(env, &game_key);
+
+    // Event emitted by GameHub contract (GameStarted)
+
+    Ok(())
+}
+
+/// Require `matchmaker`'s authorization and check the admin registered it
+fn authorize_matchmaker(env: &Env, matchmaker: &Address) -> Result<(), Error> {
+    if !registry::authorize_matchmaker(env, matchmaker) {
+        return Err(Error::NotMatchmaker);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Session Keys
+// ============================================================================
+// A player may register an ephemeral key for one session so their frontend can
+// sign moves without a wallet prompt each time (see `game_kit::session_keys`).
+// Move entry points resolve a key to its player with `authorize_move`.
+
+/// Entry points a session key may sign (`roll`, and the `Pig` moves
+/// `commit_roll`, `reveal_roll` and `bank`)
+const SESSION_KEY_MOVES: [&str; 4] = ["roll", "commit_roll", "reveal_roll", "bank"];
+
+// ============================================================================
+// Rematches
 // ============================================================================
+// Once a game has ended either player may offer a rematch, which the other
+// accepts without a fresh two-party start. The rematch keeps the seats, match
+// length and dice rules and carries the stakes over (or doubles them).
+// Accepted rematches link their sessions into a series whose cumulative score
+// is kept in persistent storage next to the match history.
+
+/// Whether a game, live or archived, already uses `session_id`
+fn session_id_taken(env: &Env, session_id: u32) -> bool {
+    env.storage().temporary().has(&DataKey::Game(session_id))
+        || env.storage().persistent().has(&DataKey::MatchResult(session_id))
+}
 
-/// Roll a single die (1-6) using deterministic PRNG
-fn roll_die(env: &Env, seed: BytesN<32>) -> u32 {
-    env.prng().seed(seed.into());
-    env.prng().gen_range::<u64>(1..=6) as u32
+/// Stakes of a rematch of `game`, checked against the current stake limits
+fn rematch_stakes(env: &Env, game: &Game, double_stakes: bool) -> Result<(i128, i128), Error> {
+    let player1_points = stakes::rematch_stake(game.player1_points, double_stakes)?;
+    let player2_points = stakes::rematch_stake(game.player2_points, double_stakes)?;
+    let limits = stakes::stake_limits(env, MAX_STAKE);
+    stakes::validate_stake(&limits, player1_points)?;
+    stakes::validate_stake(&limits, player2_points)?;
+    Ok((player1_points, player2_points))
 }
 
 // ============================================================================
@@ -118,6 +809,8 @@ impl DiceDuelContract {
         env.storage()
             .instance()
             .set(&DataKey::GameHubAddress, &game_hub);
+
+        extend_instance_ttl(&env);
     }
 
     /// Start a new game between two players with points.
@@ -132,6 +825,9 @@ impl DiceDuelContract {
     /// * `player2` - Address of second player
     /// * `player1_points` - Points amount committed by player 1
     /// * `player2_points` - Points amount committed by player 2
+    /// * `best_of` - Odd number of rounds in the match, up to 15 (single roll if `None`)
+    /// * `rules` - Dice count, sides and scoring (two six-sided dice, highest total if `None`)
+    #[allow(clippy::too_many_arguments)]
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -139,74 +835,272 @@ impl DiceDuelContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
+        best_of: Option<u32>,
+        rules: Option<DiceRules>,
     ) -> Result<(), Error> {
         // Prevent self-play: Player 1 and Player 2 must be different
         if player1 == player2 {
-            panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
+            return Err(Error::SelfPlay);
         }
 
-        // Require authentication from both players (they consent to committing points)
-        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
-        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
+        // A match needs an odd number of rounds so someone reaches a majority
+        let rounds = best_of.unwrap_or(1);
+        if rounds.is_multiple_of(2) || rounds > MAX_BEST_OF {
+            return Err(Error::InvalidBestOf);
+        }
+        let dice_rules = rules.clone().unwrap_or_else(default_dice_rules);
+        if !rules::is_valid(&dice_rules) {
+            return Err(Error::InvalidRules);
+        }
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
-
-        // Call the Game Hub to start the session and lock points
-        // This requires THIS contract's authorization (env.current_contract_address())
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+        extend_instance_ttl(&env);
 
-        // Create game (dice not rolled yet - will be generated in reveal_winner)
-        let game = Game {
-            player1: player1.clone(),
-            player2: player2.clone(),
+        // Require authentication from both players (they consent to committing points
+        // and, when given, to the match length and dice rules)
+        let mut player1_args = vec![&env, session_id.into_val(&env), player1_points.into_val(&env)];
+        let mut player2_args = vec![&env, session_id.into_val(&env), player2_points.into_val(&env)];
+        if let Some(best_of) = best_of {
+            player1_args.push_back(best_of.into_val(&env));
+            player2_args.push_back(best_of.into_val(&env));
+        }
+        if let Some(rules) = &rules {
+            player1_args.push_back(rules.into_val(&env));
+            player2_args.push_back(rules.into_val(&env));
+        }
+        player1.require_auth_for_args(player1_args);
+        player2.require_auth_for_args(player2_args);
+
+        open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
             player1_points,
             player2_points,
-            player1_rolled: false,
-            player2_rolled: false,
-            player1_die1: None,
-            player1_die2: None,
-            player2_die1: None,
-            player2_die2: None,
-            winner: None,
-        };
+            rounds,
+            dice_rules,
+            false,
+        )
+    }
+
+    /// Start a game on behalf of a registered matchmaker (see `set_matchmaker`).
+    /// The matchmaker collected both players' consent itself, so only its own
+    /// authorization is required. Plays a single round with the default dice.
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Registered matchmaker contract starting the game
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `player1` - Address of first player
+    /// * `player2` - Address of second player
+    /// * `player1_points` - Points amount committed by player 1
+    /// * `player2_points` - Points amount committed by player 2
+    pub fn start_matched_game(
+        env: Env,
+        matchmaker: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Result<(), Error> {
+        if player1 == player2 {
+            return Err(Error::SelfPlay);
+        }
+
+        extend_instance_ttl(&env);
+        authorize_matchmaker(&env, &matchmaker)?;
+
+        open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            1,
+            default_dice_rules(),
+            true,
+        )
+    }
+
+    /// Offer the other player a rematch of an ended game, replacing any offer
+    /// pending for it. The rematch is played under `new_session_id` with the
+    /// same seats, match length and dice rules.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - Address of the player proposing the rematch
+    /// * `new_session_id` - Unused session ID for the rematch
+    /// * `double_stakes` - Double both players' points instead of carrying them over
+    pub fn propose_rematch(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        new_session_id: u32,
+        double_stakes: bool,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
 
-        // Store game in temporary storage with 30-day TTL
         let game_key = DataKey::Game(session_id);
-        env.storage().temporary().set(&game_key, &game);
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&game_key)
+            .ok_or(Error::GameNotFound)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        if game.winner.is_none() {
+            return Err(Error::GameNotEnded);
+        }
+        rematch::check_rematchable(&env, session_id)?;
+        if session_id_taken(&env, new_session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+        rematch_stakes(&env, &game, double_stakes)?;
 
-        // Set TTL to ensure game is retained for at least 30 days
-        env.storage()
+        let offer = RematchOffer {
+            proposer: player,
+            new_session_id,
+            double_stakes,
+        };
+        rematch::offer_rematch(&env, session_id, &offer);
+        extend_game_ttl(&env, &game_key);
+
+        Ok(())
+    }
+
+    /// Accept the rematch the other player offered, starting it in the Game Hub
+    /// with the stakes of the offer. Returns the session ID of the rematch.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - Address of the player accepting the rematch
+    pub fn accept_rematch(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let game: Game = env
+            .storage()
             .temporary()
-            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let offer = rematch::take_rematch(&env, session_id, &player)?;
+        if session_id_taken(&env, offer.new_session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+        let (player1_points, player2_points) = rematch_stakes(&env, &game, offer.double_stakes)?;
 
-        // Event emitted by GameHub contract (GameStarted)
+        open_game(
+            &env,
+            offer.new_session_id,
+            game.player1.clone(),
+            game.player2.clone(),
+            player1_points,
+            player2_points,
+            game.best_of,
+            game.rules.clone(),
+            false,
+        )?;
+        let player1_won = game.winner.as_ref() == Some(&game.player1);
+        rematch::link_rematch(
+            &env,
+            session_id,
+            offer.new_session_id,
+            &game.player1,
+            &game.player2,
+            player1_won,
+        );
+
+        Ok(offer.new_session_id)
+    }
+
+    /// Register an ephemeral session key that may sign `player`'s moves in
+    /// this session, replacing any key registered before. The key's address is
+    /// then passed in place of the player's to the move entry points.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player delegating their moves
+    /// * `key` - Address of the session key
+    /// * `functions` - Entry points the key may call (`roll`, `bank`); empty for all
+    /// * `expires_at` - Ledger sequence from which the key is refused, or `None`
+    ///   to keep it for the whole session
+    pub fn register_session_key(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        key: Address,
+        functions: Vec<Symbol>,
+        expires_at: Option<u32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
 
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let is_player = key == game.player1 || key == game.player2;
+        session_keys::register_session_key(
+            &env,
+            session_id,
+            player,
+            key,
+            functions,
+            expires_at,
+            is_player,
+            &SESSION_KEY_MOVES,
+        )?;
         Ok(())
     }
 
-    /// Commit a roll for the current game.
-    /// Both players must roll before the winner can be revealed.
+    /// Revoke `player`'s session key; moves must be signed by the player again.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player rolling the dice
-    pub fn roll(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+    /// * `player` - Address of the player who registered the key
+    pub fn revoke_session_key(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
         player.require_auth();
+        extend_instance_ttl(&env);
+
+        if !session_keys::remove_session_key(&env, session_id, &player) {
+            return Err(Error::SessionKeyNotFound);
+        }
+        Ok(())
+    }
+
+    /// Get the session key `player` registered for a session, if any.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    pub fn get_session_key(env: Env, session_id: u32, player: Address) -> Option<SessionKey> {
+        session_keys::session_key(&env, session_id, player)
+    }
+
+    /// Commit a roll for the current round.
+    /// Both players must roll before the round can be revealed.
+    ///
+    /// `Scoring::Pig` sessions roll with `commit_roll` and `reveal_roll` instead.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player rolling the dice, or of their session key
+    pub fn roll(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        let player = authorize_move(&env, session_id, player, "roll")?;
+        extend_instance_ttl(&env);
 
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
@@ -221,6 +1115,11 @@ impl DiceDuelContract {
             return Err(Error::GameAlreadyEnded);
         }
 
+        // Press-your-luck sessions roll through `commit_roll` and `reveal_roll`
+        if game.rules.scoring == Scoring::Pig {
+            return Err(Error::WrongGameMode);
+        }
+
         // Update roll commitment for the appropriate player
         if player == game.player1 {
             if game.player1_rolled {
@@ -235,23 +1134,242 @@ impl DiceDuelContract {
         } else {
             return Err(Error::NotPlayer);
         }
+        game.last_move_ledger = env.ledger().sequence();
 
         // Store updated game in temporary storage
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
+
+        Ok(())
+    }
+
+    /// Commit to the next roll of a `Scoring::Pig` session with the hash of a
+    /// secret only the player knows. Both players commit to every roll; the
+    /// dice are drawn from both secrets once `reveal_roll` opened them.
+    ///
+    /// For the player whose turn it is, committing is the decision to roll:
+    /// they cannot `bank` until the roll is resolved. The other player may
+    /// commit at any time, and a commitment made before a player's turn counts
+    /// for their first roll.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player, or of their session key
+    /// * `roll_hash` - `keccak256` of the player's 32-byte secret for this roll
+    pub fn commit_roll(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        roll_hash: BytesN<32>,
+    ) -> Result<(), Error> {
+        let player = authorize_move(&env, session_id, player, "commit_roll")?;
+        extend_instance_ttl(&env);
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.rules.scoring != Scoring::Pig {
+            return Err(Error::WrongGameMode);
+        }
+        let commitment = if player == game.player1 {
+            &mut game.player1_roll_hash
+        } else if player == game.player2 {
+            &mut game.player2_roll_hash
+        } else {
+            return Err(Error::NotPlayer);
+        };
+        if commitment.is_some() {
+            return Err(Error::RollAlreadyCommitted);
+        }
+        *commitment = Some(roll_hash);
+        game.last_move_ledger = env.ledger().sequence();
+
+        env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
+
+        Ok(())
+    }
+
+    /// Reveal the secret behind a `commit_roll` once both players committed to
+    /// the roll. The second reveal rolls the dice of the player whose turn it
+    /// is: they are added to the turn total, or a 1 loses the turn total and
+    /// passes the turn.
+    ///
+    /// The first reveal sets a deadline `REVEAL_TIMEOUT_LEDGERS` away; past it
+    /// the roll can no longer be revealed and `reveal_winner` awards the match
+    /// to the player who revealed.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player, or of their session key
+    /// * `secret` - The secret whose `keccak256` the player committed
+    pub fn reveal_roll(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        secret: BytesN<32>,
+    ) -> Result<(), Error> {
+        let player = authorize_move(&env, session_id, player, "reveal_roll")?;
+        extend_instance_ttl(&env);
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.rules.scoring != Scoring::Pig {
+            return Err(Error::WrongGameMode);
+        }
+        let player1 = player == game.player1;
+        if !player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        if game.player1_roll_hash.is_none() || game.player2_roll_hash.is_none() {
+            return Err(Error::RollNotCommitted);
+        }
+        let now = env.ledger().sequence();
+        if game.reveal_deadline.is_some_and(|deadline| now > deadline) {
+            return Err(Error::RevealDeadlinePassed);
+        }
+
+        let (commitment, revealed) = if player1 {
+            (&game.player1_roll_hash, &mut game.player1_roll_secret)
+        } else {
+            (&game.player2_roll_hash, &mut game.player2_roll_secret)
+        };
+        if revealed.is_some() {
+            return Err(Error::RollAlreadyRevealed);
+        }
+        let hash: BytesN<32> = env.crypto().keccak256(&secret.clone().into()).into();
+        if commitment.as_ref() != Some(&hash) {
+            return Err(Error::SecretMismatch);
+        }
+        *revealed = Some(secret);
+        game.last_move_ledger = now;
+
+        match (&game.player1_roll_secret, &game.player2_roll_secret) {
+            (Some(secret1), Some(secret2)) => {
+                let secrets = (secret1.to_array(), secret2.to_array());
+                resolve_pig_roll(&env, session_id, &mut game, [&secrets.0, &secrets.1]);
+            }
+            _ => game.reveal_deadline = Some(now + REVEAL_TIMEOUT_LEDGERS),
+        }
+
+        env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
+
+        Ok(())
+    }
+
+    /// Bank the turn total in a `Scoring::Pig` session and pass the turn.
+    /// Banking `target` points wins the round; the match continues as with
+    /// `reveal_winner` until a player has a majority of `best_of`.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player whose turn it is, or of their session key
+    pub fn bank(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        let player = authorize_move(&env, session_id, player, "bank")?;
+        extend_instance_ttl(&env);
+
+        let key = DataKey::Game(session_id);
+        let mut game: Game = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::GameNotFound)?;
+
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if game.rules.scoring != Scoring::Pig {
+            return Err(Error::WrongGameMode);
+        }
+        let player1_turn = player == game.player1;
+        if !player1_turn && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        if player1_turn != game.player1_turn {
+            return Err(Error::NotYourTurn);
+        }
+        let committed = if player1_turn {
+            game.player1_roll_hash.is_some()
+        } else {
+            game.player2_roll_hash.is_some()
+        };
+        if committed {
+            return Err(Error::RollPending);
+        }
+        if game.turn_total == 0 {
+            return Err(Error::NothingToBank);
+        }
+
+        let banked = if player1_turn {
+            game.player1_banked += game.turn_total;
+            game.player1_banked
+        } else {
+            game.player2_banked += game.turn_total;
+            game.player2_banked
+        };
+        game.turn_total = 0;
+        game.player1_turn = !player1_turn;
+        game.last_move_ledger = env.ledger().sequence();
+
+        if banked >= game.rules.target {
+            let round = DiceRound {
+                round: game.round,
+                player1_dice: game.player1_dice.clone(),
+                player2_dice: game.player2_dice.clone(),
+                player1_score: game.player1_banked,
+                player2_score: game.player2_banked,
+                player1_won: player1_turn,
+            };
+            finish_round(&env, session_id, game, round, None)?;
+            return Ok(());
+        }
+
+        env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
 
         Ok(())
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
+    /// Reveal the current round and, once a player has won the match, submit the
+    /// outcome to GameHub.
     /// Can only be called after both players have rolled.
-    /// This generates dice rolls for both players, determines the winner, and ends the session.
+    /// This generates dice rolls for both players and scores the round. If neither
+    /// player has a majority of `best_of` yet, the rolls are reset for the next round;
+    /// otherwise the session ends.
+    ///
+    /// Who may call this depends on the `SettlementConfig`: the players always can;
+    /// in keeper mode anyone else can too after `keeper_delay` ledgers, and earns
+    /// `keeper_reward_bps` of the matched pot, spread over the match's rounds.
+    ///
+    /// `Scoring::Pig` rounds are decided by `bank` instead; in those sessions this
+    /// only settles a roll left unrevealed past its deadline (see `reveal_roll`),
+    /// awarding the match to the player who revealed.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
+    /// * `caller` - Address settling the round (player or keeper)
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `Address` - Address of the round's winner; the match winner once `winner` is set
+    pub fn reveal_winner(env: Env, session_id: u32, caller: Address) -> Result<Address, Error> {
+        extend_instance_ttl(&env);
+
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -265,80 +1383,94 @@ impl DiceDuelContract {
             return Ok(winner.clone());
         }
 
+        // `Pig` rounds are decided by `bank`; only a roll left unrevealed past
+        // its deadline is settled here
+        if game.rules.scoring == Scoring::Pig {
+            let deadline = game.reveal_deadline.ok_or(Error::WrongGameMode)?;
+            if env.ledger().sequence() <= deadline {
+                return Err(Error::RevealDeadlineNotReached);
+            }
+            let settlement = settlement_config(&env);
+            let keeper = authorize_settlement(&env, &settlement, &game, &caller)?;
+
+            // Withholding a reveal forfeits the match: the player who revealed
+            // is credited every round they still needed
+            let player1_won = game.player1_roll_secret.is_some();
+            let needed = majority(game.best_of);
+            if player1_won {
+                game.player1_wins = needed - 1;
+            } else {
+                game.player2_wins = needed - 1;
+            }
+            let round = DiceRound {
+                round: game.round,
+                player1_dice: game.player1_dice.clone(),
+                player2_dice: game.player2_dice.clone(),
+                player1_score: game.player1_banked,
+                player2_score: game.player2_banked,
+                player1_won,
+            };
+            if let Some(keeper) = &keeper {
+                let reward = keeper_reward(&settlement, &game);
+                game.keeper_rewards += reward;
+                credit_keeper(&env, session_id, keeper, reward);
+            }
+            return finish_round(&env, session_id, game, round, keeper);
+        }
+
         // Check both players have rolled
         if !game.player1_rolled || !game.player2_rolled {
             return Err(Error::BothPlayersNotRolled);
         }
 
-        // Generate deterministic dice rolls (1-6)
-        // Seed components (all deterministic and identical between sim/submit):
-        // 1. Session ID - unique per game
-        // 2. Player addresses - both players contribute
-        //
-        // Note: We do NOT include ledger sequence or timestamp because those differ
-        // between simulation and submission, which would cause different winners.
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-        seed_bytes.append(&game.player1.to_string().to_bytes());
-        seed_bytes.append(&game.player2.to_string().to_bytes());
-        let base_seed = env.crypto().keccak256(&seed_bytes);
-
-        // Roll dice for both players using unique seeds
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[1, 1]));
-        let player1_die1 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[1, 2]));
-        let player1_die2 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[2, 1]));
-        let player2_die1 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        let mut roll_seed_bytes = Bytes::new(&env);
-        roll_seed_bytes.append(&Bytes::from(base_seed.clone()));
-        roll_seed_bytes.append(&Bytes::from_array(&env, &[2, 2]));
-        let player2_die2 = roll_die(&env, env.crypto().keccak256(&roll_seed_bytes).into());
-
-        game.player1_die1 = Some(player1_die1);
-        game.player1_die2 = Some(player1_die2);
-        game.player2_die1 = Some(player2_die1);
-        game.player2_die2 = Some(player2_die2);
-
-        // Determine winner (if tie, player1 wins)
-        let player1_total = player1_die1 + player1_die2;
-        let player2_total = player2_die1 + player2_die2;
-        let winner = if player1_total >= player2_total {
-            game.player1.clone()
-        } else {
-            game.player2.clone()
+        let settlement = settlement_config(&env);
+        let keeper = authorize_settlement(&env, &settlement, &game, &caller)?;
+
+        // Roll dice for both players from the round seed
+        let seed = round_seed(&env, session_id, &game, None);
+        let player1_dice = roll_dice(&env, &seed, 1, &game.rules);
+        let player2_dice = roll_dice(&env, &seed, 2, &game.rules);
+        game.player1_dice = player1_dice.clone();
+        game.player2_dice = player2_dice.clone();
+
+        // Determine the round winner (if tie, player1 wins)
+        let player1_score = score_dice(&game.rules, &player1_dice);
+        let player2_score = score_dice(&game.rules, &player2_dice);
+        let player1_won_round = player1_score >= player2_score;
+        let round = DiceRound {
+            round: game.round,
+            player1_dice,
+            player2_dice,
+            player1_score,
+            player2_score,
+            player1_won: player1_won_round,
         };
 
-        // Update game with winner (this marks the game as ended)
-        game.winner = Some(winner.clone());
-        env.storage().temporary().set(&key, &game);
+        // Pay the keeper out of the stake, if a third party settled
+        if let Some(keeper) = &keeper {
+            let reward = keeper_reward(&settlement, &game);
+            game.keeper_rewards += reward;
+            credit_keeper(&env, session_id, keeper, reward);
+        }
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        finish_round(&env, session_id, game, round, keeper)
+    }
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+    /// Extend the TTL of a game and of the contract instance.
+    /// Anyone can call this to keep a long-running or idle game from being archived.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn bump(env: Env, session_id: u32) -> Result<(), Error> {
+        extend_instance_ttl(&env);
 
-        // Call GameHub to end the session
-        // This unlocks points and updates standings
-        // Event emitted by the Game Hub contract (GameEnded)
-        let player1_won = winner == game.player1; // true if player1 won, false if player2 won
-        game_hub.end_game(&session_id, &player1_won);
+        let key = DataKey::Game(session_id);
+        if !env.storage().temporary().has(&key) {
+            return Err(Error::GameNotFound);
+        }
+        extend_game_ttl(&env, &key);
 
-        Ok(winner)
+        Ok(())
     }
 
     /// Get game information.
@@ -356,6 +1488,178 @@ impl DiceDuelContract {
             .ok_or(Error::GameNotFound)
     }
 
+    /// Get the archived result of a finished game.
+    /// Available after the temporary game entry has expired.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_match(env: Env, session_id: u32) -> Result<MatchResult, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))
+            .ok_or(Error::MatchNotFound)
+    }
+
+    /// Get the winner of a finished game, for matchmakers settling stakes.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The winner, or `None` until the game is archived
+    pub fn get_winner(env: Env, session_id: u32) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get::<_, MatchResult>(&DataKey::MatchResult(session_id))
+            .map(|result| result.winner)
+    }
+
+    /// Get how a finished game's stakes are split between the players and the
+    /// keepers that settled it, for matchmakers paying out escrowed stakes.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_settlement(env: Env, session_id: u32) -> Option<Settlement> {
+        let result: MatchResult = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))?;
+        Some(Settlement {
+            winner: result.winner,
+            player1_payout: result.player1_payout,
+            player2_payout: result.player2_payout,
+            keeper_rewards: settlement::session_keepers(&env, session_id),
+        })
+    }
+
+    /// Get the rematch offered for an ended session, if one is pending.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    pub fn get_rematch(env: Env, session_id: u32) -> Option<RematchOffer> {
+        rematch::rematch_offer(&env, session_id)
+    }
+
+    /// Get the series a session belongs to, with its cumulative score.
+    /// Sessions that were never rematched have none.
+    ///
+    /// # Arguments
+    /// * `session_id` - Any session of the series
+    pub fn get_series(env: Env, session_id: u32) -> Option<Series> {
+        rematch::session_series(&env, session_id).map(|(_, series)| series)
+    }
+
+    /// Get the players, betting phase and winner of a session, for spectators
+    /// and side-bet pools.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_session_info(env: Env, session_id: u32) -> Result<SessionInfo, Error> {
+        let game: Option<Game> = env.storage().temporary().get(&DataKey::Game(session_id));
+        let Some(game) = game else {
+            // The archive does not keep the rules, but a finished game takes
+            // no bets either way
+            let result: MatchResult = env
+                .storage()
+                .persistent()
+                .get(&DataKey::MatchResult(session_id))
+                .ok_or(Error::GameNotFound)?;
+            return Ok(SessionInfo {
+                player1: result.player1,
+                player2: result.player2,
+                awaiting_first_move: false,
+                predetermined: true,
+                winner: Some(result.winner),
+            });
+        };
+
+        Ok(SessionInfo {
+            predetermined: outcome_predetermined(&game.rules),
+            awaiting_first_move: game.winner.is_none()
+                && game.round == 1
+                && !game.player1_rolled
+                && !game.player2_rolled
+                && game.rolls == 0
+                && game.player1_roll_hash.is_none()
+                && game.player2_roll_hash.is_none(),
+            player1: game.player1,
+            player2: game.player2,
+            winner: game.winner,
+        })
+    }
+
+    /// Get the public seed material a session's dice are derived from, so
+    /// anyone can recompute every roll with the `provably-fair` crate. `Pig`
+    /// rolls also take both players' secrets, public in their `reveal_roll` calls.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_seed_material(env: Env, session_id: u32) -> Result<SeedMaterial, Error> {
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        Ok(SeedMaterial {
+            session_id,
+            player1: game.player1.to_string(),
+            player2: game.player2.to_string(),
+            round: game.round,
+            rolls: game.rolls,
+        })
+    }
+
+    /// Get the number of archived matches a player took part in.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    pub fn get_player_match_count(env: Env, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerMatchCount(player))
+            .unwrap_or(0)
+    }
+
+    /// Get a page of a player's archived matches, oldest first.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    /// * `start` - Index of the first match to return
+    /// * `limit` - Maximum number of matches to return (capped at 50)
+    pub fn get_player_matches(
+        env: Env,
+        player: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<MatchResult> {
+        let count = Self::get_player_match_count(env.clone(), player.clone());
+        let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE)).min(count);
+
+        let mut results = Vec::new(&env);
+        for index in start..end {
+            let session_id: Option<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PlayerMatch(player.clone(), index));
+            if let Some(result) = session_id.and_then(|id| {
+                env.storage()
+                    .persistent()
+                    .get::<_, MatchResult>(&DataKey::MatchResult(id))
+            }) {
+                results.push_back(result);
+            }
+        }
+        results
+    }
+
+    /// Get the total keeper rewards an address has earned settling games.
+    ///
+    /// # Arguments
+    /// * `keeper` - Address of the keeper
+    pub fn get_keeper_earnings(env: Env, keeper: Address) -> i128 {
+        settlement::keeper_earnings(&env, keeper)
+    }
+
     // ========================================================================
     // Admin Functions
     // ========================================================================
@@ -364,69 +1668,178 @@ impl DiceDuelContract {
     ///
     /// # Returns
     /// * `Address` - The admin address
-    pub fn get_admin(env: Env) -> Address {
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set")
+            .ok_or(Error::AdminNotSet)
     }
 
     /// Set a new admin address
     ///
     /// # Arguments
     /// * `new_admin` - The new admin address
-    pub fn set_admin(env: Env, new_admin: Address) {
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Get the current GameHub contract address
     ///
     /// # Returns
     /// * `Address` - The GameHub contract address
-    pub fn get_hub(env: Env) -> Address {
+    pub fn get_hub(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set")
+            .ok_or(Error::GameHubNotSet)
     }
 
     /// Set a new GameHub contract address
     ///
     /// # Arguments
     /// * `new_hub` - The new GameHub contract address
-    pub fn set_hub(env: Env, new_hub: Address) {
+    pub fn set_hub(env: Env, new_hub: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.storage()
             .instance()
             .set(&DataKey::GameHubAddress, &new_hub);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    ///
+    /// # Returns
+    /// * `TtlConfig` - The configured thresholds (or the defaults)
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl_config(&env)
+    }
+
+    /// Set the storage TTL thresholds
+    ///
+    /// # Arguments
+    /// * `config` - New thresholds; each threshold must not exceed its extension target, and
+    ///   no target may exceed the network's maximum TTL
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        ttl::set_ttl_config(&env, &config)?;
+
+        Ok(())
+    }
+
+    /// Get the settlement rules for `reveal_winner`
+    ///
+    /// # Returns
+    /// * `SettlementConfig` - The configured rules (or the participants-only default)
+    pub fn get_settlement_config(env: Env) -> SettlementConfig {
+        settlement_config(&env)
+    }
+
+    /// Set the settlement rules for `reveal_winner`
+    ///
+    /// # Arguments
+    /// * `config` - New rules; the keeper reward may not exceed 500 basis points
+    pub fn set_settlement_config(env: Env, config: SettlementConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        settlement::set_settlement_config(&env, &config)?;
+
+        Ok(())
+    }
+
+    /// Get the stake limits new games are checked against
+    pub fn get_stake_limits(env: Env) -> StakeLimits {
+        stakes::stake_limits(&env, MAX_STAKE)
+    }
+
+    /// Set the stake limits for new games; running games keep their stakes
+    ///
+    /// # Arguments
+    /// * `limits` - New limits; the minimum must be at least 1 and at most the
+    ///   maximum, which may not exceed `i128::MAX / 2`
+    pub fn set_stake_limits(env: Env, limits: StakeLimits) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        stakes::set_stake_limits(&env, &limits, MAX_STAKE)?;
+
+        Ok(())
+    }
+
+    /// Check whether a contract may call `start_matched_game`
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Address of the contract
+    pub fn is_matchmaker(env: Env, matchmaker: Address) -> bool {
+        registry::is_matchmaker(&env, &matchmaker)
+    }
+
+    /// Register or remove a matchmaker contract
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Address of the contract (a lobby, a tournament)
+    /// * `allowed` - Whether it may call `start_matched_game`
+    pub fn set_matchmaker(env: Env, matchmaker: Address, allowed: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        registry::set_matchmaker(&env, matchmaker, allowed);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
     /// * `new_wasm_hash` - The hash of the new WASM binary
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
     }
 }
 
@@ -436,3 +1849,6 @@ impl DiceDuelContract {
 
 #[cfg(test)]
 mod test;
+
+#[cfg(test)]
+mod rules_test;
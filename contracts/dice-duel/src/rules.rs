@@ -0,0 +1,113 @@
+//! Scoring rules for Dice Duel.
+//!
+//! Everything here is pure: no storage, no auth and no `Env`, only the rolled
+//! faces and the session's `DiceRules`. The contract rolls the dice and compares
+//! the returned scores, so each scoring mode can be unit tested on its own
+//! (see `rules_test.rs`).
+
+use crate::{DiceRules, Scoring};
+
+/// Most dice a session may roll per player
+pub const MAX_DICE: u32 = 10;
+
+/// Most sides a die may have
+pub const MAX_SIDES: u32 = 100;
+
+/// Largest banking target a `Pig` session may set
+pub const MAX_PIG_TARGET: u32 = 1_000;
+
+/// Weight of the primary ranking in `Highest` and `PokerDice` scores. Larger than
+/// any possible total, so the total only breaks ties.
+const RANK_WEIGHT: u32 = 10_000;
+
+/// Poker-dice hands, weakest first
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Hand {
+    Nothing = 0,
+    Pair = 1,
+    TwoPair = 2,
+    ThreeOfAKind = 3,
+    Straight = 4,
+    FullHouse = 5,
+    FourOfAKind = 6,
+    FiveOfAKind = 7,
+}
+
+/// Whether a session can be played with these rules
+pub fn is_valid(rules: &DiceRules) -> bool {
+    (1..=MAX_DICE).contains(&rules.dice)
+        && (2..=MAX_SIDES).contains(&rules.sides)
+        && (rules.scoring != Scoring::Pig || (1..=MAX_PIG_TARGET).contains(&rules.target))
+}
+
+/// Score one player's dice; the higher score wins the round
+pub fn score(rules: &DiceRules, dice: &[u32]) -> u32 {
+    let total: u32 = dice.iter().sum();
+    match rules.scoring {
+        Scoring::Sum => total,
+        Scoring::Highest => highest(dice) * RANK_WEIGHT + total,
+        Scoring::PokerDice => poker_hand(dice) as u32 * RANK_WEIGHT + total,
+        Scoring::DoublesBonus => {
+            if dice.len() > 1 && dice.iter().all(|face| *face == dice[0]) {
+                total * 2
+            } else {
+                total
+            }
+        }
+        Scoring::Pig => pig_roll(dice).unwrap_or(0),
+    }
+}
+
+/// Points a `Pig` roll adds to the turn total, or `None` if any die shows a 1
+/// and the turn total is lost
+pub fn pig_roll(dice: &[u32]) -> Option<u32> {
+    if dice.contains(&1) {
+        None
+    } else {
+        Some(dice.iter().sum())
+    }
+}
+
+/// Highest single face rolled
+pub fn highest(dice: &[u32]) -> u32 {
+    dice.iter().copied().max().unwrap_or(0)
+}
+
+/// Best poker-dice hand in a roll. Five or more matching faces count as five of
+/// a kind; a straight needs at least five dice with consecutive faces.
+pub fn poker_hand(dice: &[u32]) -> Hand {
+    let mut faces = [0u32; MAX_DICE as usize];
+    let len = dice.len().min(faces.len());
+    faces[..len].copy_from_slice(&dice[..len]);
+    let faces = &mut faces[..len];
+    faces.sort_unstable();
+
+    // Sizes of the two largest groups of matching faces
+    let (mut largest, mut second) = (0, 0);
+    let mut start = 0;
+    while start < len {
+        let mut end = start + 1;
+        while end < len && faces[end] == faces[start] {
+            end += 1;
+        }
+        let group = end - start;
+        if group > largest {
+            second = largest;
+            largest = group;
+        } else if group > second {
+            second = group;
+        }
+        start = end;
+    }
+
+    match (largest, second) {
+        (5.., _) => Hand::FiveOfAKind,
+        (4, _) => Hand::FourOfAKind,
+        (3, 2..) => Hand::FullHouse,
+        (1, _) if len >= 5 && faces[len - 1] - faces[0] == len as u32 - 1 => Hand::Straight,
+        (3, _) => Hand::ThreeOfAKind,
+        (2, 2) => Hand::TwoPair,
+        (2, _) => Hand::Pair,
+        _ => Hand::Nothing,
+    }
+}
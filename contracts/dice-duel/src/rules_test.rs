@@ -0,0 +1,100 @@
+#![cfg(test)]
+
+//! Unit tests for the pure scoring rules. No `Env` is needed here.
+
+use crate::rules::{self, Hand};
+use crate::{DiceRules, Scoring};
+
+fn rules_with(dice: u32, scoring: Scoring) -> DiceRules {
+    DiceRules {
+        dice,
+        sides: 6,
+        scoring,
+        target: 100,
+    }
+}
+
+#[test]
+fn test_sum_scores_total() {
+    let rules = rules_with(3, Scoring::Sum);
+    assert_eq!(rules::score(&rules, &[1, 4, 6]), 11);
+    assert_eq!(rules::score(&rules, &[6, 6, 6]), 18);
+}
+
+#[test]
+fn test_highest_die_beats_higher_total() {
+    let rules = rules_with(3, Scoring::Highest);
+    assert!(rules::score(&rules, &[6, 1, 1]) > rules::score(&rules, &[5, 5, 5]));
+    // Equal highest dice fall back to the total
+    assert!(rules::score(&rules, &[6, 2, 1]) > rules::score(&rules, &[6, 1, 1]));
+}
+
+#[test]
+fn test_doubles_bonus() {
+    let rules = rules_with(2, Scoring::DoublesBonus);
+    assert_eq!(rules::score(&rules, &[3, 3]), 12);
+    assert_eq!(rules::score(&rules, &[3, 4]), 7);
+    // A single die is never a double
+    assert_eq!(rules::score(&rules_with(1, Scoring::DoublesBonus), &[5]), 5);
+}
+
+#[test]
+fn test_poker_hands() {
+    assert_eq!(rules::poker_hand(&[1, 2, 3, 4, 6]), Hand::Nothing);
+    assert_eq!(rules::poker_hand(&[2, 2, 3, 4, 6]), Hand::Pair);
+    assert_eq!(rules::poker_hand(&[2, 2, 4, 4, 6]), Hand::TwoPair);
+    assert_eq!(rules::poker_hand(&[5, 2, 5, 4, 5]), Hand::ThreeOfAKind);
+    assert_eq!(rules::poker_hand(&[3, 5, 2, 6, 4]), Hand::Straight);
+    assert_eq!(rules::poker_hand(&[2, 5, 2, 5, 5]), Hand::FullHouse);
+    assert_eq!(rules::poker_hand(&[1, 1, 6, 1, 1]), Hand::FourOfAKind);
+    assert_eq!(rules::poker_hand(&[4, 4, 4, 4, 4]), Hand::FiveOfAKind);
+}
+
+#[test]
+fn test_straight_needs_five_dice() {
+    assert_eq!(rules::poker_hand(&[1, 2, 3, 4]), Hand::Nothing);
+    assert_eq!(rules::poker_hand(&[1, 2, 3, 4, 5, 6]), Hand::Straight);
+}
+
+#[test]
+fn test_poker_hand_outranks_total() {
+    let rules = rules_with(5, Scoring::PokerDice);
+    // A pair of ones beats a high-card roll with a larger total
+    assert!(rules::score(&rules, &[1, 1, 2, 3, 5]) > rules::score(&rules, &[6, 5, 4, 3, 1]));
+    // Same hand: the higher total wins
+    assert!(rules::score(&rules, &[6, 6, 1, 2, 3]) > rules::score(&rules, &[2, 2, 1, 3, 4]));
+}
+
+#[test]
+fn test_pig_roll_busts_on_any_one() {
+    assert_eq!(rules::pig_roll(&[4]), Some(4));
+    assert_eq!(rules::pig_roll(&[3, 5]), Some(8));
+    assert_eq!(rules::pig_roll(&[1]), None);
+    assert_eq!(rules::pig_roll(&[6, 1]), None);
+    assert_eq!(rules::score(&rules_with(2, Scoring::Pig), &[6, 1]), 0);
+}
+
+#[test]
+fn test_pig_target_limits() {
+    let pig = rules_with(1, Scoring::Pig);
+    assert!(rules::is_valid(&pig));
+    assert!(!rules::is_valid(&DiceRules { target: 0, ..pig.clone() }));
+    let target = rules::MAX_PIG_TARGET + 1;
+    assert!(!rules::is_valid(&DiceRules { target, ..pig }));
+    // Other modes ignore the target
+    assert!(rules::is_valid(&DiceRules { target: 0, ..rules_with(1, Scoring::Sum) }));
+}
+
+#[test]
+fn test_rule_limits() {
+    assert!(rules::is_valid(&rules_with(1, Scoring::Sum)));
+    assert!(rules::is_valid(&rules_with(rules::MAX_DICE, Scoring::Sum)));
+    assert!(!rules::is_valid(&rules_with(0, Scoring::Sum)));
+    assert!(!rules::is_valid(&rules_with(rules::MAX_DICE + 1, Scoring::Sum)));
+    let coin = DiceRules {
+        sides: 2,
+        ..rules_with(1, Scoring::Sum)
+    };
+    assert!(rules::is_valid(&coin));
+    assert!(!rules::is_valid(&DiceRules { sides: 1, ..coin }));
+}
@@ -1,11 +1,17 @@
 #![cfg(test)]
 
+extern crate std;
+
 // Unit tests for the dice-duel contract using a simple mock GameHub.
 // These tests verify game logic independently of the full GameHub system.
 
-use crate::{DiceDuelContract, DiceDuelContractClient, Error};
+use crate::{
+    DataKey, DiceDuelContract, DiceDuelContractClient, DiceRules, Error, RematchOffer, Scoring,
+    Series, SessionKey, SettlementConfig, SettlementMode, StakeLimits, REVEAL_TIMEOUT_LEDGERS,
+};
+use provably_fair::{dice_duel, NativeKeccak, Session, MAX_STRKEY_LEN};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+use soroban_sdk::{contract, contractimpl, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -47,6 +53,20 @@ fn setup_test() -> (
     MockGameHubClient<'static>,
     Address,
     Address,
+) {
+    setup_test_with_min_ttl(u32::MAX / 2)
+}
+
+/// Same as `setup_test`, but new storage entries start with `min_ttl` ledgers
+/// so tests can observe TTL extension and expiry.
+fn setup_test_with_min_ttl(
+    min_ttl: u32,
+) -> (
+    Env,
+    DiceDuelContractClient<'static>,
+    MockGameHubClient<'static>,
+    Address,
+    Address,
 ) {
     let env = Env::default();
     env.mock_all_auths();
@@ -58,8 +78,8 @@ fn setup_test() -> (
         sequence_number: 100,
         network_id: Default::default(),
         base_reserve: 10,
-        min_temp_entry_ttl: u32::MAX / 2,
-        min_persistent_entry_ttl: u32::MAX / 2,
+        min_temp_entry_ttl: min_ttl,
+        min_persistent_entry_ttl: min_ttl,
         max_entry_ttl: u32::MAX / 2,
     });
 
@@ -122,6 +142,7 @@ fn assert_dice_duel_error<T, E>(
 // ============================================================================
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_complete_game() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
@@ -129,7 +150,7 @@ fn test_complete_game() {
     let points = 100_0000000;
 
     // Start game
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None, &None);
 
     // Verify initial state
     let game = client.get_game(&session_id);
@@ -140,17 +161,15 @@ fn test_complete_game() {
     assert_eq!(game.player2_points, points);
     assert_eq!(game.player1_rolled, false);
     assert_eq!(game.player2_rolled, false);
-    assert!(game.player1_die1.is_none());
-    assert!(game.player1_die2.is_none());
-    assert!(game.player2_die1.is_none());
-    assert!(game.player2_die2.is_none());
+    assert!(game.player1_dice.is_empty());
+    assert!(game.player2_dice.is_empty());
 
     // Players roll
     client.roll(&session_id, &player1);
     client.roll(&session_id, &player2);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
     assert!(winner == player1 || winner == player2);
 
     // Verify dice values and winner stored
@@ -158,18 +177,13 @@ fn test_complete_game() {
     assert!(final_game.winner.is_some());
     assert_eq!(final_game.winner.unwrap(), winner);
 
-    let p1d1 = final_game.player1_die1.unwrap();
-    let p1d2 = final_game.player1_die2.unwrap();
-    let p2d1 = final_game.player2_die1.unwrap();
-    let p2d2 = final_game.player2_die2.unwrap();
+    assert_eq!(final_game.player1_dice.len(), 2);
+    assert_eq!(final_game.player2_dice.len(), 2);
+    assert!(final_game.player1_dice.iter().all(|die| (1..=6).contains(&die)));
+    assert!(final_game.player2_dice.iter().all(|die| (1..=6).contains(&die)));
 
-    assert!((1..=6).contains(&p1d1));
-    assert!((1..=6).contains(&p1d2));
-    assert!((1..=6).contains(&p2d1));
-    assert!((1..=6).contains(&p2d2));
-
-    let total1 = p1d1 + p1d2;
-    let total2 = p2d1 + p2d2;
+    let total1: u32 = final_game.player1_dice.iter().sum();
+    let total2: u32 = final_game.player2_dice.iter().sum();
     assert!((2..=12).contains(&total1));
     assert!((2..=12).contains(&total2));
 }
@@ -181,7 +195,7 @@ fn test_cannot_roll_twice() {
     let session_id = 2u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None, &None);
 
     client.roll(&session_id, &player1);
     let result = client.try_roll(&session_id, &player1);
@@ -195,10 +209,10 @@ fn test_cannot_reveal_before_both_roll() {
     let session_id = 3u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None, &None);
     client.roll(&session_id, &player1);
 
-    let result = client.try_reveal_winner(&session_id);
+    let result = client.try_reveal_winner(&session_id, &player1);
     assert_dice_duel_error(&result, Error::BothPlayersNotRolled);
 }
 
@@ -209,7 +223,7 @@ fn test_non_player_cannot_roll() {
     let session_id = 4u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None, &None);
 
     let non_player = Address::generate(&_env);
     let result = client.try_roll(&session_id, &non_player);
@@ -223,10 +237,10 @@ fn test_cannot_roll_after_game_ended() {
     let session_id = 5u32;
     let points = 100_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None, &None);
     client.roll(&session_id, &player1);
     client.roll(&session_id, &player2);
-    client.reveal_winner(&session_id);
+    client.reveal_winner(&session_id, &player1);
 
     let result = client.try_roll(&session_id, &player1);
     assert_dice_duel_error(&result, Error::GameAlreadyEnded);
@@ -243,3 +257,1170 @@ fn test_upgrade_function_exists() {
     // Should fail (WASM doesn't exist) but confirms function signature is correct
     assert!(result.is_err());
 }
+
+#[test]
+fn test_cannot_play_against_self() {
+    let (_env, client, _hub, player1, _player2) = setup_test();
+
+    let points = 100_0000000;
+    let result = client.try_start_game(&90u32, &player1, &player1, &points, &points, &None, &None);
+    assert_dice_duel_error(&result, Error::SelfPlay);
+}
+
+#[test]
+fn test_cannot_reuse_session_id() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let other = Address::generate(&env);
+
+    let points = 100_0000000;
+    client.start_game(&91u32, &player1, &player2, &points, &points, &None, &None);
+    let result = client.try_start_game(&91u32, &other, &player2, &points, &points, &None, &None);
+    assert_dice_duel_error(&result, Error::SessionIdTaken);
+    assert_eq!(client.get_game(&91u32).player1, player1);
+}
+
+#[test]
+fn test_missing_admin_and_hub_are_typed_errors() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // A game that is ready to settle when the hub goes missing
+    client.start_game(&91u32, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    client.roll(&91u32, &player1);
+    client.roll(&91u32, &player2);
+
+    // Simulate an instance that lost its configured addresses
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::Admin);
+        env.storage().instance().remove(&DataKey::GameHubAddress);
+    });
+
+    assert_dice_duel_error(&client.try_get_admin(), Error::AdminNotSet);
+    assert_dice_duel_error(&client.try_set_admin(&player1), Error::AdminNotSet);
+    assert_dice_duel_error(&client.try_set_hub(&player1), Error::AdminNotSet);
+    let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+    assert_dice_duel_error(&client.try_upgrade(&new_wasm_hash), Error::AdminNotSet);
+    assert_dice_duel_error(&client.try_get_hub(), Error::GameHubNotSet);
+    assert_dice_duel_error(&client.try_reveal_winner(&91u32, &player1), Error::GameHubNotSet);
+}
+
+// ============================================================================
+// Best-of-N Tests
+// ============================================================================
+
+#[test]
+fn test_best_of_match_plays_until_majority() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 50u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &Some(3), &None);
+
+    let mut game = client.get_game(&session_id);
+    while game.winner.is_none() {
+        client.roll(&session_id, &player1);
+        client.roll(&session_id, &player2);
+        client.reveal_winner(&session_id, &player1);
+        game = client.get_game(&session_id);
+    }
+
+    // Someone took two rounds, in two or three rounds
+    let winner = game.winner.clone().unwrap();
+    let (winner_wins, loser_wins) = if winner == player1 {
+        (game.player1_wins, game.player2_wins)
+    } else {
+        (game.player2_wins, game.player1_wins)
+    };
+    assert_eq!(winner_wins, 2);
+    assert!(loser_wins <= 1);
+    assert_eq!(game.rounds.len(), winner_wins + loser_wins);
+    for (index, round) in game.rounds.iter().enumerate() {
+        assert_eq!(round.round, index as u32 + 1);
+        assert_eq!(round.player1_score, round.player1_dice.iter().sum::<u32>());
+        assert_eq!(round.player2_score, round.player2_dice.iter().sum::<u32>());
+        assert_eq!(round.player1_won, round.player1_score >= round.player2_score);
+    }
+    assert_eq!(game.rounds.last().unwrap().player1_won, winner == player1);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.best_of, 3);
+    assert_eq!(archived.player1_wins, game.player1_wins);
+    assert_eq!(archived.player2_wins, game.player2_wins);
+}
+
+#[test]
+fn test_rolls_reset_between_rounds() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 51u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &Some(5), &None);
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+    let round_winner = client.reveal_winner(&session_id, &player1);
+
+    // One round cannot decide a best-of-5
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none());
+    assert_eq!(game.round, 2);
+    assert!(!game.player1_rolled);
+    assert!(!game.player2_rolled);
+    assert_eq!(game.rounds.len(), 1);
+    assert_eq!(game.rounds.get(0).unwrap().player1_won, round_winner == player1);
+
+    // The next round needs fresh rolls from both players
+    client.roll(&session_id, &player1);
+    let result = client.try_reveal_winner(&session_id, &player1);
+    assert_dice_duel_error(&result, Error::BothPlayersNotRolled);
+    let result = client.try_get_match(&session_id);
+    assert_dice_duel_error(&result, Error::MatchNotFound);
+}
+
+#[test]
+fn test_invalid_best_of_rejected() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    for best_of in [0u32, 2, 17] {
+        let points = 100_0000000;
+        let best_of = Some(best_of);
+        let result =
+            client.try_start_game(&52u32, &player1, &player2, &points, &points, &best_of, &None);
+        assert_dice_duel_error(&result, Error::InvalidBestOf);
+    }
+}
+
+// ============================================================================
+// Dice Rules Tests
+// ============================================================================
+
+#[test]
+fn test_custom_dice_rules() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let rules = DiceRules {
+        dice: 5,
+        sides: 20,
+        scoring: Scoring::PokerDice,
+        target: 0,
+    };
+    let session_id = 60u32;
+    let points = 100_0000000;
+    let custom = Some(rules.clone());
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None, &custom);
+    assert_eq!(client.get_game(&session_id).rules, rules);
+
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+    let winner = client.reveal_winner(&session_id, &player1);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1_dice.len(), 5);
+    assert_eq!(game.player2_dice.len(), 5);
+    assert!(game.player1_dice.iter().all(|die| (1..=20).contains(&die)));
+    assert!(game.player2_dice.iter().all(|die| (1..=20).contains(&die)));
+
+    // The round is decided by the poker-dice score, not the plain total
+    let round = game.rounds.get(0).unwrap();
+    assert!(round.player1_score >= round.player1_dice.iter().sum::<u32>());
+    assert_eq!(round.player1_won, round.player1_score >= round.player2_score);
+    assert_eq!(winner == player1, round.player1_won);
+}
+
+#[test]
+fn test_default_dice_rules() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    client.start_game(&61u32, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    let rules = client.get_game(&61u32).rules;
+    assert_eq!(rules.dice, 2);
+    assert_eq!(rules.sides, 6);
+    assert_eq!(rules.scoring, Scoring::Sum);
+}
+
+#[test]
+fn test_invalid_dice_rules_rejected() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let points = 100_0000000;
+    for (dice, sides) in [(0, 6), (11, 6), (2, 1), (2, 101)] {
+        let rules = Some(DiceRules {
+            dice,
+            sides,
+            scoring: Scoring::Sum,
+            target: 0,
+        });
+        let result =
+            client.try_start_game(&62u32, &player1, &player2, &points, &points, &None, &rules);
+        assert_dice_duel_error(&result, Error::InvalidRules);
+    }
+}
+
+// ============================================================================
+// Press-Your-Luck (Pig) Tests
+// ============================================================================
+
+/// Start a one-die `Pig` session to `target` points
+fn start_pig(
+    client: &DiceDuelContractClient<'static>,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    best_of: Option<u32>,
+    target: u32,
+) {
+    let rules = Some(DiceRules {
+        dice: 1,
+        sides: 6,
+        scoring: Scoring::Pig,
+        target,
+    });
+    let points = 100_0000000;
+    client.start_game(&session_id, player1, player2, &points, &points, &best_of, &rules);
+}
+
+/// Secret `seat` (1 or 2) commits to for a roll; unique per roll of a session
+fn roll_secret(env: &Env, seat: u8, round: u32, roll: u32) -> BytesN<32> {
+    let mut secret = [seat; 32];
+    secret[..4].copy_from_slice(&round.to_be_bytes());
+    secret[4..8].copy_from_slice(&roll.to_be_bytes());
+    BytesN::from_array(env, &secret)
+}
+
+/// Commitment to a roll secret
+fn roll_hash(env: &Env, secret: &BytesN<32>) -> BytesN<32> {
+    env.crypto().keccak256(&secret.clone().into()).into()
+}
+
+/// Make the next roll: both players commit to a secret, then both reveal.
+/// Returns the secrets, player 1's first.
+fn pig_roll(
+    env: &Env,
+    client: &DiceDuelContractClient<'static>,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+) -> [BytesN<32>; 2] {
+    let game = client.get_game(&session_id);
+    let secrets = [1, 2].map(|seat| roll_secret(env, seat, game.round, game.rolls + 1));
+    for (player, secret) in [(player1, &secrets[0]), (player2, &secrets[1])] {
+        client.commit_roll(&session_id, player, &roll_hash(env, secret));
+    }
+    for (player, secret) in [(player1, &secrets[0]), (player2, &secrets[1])] {
+        client.reveal_roll(&session_id, player, secret);
+    }
+    secrets
+}
+
+/// Take one turn: roll until a bust or until `hold` points are on the table, then bank
+fn play_pig_turn(
+    env: &Env,
+    client: &DiceDuelContractClient<'static>,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+) {
+    let hold = 8;
+    let player = if client.get_game(&session_id).player1_turn { player1 } else { player2 };
+    loop {
+        pig_roll(env, client, session_id, player1, player2);
+        let game = client.get_game(&session_id);
+        if game.turn_total == 0 {
+            return;
+        }
+        if game.turn_total >= hold {
+            client.bank(&session_id, player);
+            return;
+        }
+    }
+}
+
+#[test]
+fn test_pig_game_to_target() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 70u32;
+    start_pig(&client, session_id, &player1, &player2, None, 20);
+
+    let mut game = client.get_game(&session_id);
+    while game.winner.is_none() {
+        play_pig_turn(&env, &client, session_id, &player1, &player2);
+        game = client.get_game(&session_id);
+    }
+
+    // The winner banked at least the target
+    let round = game.rounds.get(0).unwrap();
+    let winner = game.winner.clone().unwrap();
+    assert_eq!(round.player1_won, winner == player1);
+    let winning_score = if round.player1_won { round.player1_score } else { round.player2_score };
+    assert!(winning_score >= 20);
+    assert_eq!(client.get_match(&session_id).winner, winner);
+}
+
+#[test]
+fn test_pig_turn_order_and_bust() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 71u32;
+    start_pig(&client, session_id, &player1, &player2, None, 100);
+    assert_dice_duel_error(&client.try_bank(&session_id, &player2), Error::NotYourTurn);
+    assert_dice_duel_error(&client.try_bank(&session_id, &player1), Error::NothingToBank);
+
+    // Player 1 keeps rolling until a 1 hands the turn over
+    let mut turn_total = 0;
+    loop {
+        pig_roll(&env, &client, session_id, &player1, &player2);
+        let game = client.get_game(&session_id);
+        let die = game.player1_dice.get(0).unwrap();
+        if die == 1 {
+            assert_eq!(game.turn_total, 0);
+            assert!(!game.player1_turn);
+            break;
+        }
+        turn_total += die;
+        assert_eq!(game.turn_total, turn_total);
+        assert!(game.player1_turn);
+    }
+    assert_eq!(client.get_game(&session_id).player1_banked, 0);
+    assert_dice_duel_error(&client.try_bank(&session_id, &player1), Error::NotYourTurn);
+}
+
+#[test]
+fn test_pig_bank_passes_turn() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 72u32;
+    start_pig(&client, session_id, &player1, &player2, None, 100);
+
+    // Roll until player 1 has something to bank (a bust hands the turn back and forth)
+    loop {
+        pig_roll(&env, &client, session_id, &player1, &player2);
+        let game = client.get_game(&session_id);
+        if game.player1_turn && game.turn_total > 0 {
+            break;
+        }
+    }
+    let turn_total = client.get_game(&session_id).turn_total;
+    client.bank(&session_id, &player1);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1_banked, turn_total);
+    assert_eq!(game.turn_total, 0);
+    assert!(!game.player1_turn);
+    assert!(game.winner.is_none());
+}
+
+#[test]
+fn test_pig_best_of_three() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 73u32;
+    start_pig(&client, session_id, &player1, &player2, Some(3), 10);
+
+    let mut game = client.get_game(&session_id);
+    while game.winner.is_none() {
+        play_pig_turn(&env, &client, session_id, &player1, &player2);
+        let next = client.get_game(&session_id);
+        if next.winner.is_none() && next.round > game.round {
+            // Banked state is cleared and the round's loser opens the next one
+            let last = next.rounds.last().unwrap();
+            assert_eq!(next.player1_banked + next.player2_banked, 0);
+            assert_eq!(next.player1_turn, !last.player1_won);
+        }
+        game = next;
+    }
+    assert_eq!(game.player1_wins.max(game.player2_wins), 2);
+}
+
+#[test]
+fn test_game_mode_entry_points() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // `commit_roll` and `bank` are for Pig sessions only
+    client.start_game(&74u32, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    assert_dice_duel_error(&client.try_bank(&74u32, &player1), Error::WrongGameMode);
+    let hash = BytesN::from_array(&env, &[0; 32]);
+    let result = client.try_commit_roll(&74u32, &player1, &hash);
+    assert_dice_duel_error(&result, Error::WrongGameMode);
+
+    // ...and Pig rounds are never rolled with `roll` nor revealed
+    start_pig(&client, 75, &player1, &player2, None, 50);
+    assert_dice_duel_error(&client.try_roll(&75u32, &player1), Error::WrongGameMode);
+    let result = client.try_reveal_winner(&75u32, &player1);
+    assert_dice_duel_error(&result, Error::WrongGameMode);
+
+    // A Pig target of zero could never be reached
+    let rules = Some(DiceRules {
+        dice: 1,
+        sides: 6,
+        scoring: Scoring::Pig,
+        target: 0,
+    });
+    let points = 100_0000000;
+    let result =
+        client.try_start_game(&76u32, &player1, &player2, &points, &points, &None, &rules);
+    assert_dice_duel_error(&result, Error::InvalidRules);
+}
+
+#[test]
+fn test_pig_roll_needs_both_secrets() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 77u32;
+    start_pig(&client, session_id, &player1, &player2, None, 100);
+    let [secret1, secret2] = [1, 2].map(|seat| roll_secret(&env, seat, 1, 1));
+
+    // Committing is the decision to roll: player 1 can no longer bank
+    client.commit_roll(&session_id, &player1, &roll_hash(&env, &secret1));
+    let result = client.try_commit_roll(&session_id, &player1, &roll_hash(&env, &secret1));
+    assert_dice_duel_error(&result, Error::RollAlreadyCommitted);
+    assert_dice_duel_error(&client.try_bank(&session_id, &player1), Error::RollPending);
+
+    // Nobody reveals before both committed
+    let result = client.try_reveal_roll(&session_id, &player1, &secret1);
+    assert_dice_duel_error(&result, Error::RollNotCommitted);
+    client.commit_roll(&session_id, &player2, &roll_hash(&env, &secret2));
+
+    // A reveal must open the player's own commitment
+    let result = client.try_reveal_roll(&session_id, &player2, &secret1);
+    assert_dice_duel_error(&result, Error::SecretMismatch);
+    client.reveal_roll(&session_id, &player1, &secret1);
+    let result = client.try_reveal_roll(&session_id, &player1, &secret1);
+    assert_dice_duel_error(&result, Error::RollAlreadyRevealed);
+
+    let game = client.get_game(&session_id);
+    let deadline = env.ledger().sequence() + REVEAL_TIMEOUT_LEDGERS;
+    assert_eq!(game.reveal_deadline, Some(deadline));
+    assert_eq!(game.rolls, 0);
+
+    // The second reveal rolls and clears the commitments for the next roll
+    client.reveal_roll(&session_id, &player2, &secret2);
+    let game = client.get_game(&session_id);
+    assert_eq!(game.rolls, 1);
+    assert_eq!(game.player1_dice.len(), 1);
+    assert_eq!((game.player1_roll_hash, game.player2_roll_hash), (None, None));
+    assert_eq!((game.player1_roll_secret, game.player2_roll_secret), (None, None));
+    assert_eq!(game.reveal_deadline, None);
+}
+
+#[test]
+fn test_pig_withheld_reveal_forfeits_match() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 78u32;
+    start_pig(&client, session_id, &player1, &player2, Some(3), 100);
+    let [secret1, secret2] = [1, 2].map(|seat| roll_secret(&env, seat, 1, 1));
+    client.commit_roll(&session_id, &player1, &roll_hash(&env, &secret1));
+    client.commit_roll(&session_id, &player2, &roll_hash(&env, &secret2));
+
+    // Player 2 reveals; player 1, who could now compute the roll, does not
+    client.reveal_roll(&session_id, &player2, &secret2);
+    advance_ledgers(&env, REVEAL_TIMEOUT_LEDGERS);
+    let result = client.try_reveal_winner(&session_id, &player2);
+    assert_dice_duel_error(&result, Error::RevealDeadlineNotReached);
+
+    advance_ledgers(&env, 1);
+    let result = client.try_reveal_roll(&session_id, &player1, &secret1);
+    assert_dice_duel_error(&result, Error::RevealDeadlinePassed);
+
+    // The forfeit ends the whole match
+    assert_eq!(client.reveal_winner(&session_id, &player2), player2);
+    let game = client.get_game(&session_id);
+    assert_eq!(game.winner, Some(player2.clone()));
+    assert_eq!((game.player1_wins, game.player2_wins), (0, 2));
+    assert_eq!(client.get_match(&session_id).winner, player2);
+}
+
+// ============================================================================
+// Storage TTL Tests
+// ============================================================================
+
+/// TTL a freshly created entry gets in the TTL tests
+const SHORT_TTL: u32 = 100;
+
+/// Advance the ledger sequence by `ledgers`
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_move_extends_game_ttl() {
+    let (env, client, _hub, player1, player2) = setup_test_with_min_ttl(SHORT_TTL);
+
+    let session_id = 100u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+
+    // Move close to the end of the original 30-day TTL, then go past it
+    advance_ledgers(&env, 518_400 - 100);
+    client.roll(&session_id, &player1);
+    advance_ledgers(&env, 1_000);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1, player1);
+}
+
+// ============================================================================
+// Match History Tests
+// ============================================================================
+
+/// Play a game to completion and return the winner
+fn finish_game(
+    client: &DiceDuelContractClient,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+) -> Address {
+    client.roll(&session_id, player1);
+    client.roll(&session_id, player2);
+    client.reveal_winner(&session_id, player1)
+}
+
+#[test]
+fn test_match_archived_after_game_end() {
+    let (env, client, _hub, player1, player2) = setup_test_with_min_ttl(SHORT_TTL);
+
+    let session_id = 200u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &50_0000000, &None, &None);
+
+    let result = client.try_get_match(&session_id);
+    assert_dice_duel_error(&result, Error::MatchNotFound);
+
+    let winner = finish_game(&client, session_id, &player1, &player2);
+    let game = client.get_game(&session_id);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.session_id, session_id);
+    assert_eq!(archived.player1, player1);
+    assert_eq!(archived.player2, player2);
+    assert_eq!(archived.player1_points, 100_0000000);
+    assert_eq!(archived.player2_points, 50_0000000);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.player1_won, winner == player1);
+    assert_eq!(archived.end_ledger, env.ledger().sequence());
+    let expected_hash: BytesN<32> = env.crypto().keccak256(&game.to_xdr(&env)).into();
+    assert_eq!(archived.state_hash, expected_hash);
+
+    // The archive outlives the temporary game entry
+    advance_ledgers(&env, 518_400 + 1);
+    let result = client.try_get_game(&session_id);
+    assert_dice_duel_error(&result, Error::GameNotFound);
+    assert_eq!(client.get_match(&session_id), archived);
+}
+
+#[test]
+fn test_player_match_history_pagination() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+
+    for (session_id, opponent) in [(201u32, &player2), (202, &player3), (203, &player2)] {
+        let points = 100_0000000;
+        client.start_game(&session_id, &player1, opponent, &points, &points, &None, &None);
+        finish_game(&client, session_id, &player1, opponent);
+    }
+
+    assert_eq!(client.get_player_match_count(&player1), 3);
+    assert_eq!(client.get_player_match_count(&player2), 2);
+    assert_eq!(client.get_player_match_count(&player3), 1);
+
+    let page = client.get_player_matches(&player1, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().session_id, 201);
+    assert_eq!(page.get(1).unwrap().session_id, 202);
+
+    let page = client.get_player_matches(&player1, &2, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().session_id, 203);
+
+    let page = client.get_player_matches(&player2, &5, &10);
+    assert_eq!(page.len(), 0);
+}
+
+// ============================================================================
+// Settlement Tests
+// ============================================================================
+
+#[test]
+fn test_participants_only_settlement() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    assert_eq!(client.get_settlement_config().mode, SettlementMode::Participants);
+
+    let session_id = 300u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+
+    // Outsiders cannot settle, however long they wait
+    let stranger = Address::generate(&env);
+    advance_ledgers(&env, 10_000);
+    let result = client.try_reveal_winner(&session_id, &stranger);
+    assert_dice_duel_error(&result, Error::NotPlayer);
+
+    let winner = client.reveal_winner(&session_id, &player2);
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.keeper, None);
+    assert_eq!(archived.keeper_reward, 0);
+}
+
+#[test]
+fn test_keeper_settlement_earns_reward() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let keeper = Address::generate(&env);
+
+    // Nobody escrows a game started straight through the hub, so nobody
+    // could pay its keeper
+    let direct_id = 300u32;
+    client.start_game(&direct_id, &player1, &player2, &100_0000000, &50_0000000, &None, &None);
+    client.roll(&direct_id, &player1);
+    client.roll(&direct_id, &player2);
+    advance_ledgers(&env, 10);
+    let result = client.try_reveal_winner(&direct_id, &keeper);
+    assert_dice_duel_error(&result, Error::NotPlayer);
+
+    let session_id = 301u32;
+    client.start_matched_game(&lobby, &session_id, &player1, &player2, &100_0000000, &50_0000000);
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+
+    // The players get a head start after the last roll
+    advance_ledgers(&env, 9);
+    let result = client.try_reveal_winner(&session_id, &keeper);
+    assert_dice_duel_error(&result, Error::SettlementTooEarly);
+
+    advance_ledgers(&env, 1);
+    let winner = client.reveal_winner(&session_id, &keeper);
+
+    // 1% of the matched 100 point pot, taken out of the winnings
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.keeper, Some(keeper.clone()));
+    assert_eq!(archived.keeper_reward, 1_0000000);
+    let payouts = (archived.player1_payout, archived.player2_payout);
+    if archived.player1_won {
+        assert_eq!(payouts, (149_0000000, 0));
+    } else {
+        assert_eq!(payouts, (50_0000000, 99_0000000));
+    }
+    assert_eq!(client.get_keeper_earnings(&keeper), 1_0000000);
+
+    // Settling again is a no-op and pays nothing more
+    assert_eq!(client.reveal_winner(&session_id, &keeper), winner);
+    assert_eq!(client.get_keeper_earnings(&keeper), 1_0000000);
+}
+
+#[test]
+fn test_player_settles_free_in_keeper_mode() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    client.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+
+    let session_id = 302u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    finish_game(&client, session_id, &player1, &player2);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.keeper, None);
+    assert_eq!(archived.keeper_reward, 0);
+    assert_eq!(client.get_keeper_earnings(&player1), 0);
+}
+
+#[test]
+fn test_set_settlement_config_rejects_large_reward() {
+    let (_env, client, _hub, _player1, _player2) = setup_test();
+
+    let result = client.try_set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 501,
+        keeper_delay: 0,
+    });
+    assert_dice_duel_error(&result, Error::InvalidSettlementConfig);
+}
+
+// ============================================================================
+// Matchmaking Tests
+// ============================================================================
+
+#[test]
+fn test_matched_game_plays_with_default_rules() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let lobby = Address::generate(&env);
+
+    assert!(!client.is_matchmaker(&lobby));
+    client.set_matchmaker(&lobby, &true);
+    assert!(client.is_matchmaker(&lobby));
+
+    let session_id = 100u32;
+    let points = 100_0000000;
+    client.start_matched_game(&lobby, &session_id, &player1, &player2, &points, &points);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.best_of, 1);
+    assert_eq!(game.rules.dice, 2);
+    assert_eq!(client.get_winner(&session_id), None);
+
+    client.roll(&session_id, &player1);
+    client.roll(&session_id, &player2);
+    let winner = client.reveal_winner(&session_id, &player1);
+    assert_eq!(client.get_winner(&session_id), Some(winner));
+}
+
+#[test]
+fn test_unregistered_matchmaker_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let lobby = Address::generate(&env);
+    let points = 100_0000000;
+
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player2, &points, &points);
+    assert_dice_duel_error(&result, Error::NotMatchmaker);
+
+    // Removing a matchmaker revokes it
+    client.set_matchmaker(&lobby, &true);
+    client.set_matchmaker(&lobby, &false);
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player2, &points, &points);
+    assert_dice_duel_error(&result, Error::NotMatchmaker);
+
+    client.set_matchmaker(&lobby, &true);
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player1, &points, &points);
+    assert_dice_duel_error(&result, Error::SelfPlay);
+}
+
+// ============================================================================
+// Session Info Tests
+// ============================================================================
+
+#[test]
+fn test_session_info_tracks_betting_phase() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let session_id = 400u32;
+    let result = client.try_get_session_info(&session_id);
+    assert_dice_duel_error(&result, Error::GameNotFound);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    let info = client.get_session_info(&session_id);
+    assert_eq!(info.player1, player1);
+    assert_eq!(info.player2, player2);
+    assert!(info.awaiting_first_move);
+    assert_eq!(info.winner, None);
+
+    // The first roll closes the betting phase
+    client.roll(&session_id, &player2);
+    assert!(!client.get_session_info(&session_id).awaiting_first_move);
+
+    client.roll(&session_id, &player1);
+    let winner = client.reveal_winner(&session_id, &player1);
+    let info = client.get_session_info(&session_id);
+    assert!(!info.awaiting_first_move);
+    assert_eq!(info.winner, Some(winner));
+}
+
+// ============================================================================
+// Provable Fairness Tests
+// ============================================================================
+
+/// A player's dice recomputed natively from the session's public seed material
+fn native_roll(
+    client: &DiceDuelContractClient<'static>,
+    session_id: u32,
+    round: u32,
+    pig_roll: Option<(u32, &[BytesN<32>; 2])>,
+    player: u8,
+    rules: &DiceRules,
+) -> std::vec::Vec<u32> {
+    let material = client.get_seed_material(&session_id);
+    let player1 = material.player1.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let player2 = material.player2.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let session = Session {
+        session_id: material.session_id,
+        player1: player1.as_slice(),
+        player2: player2.as_slice(),
+    };
+    let seed = match pig_roll {
+        Some((roll, secrets)) => {
+            let [secret1, secret2] = secrets.clone().map(|secret| secret.to_array());
+            dice_duel::pig_roll_seed(&NativeKeccak, &session, round, roll, [&secret1, &secret2])
+        }
+        None => dice_duel::round_seed(&NativeKeccak, &session, round),
+    };
+    dice_duel::roll(&NativeKeccak, &seed, player, rules.dice, rules.sides).collect()
+}
+
+#[test]
+fn test_rounds_match_native_recomputation() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let points = 100_0000000;
+
+    for session_id in 500u32..540 {
+        let rules = DiceRules {
+            dice: session_id % 5 + 1,
+            sides: [2, 6, 20, 100][session_id as usize % 4],
+            scoring: Scoring::Sum,
+            target: 100,
+        };
+        let (best_of, custom) = (Some(3), Some(rules.clone()));
+        client.start_game(&session_id, &player1, &player2, &points, &points, &best_of, &custom);
+        let material = client.get_seed_material(&session_id);
+        assert_eq!(material.session_id, session_id);
+        assert_eq!(material.player1, player1.to_string());
+        assert_eq!(material.player2, player2.to_string());
+
+        let mut game = client.get_game(&session_id);
+        while game.winner.is_none() {
+            client.roll(&session_id, &player1);
+            client.roll(&session_id, &player2);
+            client.reveal_winner(&session_id, &player1);
+            game = client.get_game(&session_id);
+        }
+        for round in game.rounds.iter() {
+            let native = |seat| native_roll(&client, session_id, round.round, None, seat, &rules);
+            assert_eq!(round.player1_dice.iter().collect::<std::vec::Vec<_>>(), native(1));
+            assert_eq!(round.player2_dice.iter().collect::<std::vec::Vec<_>>(), native(2));
+        }
+    }
+}
+
+#[test]
+fn test_pig_rolls_match_native_recomputation() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let rules = DiceRules {
+        dice: 1,
+        sides: 6,
+        scoring: Scoring::Pig,
+        target: 20,
+    };
+
+    for session_id in 540u32..550 {
+        start_pig(&client, session_id, &player1, &player2, None, rules.target);
+        let mut game = client.get_game(&session_id);
+        while game.winner.is_none() {
+            let (player, seat) = if game.player1_turn { (&player1, 1) } else { (&player2, 2) };
+            let secrets = pig_roll(&env, &client, session_id, &player1, &player2);
+            game = client.get_game(&session_id);
+            let material = client.get_seed_material(&session_id);
+            assert_eq!(material.rolls, game.rolls);
+            let dice = if seat == 1 { &game.player1_dice } else { &game.player2_dice };
+            let pig_roll = Some((material.rolls, &secrets));
+            assert_eq!(
+                dice.iter().collect::<std::vec::Vec<_>>(),
+                native_roll(&client, session_id, material.round, pig_roll, seat, &rules)
+            );
+            if game.turn_total >= 8 {
+                client.bank(&session_id, player);
+                game = client.get_game(&session_id);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_seed_material_requires_game() {
+    let (_env, client, _hub, _player1, _player2) = setup_test();
+    assert_dice_duel_error(&client.try_get_seed_material(&560u32), Error::GameNotFound);
+}
+
+// ============================================================================
+// Session Key Tests
+// ============================================================================
+
+/// Register a fresh session key for `player`, scoped to `functions` (every move
+/// if empty)
+fn register_key(
+    env: &Env,
+    client: &DiceDuelContractClient<'static>,
+    session_id: u32,
+    player: &Address,
+    functions: &[&str],
+    expires_at: Option<u32>,
+) -> Address {
+    let key = Address::generate(env);
+    let mut scope = Vec::new(env);
+    for function in functions {
+        scope.push_back(Symbol::new(env, function));
+    }
+    client.register_session_key(&session_id, player, &key, &scope, &expires_at);
+    key
+}
+
+#[test]
+fn test_session_key_signs_moves() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 600u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+
+    let key1 = register_key(&env, &client, session_id, &player1, &[], None);
+    assert_eq!(
+        client.get_session_key(&session_id, &player1),
+        Some(SessionKey {
+            player: player1.clone(),
+            key: key1.clone(),
+            functions: Vec::new(&env),
+            expires_at: None,
+        })
+    );
+    assert_eq!(client.get_session_key(&session_id, &player2), None);
+
+    // The key signs in place of the player, and the move counts as theirs
+    client.roll(&session_id, &key1);
+    assert_eq!(env.auths().len(), 1);
+    assert_eq!(env.auths()[0].0, key1);
+    assert!(client.get_game(&session_id).player1_rolled);
+    assert_dice_duel_error(&client.try_roll(&session_id, &player1), Error::AlreadyRolled);
+
+    // Players can still move with their own signature
+    client.roll(&session_id, &player2);
+    assert_eq!(env.auths()[0].0, player2);
+    client.reveal_winner(&session_id, &player1);
+}
+
+#[test]
+fn test_session_key_scope_and_expiry() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 610u32;
+    start_pig(&client, session_id, &player1, &player2, None, 100);
+
+    let expires_at = env.ledger().sequence() + 10;
+    let moves = ["commit_roll", "reveal_roll"];
+    let key = register_key(&env, &client, session_id, &player1, &moves, Some(expires_at));
+
+    // Roll until a turn total is on the table: the key may roll, but not bank
+    let mut game = client.get_game(&session_id);
+    while game.player1_turn && game.turn_total == 0 {
+        pig_roll(&env, &client, session_id, &key, &player2);
+        game = client.get_game(&session_id);
+    }
+    if game.player1_turn {
+        assert_dice_duel_error(&client.try_bank(&session_id, &key), Error::SessionKeyNotAllowed);
+        client.bank(&session_id, &player1);
+    }
+
+    advance_ledgers(&env, 10);
+    let hash = roll_hash(&env, &roll_secret(&env, 1, 1, 0));
+    let result = client.try_commit_roll(&session_id, &key, &hash);
+    assert_dice_duel_error(&result, Error::SessionKeyExpired);
+}
+
+#[test]
+fn test_revoked_session_key_is_refused() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 620u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+
+    let key = register_key(&env, &client, session_id, &player1, &[], None);
+    client.revoke_session_key(&session_id, &player1);
+    assert_eq!(client.get_session_key(&session_id, &player1), None);
+    assert_dice_duel_error(&client.try_roll(&session_id, &key), Error::NotPlayer);
+    assert_dice_duel_error(
+        &client.try_revoke_session_key(&session_id, &player1),
+        Error::SessionKeyNotFound,
+    );
+
+    // Registering a new key revokes the previous one
+    let old_key = register_key(&env, &client, session_id, &player1, &[], None);
+    let new_key = register_key(&env, &client, session_id, &player1, &[], None);
+    assert_dice_duel_error(&client.try_roll(&session_id, &old_key), Error::NotPlayer);
+    client.roll(&session_id, &new_key);
+}
+
+#[test]
+fn test_invalid_session_keys_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 630u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    let none = Vec::new(&env);
+    let now = Some(env.ledger().sequence());
+
+    let key = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let result = client.try_register_session_key(&session_id, &outsider, &key, &none, &None);
+    assert_dice_duel_error(&result, Error::NotPlayer);
+    let result = client.try_register_session_key(&session_id, &player1, &player2, &none, &None);
+    assert_dice_duel_error(&result, Error::InvalidSessionKey);
+    let result = client.try_register_session_key(&session_id, &player1, &key, &none, &now);
+    assert_dice_duel_error(&result, Error::InvalidSessionKey);
+    let scope = Vec::from_array(&env, [Symbol::new(&env, "reveal_winner")]);
+    let result = client.try_register_session_key(&session_id, &player1, &key, &scope, &None);
+    assert_dice_duel_error(&result, Error::InvalidSessionKey);
+
+    // A key signs for one player only
+    client.register_session_key(&session_id, &player1, &key, &none, &None);
+    let result = client.try_register_session_key(&session_id, &player2, &key, &none, &None);
+    assert_dice_duel_error(&result, Error::InvalidSessionKey);
+}
+
+// ============================================================================
+// Rematch Tests
+// ============================================================================
+
+#[test]
+fn test_rematch_links_sessions_into_series() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let rules = DiceRules { dice: 3, sides: 8, scoring: Scoring::Highest, target: 0 };
+    let (best_of, dice_rules) = (Some(3), Some(rules.clone()));
+    client.start_game(&700, &player1, &player2, &100_0000000, &50_0000000, &best_of, &dice_rules);
+    while client.get_game(&700).winner.is_none() {
+        finish_game(&client, 700, &player1, &player2);
+    }
+    assert_eq!(client.get_series(&700), None);
+
+    // The winner's opponent asks for a rematch, which the other player accepts
+    client.propose_rematch(&700, &player2, &701, &false);
+    assert_eq!(
+        client.get_rematch(&700),
+        Some(RematchOffer { proposer: player2.clone(), new_session_id: 701, double_stakes: false })
+    );
+    assert_eq!(client.accept_rematch(&700, &player1), 701);
+    assert_eq!(env.auths()[0].0, player1);
+    assert_eq!(client.get_rematch(&700), None);
+
+    // Seats, stakes, match length and rules carry over
+    let rematch = client.get_game(&701);
+    assert_eq!((&rematch.player1, &rematch.player2), (&player1, &player2));
+    assert_eq!((rematch.player1_points, rematch.player2_points), (100_0000000, 50_0000000));
+    assert_eq!((rematch.best_of, rematch.rules), (3, rules));
+    assert_eq!(rematch.winner, None);
+
+    // The series scores the first game at once and the rematch when it ends
+    let first_won = client.get_winner(&700) == Some(player1.clone());
+    let series = client.get_series(&701).unwrap();
+    assert_eq!(client.get_series(&700), Some(series.clone()));
+    assert_eq!(series.sessions, Vec::from_array(&env, [700, 701]));
+    assert_eq!(series.player1_wins, u32::from(first_won));
+    assert_eq!(series.player2_wins, u32::from(!first_won));
+    while client.get_game(&701).winner.is_none() {
+        finish_game(&client, 701, &player1, &player2);
+    }
+    let second_won = client.get_winner(&701) == Some(player1.clone());
+    let series = client.get_series(&700).unwrap();
+    assert_eq!(
+        series,
+        Series {
+            player1: player1.clone(),
+            player2: player2.clone(),
+            sessions: Vec::from_array(&env, [700, 701]),
+            player1_wins: u32::from(first_won) + u32::from(second_won),
+            player2_wins: u32::from(!first_won) + u32::from(!second_won),
+        }
+    );
+
+    // A double-or-nothing rematch extends the same series
+    client.propose_rematch(&701, &player1, &702, &true);
+    client.accept_rematch(&701, &player2);
+    let rematch = client.get_game(&702);
+    assert_eq!((rematch.player1_points, rematch.player2_points), (200_0000000, 100_0000000));
+    assert_eq!(client.get_series(&702).unwrap().sessions, Vec::from_array(&env, [700, 701, 702]));
+}
+
+#[test]
+fn test_rematch_errors() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.start_game(&710, &player1, &player2, &100_0000000, &50_0000000, &None, &None);
+
+    assert_dice_duel_error(
+        &client.try_propose_rematch(&710, &player1, &711, &false),
+        Error::GameNotEnded,
+    );
+    finish_game(&client, 710, &player1, &player2);
+    let outsider = Address::generate(&env);
+    assert_dice_duel_error(
+        &client.try_propose_rematch(&710, &outsider, &711, &false),
+        Error::NotPlayer,
+    );
+    assert_dice_duel_error(
+        &client.try_propose_rematch(&710, &player1, &710, &false),
+        Error::SessionIdTaken,
+    );
+    assert_dice_duel_error(&client.try_accept_rematch(&710, &player2), Error::RematchNotFound);
+
+    // Only the other player can accept an offer
+    client.propose_rematch(&710, &player1, &711, &false);
+    assert_dice_duel_error(&client.try_accept_rematch(&710, &player1), Error::RematchNotFound);
+    assert_dice_duel_error(&client.try_accept_rematch(&710, &outsider), Error::NotPlayer);
+
+    // The offered session ID was taken before the offer was accepted
+    client.start_game(&711, &player1, &outsider, &10, &10, &None, &None);
+    assert_dice_duel_error(&client.try_accept_rematch(&710, &player2), Error::SessionIdTaken);
+
+    // Once rematched, only the latest session of the series can be
+    client.propose_rematch(&710, &player2, &712, &false);
+    client.accept_rematch(&710, &player1);
+    assert_dice_duel_error(
+        &client.try_propose_rematch(&710, &player1, &713, &false),
+        Error::RematchAlreadyPlayed,
+    );
+
+    // Doubled stakes must stay within the stake limits
+    client.set_stake_limits(&StakeLimits { min_stake: 1, max_stake: 150_0000000 });
+    client.start_game(&720, &player1, &player2, &100_0000000, &50_0000000, &None, &None);
+    finish_game(&client, 720, &player1, &player2);
+    assert_dice_duel_error(
+        &client.try_propose_rematch(&720, &player1, &721, &true),
+        Error::StakeOutOfRange,
+    );
+    client.propose_rematch(&720, &player1, &721, &false);
+}
+
+// ============================================================================
+// Stake Tests
+// ============================================================================
+
+#[test]
+fn test_non_positive_stakes_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    for (points1, points2) in [(0, 10), (10, 0), (-5, 10), (10, -1)] {
+        let result =
+            client.try_start_game(&800, &player1, &player2, &points1, &points2, &None, &None);
+        assert_dice_duel_error(&result, Error::InvalidStake);
+    }
+
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let result = client.try_start_matched_game(&lobby, &800, &player1, &player2, &0, &10);
+    assert_dice_duel_error(&result, Error::InvalidStake);
+}
+
+#[test]
+fn test_stake_limits() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let default_limits = StakeLimits { min_stake: 1, max_stake: i128::MAX / 2 };
+    assert_eq!(client.get_stake_limits(), default_limits);
+
+    let limits = StakeLimits { min_stake: 10, max_stake: 1_000 };
+    client.set_stake_limits(&limits);
+    assert_eq!(client.get_stake_limits(), limits);
+    for (points1, points2) in [(9, 100), (100, 1_001)] {
+        let result =
+            client.try_start_game(&810, &player1, &player2, &points1, &points2, &None, &None);
+        assert_dice_duel_error(&result, Error::StakeOutOfRange);
+    }
+    client.start_game(&810, &player1, &player2, &10, &1_000, &None, &None);
+
+    for (min_stake, max_stake) in [(0, 100), (101, 100), (1, i128::MAX / 2 + 1)] {
+        let result = client.try_set_stake_limits(&StakeLimits { min_stake, max_stake });
+        assert_dice_duel_error(&result, Error::InvalidStakeLimits);
+    }
+}
+
+#[test]
+fn test_asymmetric_stakes_matched_payout() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    client.start_game(&820, &player1, &player2, &200_0000000, &50_0000000, &None, &None);
+    let winner = finish_game(&client, 820, &player1, &player2);
+
+    // The winner takes the smaller stake from the loser; player 1's unmatched
+    // 150 goes back to them whoever wins
+    let result = client.get_match(&820);
+    if winner == player1 {
+        assert_eq!((result.player1_payout, result.player2_payout), (250_0000000, 0));
+    } else {
+        assert_eq!((result.player1_payout, result.player2_payout), (150_0000000, 100_0000000));
+    }
+}
@@ -0,0 +1,474 @@
+#![no_std]
+
+//! # Challenge Lobby
+//!
+//! Starting a game normally needs both players to sign the same transaction.
+//! The lobby splits that into two: player 1 posts a challenge and escrows a
+//! token stake, and any eligible player 2 accepts it later in a transaction of
+//! their own, matching the stake. Acceptance starts the game through its
+//! `start_matched_game` entry point, which in turn calls `GameHub::start_game`.
+//!
+//! Challenges nobody accepts can be cancelled by player 1, or expired by anyone
+//! once their expiry ledger has passed; both refund the stake. Once the game
+//! has a winner, anyone can `claim` the challenge to pay out both stakes as
+//! the game settled them: to the winner, less the rewards of any keepers that
+//! settled the game, which the lobby pays them too. A game that is still
+//! unfinished `GAME_DEADLINE_LEDGERS` after acceptance can be refunded by
+//! anyone with `refund_stale`, which returns both stakes.
+//!
+//! **Game Integration:**
+//! The lobby only works with games whose admin registered it with
+//! `set_matchmaker(lobby, true)`. Players choose the game contract when they
+//! post or accept a challenge, so they trust it to report the winner.
+
+use matchmaking::{MatchedGameClient, escrow};
+use soroban_sdk::{
+    Address, BytesN, Env, Vec, contract, contracterror, contractimpl, contracttype, token
+};
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors returned by the contract.
+///
+/// Codes are stable: clients match on them, so existing variants are never
+/// renumbered or reused and new failures get the next free code.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// No challenge exists with that id
+    ChallengeNotFound = 1,
+    /// The stake is zero or negative
+    InvalidStake = 2,
+    /// The expiry ledger has passed or is further out than `MAX_CHALLENGE_LEDGERS`
+    InvalidExpiry = 3,
+    /// The challenge is no longer open (accepted, cancelled, expired or claimed)
+    ChallengeClosed = 4,
+    /// The challenge expired before it was accepted
+    ChallengeExpired = 5,
+    /// `expire` was called before the expiry ledger passed
+    ChallengeNotExpired = 6,
+    /// The challenge is reserved for another opponent, or player 1 tried to accept it
+    NotEligible = 7,
+    /// Only the player who posted the challenge may cancel it
+    NotChallenger = 8,
+    /// `claim` was called on a challenge that was never accepted
+    NotAccepted = 9,
+    /// The game has not reported a winner yet
+    GameNotFinished = 10,
+    /// The stake token address is missing from instance storage
+    TokenNotSet = 11,
+    /// The admin address is missing from instance storage
+    AdminNotSet = 12,
+    /// The game's settlement has a negative amount or does not add up to both stakes
+    InvalidSettlement = 13,
+    /// `refund_stale` was called before the claim deadline passed
+    ClaimDeadlineNotPassed = 14,
+    /// `refund_stale` was called on a game that has a winner; claim it instead
+    GameFinished = 15,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ChallengeStatus {
+    /// Waiting for an opponent; player 1's stake is escrowed
+    Open = 0,
+    /// The game started; both stakes are escrowed until `claim`
+    Accepted = 1,
+    /// Player 1 withdrew the challenge and got their stake back
+    Cancelled = 2,
+    /// Nobody accepted in time and player 1 got their stake back
+    Expired = 3,
+    /// Both stakes were paid out to the winner and any keepers
+    Claimed = 4,
+    /// The game was unfinished at the claim deadline and both stakes were refunded
+    Refunded = 5,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Challenge {
+    pub challenge_id: u32,
+    /// Game contract the match is played on
+    pub game: Address,
+    /// Session id the game is started with
+    pub session_id: u32,
+    pub player1: Address,
+    /// Only this address may accept, when set
+    pub opponent: Option<Address>,
+    /// Player who accepted (set on acceptance)
+    pub player2: Option<Address>,
+    /// Tokens escrowed by each player, also committed as their game points
+    pub stake: i128,
+    /// Last ledger at which the challenge can be accepted
+    pub expiry_ledger: u32,
+    /// Last ledger before an unfinished game can be refunded (set on acceptance)
+    pub claim_deadline: Option<u32>,
+    pub status: ChallengeStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    ChallengeCounter,
+    Challenge(u32),                // challenge_id -> Challenge
+}
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// Challenges hold escrowed tokens, so they live in persistent storage and are
+// kept alive for the whole window in which they can still be settled
+
+/// TTL for challenges (30 days in ledgers, ~5 seconds per ledger)
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+const CHALLENGE_TTL_LEDGERS: u32 = 518_400;
+
+/// Longest a challenge may stay open (7 days in ledgers)
+pub const MAX_CHALLENGE_LEDGERS: u32 = 120_960;
+
+/// How long an accepted game has to finish before it can be refunded (7 days in ledgers)
+pub const GAME_DEADLINE_LEDGERS: u32 = 120_960;
+
+/// Store a challenge and extend its TTL
+fn save_challenge(env: &Env, challenge: &Challenge) {
+    let key = DataKey::Challenge(challenge.challenge_id);
+    escrow::save(env, &key, challenge, CHALLENGE_TTL_LEDGERS);
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn load_challenge(env: &Env, challenge_id: u32) -> Result<Challenge, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Challenge(challenge_id))
+        .ok_or(Error::ChallengeNotFound)
+}
+
+fn stake_token(env: &Env) -> Result<token::Client<'_>, Error> {
+    escrow::token(env).ok_or(Error::TokenNotSet)
+}
+
+/// Return player 1's stake and close an open challenge with `status`
+fn refund(env: &Env, challenge: &mut Challenge, status: ChallengeStatus) -> Result<(), Error> {
+    stake_token(env)?.transfer(
+        &env.current_contract_address(),
+        &challenge.player1,
+        &challenge.stake,
+    );
+    challenge.status = status;
+    save_challenge(env, challenge);
+    Ok(())
+}
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct LobbyContract;
+
+#[contractimpl]
+impl LobbyContract {
+    /// Initialize the contract with an admin and the stake token
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can upgrade contract)
+    /// * `token` - Token contract stakes are escrowed in
+    pub fn __constructor(env: Env, admin: Address, token: Address) {
+        escrow::initialize(&env, &admin, &token);
+    }
+
+    /// Post a challenge and escrow the stake.
+    ///
+    /// # Arguments
+    /// * `player1` - Player posting the challenge
+    /// * `game` - Game contract to play on (must have registered this lobby)
+    /// * `session_id` - Session id the game will be started with
+    /// * `stake` - Tokens each player puts up; the winner takes both
+    /// * `opponent` - Only this player may accept (anyone else if `None`)
+    /// * `expiry_ledger` - Last ledger at which the challenge can be accepted
+    ///
+    /// # Returns
+    /// * `u32` - The new challenge id
+    pub fn post_challenge(
+        env: Env,
+        player1: Address,
+        game: Address,
+        session_id: u32,
+        stake: i128,
+        opponent: Option<Address>,
+        expiry_ledger: u32,
+    ) -> Result<u32, Error> {
+        player1.require_auth();
+        escrow::extend_instance_ttl(&env);
+
+        if stake <= 0 {
+            return Err(Error::InvalidStake);
+        }
+        let now = env.ledger().sequence();
+        if expiry_ledger < now || expiry_ledger - now > MAX_CHALLENGE_LEDGERS {
+            return Err(Error::InvalidExpiry);
+        }
+        if opponent.as_ref() == Some(&player1) {
+            return Err(Error::NotEligible);
+        }
+
+        stake_token(&env)?.transfer(&player1, env.current_contract_address(), &stake);
+
+        let challenge_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::ChallengeCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::ChallengeCounter, &challenge_id);
+
+        save_challenge(
+            &env,
+            &Challenge {
+                challenge_id,
+                game,
+                session_id,
+                player1,
+                opponent,
+                player2: None,
+                stake,
+                expiry_ledger,
+                claim_deadline: None,
+                status: ChallengeStatus::Open,
+            },
+        );
+
+        Ok(challenge_id)
+    }
+
+    /// Accept an open challenge, escrow the matching stake and start the game.
+    ///
+    /// # Arguments
+    /// * `challenge_id` - The challenge to accept
+    /// * `player2` - Accepting player
+    pub fn accept(env: Env, challenge_id: u32, player2: Address) -> Result<(), Error> {
+        player2.require_auth();
+        escrow::extend_instance_ttl(&env);
+
+        let mut challenge = load_challenge(&env, challenge_id)?;
+        if challenge.status != ChallengeStatus::Open {
+            return Err(Error::ChallengeClosed);
+        }
+        if env.ledger().sequence() > challenge.expiry_ledger {
+            return Err(Error::ChallengeExpired);
+        }
+        if player2 == challenge.player1
+            || challenge.opponent.as_ref().is_some_and(|opponent| *opponent != player2)
+        {
+            return Err(Error::NotEligible);
+        }
+
+        stake_token(&env)?.transfer(&player2, env.current_contract_address(), &challenge.stake);
+
+        // The game checks this lobby is a registered matchmaker and calls
+        // GameHub::start_game; any failure there rolls the acceptance back
+        MatchedGameClient::new(&env, &challenge.game).start_matched_game(
+            &env.current_contract_address(),
+            &challenge.session_id,
+            &challenge.player1,
+            &player2,
+            &challenge.stake,
+            &challenge.stake,
+        );
+
+        challenge.player2 = Some(player2);
+        challenge.claim_deadline = Some(env.ledger().sequence() + GAME_DEADLINE_LEDGERS);
+        challenge.status = ChallengeStatus::Accepted;
+        save_challenge(&env, &challenge);
+
+        Ok(())
+    }
+
+    /// Withdraw an open challenge and refund the stake.
+    ///
+    /// # Arguments
+    /// * `challenge_id` - The challenge to cancel
+    /// * `player1` - Player who posted it
+    pub fn cancel(env: Env, challenge_id: u32, player1: Address) -> Result<(), Error> {
+        player1.require_auth();
+        escrow::extend_instance_ttl(&env);
+
+        let mut challenge = load_challenge(&env, challenge_id)?;
+        if challenge.player1 != player1 {
+            return Err(Error::NotChallenger);
+        }
+        if challenge.status != ChallengeStatus::Open {
+            return Err(Error::ChallengeClosed);
+        }
+
+        refund(&env, &mut challenge, ChallengeStatus::Cancelled)
+    }
+
+    /// Close a challenge nobody accepted in time and refund the stake.
+    /// Anyone may call this once the expiry ledger has passed.
+    ///
+    /// # Arguments
+    /// * `challenge_id` - The challenge to expire
+    pub fn expire(env: Env, challenge_id: u32) -> Result<(), Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut challenge = load_challenge(&env, challenge_id)?;
+        if challenge.status != ChallengeStatus::Open {
+            return Err(Error::ChallengeClosed);
+        }
+        if env.ledger().sequence() <= challenge.expiry_ledger {
+            return Err(Error::ChallengeNotExpired);
+        }
+
+        refund(&env, &mut challenge, ChallengeStatus::Expired)
+    }
+
+    /// Pay out both stakes of an accepted challenge as its game settled them:
+    /// each player's payout, and the reward of every keeper that settled the
+    /// game. Anyone may call this once the game reports a winner.
+    ///
+    /// # Arguments
+    /// * `challenge_id` - The challenge to settle
+    ///
+    /// # Returns
+    /// * `Address` - The winner who was paid
+    pub fn claim(env: Env, challenge_id: u32) -> Result<Address, Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut challenge = load_challenge(&env, challenge_id)?;
+        match challenge.status {
+            ChallengeStatus::Accepted => {}
+            ChallengeStatus::Open => return Err(Error::NotAccepted),
+            _ => return Err(Error::ChallengeClosed),
+        }
+
+        let settlement = MatchedGameClient::new(&env, &challenge.game)
+            .get_settlement(&challenge.session_id)
+            .ok_or(Error::GameNotFinished)?;
+        let player2 = challenge.player2.clone().ok_or(Error::NotAccepted)?;
+
+        // Only ever pay out what this challenge escrowed
+        if settlement.total() != Some(challenge.stake * 2) {
+            return Err(Error::InvalidSettlement);
+        }
+        let mut payments = Vec::new(&env);
+        payments.push_back((challenge.player1.clone(), settlement.player1_payout));
+        payments.push_back((player2, settlement.player2_payout));
+        for reward in settlement.keeper_rewards.iter() {
+            payments.push_back((reward.keeper, reward.amount));
+        }
+
+        let token = stake_token(&env)?;
+        for (recipient, amount) in payments.iter() {
+            if amount > 0 {
+                token.transfer(&env.current_contract_address(), &recipient, &amount);
+            }
+        }
+
+        challenge.status = ChallengeStatus::Claimed;
+        save_challenge(&env, &challenge);
+
+        Ok(settlement.winner)
+    }
+
+    /// Refund both stakes of an accepted challenge whose game never finished.
+    /// Anyone may call this once the claim deadline has passed, as long as the
+    /// game still has no winner.
+    ///
+    /// # Arguments
+    /// * `challenge_id` - The challenge to refund
+    pub fn refund_stale(env: Env, challenge_id: u32) -> Result<(), Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut challenge = load_challenge(&env, challenge_id)?;
+        match challenge.status {
+            ChallengeStatus::Accepted => {}
+            ChallengeStatus::Open => return Err(Error::NotAccepted),
+            _ => return Err(Error::ChallengeClosed),
+        }
+        let claim_deadline = challenge.claim_deadline.ok_or(Error::NotAccepted)?;
+        if env.ledger().sequence() <= claim_deadline {
+            return Err(Error::ClaimDeadlineNotPassed);
+        }
+        let game = MatchedGameClient::new(&env, &challenge.game);
+        if game.get_settlement(&challenge.session_id).is_some() {
+            return Err(Error::GameFinished);
+        }
+        let player2 = challenge.player2.clone().ok_or(Error::NotAccepted)?;
+
+        let token = stake_token(&env)?;
+        for player in [&challenge.player1, &player2] {
+            token.transfer(&env.current_contract_address(), player, &challenge.stake);
+        }
+
+        challenge.status = ChallengeStatus::Refunded;
+        save_challenge(&env, &challenge);
+
+        Ok(())
+    }
+
+    /// Get a challenge
+    ///
+    /// # Arguments
+    /// * `challenge_id` - The challenge id returned by `post_challenge`
+    pub fn get_challenge(env: Env, challenge_id: u32) -> Result<Challenge, Error> {
+        load_challenge(&env, challenge_id)
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        escrow::admin(&env).ok_or(Error::AdminNotSet)
+    }
+
+    /// Set a new admin address
+    ///
+    /// # Arguments
+    /// * `new_admin` - The new admin address
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        escrow::require_admin(&env).ok_or(Error::AdminNotSet)?;
+        escrow::set_admin(&env, &new_admin);
+
+        Ok(())
+    }
+
+    /// Get the token stakes are escrowed in
+    pub fn get_token(env: Env) -> Result<Address, Error> {
+        escrow::token_address(&env).ok_or(Error::TokenNotSet)
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        escrow::require_admin(&env).ok_or(Error::AdminNotSet)?;
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
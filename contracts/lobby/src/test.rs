@@ -0,0 +1,300 @@
+#![cfg(test)]
+
+// Unit tests for the lobby contract, played on a real dice-duel contract with a
+// simple mock GameHub and a Stellar asset as the stake token.
+
+use crate::{ChallengeStatus, Error, LobbyContract, LobbyContractClient};
+use dice_duel::{DiceDuelContract, DiceDuelContractClient, SettlementConfig, SettlementMode};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+// ============================================================================
+// Mock GameHub for Unit Testing
+// ============================================================================
+
+#[contract]
+pub struct MockGameHub;
+
+#[contractimpl]
+impl MockGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+        // Mock implementation - does nothing
+    }
+
+    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+        // Mock implementation - does nothing
+    }
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const STAKE: i128 = 100_0000000;
+const BALANCE: i128 = 10 * STAKE;
+
+struct Setup {
+    env: Env,
+    lobby: LobbyContractClient<'static>,
+    game: DiceDuelContractClient<'static>,
+    token: TokenClient<'static>,
+    player1: Address,
+    player2: Address,
+}
+
+fn setup_test() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(100);
+
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(MockGameHub, ());
+    let game_addr = env.register(DiceDuelContract, (&admin, &hub_addr));
+    let game = DiceDuelContractClient::new(&env, &game_addr);
+
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let lobby_addr = env.register(LobbyContract, (&admin, &token_addr));
+    let lobby = LobbyContractClient::new(&env, &lobby_addr);
+
+    // The game trusts the lobby to start matches for its players
+    game.set_matchmaker(&lobby_addr, &true);
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    let asset = StellarAssetClient::new(&env, &token_addr);
+    asset.mint(&player1, &BALANCE);
+    asset.mint(&player2, &BALANCE);
+    let token = TokenClient::new(&env, &token_addr);
+
+    Setup {
+        env,
+        lobby,
+        game,
+        token,
+        player1,
+        player2,
+    }
+}
+
+/// Post an open challenge on session 1 that expires in 100 ledgers
+fn post_open(s: &Setup) -> u32 {
+    let expiry = s.env.ledger().sequence() + 100;
+    s.lobby
+        .post_challenge(&s.player1, &s.game.address, &1u32, &STAKE, &None, &expiry)
+}
+
+// ============================================================================
+// Challenge Lifecycle Tests
+// ============================================================================
+
+#[test]
+fn test_accept_starts_game_and_claim_pays_winner() {
+    let s = setup_test();
+
+    let challenge_id = post_open(&s);
+    assert_eq!(s.token.balance(&s.player1), BALANCE - STAKE);
+    assert_eq!(s.token.balance(&s.lobby.address), STAKE);
+    assert_eq!(s.lobby.get_challenge(&challenge_id).status, ChallengeStatus::Open);
+
+    // Player 2 accepts in a transaction of their own
+    s.lobby.accept(&challenge_id, &s.player2);
+    let challenge = s.lobby.get_challenge(&challenge_id);
+    assert_eq!(challenge.status, ChallengeStatus::Accepted);
+    assert_eq!(challenge.player2, Some(s.player2.clone()));
+    assert_eq!(s.token.balance(&s.lobby.address), 2 * STAKE);
+
+    let game = s.game.get_game(&1u32);
+    assert_eq!(game.player1, s.player1);
+    assert_eq!(game.player2, s.player2);
+    assert_eq!(game.player1_points, STAKE);
+
+    // Nothing to claim until the game has a winner
+    assert_eq!(s.lobby.try_claim(&challenge_id), Err(Ok(Error::GameNotFinished)));
+
+    s.game.roll(&1u32, &s.player1);
+    s.game.roll(&1u32, &s.player2);
+    let winner = s.game.reveal_winner(&1u32, &s.player1);
+
+    assert_eq!(s.lobby.claim(&challenge_id), winner);
+    assert_eq!(s.token.balance(&winner), BALANCE + STAKE);
+    assert_eq!(s.token.balance(&s.lobby.address), 0);
+    assert_eq!(s.lobby.get_challenge(&challenge_id).status, ChallengeStatus::Claimed);
+    assert_eq!(s.lobby.try_claim(&challenge_id), Err(Ok(Error::ChallengeClosed)));
+}
+
+#[test]
+fn test_claim_pays_keeper_that_settled_the_game() {
+    let s = setup_test();
+    s.game.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+
+    let challenge_id = post_open(&s);
+    s.lobby.accept(&challenge_id, &s.player2);
+    s.game.roll(&1u32, &s.player1);
+    s.game.roll(&1u32, &s.player2);
+
+    // Nobody settles within the players' head start, so a keeper does
+    let keeper = Address::generate(&s.env);
+    let sequence = s.env.ledger().sequence();
+    s.env.ledger().set_sequence_number(sequence + 10);
+    let winner = s.game.reveal_winner(&1u32, &keeper);
+
+    // 1% of the matched pot comes out of the winnings and goes to the keeper
+    let reward = 2 * STAKE / 100;
+    assert_eq!(s.lobby.claim(&challenge_id), winner);
+    assert_eq!(s.token.balance(&keeper), reward);
+    assert_eq!(s.token.balance(&winner), BALANCE + STAKE - reward);
+    assert_eq!(s.token.balance(&s.lobby.address), 0);
+    assert_eq!(s.game.get_keeper_earnings(&keeper), reward);
+}
+
+#[test]
+fn test_cancel_refunds_stake() {
+    let s = setup_test();
+    let challenge_id = post_open(&s);
+
+    let result = s.lobby.try_cancel(&challenge_id, &s.player2);
+    assert_eq!(result, Err(Ok(Error::NotChallenger)));
+
+    s.lobby.cancel(&challenge_id, &s.player1);
+    assert_eq!(s.token.balance(&s.player1), BALANCE);
+    assert_eq!(s.lobby.get_challenge(&challenge_id).status, ChallengeStatus::Cancelled);
+
+    let result = s.lobby.try_accept(&challenge_id, &s.player2);
+    assert_eq!(result, Err(Ok(Error::ChallengeClosed)));
+    assert_eq!(s.lobby.try_claim(&challenge_id), Err(Ok(Error::ChallengeClosed)));
+}
+
+#[test]
+fn test_expired_challenge_refunds_stake() {
+    let s = setup_test();
+    let challenge_id = post_open(&s);
+    let expiry = s.lobby.get_challenge(&challenge_id).expiry_ledger;
+
+    // Still acceptable on the expiry ledger itself
+    s.env.ledger().set_sequence_number(expiry);
+    let result = s.lobby.try_expire(&challenge_id);
+    assert_eq!(result, Err(Ok(Error::ChallengeNotExpired)));
+
+    s.env.ledger().set_sequence_number(expiry + 1);
+    let result = s.lobby.try_accept(&challenge_id, &s.player2);
+    assert_eq!(result, Err(Ok(Error::ChallengeExpired)));
+    assert_eq!(s.token.balance(&s.player2), BALANCE);
+
+    s.lobby.expire(&challenge_id);
+    assert_eq!(s.token.balance(&s.player1), BALANCE);
+    assert_eq!(s.lobby.get_challenge(&challenge_id).status, ChallengeStatus::Expired);
+    assert_eq!(s.lobby.try_expire(&challenge_id), Err(Ok(Error::ChallengeClosed)));
+}
+
+#[test]
+fn test_unfinished_game_refunded_after_claim_deadline() {
+    let s = setup_test();
+    let challenge_id = post_open(&s);
+    assert_eq!(s.lobby.try_refund_stale(&challenge_id), Err(Ok(Error::NotAccepted)));
+
+    s.lobby.accept(&challenge_id, &s.player2);
+    s.game.roll(&1u32, &s.player1);
+    let deadline = s.lobby.get_challenge(&challenge_id).claim_deadline.unwrap();
+    assert_eq!(deadline, s.env.ledger().sequence() + crate::GAME_DEADLINE_LEDGERS);
+
+    // Player 2 never rolls, so the game never ends
+    s.env.ledger().set_sequence_number(deadline);
+    let result = s.lobby.try_refund_stale(&challenge_id);
+    assert_eq!(result, Err(Ok(Error::ClaimDeadlineNotPassed)));
+
+    s.env.ledger().set_sequence_number(deadline + 1);
+    s.lobby.refund_stale(&challenge_id);
+    assert_eq!(s.token.balance(&s.player1), BALANCE);
+    assert_eq!(s.token.balance(&s.player2), BALANCE);
+    assert_eq!(s.token.balance(&s.lobby.address), 0);
+    assert_eq!(s.lobby.get_challenge(&challenge_id).status, ChallengeStatus::Refunded);
+    assert_eq!(s.lobby.try_claim(&challenge_id), Err(Ok(Error::ChallengeClosed)));
+}
+
+#[test]
+fn test_finished_game_not_refunded_after_claim_deadline() {
+    let s = setup_test();
+    let challenge_id = post_open(&s);
+    s.lobby.accept(&challenge_id, &s.player2);
+    s.game.roll(&1u32, &s.player1);
+    s.game.roll(&1u32, &s.player2);
+    let winner = s.game.reveal_winner(&1u32, &s.player1);
+
+    // A game that has a winner is claimed, however late
+    let deadline = s.lobby.get_challenge(&challenge_id).claim_deadline.unwrap();
+    s.env.ledger().set_sequence_number(deadline + 1);
+    assert_eq!(s.lobby.try_refund_stale(&challenge_id), Err(Ok(Error::GameFinished)));
+    assert_eq!(s.lobby.claim(&challenge_id), winner);
+}
+
+#[test]
+fn test_challenge_reserved_for_opponent() {
+    let s = setup_test();
+    let other = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&other, &BALANCE);
+
+    let expiry = s.env.ledger().sequence() + 100;
+    let opponent = Some(s.player2.clone());
+    let challenge_id =
+        s.lobby
+            .post_challenge(&s.player1, &s.game.address, &1u32, &STAKE, &opponent, &expiry);
+
+    let result = s.lobby.try_accept(&challenge_id, &other);
+    assert_eq!(result, Err(Ok(Error::NotEligible)));
+    let result = s.lobby.try_accept(&challenge_id, &s.player1);
+    assert_eq!(result, Err(Ok(Error::NotEligible)));
+
+    s.lobby.accept(&challenge_id, &s.player2);
+    assert_eq!(s.game.get_game(&1u32).player2, s.player2);
+}
+
+#[test]
+fn test_invalid_challenges_rejected() {
+    let s = setup_test();
+    let game = &s.game.address;
+    let now = s.env.ledger().sequence();
+
+    let result = s.lobby.try_post_challenge(&s.player1, game, &1u32, &0, &None, &(now + 1));
+    assert_eq!(result, Err(Ok(Error::InvalidStake)));
+
+    let result = s.lobby.try_post_challenge(&s.player1, game, &1u32, &STAKE, &None, &(now - 1));
+    assert_eq!(result, Err(Ok(Error::InvalidExpiry)));
+
+    let too_late = now + crate::MAX_CHALLENGE_LEDGERS + 1;
+    let result = s.lobby.try_post_challenge(&s.player1, game, &1u32, &STAKE, &None, &too_late);
+    assert_eq!(result, Err(Ok(Error::InvalidExpiry)));
+
+    let own = Some(s.player1.clone());
+    let result = s.lobby.try_post_challenge(&s.player1, game, &1u32, &STAKE, &own, &(now + 1));
+    assert_eq!(result, Err(Ok(Error::NotEligible)));
+
+    assert_eq!(s.lobby.try_get_challenge(&99u32), Err(Ok(Error::ChallengeNotFound)));
+    assert_eq!(s.token.balance(&s.player1), BALANCE);
+}
+
+#[test]
+fn test_accept_rolls_back_when_lobby_not_registered() {
+    let s = setup_test();
+    let challenge_id = post_open(&s);
+    s.game.set_matchmaker(&s.lobby.address, &false);
+
+    // The game rejects the lobby, so the whole acceptance is undone
+    assert!(s.lobby.try_accept(&challenge_id, &s.player2).is_err());
+    assert_eq!(s.token.balance(&s.player2), BALANCE);
+    assert_eq!(s.lobby.get_challenge(&challenge_id).status, ChallengeStatus::Open);
+}
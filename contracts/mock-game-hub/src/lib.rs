@@ -1,15 +1,40 @@
 #![no_std]
 
-use soroban_sdk::{contract, contractevent, contractimpl, Address, Env};
+use soroban_sdk::{
+    contract, contracterror, contractevent, contractimpl, contracttype, Address, Env, Vec,
+};
 
 /// Mock Game Hub contract for game studio development
 ///
 /// This contract provides the same external interface that games expect
-/// (start_game, end_game) but does nothing internally. It exists purely
-/// for game contracts to compile and integrate during development.
+/// (start_game, end_game and the multi-player start_multi_game, end_multi_game)
+/// but does nothing internally beyond checking its inputs: it only remembers who
+/// started a multi-player session, so the final standings can be checked
+/// against them. It exists purely for game contracts to compile and integrate
+/// during development.
 #[contract]
 pub struct MockGameHub;
 
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// A player staked zero or negative points
+    InvalidPoints = 1,
+    /// `end_multi_game` for a session `start_multi_game` never started
+    SessionNotFound = 2,
+    /// `ranking` and `payouts` differ in length
+    PayoutsMismatch = 3,
+    /// `ranking` is not an ordering of the players the session started with
+    RankingMismatch = 4,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    MultiGame(u32), // session_id -> players the session started with
+}
+
 #[contractevent]
 pub struct GameStarted {
     pub session_id: u32,
@@ -26,6 +51,21 @@ pub struct GameEnded {
     pub player1_won: bool,
 }
 
+#[contractevent]
+pub struct MultiGameStarted {
+    pub session_id: u32,
+    pub game_id: Address,
+    pub players: Vec<Address>,
+    pub points: Vec<i128>,
+}
+
+#[contractevent]
+pub struct MultiGameEnded {
+    pub session_id: u32,
+    pub ranking: Vec<Address>,
+    pub payouts: Vec<i128>,
+}
+
 #[contractimpl]
 impl MockGameHub {
     /// Start a game session
@@ -35,8 +75,8 @@ impl MockGameHub {
     /// * `session_id` - Unique identifier for this game session
     /// * `player1` - Address of first player
     /// * `player2` - Address of second player
-    /// * `player1_points` - Points amount for player 1 (ignored in mock)
-    /// * `player2_points` - Points amount for player 2 (ignored in mock)
+    /// * `player1_points` - Points amount for player 1, must be positive
+    /// * `player2_points` - Points amount for player 2, must be positive
     pub fn start_game(
         env: Env,
         game_id: Address,
@@ -45,7 +85,10 @@ impl MockGameHub {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
-    ) {
+    ) -> Result<(), Error> {
+        if player1_points <= 0 || player2_points <= 0 {
+            return Err(Error::InvalidPoints);
+        }
         // No auth required for mock
         GameStarted {
             session_id,
@@ -58,6 +101,7 @@ impl MockGameHub {
         .publish(&env);
         // bump instance ttl if required
         env.storage().instance().extend_ttl(17_280, 518_400);
+        Ok(())
     }
 
     /// End a game session and declare winner
@@ -77,6 +121,75 @@ impl MockGameHub {
         }
         .publish(&env);
     }
+
+    /// Start a game session with more than two players
+    ///
+    /// # Arguments
+    /// * `game_id` - Address of the game contract calling this method
+    /// * `session_id` - Unique identifier for this game session
+    /// * `players` - Addresses of the players, in seat order
+    /// * `points` - Points amount for each player, all must be positive
+    pub fn start_multi_game(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    ) -> Result<(), Error> {
+        if points.iter().any(|amount| amount <= 0) {
+            return Err(Error::InvalidPoints);
+        }
+        // No auth required for mock
+        let key = DataKey::MultiGame(session_id);
+        env.storage().temporary().set(&key, &players);
+        MultiGameStarted {
+            session_id,
+            game_id,
+            players,
+            points,
+        }
+        .publish(&env);
+        env.storage().instance().extend_ttl(17_280, 518_400);
+        Ok(())
+    }
+
+    /// End a multi-player game session with its final standings
+    ///
+    /// # Arguments
+    /// * `session_id` - The game session being ended
+    /// * `ranking` - The started players ordered by finishing place, winner first
+    /// * `payouts` - Points paid to each place in `ranking`, one per place
+    pub fn end_multi_game(
+        env: Env,
+        session_id: u32,
+        ranking: Vec<Address>,
+        payouts: Vec<i128>,
+    ) -> Result<(), Error> {
+        let key = DataKey::MultiGame(session_id);
+        let players: Vec<Address> = env
+            .storage()
+            .temporary()
+            .get(&key)
+            .ok_or(Error::SessionNotFound)?;
+        if ranking.len() != payouts.len() {
+            return Err(Error::PayoutsMismatch);
+        }
+        let ranks_everyone = ranking.len() == players.len()
+            && players.iter().all(|player| ranking.contains(&player));
+        if !ranks_everyone {
+            return Err(Error::RankingMismatch);
+        }
+        env.storage().temporary().remove(&key);
+
+        // No auth required for mock
+        MultiGameEnded {
+            session_id,
+            ranking,
+            payouts,
+        }
+        .publish(&env);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -84,6 +197,7 @@ mod test {
     use super::*;
     use soroban_sdk::testutils::Address as _;
     #[test]
+    #[allow(deprecated)]
     fn test_start_and_end_game() {
         let env = Env::default();
         let contract_id = env.register_contract(None, MockGameHub);
@@ -94,4 +208,89 @@ mod test {
         client.start_game(&game_id, &1, &player1, &player2, &1000, &1000);
         client.end_game(&1, &true);
     }
+
+    #[test]
+    fn test_start_and_end_multi_game() {
+        let env = Env::default();
+        let contract_id = env.register(MockGameHub, ());
+        let client = MockGameHubClient::new(&env, &contract_id);
+        let game_id = Address::generate(&env);
+        let players = Vec::from_array(
+            &env,
+            [
+                Address::generate(&env),
+                Address::generate(&env),
+                Address::generate(&env),
+            ],
+        );
+        let points = Vec::from_array(&env, [100i128, 100, 100]);
+        client.start_multi_game(&game_id, &2, &players, &points);
+        client.end_multi_game(&2, &players, &Vec::from_array(&env, [210i128, 90, 0]));
+    }
+
+    #[test]
+    fn test_end_multi_game_checks_standings() {
+        let env = Env::default();
+        let contract_id = env.register(MockGameHub, ());
+        let client = MockGameHubClient::new(&env, &contract_id);
+        let game_id = Address::generate(&env);
+        let players = Vec::from_array(
+            &env,
+            [
+                Address::generate(&env),
+                Address::generate(&env),
+                Address::generate(&env),
+            ],
+        );
+        let payouts = Vec::from_array(&env, [210i128, 90, 0]);
+        let result = client.try_end_multi_game(&2, &players, &payouts);
+        assert_eq!(result, Err(Ok(Error::SessionNotFound)));
+
+        let points = Vec::from_array(&env, [100i128, 100, 100]);
+        client.start_multi_game(&game_id, &2, &players, &points);
+        let short = Vec::from_array(&env, [210i128, 90]);
+        let result = client.try_end_multi_game(&2, &players, &short);
+        assert_eq!(result, Err(Ok(Error::PayoutsMismatch)));
+
+        // Every started player is ranked exactly once
+        let mut stranger = players.clone();
+        stranger.set(2, Address::generate(&env));
+        let result = client.try_end_multi_game(&2, &stranger, &payouts);
+        assert_eq!(result, Err(Ok(Error::RankingMismatch)));
+        let mut twice = players.clone();
+        twice.set(2, players.get(0).unwrap());
+        let result = client.try_end_multi_game(&2, &twice, &payouts);
+        assert_eq!(result, Err(Ok(Error::RankingMismatch)));
+
+        let ranking = Vec::from_array(
+            &env,
+            [
+                players.get(2).unwrap(),
+                players.get(0).unwrap(),
+                players.get(1).unwrap(),
+            ],
+        );
+        client.end_multi_game(&2, &ranking, &payouts);
+        let result = client.try_end_multi_game(&2, &ranking, &payouts);
+        assert_eq!(result, Err(Ok(Error::SessionNotFound)));
+    }
+
+    #[test]
+    fn test_non_positive_points_rejected() {
+        let env = Env::default();
+        let contract_id = env.register(MockGameHub, ());
+        let client = MockGameHubClient::new(&env, &contract_id);
+        let game_id = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+        let result = client.try_start_game(&game_id, &1, &player1, &player2, &0, &1000);
+        assert_eq!(result, Err(Ok(Error::InvalidPoints)));
+        let result = client.try_start_game(&game_id, &1, &player1, &player2, &1000, &-5);
+        assert_eq!(result, Err(Ok(Error::InvalidPoints)));
+
+        let players = Vec::from_array(&env, [player1, player2, Address::generate(&env)]);
+        let points = Vec::from_array(&env, [100i128, 0, 100]);
+        let result = client.try_start_multi_game(&game_id, &2, &players, &points);
+        assert_eq!(result, Err(Ok(Error::InvalidPoints)));
+    }
 }
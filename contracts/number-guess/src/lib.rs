@@ -5,14 +5,30 @@
 //! A simple two-player guessing game where players guess a number between 1 and 10.
 //! The player whose guess is closest to the randomly generated number wins.
 //!
+//! The range and how guesses are scored can be chosen per session with
+//! `GuessRules` (see `rules.rs`).
+//!
+//! A session can also be played as a best-of-N match: each round both players
+//! guess again, and the first to win a majority of the rounds wins the session.
+//!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
+use game_kit::rematch;
+use game_kit::session_keys::{self, authorize_move};
+use game_kit::settlement::{self, credit_keeper, settlement_config};
+use game_kit::stakes;
+use game_kit::ttl::{self, extend_game_ttl, extend_instance_ttl, ttl_config, HISTORY_TTL_LEDGERS};
+use matchmaking::registry;
+use provably_fair::{number_guess, Session, MAX_STRKEY_LEN};
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror, contractimpl, contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Vec, contract, contractclient,
+    contracterror, contractimpl, contracttype, vec, xdr::ToXdr
 };
 
+mod rules;
+
 // Import GameHub contract interface
 // This allows us to call into the GameHub contract
 #[contractclient(name = "GameHubClient")]
@@ -38,21 +54,107 @@ pub trait GameHub {
 // Errors
 // ============================================================================
 
+/// Errors returned by the contract.
+///
+/// Codes are stable: clients match on them, so existing variants are never
+/// renumbered or reused and new failures get the next free code.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
+    /// No game exists for the session (never started, or expired)
     GameNotFound = 1,
+    /// The address is not one of the two players, or may not settle this game
+    /// (keepers only settle games whose stakes a matchmaker escrows)
     NotPlayer = 2,
+    /// The player already guessed this round
     AlreadyGuessed = 3,
+    /// `reveal_winner` was called before both players guessed
     BothPlayersNotGuessed = 4,
+    /// The game already has a winner
     GameAlreadyEnded = 5,
+    /// A TTL threshold exceeds its extension target, or a target exceeds the
+    /// network's maximum TTL
+    InvalidTtlConfig = 6,
+    /// No archived result exists for the session
+    MatchNotFound = 7,
+    /// A keeper tried to settle before `keeper_delay` ledgers passed
+    SettlementTooEarly = 8,
+    /// The keeper reward exceeds 500 basis points
+    InvalidSettlementConfig = 9,
+    /// `best_of` is even or above 15
+    InvalidBestOf = 10,
+    /// The guess is outside the session's `min`-`max` range
+    GuessOutOfRange = 11,
+    /// The rules' `min` is not below `max`
+    InvalidRules = 12,
+    /// Player 1 and player 2 are the same address
+    SelfPlay = 13,
+    /// The GameHub address is missing from instance storage
+    GameHubNotSet = 14,
+    /// The admin address is missing from instance storage
+    AdminNotSet = 15,
+    /// The caller of `start_matched_game` is not a registered matchmaker
+    NotMatchmaker = 16,
+    /// A session key is a player of the session, expires in the past, is
+    /// scoped to an unknown entry point or already signs for the other player
+    InvalidSessionKey = 17,
+    /// The session key's `expires_at` ledger has been reached
+    SessionKeyExpired = 18,
+    /// The session key is not scoped to this entry point
+    SessionKeyNotAllowed = 19,
+    /// The player has no session key registered for the session
+    SessionKeyNotFound = 20,
+    /// A rematch was proposed before the game ended
+    GameNotEnded = 21,
+    /// No rematch was offered to the player for the session
+    RematchNotFound = 22,
+    /// The session was already rematched; only the latest session of a series can be
+    RematchAlreadyPlayed = 23,
+    /// The session ID is already used by another game
+    SessionIdTaken = 24,
+    /// Doubling the stakes overflows
+    StakeOverflow = 25,
+    /// A player committed zero or negative points
+    InvalidStake = 26,
+    /// A player's points are outside the admin's stake limits
+    StakeOutOfRange = 27,
+    /// The minimum stake is below 1 or above the maximum, or the maximum is
+    /// above `i128::MAX / 2`
+    InvalidStakeLimits = 28,
+}
+
+impl From<game_kit::Error> for Error {
+    fn from(error: game_kit::Error) -> Self {
+        match error {
+            game_kit::Error::InvalidTtlConfig => Error::InvalidTtlConfig,
+            game_kit::Error::InvalidSessionKey => Error::InvalidSessionKey,
+            game_kit::Error::SessionKeyExpired => Error::SessionKeyExpired,
+            game_kit::Error::SessionKeyNotAllowed => Error::SessionKeyNotAllowed,
+            game_kit::Error::RematchNotFound => Error::RematchNotFound,
+            game_kit::Error::RematchAlreadyPlayed => Error::RematchAlreadyPlayed,
+            game_kit::Error::StakeOverflow => Error::StakeOverflow,
+            game_kit::Error::InvalidStake => Error::InvalidStake,
+            game_kit::Error::StakeOutOfRange => Error::StakeOutOfRange,
+            game_kit::Error::InvalidStakeLimits => Error::InvalidStakeLimits,
+            game_kit::Error::NotPlayer => Error::NotPlayer,
+            game_kit::Error::SettlementTooEarly => Error::SettlementTooEarly,
+            game_kit::Error::InvalidSettlementConfig => Error::InvalidSettlementConfig,
+        }
+    }
 }
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
+pub use game_kit::rematch::{RematchOffer, Series};
+pub use game_kit::session_keys::SessionKey;
+pub use game_kit::settlement::{SettlementConfig, SettlementMode};
+pub use game_kit::stakes::StakeLimits;
+pub use game_kit::ttl::TtlConfig;
+pub use matchmaking::{KeeperReward, SessionInfo, Settlement};
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
@@ -64,6 +166,103 @@ pub struct Game {
     pub player2_guess: Option<u32>,
     pub winning_number: Option<u32>,
     pub winner: Option<Address>,
+    /// Ledger of the last start or guess (keepers wait `keeper_delay` after it)
+    pub last_move_ledger: u32,
+    /// Whether a matchmaker (lobby or tournament) escrows the stakes and pays
+    /// keeper rewards; keepers may only settle such games
+    pub escrowed: bool,
+    /// Match length; the first player to win a majority of rounds wins (1 = single guess)
+    pub best_of: u32,
+    /// Current round, starting at 1
+    pub round: u32,
+    pub player1_wins: u32,
+    pub player2_wins: u32,
+    /// Every revealed round, oldest first
+    pub rounds: Vec<GuessRound>,
+    /// Keeper rewards paid out of the stake so far
+    pub keeper_rewards: i128,
+    /// Range and scoring agreed at `start_game`
+    pub rules: GuessRules,
+}
+
+/// How a round is scored
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum GuessScoring {
+    /// Closest guess wins
+    Closest = 0,
+    /// Closest guess wins, and an exact guess wins the whole match on the spot
+    Jackpot = 1,
+    /// A guess calls over or under the middle of the range; a right call beats a
+    /// wrong one, otherwise the closest guess wins
+    OverUnder = 2,
+    /// Closest guess without going over wins; if both go over, the lower one wins
+    PriceIsRight = 3,
+}
+
+/// Guess range and scoring for a session, chosen at `start_game`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuessRules {
+    /// Lowest and highest allowed guess (and drawn number), inclusive
+    pub min: u32,
+    pub max: u32,
+    pub scoring: GuessScoring,
+}
+
+/// Guesses and result of one revealed round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuessRound {
+    pub round: u32,
+    pub player1_guess: u32,
+    pub player2_guess: u32,
+    pub winning_number: u32,
+    pub player1_won: bool,
+}
+
+/// Archived result of a finished game, kept in persistent storage
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchResult {
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_won: bool,
+    pub winner: Address,
+    /// Points paid back to each player under the matched-stakes rule, less the
+    /// keeper rewards; with them they add up to both stakes. Informational only:
+    /// the Game Hub is told just the winner and settles the points itself.
+    pub player1_payout: i128,
+    pub player2_payout: i128,
+    /// Match length and rounds won by each player
+    pub best_of: u32,
+    pub player1_wins: u32,
+    pub player2_wins: u32,
+    /// Keccak-256 of the final game state (XDR encoded)
+    pub state_hash: BytesN<32>,
+    /// Ledger sequence at which the game ended
+    pub end_ledger: u32,
+    /// Third party that settled the final round, if it was not one of the players
+    pub keeper: Option<Address>,
+    /// Points credited to keepers over the whole match
+    pub keeper_reward: i128,
+}
+
+/// Public inputs a session's winning numbers are derived from, for recomputing
+/// them with the `provably-fair` crate (see `get_seed_material`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeedMaterial {
+    pub session_id: u32,
+    /// Player addresses as the strkey text the seeds hash
+    pub player1: String,
+    pub player2: String,
+    /// Current round; resolved rounds and their guesses are listed in `Game::rounds`
+    pub round: u32,
 }
 
 #[contracttype]
@@ -72,17 +271,333 @@ pub enum DataKey {
     Game(u32),
     GameHubAddress,
     Admin,
+    MatchResult(u32),              // session_id -> MatchResult
+    PlayerMatchCount(Address),     // player -> number of archived matches
+    PlayerMatch(Address, u32),     // (player, index) -> session_id
+}
+
+// ============================================================================
+// Best-of-N Matches
+// ============================================================================
+
+/// Longest match `start_game` accepts
+const MAX_BEST_OF: u32 = 15;
+
+/// Rounds a player must win to take a best-of-`best_of` match
+fn majority(best_of: u32) -> u32 {
+    best_of / 2 + 1
+}
+
+// ============================================================================
+// Guess Rules
+// ============================================================================
+
+/// Classic rules, used when no rules were given: guess 1-10, closest wins
+fn default_guess_rules() -> GuessRules {
+    GuessRules {
+        min: 1,
+        max: 10,
+        scoring: GuessScoring::Closest,
+    }
+}
+
+// ============================================================================
+// Winning Numbers
+// ============================================================================
+// Drawn through `provably_fair::number_guess`, so anyone can recompute them
+// from `get_seed_material`
+
+/// Winning numbers are drawn from public seed material, so they can be computed
+/// before the first guess (see `SessionInfo::predetermined`)
+const OUTCOME_PREDETERMINED: bool = true;
+
+/// keccak256 on the host, for the outcome derivations of `provably_fair`
+struct HostKeccak<'a>(&'a Env);
+
+impl provably_fair::Keccak256 for HostKeccak<'_> {
+    fn hash(&self, parts: &[&[u8]]) -> [u8; 32] {
+        let mut preimage = Bytes::new(self.0);
+        for part in parts {
+            preimage.extend_from_slice(part);
+        }
+        self.0.crypto().keccak256(&preimage).to_array()
+    }
+}
+
+/// Draw the current round's winning number in the session's range.
+///
+/// Seed components (all deterministic and identical between sim/submit):
+/// 1. Session ID - unique per game, same between simulation and submission
+/// 2. Player addresses - both players contribute, same between sim/submit
+/// 3. Round number - a fresh number every round of a match
+/// 4. Guesses - committed before reveal, same between sim/submit
+///
+/// Note: We do NOT include ledger sequence or timestamp because those differ
+/// between simulation and submission, which would cause different winners.
+///
+/// This ensures:
+/// - Same result between simulation and submission (fully deterministic)
+/// - Cannot be easily gamed (both players contribute to randomness)
+fn winning_number(env: &Env, session_id: u32, game: &Game, guess1: u32, guess2: u32) -> u32 {
+    let player1 = game.player1.to_string().to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let player2 = game.player2.to_string().to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let session = Session {
+        session_id,
+        player1: player1.as_slice(),
+        player2: player2.as_slice(),
+    };
+    let seed = number_guess::round_seed(&HostKeccak(env), &session, game.round, guess1, guess2);
+    number_guess::winning_number(&seed, game.rules.min, game.rules.max)
+}
+
+// ============================================================================
+// Match History
+// ============================================================================
+// Finished games are archived in persistent storage so results outlive the
+// temporary game entry (disputes, statistics)
+
+/// Maximum number of results returned by one `get_player_matches` call
+const MAX_HISTORY_PAGE: u32 = 50;
+
+/// Archive the final state of a game and index it under both players.
+/// Must be called once, after the winner has been set.
+fn archive_match(
+    env: &Env,
+    session_id: u32,
+    game: &Game,
+    player1_won: bool,
+    keeper: Option<Address>,
+) {
+    let winner = if player1_won {
+        game.player1.clone()
+    } else {
+        game.player2.clone()
+    };
+    let (player1_payout, player2_payout) = stakes::settle_stakes(
+        game.player1_points,
+        game.player2_points,
+        player1_won,
+        game.keeper_rewards,
+    );
+    let result = MatchResult {
+        session_id,
+        player1: game.player1.clone(),
+        player2: game.player2.clone(),
+        player1_points: game.player1_points,
+        player2_points: game.player2_points,
+        player1_won,
+        winner,
+        player1_payout,
+        player2_payout,
+        best_of: game.best_of,
+        player1_wins: game.player1_wins,
+        player2_wins: game.player2_wins,
+        state_hash: env.crypto().keccak256(&game.clone().to_xdr(env)).into(),
+        end_ledger: env.ledger().sequence(),
+        keeper,
+        keeper_reward: game.keeper_rewards,
+    };
+
+    let key = DataKey::MatchResult(session_id);
+    env.storage().persistent().set(&key, &result);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+
+    index_player_match(env, &game.player1, session_id);
+    index_player_match(env, &game.player2, session_id);
+    rematch::record_series_result(env, session_id, player1_won);
+}
+
+/// Append a session to a player's match index
+fn index_player_match(env: &Env, player: &Address, session_id: u32) {
+    let count_key = DataKey::PlayerMatchCount(player.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let entry_key = DataKey::PlayerMatch(player.clone(), count);
+    env.storage().persistent().set(&entry_key, &session_id);
+    env.storage().persistent().set(&count_key, &(count + 1));
+
+    env.storage()
+        .persistent()
+        .extend_ttl(&entry_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+}
+
+// ============================================================================
+// Settlement
+// ============================================================================
+// Players can always settle their own game. In keeper mode anyone else can
+// settle it too, once the players have had `keeper_delay` ledgers to do it
+// themselves, and earns a share of the pot that the contract escrowing the
+// stakes pays out (see `game_kit::settlement`).
+
+/// Check that `caller` may settle `game` now.
+/// Returns the keeper to reward, or `None` when one of the players settles.
+fn authorize_settlement(
+    env: &Env,
+    config: &SettlementConfig,
+    game: &Game,
+    caller: &Address,
+) -> Result<Option<Address>, Error> {
+    let is_player = *caller == game.player1 || *caller == game.player2;
+    settlement::authorize_settlement(
+        env,
+        config,
+        caller,
+        is_player,
+        game.escrowed,
+        game.last_move_ledger,
+    )
+    .map_err(Error::from)
+}
+
+/// Keeper reward for settling one round: the configured share of the
+/// matched pot, spread over the match length
+fn keeper_reward(config: &SettlementConfig, game: &Game) -> i128 {
+    let pot = 2 * game.player1_points.min(game.player2_points);
+    settlement::keeper_reward(config, pot, game.best_of)
+}
+
+// ============================================================================
+// Stakes
+// ============================================================================
+// Both players must commit a positive number of points within the admin's
+// limits. Unequal stakes are matched at the smaller one: the winner takes that
+// much from the loser less any keeper rewards, and the rest of the larger stake
+// goes back to its owner (see `game_kit::stakes`).
+
+/// Largest stake the admin can allow, so that two stakes always add up
+const MAX_STAKE: i128 = i128::MAX / 2;
+
+// ============================================================================
+// Matchmaking
+// ============================================================================
+// Contracts registered by the admin (a lobby, a tournament) may start games
+// for players who gave their consent to the matchmaker instead of signing the
+// start themselves. Both entry points share `open_game` once checks pass.
+
+/// Register the session with the GameHub and store the new game.
+/// Players, match length and rules must already be validated and authorized;
+/// the stakes are checked against the stake limits here. `escrowed` marks
+/// games a registered matchmaker started.
+#[allow(clippy::too_many_arguments)]
+fn open_game(
+    env: &Env,
+    session_id: u32,
+    player1: Address,
+    player2: Address,
+    player1_points: i128,
+    player2_points: i128,
+    best_of: u32,
+    rules: GuessRules,
+    escrowed: bool,
+) -> Result<(), Error> {
+    if session_id_taken(env, session_id) {
+        return Err(Error::SessionIdTaken);
+    }
+    let limits = stakes::stake_limits(env, MAX_STAKE);
+    stakes::validate_stake(&limits, player1_points)?;
+    stakes::validate_stake(&limits, player2_points)?;
+
+    // Get GameHub address
+    let game_hub_addr: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .ok_or(Error::GameHubNotSet)?;
+
+    // Create GameHub client
+    let game_hub = GameHubClient::new(env, &game_hub_addr);
+
+    // Call Game Hub to start the session and lock points
+    // This requires THIS contract's authorization (env.current_contract_address())
+    game_hub.start_game(
+        &env.current_contract_address(),
+        &session_id,
+        &player1,
+        &player2,
+        &player1_points,
+        &player2_points,
+    );
+
+    // Create game (winning_number not set yet - will be generated in reveal_winner)
+    let game = Game {
+        player1: player1.clone(),
+        player2: player2.clone(),
+        player1_points,
+        player2_points,
+        player1_guess: None,
+        player2_guess: None,
+        winning_number: None,
+        winner: None,
+        last_move_ledger: env.ledger().sequence(),
+        escrowed,
+        best_of,
+        round: 1,
+        player1_wins: 0,
+        player2_wins: 0,
+        rounds: Vec::new(env),
+        keeper_rewards: 0,
+        rules,
+    };
+
+    // Store game in temporary storage with 30-day TTL
+    let game_key = DataKey::Game(session_id);
+    env.storage().temporary().set(&game_key, &game);
+
+    // Set TTL to ensure game is retained for at least 30 days
+    extend_game_ttl(env, &game_key);
+
+    // Event emitted by the Game Hub contract (GameStarted)
+
+    Ok(())
+}
+
+/// Require `matchmaker`'s authorization and check the admin registered it
+fn authorize_matchmaker(env: &Env, matchmaker: &Address) -> Result<(), Error> {
+    if !registry::authorize_matchmaker(env, matchmaker) {
+        return Err(Error::NotMatchmaker);
+    }
+    Ok(())
 }
 
 // ============================================================================
-// Storage TTL Management
+// Session Keys
 // ============================================================================
-// TTL (Time To Live) ensures game data doesn't expire unexpectedly
-// Games are stored in temporary storage with a minimum 30-day retention
+// A player may register an ephemeral key for one session so their frontend can
+// sign moves without a wallet prompt each time (see `game_kit::session_keys`).
+// Move entry points resolve a key to its player with `authorize_move`.
 
-/// TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
-/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
-const GAME_TTL_LEDGERS: u32 = 518_400;
+/// Entry points a session key may sign (`make_guess`)
+const SESSION_KEY_MOVES: [&str; 1] = ["make_guess"];
+
+// ============================================================================
+// Rematches
+// ============================================================================
+// Once a game has ended either player may offer a rematch, which the other
+// accepts without a fresh two-party start. The rematch keeps the seats, match
+// length and guess rules and carries the stakes over (or doubles them).
+// Accepted rematches link their sessions into a series whose cumulative score
+// is kept in persistent storage next to the match history.
+
+/// Whether a game, live or archived, already uses `session_id`
+fn session_id_taken(env: &Env, session_id: u32) -> bool {
+    env.storage().temporary().has(&DataKey::Game(session_id))
+        || env.storage().persistent().has(&DataKey::MatchResult(session_id))
+}
+
+/// Stakes of a rematch of `game`, checked against the current stake limits
+fn rematch_stakes(env: &Env, game: &Game, double_stakes: bool) -> Result<(i128, i128), Error> {
+    let player1_points = stakes::rematch_stake(game.player1_points, double_stakes)?;
+    let player2_points = stakes::rematch_stake(game.player2_points, double_stakes)?;
+    let limits = stakes::stake_limits(env, MAX_STAKE);
+    stakes::validate_stake(&limits, player1_points)?;
+    stakes::validate_stake(&limits, player2_points)?;
+    Ok((player1_points, player2_points))
+}
 
 // ============================================================================
 // Contract Definition
@@ -104,6 +619,8 @@ impl NumberGuessContract {
         env.storage()
             .instance()
             .set(&DataKey::GameHubAddress, &game_hub);
+
+        extend_instance_ttl(&env);
     }
 
     /// Start a new game between two players with points.
@@ -118,6 +635,9 @@ impl NumberGuessContract {
     /// * `player2` - Address of second player
     /// * `player1_points` - Points amount committed by player 1
     /// * `player2_points` - Points amount committed by player 2
+    /// * `best_of` - Odd number of rounds in the match, up to 15 (single guess if `None`)
+    /// * `rules` - Guess range and scoring (1-10, closest wins if `None`)
+    #[allow(clippy::too_many_arguments)]
     pub fn start_game(
         env: Env,
         session_id: u32,
@@ -125,77 +645,271 @@ impl NumberGuessContract {
         player2: Address,
         player1_points: i128,
         player2_points: i128,
+        best_of: Option<u32>,
+        rules: Option<GuessRules>,
     ) -> Result<(), Error> {
         // Prevent self-play: Player 1 and Player 2 must be different
         if player1 == player2 {
-            panic!("Cannot play against yourself: Player 1 and Player 2 must be different addresses");
+            return Err(Error::SelfPlay);
         }
 
-        // Require authentication from both players (they consent to committing points)
-        player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
-        player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
+        // A match needs an odd number of rounds so someone reaches a majority
+        let rounds = best_of.unwrap_or(1);
+        if rounds.is_multiple_of(2) || rounds > MAX_BEST_OF {
+            return Err(Error::InvalidBestOf);
+        }
+        let guess_rules = rules.clone().unwrap_or_else(default_guess_rules);
+        if !rules::is_valid(&guess_rules) {
+            return Err(Error::InvalidRules);
+        }
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        extend_instance_ttl(&env);
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
+        // Require authentication from both players (they consent to committing points
+        // and, when given, to the match length and rules)
+        let mut player1_args = vec![&env, session_id.into_val(&env), player1_points.into_val(&env)];
+        let mut player2_args = vec![&env, session_id.into_val(&env), player2_points.into_val(&env)];
+        if let Some(best_of) = best_of {
+            player1_args.push_back(best_of.into_val(&env));
+            player2_args.push_back(best_of.into_val(&env));
+        }
+        if let Some(rules) = &rules {
+            player1_args.push_back(rules.into_val(&env));
+            player2_args.push_back(rules.into_val(&env));
+        }
+        player1.require_auth_for_args(player1_args);
+        player2.require_auth_for_args(player2_args);
 
-        // Call Game Hub to start the session and lock points
-        // This requires THIS contract's authorization (env.current_contract_address())
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+        open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            rounds,
+            guess_rules,
+            false,
+        )
+    }
+
+    /// Start a game on behalf of a registered matchmaker (see `set_matchmaker`).
+    /// The matchmaker collected both players' consent itself, so only its own
+    /// authorization is required. Plays a single round with the default rules.
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Registered matchmaker contract starting the game
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `player1` - Address of first player
+    /// * `player2` - Address of second player
+    /// * `player1_points` - Points amount committed by player 1
+    /// * `player2_points` - Points amount committed by player 2
+    pub fn start_matched_game(
+        env: Env,
+        matchmaker: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Result<(), Error> {
+        if player1 == player2 {
+            return Err(Error::SelfPlay);
+        }
 
-        // Create game (winning_number not set yet - will be generated in reveal_winner)
-        let game = Game {
-            player1: player1.clone(),
-            player2: player2.clone(),
+        extend_instance_ttl(&env);
+        authorize_matchmaker(&env, &matchmaker)?;
+
+        open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
             player1_points,
             player2_points,
-            player1_guess: None,
-            player2_guess: None,
-            winning_number: None,
-            winner: None,
-        };
+            1,
+            default_guess_rules(),
+            true,
+        )
+    }
+
+    /// Offer the other player a rematch of an ended game, replacing any offer
+    /// pending for it. The rematch is played under `new_session_id` with the
+    /// same seats, match length and guess rules.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - Address of the player proposing the rematch
+    /// * `new_session_id` - Unused session ID for the rematch
+    /// * `double_stakes` - Double both players' points instead of carrying them over
+    pub fn propose_rematch(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        new_session_id: u32,
+        double_stakes: bool,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
 
-        // Store game in temporary storage with 30-day TTL
         let game_key = DataKey::Game(session_id);
-        env.storage().temporary().set(&game_key, &game);
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&game_key)
+            .ok_or(Error::GameNotFound)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        if game.winner.is_none() {
+            return Err(Error::GameNotEnded);
+        }
+        rematch::check_rematchable(&env, session_id)?;
+        if session_id_taken(&env, new_session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+        rematch_stakes(&env, &game, double_stakes)?;
 
-        // Set TTL to ensure game is retained for at least 30 days
-        env.storage()
+        let offer = RematchOffer {
+            proposer: player,
+            new_session_id,
+            double_stakes,
+        };
+        rematch::offer_rematch(&env, session_id, &offer);
+        extend_game_ttl(&env, &game_key);
+
+        Ok(())
+    }
+
+    /// Accept the rematch the other player offered, starting it in the Game Hub
+    /// with the stakes of the offer. Returns the session ID of the rematch.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - Address of the player accepting the rematch
+    pub fn accept_rematch(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let game: Game = env
+            .storage()
             .temporary()
-            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let offer = rematch::take_rematch(&env, session_id, &player)?;
+        if session_id_taken(&env, offer.new_session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+        let (player1_points, player2_points) = rematch_stakes(&env, &game, offer.double_stakes)?;
+
+        open_game(
+            &env,
+            offer.new_session_id,
+            game.player1.clone(),
+            game.player2.clone(),
+            player1_points,
+            player2_points,
+            game.best_of,
+            game.rules.clone(),
+            false,
+        )?;
+        let player1_won = game.winner.as_ref() == Some(&game.player1);
+        rematch::link_rematch(
+            &env,
+            session_id,
+            offer.new_session_id,
+            &game.player1,
+            &game.player2,
+            player1_won,
+        );
+
+        Ok(offer.new_session_id)
+    }
 
-        // Event emitted by the Game Hub contract (GameStarted)
+    /// Register an ephemeral session key that may sign `player`'s moves in
+    /// this session, replacing any key registered before. The key's address is
+    /// then passed in place of the player's to the move entry points.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player delegating their moves
+    /// * `key` - Address of the session key
+    /// * `functions` - Entry points the key may call (`make_guess`); empty for all
+    /// * `expires_at` - Ledger sequence from which the key is refused, or `None`
+    ///   to keep it for the whole session
+    pub fn register_session_key(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        key: Address,
+        functions: Vec<Symbol>,
+        expires_at: Option<u32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
 
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let is_player = key == game.player1 || key == game.player2;
+        session_keys::register_session_key(
+            &env,
+            session_id,
+            player,
+            key,
+            functions,
+            expires_at,
+            is_player,
+            &SESSION_KEY_MOVES,
+        )?;
         Ok(())
     }
 
-    /// Make a guess for the current game.
-    /// Players can guess a number between 1 and 10.
+    /// Revoke `player`'s session key; moves must be signed by the player again.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player making the guess
-    /// * `guess` - The guessed number (1-10)
-    pub fn make_guess(env: Env, session_id: u32, player: Address, guess: u32) -> Result<(), Error> {
+    /// * `player` - Address of the player who registered the key
+    pub fn revoke_session_key(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
         player.require_auth();
+        extend_instance_ttl(&env);
 
-        // Validate guess is in range
-        if guess < 1 || guess > 10 {
-            panic!("Guess must be between 1 and 10");
+        if !session_keys::remove_session_key(&env, session_id, &player) {
+            return Err(Error::SessionKeyNotFound);
         }
+        Ok(())
+    }
+
+    /// Get the session key `player` registered for a session, if any.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    pub fn get_session_key(env: Env, session_id: u32, player: Address) -> Option<SessionKey> {
+        session_keys::session_key(&env, session_id, player)
+    }
+
+    /// Make a guess for the current round.
+    /// Players guess a number in the session's range (1-10 by default).
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player making the guess, or of their session key
+    /// * `guess` - The guessed number, between the session's `min` and `max`
+    pub fn make_guess(env: Env, session_id: u32, player: Address, guess: u32) -> Result<(), Error> {
+        let player = authorize_move(&env, session_id, player, "make_guess")?;
+        extend_instance_ttl(&env);
 
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
@@ -205,6 +919,11 @@ impl NumberGuessContract {
             .get(&key)
             .ok_or(Error::GameNotFound)?;
 
+        // Validate guess is in the session's range
+        if !rules::in_range(&game.rules, guess) {
+            return Err(Error::GuessOutOfRange);
+        }
+
         // Check game is still active (no winner yet)
         if game.winner.is_some() {
             return Err(Error::GameAlreadyEnded);
@@ -224,25 +943,37 @@ impl NumberGuessContract {
         } else {
             return Err(Error::NotPlayer);
         }
+        game.last_move_ledger = env.ledger().sequence();
 
         // Store updated game in temporary storage
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
 
         // No event emitted - game state can be queried via get_game()
 
         Ok(())
     }
 
-    /// Reveal the winner of the game and submit outcome to GameHub.
+    /// Reveal the current round and, once a player has won the match, submit the
+    /// outcome to GameHub.
     /// Can only be called after both players have made their guesses.
-    /// This generates the winning number, determines the winner, and ends the session.
+    /// This generates the winning number and scores the round. If neither player has
+    /// a majority of `best_of` yet, the guesses are cleared for the next round;
+    /// otherwise the session ends. A `Jackpot` hit ends the session straight away.
+    ///
+    /// Who may call this depends on the `SettlementConfig`: the players always can;
+    /// in keeper mode anyone else can too after `keeper_delay` ledgers, and earns
+    /// `keeper_reward_bps` of the matched pot, spread over the match's rounds.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
+    /// * `caller` - Address settling the round (player or keeper)
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `Address` - Address of the round's winner; the match winner once `winner` is set
+    pub fn reveal_winner(env: Env, session_id: u32, caller: Address) -> Result<Address, Error> {
+        extend_instance_ttl(&env);
+
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -260,68 +991,65 @@ impl NumberGuessContract {
         let guess1 = game.player1_guess.ok_or(Error::BothPlayersNotGuessed)?;
         let guess2 = game.player2_guess.ok_or(Error::BothPlayersNotGuessed)?;
 
-        // Generate random winning number between 1 and 10 using seeded PRNG
-        // This is done AFTER both players have committed their guesses
-        //
-        // Seed components (all deterministic and identical between sim/submit):
-        // 1. Session ID - unique per game, same between simulation and submission
-        // 2. Player addresses - both players contribute, same between sim/submit
-        // 3. Guesses - committed before reveal, same between sim/submit
-        //
-        // Note: We do NOT include ledger sequence or timestamp because those differ
-        // between simulation and submission, which would cause different winners.
-        //
-        // This ensures:
-        // - Same result between simulation and submission (fully deterministic)
-        // - Cannot be easily gamed (both players contribute to randomness)
-
-        // Build seed more efficiently using native arrays where possible
-        // Total: 12 bytes of fixed data (session_id + 2 guesses)
-        let mut fixed_data = [0u8; 12];
-        fixed_data[0..4].copy_from_slice(&session_id.to_be_bytes());
-        fixed_data[4..8].copy_from_slice(&guess1.to_be_bytes());
-        fixed_data[8..12].copy_from_slice(&guess2.to_be_bytes());
-
-        // Only use Bytes for the final concatenation with player addresses
-        let mut seed_bytes = Bytes::from_array(&env, &fixed_data);
-        seed_bytes.append(&game.player1.to_string().to_bytes());
-        seed_bytes.append(&game.player2.to_string().to_bytes());
-
-        let seed = env.crypto().keccak256(&seed_bytes);
-        env.prng().seed(seed.into());
-        let winning_number = env.prng().gen_range::<u64>(1..=10) as u32;
-        game.winning_number = Some(winning_number);
-
-        // Calculate distances
-        let distance1 = if guess1 > winning_number {
-            guess1 - winning_number
-        } else {
-            winning_number - guess1
-        };
+        let settlement = settlement_config(&env);
+        let keeper = authorize_settlement(&env, &settlement, &game, &caller)?;
 
-        let distance2 = if guess2 > winning_number {
-            guess2 - winning_number
-        } else {
-            winning_number - guess2
-        };
+        // Draw the winning number in the session's range. This is done AFTER
+        // both players have committed their guesses
+        let winning_number = winning_number(&env, session_id, &game, guess1, guess2);
+        game.winning_number = Some(winning_number);
 
-        // Determine winner (if equal distance, player1 wins)
-        let winner = if distance1 <= distance2 {
+        // Determine the round winner under the session's scoring (ties go to player1)
+        let player1_won_round = rules::player1_wins(&game.rules, guess1, guess2, winning_number);
+        let jackpot = rules::is_jackpot(
+            &game.rules,
+            if player1_won_round { guess1 } else { guess2 },
+            winning_number,
+        );
+        let winner = if player1_won_round {
+            game.player1_wins += 1;
             game.player1.clone()
         } else {
+            game.player2_wins += 1;
             game.player2.clone()
         };
+        game.rounds.push_back(GuessRound {
+            round: game.round,
+            player1_guess: guess1,
+            player2_guess: guess2,
+            winning_number,
+            player1_won: player1_won_round,
+        });
+
+        // Pay the keeper out of the stake, if a third party settled
+        if let Some(keeper) = &keeper {
+            let reward = keeper_reward(&settlement, &game);
+            game.keeper_rewards += reward;
+            credit_keeper(&env, session_id, keeper, reward);
+        }
+
+        // Nobody has a majority (or hit a jackpot) yet: both players guess again
+        let needed = majority(game.best_of);
+        if !jackpot && game.player1_wins < needed && game.player2_wins < needed {
+            game.round += 1;
+            game.player1_guess = None;
+            game.player2_guess = None;
+            env.storage().temporary().set(&key, &game);
+            extend_game_ttl(&env, &key);
+            return Ok(winner);
+        }
 
         // Update game with winner (this marks the game as ended)
         game.winner = Some(winner.clone());
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
 
         // Get GameHub address
         let game_hub_addr: Address = env
             .storage()
             .instance()
             .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+            .ok_or(Error::GameHubNotSet)?;
 
         // Create GameHub client
         let game_hub = GameHubClient::new(&env, &game_hub_addr);
@@ -332,9 +1060,29 @@ impl NumberGuessContract {
         let player1_won = winner == game.player1; // true if player1 won, false if player2 won
         game_hub.end_game(&session_id, &player1_won);
 
+        // Archive the result so it outlives the temporary game entry
+        archive_match(&env, session_id, &game, player1_won, keeper);
+
         Ok(winner)
     }
 
+    /// Extend the TTL of a game and of the contract instance.
+    /// Anyone can call this to keep a long-running or idle game from being archived.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn bump(env: Env, session_id: u32) -> Result<(), Error> {
+        extend_instance_ttl(&env);
+
+        let key = DataKey::Game(session_id);
+        if !env.storage().temporary().has(&key) {
+            return Err(Error::GameNotFound);
+        }
+        extend_game_ttl(&env, &key);
+
+        Ok(())
+    }
+
     /// Get game information.
     ///
     /// # Arguments
@@ -350,6 +1098,172 @@ impl NumberGuessContract {
             .ok_or(Error::GameNotFound)
     }
 
+    /// Get the archived result of a finished game.
+    /// Available after the temporary game entry has expired.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_match(env: Env, session_id: u32) -> Result<MatchResult, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))
+            .ok_or(Error::MatchNotFound)
+    }
+
+    /// Get the winner of a finished game, for matchmakers settling stakes.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The winner, or `None` until the game is archived
+    pub fn get_winner(env: Env, session_id: u32) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get::<_, MatchResult>(&DataKey::MatchResult(session_id))
+            .map(|result| result.winner)
+    }
+
+    /// Get how a finished game's stakes are split between the players and the
+    /// keepers that settled it, for matchmakers paying out escrowed stakes.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_settlement(env: Env, session_id: u32) -> Option<Settlement> {
+        let result: MatchResult = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))?;
+        Some(Settlement {
+            winner: result.winner,
+            player1_payout: result.player1_payout,
+            player2_payout: result.player2_payout,
+            keeper_rewards: settlement::session_keepers(&env, session_id),
+        })
+    }
+
+    /// Get the rematch offered for an ended session, if one is pending.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    pub fn get_rematch(env: Env, session_id: u32) -> Option<RematchOffer> {
+        rematch::rematch_offer(&env, session_id)
+    }
+
+    /// Get the series a session belongs to, with its cumulative score.
+    /// Sessions that were never rematched have none.
+    ///
+    /// # Arguments
+    /// * `session_id` - Any session of the series
+    pub fn get_series(env: Env, session_id: u32) -> Option<Series> {
+        rematch::session_series(&env, session_id).map(|(_, series)| series)
+    }
+
+    /// Get the players, betting phase and winner of a session, for spectators
+    /// and side-bet pools.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_session_info(env: Env, session_id: u32) -> Result<SessionInfo, Error> {
+        let archived: Option<MatchResult> =
+            env.storage().persistent().get(&DataKey::MatchResult(session_id));
+        if let Some(result) = archived {
+            return Ok(SessionInfo {
+                player1: result.player1,
+                player2: result.player2,
+                awaiting_first_move: false,
+                predetermined: OUTCOME_PREDETERMINED,
+                winner: Some(result.winner),
+            });
+        }
+
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        Ok(SessionInfo {
+            predetermined: OUTCOME_PREDETERMINED,
+            awaiting_first_move: game.winner.is_none()
+                && game.round == 1
+                && game.player1_guess.is_none()
+                && game.player2_guess.is_none(),
+            player1: game.player1,
+            player2: game.player2,
+            winner: game.winner,
+        })
+    }
+
+    /// Get the public seed material a session's winning numbers are derived
+    /// from, so anyone can recompute them with the `provably-fair` crate.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_seed_material(env: Env, session_id: u32) -> Result<SeedMaterial, Error> {
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        Ok(SeedMaterial {
+            session_id,
+            player1: game.player1.to_string(),
+            player2: game.player2.to_string(),
+            round: game.round,
+        })
+    }
+
+    /// Get the number of archived matches a player took part in.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    pub fn get_player_match_count(env: Env, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerMatchCount(player))
+            .unwrap_or(0)
+    }
+
+    /// Get a page of a player's archived matches, oldest first.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    /// * `start` - Index of the first match to return
+    /// * `limit` - Maximum number of matches to return (capped at 50)
+    pub fn get_player_matches(
+        env: Env,
+        player: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<MatchResult> {
+        let count = Self::get_player_match_count(env.clone(), player.clone());
+        let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE)).min(count);
+
+        let mut results = Vec::new(&env);
+        for index in start..end {
+            let session_id: Option<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PlayerMatch(player.clone(), index));
+            if let Some(result) = session_id.and_then(|id| {
+                env.storage()
+                    .persistent()
+                    .get::<_, MatchResult>(&DataKey::MatchResult(id))
+            }) {
+                results.push_back(result);
+            }
+        }
+        results
+    }
+
+    /// Get the total keeper rewards an address has earned settling games.
+    ///
+    /// # Arguments
+    /// * `keeper` - Address of the keeper
+    pub fn get_keeper_earnings(env: Env, keeper: Address) -> i128 {
+        settlement::keeper_earnings(&env, keeper)
+    }
+
     // ========================================================================
     // Admin Functions
     // ========================================================================
@@ -358,69 +1272,178 @@ impl NumberGuessContract {
     ///
     /// # Returns
     /// * `Address` - The admin address
-    pub fn get_admin(env: Env) -> Address {
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set")
+            .ok_or(Error::AdminNotSet)
     }
 
     /// Set a new admin address
     ///
     /// # Arguments
     /// * `new_admin` - The new admin address
-    pub fn set_admin(env: Env, new_admin: Address) {
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Get the current GameHub contract address
     ///
     /// # Returns
     /// * `Address` - The GameHub contract address
-    pub fn get_hub(env: Env) -> Address {
+    pub fn get_hub(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set")
+            .ok_or(Error::GameHubNotSet)
     }
 
     /// Set a new GameHub contract address
     ///
     /// # Arguments
     /// * `new_hub` - The new GameHub contract address
-    pub fn set_hub(env: Env, new_hub: Address) {
+    pub fn set_hub(env: Env, new_hub: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.storage()
             .instance()
             .set(&DataKey::GameHubAddress, &new_hub);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    ///
+    /// # Returns
+    /// * `TtlConfig` - The configured thresholds (or the defaults)
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl_config(&env)
+    }
+
+    /// Set the storage TTL thresholds
+    ///
+    /// # Arguments
+    /// * `config` - New thresholds; each threshold must not exceed its extension target, and
+    ///   no target may exceed the network's maximum TTL
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        ttl::set_ttl_config(&env, &config)?;
+
+        Ok(())
+    }
+
+    /// Get the settlement rules for `reveal_winner`
+    ///
+    /// # Returns
+    /// * `SettlementConfig` - The configured rules (or the participants-only default)
+    pub fn get_settlement_config(env: Env) -> SettlementConfig {
+        settlement_config(&env)
+    }
+
+    /// Set the settlement rules for `reveal_winner`
+    ///
+    /// # Arguments
+    /// * `config` - New rules; the keeper reward may not exceed 500 basis points
+    pub fn set_settlement_config(env: Env, config: SettlementConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        settlement::set_settlement_config(&env, &config)?;
+
+        Ok(())
+    }
+
+    /// Get the stake limits new games are checked against
+    pub fn get_stake_limits(env: Env) -> StakeLimits {
+        stakes::stake_limits(&env, MAX_STAKE)
+    }
+
+    /// Set the stake limits for new games; running games keep their stakes
+    ///
+    /// # Arguments
+    /// * `limits` - New limits; the minimum must be at least 1 and at most the
+    ///   maximum, which may not exceed `i128::MAX / 2`
+    pub fn set_stake_limits(env: Env, limits: StakeLimits) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        stakes::set_stake_limits(&env, &limits, MAX_STAKE)?;
+
+        Ok(())
+    }
+
+    /// Check whether a contract may call `start_matched_game`
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Address of the contract
+    pub fn is_matchmaker(env: Env, matchmaker: Address) -> bool {
+        registry::is_matchmaker(&env, &matchmaker)
+    }
+
+    /// Register or remove a matchmaker contract
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Address of the contract (a lobby, a tournament)
+    /// * `allowed` - Whether it may call `start_matched_game`
+    pub fn set_matchmaker(env: Env, matchmaker: Address, allowed: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        registry::set_matchmaker(&env, matchmaker, allowed);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
     /// * `new_wasm_hash` - The hash of the new WASM binary
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
     }
 }
 
@@ -430,3 +1453,6 @@ impl NumberGuessContract {
 
 #[cfg(test)]
 mod test;
+
+#[cfg(test)]
+mod rules_test;
@@ -0,0 +1,58 @@
+//! Scoring rules for Number Guess.
+//!
+//! Everything here is pure: no storage, no auth and no `Env`, only the two
+//! guesses, the drawn number and the session's `GuessRules`, so each scoring
+//! mode can be unit tested on its own (see `rules_test.rs`).
+
+use crate::{GuessRules, GuessScoring};
+
+/// Whether a session can be played with these rules
+pub fn is_valid(rules: &GuessRules) -> bool {
+    rules.min < rules.max
+}
+
+/// Whether a guess lies inside the session's range
+pub fn in_range(rules: &GuessRules, guess: u32) -> bool {
+    (rules.min..=rules.max).contains(&guess)
+}
+
+/// Whether player 1 wins the round. Ties always go to player 1.
+pub fn player1_wins(rules: &GuessRules, guess1: u32, guess2: u32, number: u32) -> bool {
+    match rules.scoring {
+        GuessScoring::Closest | GuessScoring::Jackpot => closer(guess1, guess2, number),
+        GuessScoring::OverUnder => {
+            let right1 = is_over(rules, guess1) == is_over(rules, number);
+            let right2 = is_over(rules, guess2) == is_over(rules, number);
+            if right1 != right2 {
+                right1
+            } else {
+                closer(guess1, guess2, number)
+            }
+        }
+        GuessScoring::PriceIsRight => {
+            let over1 = guess1 > number;
+            let over2 = guess2 > number;
+            if over1 != over2 {
+                !over1
+            } else {
+                // Both under: highest guess is closest. Both over: lowest guess is.
+                closer(guess1, guess2, number)
+            }
+        }
+    }
+}
+
+/// Whether a guess takes the whole match in `Jackpot` scoring
+pub fn is_jackpot(rules: &GuessRules, guess: u32, number: u32) -> bool {
+    rules.scoring == GuessScoring::Jackpot && guess == number
+}
+
+/// Whether a value calls "over" the midpoint of the range (the midpoint itself is under)
+fn is_over(rules: &GuessRules, value: u32) -> bool {
+    value > rules.min + (rules.max - rules.min) / 2
+}
+
+/// Whether guess 1 is at least as close to the number as guess 2
+fn closer(guess1: u32, guess2: u32, number: u32) -> bool {
+    guess1.abs_diff(number) <= guess2.abs_diff(number)
+}
@@ -0,0 +1,69 @@
+#![cfg(test)]
+
+//! Unit tests for the pure scoring rules. No `Env` is needed here.
+
+use crate::rules;
+use crate::{GuessRules, GuessScoring};
+
+fn rules_with(scoring: GuessScoring) -> GuessRules {
+    GuessRules {
+        min: 1,
+        max: 10,
+        scoring,
+    }
+}
+
+#[test]
+fn test_closest_wins_and_ties_go_to_player1() {
+    let rules = rules_with(GuessScoring::Closest);
+    assert!(rules::player1_wins(&rules, 5, 8, 6));
+    assert!(!rules::player1_wins(&rules, 2, 8, 6));
+    assert!(rules::player1_wins(&rules, 4, 8, 6));
+}
+
+#[test]
+fn test_over_under_call_beats_distance() {
+    let rules = rules_with(GuessScoring::OverUnder);
+    // The line is 5: the number 6 is over, so 10 is a right call and 5 a wrong one
+    assert!(!rules::player1_wins(&rules, 5, 10, 6));
+    assert!(rules::player1_wins(&rules, 10, 5, 6));
+    // Both right (or both wrong): closest wins
+    assert!(rules::player1_wins(&rules, 7, 10, 6));
+    assert!(!rules::player1_wins(&rules, 1, 4, 3));
+}
+
+#[test]
+fn test_price_is_right_never_goes_over() {
+    let rules = rules_with(GuessScoring::PriceIsRight);
+    assert!(rules::player1_wins(&rules, 3, 7, 6));
+    assert!(!rules::player1_wins(&rules, 7, 2, 6));
+    assert!(rules::player1_wins(&rules, 6, 5, 6));
+    // Both over: the lower guess is closest
+    assert!(!rules::player1_wins(&rules, 9, 7, 6));
+}
+
+#[test]
+fn test_jackpot_needs_exact_hit() {
+    let jackpot = rules_with(GuessScoring::Jackpot);
+    assert!(rules::is_jackpot(&jackpot, 6, 6));
+    assert!(!rules::is_jackpot(&jackpot, 5, 6));
+    assert!(!rules::is_jackpot(&rules_with(GuessScoring::Closest), 6, 6));
+    // Rounds without a hit are scored by distance
+    assert!(!rules::player1_wins(&jackpot, 2, 5, 6));
+}
+
+#[test]
+fn test_range_limits() {
+    let rules = GuessRules {
+        min: 50,
+        max: 150,
+        scoring: GuessScoring::Closest,
+    };
+    assert!(rules::is_valid(&rules));
+    assert!(rules::in_range(&rules, 50));
+    assert!(rules::in_range(&rules, 150));
+    assert!(!rules::in_range(&rules, 49));
+    assert!(!rules::in_range(&rules, 151));
+    assert!(!rules::is_valid(&GuessRules { max: 50, ..rules.clone() }));
+    assert!(!rules::is_valid(&GuessRules { min: 151, ..rules }));
+}
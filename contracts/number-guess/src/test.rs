@@ -6,9 +6,13 @@
 // Note: These tests use a minimal mock for isolation and speed.
 // For full integration tests with the real Game Hub contract, see the platform repo.
 
-use crate::{Error, NumberGuessContract, NumberGuessContractClient};
+use crate::{
+    DataKey, Error, GuessRules, GuessScoring, NumberGuessContract, NumberGuessContractClient,
+    RematchOffer, Series, SessionKey, SettlementConfig, SettlementMode, StakeLimits,
+};
+use provably_fair::{number_guess, NativeKeccak, Session, MAX_STRKEY_LEN};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env};
+use soroban_sdk::{contract, contractimpl, xdr::ToXdr, Address, BytesN, Env, Symbol, Vec};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -50,6 +54,20 @@ fn setup_test() -> (
     MockGameHubClient<'static>,
     Address,
     Address,
+) {
+    setup_test_with_min_ttl(u32::MAX / 2)
+}
+
+/// Same as `setup_test`, but new storage entries start with `min_ttl` ledgers
+/// so tests can observe TTL extension and expiry.
+fn setup_test_with_min_ttl(
+    min_ttl: u32,
+) -> (
+    Env,
+    NumberGuessContractClient<'static>,
+    MockGameHubClient<'static>,
+    Address,
+    Address,
 ) {
     let env = Env::default();
     env.mock_all_auths();
@@ -61,8 +79,8 @@ fn setup_test() -> (
         sequence_number: 100,
         network_id: Default::default(),
         base_reserve: 10,
-        min_temp_entry_ttl: u32::MAX / 2,
-        min_persistent_entry_ttl: u32::MAX / 2,
+        min_temp_entry_ttl: min_ttl,
+        min_persistent_entry_ttl: min_ttl,
         max_entry_ttl: u32::MAX / 2,
     });
 
@@ -144,6 +162,7 @@ fn assert_number_guess_error<T, E>(
 // ============================================================================
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_complete_game() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
@@ -151,7 +170,7 @@ fn test_complete_game() {
     let points = 100_0000000;
 
     // Start game
-    client.start_game(&session_id, &player1, &player2, &points, &points);
+    client.start_game(&session_id, &player1, &player2, &points, &points, &None, &None);
 
     // Get game to verify state
     let game = client.get_game(&session_id);
@@ -167,7 +186,7 @@ fn test_complete_game() {
     client.make_guess(&session_id, &player2, &7);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
     assert!(winner == player1 || winner == player2);
 
     // Verify game is ended and winning number is now set
@@ -180,16 +199,17 @@ fn test_complete_game() {
 }
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_winning_number_in_range() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 2u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Make guesses and reveal winner to generate winning number
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &7);
-    client.reveal_winner(&session_id);
+    client.reveal_winner(&session_id, &player1);
 
     let game = client.get_game(&session_id);
     let winning_number = game
@@ -210,8 +230,8 @@ fn test_multiple_sessions() {
     let session1 = 3u32;
     let session2 = 4u32;
 
-    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000);
-    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000);
+    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000, &None, &None);
 
     // Verify both games exist and are independent
     let game1 = client.get_game(&session1);
@@ -226,18 +246,19 @@ fn test_multiple_sessions() {
 // ============================================================================
 
 #[test]
+#[allow(clippy::manual_abs_diff)]
 fn test_closest_guess_wins() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 5u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Player1 guesses closer (1 away from any number between 1-10)
     // Player2 guesses further (at least 2 away)
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &10);
 
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
 
     // Get the final game state to check the winning number
     let game = client.get_game(&session_id);
@@ -271,29 +292,30 @@ fn test_tie_game_player1_wins() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 6u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Both players guess the same number (guaranteed tie)
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &5);
 
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
     assert_eq!(winner, player1, "Player1 should win in a tie");
 }
 
 #[test]
+#[allow(clippy::manual_abs_diff)]
 fn test_exact_guess_wins() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 7u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Player1 guesses 5 (middle), player2 guesses 10 (edge)
     // Player1 is more likely to be closer to the winning number
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &10);
 
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
     let game = client.get_game(&session_id);
     let winning_number = game.winning_number.unwrap();
 
@@ -325,7 +347,7 @@ fn test_cannot_guess_twice() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 8u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Make first guess
     client.make_guess(&session_id, &player1, &5);
@@ -340,18 +362,17 @@ fn test_cannot_reveal_before_both_guesses() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 9u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Only player1 guesses
     client.make_guess(&session_id, &player1, &5);
 
     // Try to reveal winner - should fail
-    let result = client.try_reveal_winner(&session_id);
+    let result = client.try_reveal_winner(&session_id, &player1);
     assert_number_guess_error(&result, Error::BothPlayersNotGuessed);
 }
 
 #[test]
-#[should_panic(expected = "Guess must be between 1 and 10")]
 fn test_cannot_guess_below_range() {
     let (env, client, _hub, player1, _player2) = setup_test();
 
@@ -362,14 +383,16 @@ fn test_cannot_guess_below_range() {
         &Address::generate(&env),
         &100_0000000,
         &100_0000000,
+        &None,
+        &None,
     );
 
-    // Try to guess 0 (below range) - should panic
-    client.make_guess(&session_id, &player1, &0);
+    // Try to guess 0 (below range)
+    let result = client.try_make_guess(&session_id, &player1, &0);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
 }
 
 #[test]
-#[should_panic(expected = "Guess must be between 1 and 10")]
 fn test_cannot_guess_above_range() {
     let (env, client, _hub, player1, _player2) = setup_test();
 
@@ -380,10 +403,22 @@ fn test_cannot_guess_above_range() {
         &Address::generate(&env),
         &100_0000000,
         &100_0000000,
+        &None,
+        &None,
     );
 
-    // Try to guess 11 (above range) - should panic
-    client.make_guess(&session_id, &player1, &11);
+    // Try to guess 11 (above range)
+    let result = client.try_make_guess(&session_id, &player1, &11);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
+}
+
+#[test]
+fn test_cannot_play_against_self() {
+    let (_env, client, _hub, player1, _player2) = setup_test();
+
+    let points = 100_0000000;
+    let result = client.try_start_game(&90u32, &player1, &player1, &points, &points, &None, &None);
+    assert_number_guess_error(&result, Error::SelfPlay);
 }
 
 #[test]
@@ -392,7 +427,7 @@ fn test_non_player_cannot_guess() {
     let non_player = Address::generate(&env);
 
     let session_id = 11u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Non-player tries to guess
     let result = client.try_make_guess(&session_id, &non_player, &5);
@@ -403,7 +438,7 @@ fn test_non_player_cannot_guess() {
 fn test_cannot_reveal_nonexistent_game() {
     let (_env, client, _hub, _player1, _player2) = setup_test();
 
-    let result = client.try_reveal_winner(&999);
+    let result = client.try_reveal_winner(&999, &_player1);
     assert_number_guess_error(&result, Error::GameNotFound);
 }
 
@@ -412,14 +447,14 @@ fn test_cannot_guess_after_game_ended() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 12u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     // Both players make guesses
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &7);
 
     // Reveal winner - game ends
-    let _winner = client.reveal_winner(&session_id);
+    let _winner = client.reveal_winner(&session_id, &player1);
 
     // Try to make another guess after game has ended - should fail
     let result = client.try_make_guess(&session_id, &player1, &3);
@@ -431,17 +466,17 @@ fn test_cannot_reveal_twice() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
     let session_id = 14u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
 
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &7);
 
     // First reveal succeeds
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
     assert!(winner == player1 || winner == player2);
 
     // Second reveal should return same winner (idempotent)
-    let winner2 = client.reveal_winner(&session_id);
+    let winner2 = client.reveal_winner(&session_id, &player1);
     assert_eq!(winner, winner2);
 }
 
@@ -459,8 +494,8 @@ fn test_multiple_games_independent() {
     let session2 = 21u32;
 
     // Start two games
-    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000);
-    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000);
+    client.start_game(&session1, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    client.start_game(&session2, &player3, &player4, &50_0000000, &50_0000000, &None, &None);
 
     // Play both games independently
     client.make_guess(&session1, &player1, &3);
@@ -469,8 +504,8 @@ fn test_multiple_games_independent() {
     client.make_guess(&session2, &player4, &2);
 
     // Reveal both winners
-    let winner1 = client.reveal_winner(&session1);
-    let winner2 = client.reveal_winner(&session2);
+    let winner1 = client.reveal_winner(&session1, &player1);
+    let winner2 = client.reveal_winner(&session2, &player3);
 
     assert!(winner1 == player1 || winner1 == player2);
     assert!(winner2 == player3 || winner2 == player4);
@@ -495,7 +530,7 @@ fn test_asymmetric_points() {
     let points1 = 200_0000000;
     let points2 = 50_0000000;
 
-    client.start_game(&session_id, &player1, &player2, &points1, &points2);
+    client.start_game(&session_id, &player1, &player2, &points1, &points2, &None, &None);
 
     let game = client.get_game(&session_id);
     assert_eq!(game.player1_points, points1);
@@ -503,11 +538,16 @@ fn test_asymmetric_points() {
 
     client.make_guess(&session_id, &player1, &5);
     client.make_guess(&session_id, &player2, &5);
-    client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1);
 
-    // Game completes successfully with asymmetric points
-    let final_game = client.get_game(&session_id);
-    assert!(final_game.winner.is_some()); // Game has ended
+    // Stakes are matched at the smaller one: the winner takes 50 from the
+    // loser, and player 1's unmatched 150 goes back to them either way
+    let result = client.get_match(&session_id);
+    if winner == player1 {
+        assert_eq!((result.player1_payout, result.player2_payout), (250_0000000, 0));
+    } else {
+        assert_eq!((result.player1_payout, result.player2_payout), (150_0000000, 100_0000000));
+    }
 }
 
 // ============================================================================
@@ -537,3 +577,743 @@ fn test_upgrade_function_exists() {
     // This confirms the authorization check passed
     assert!(result.is_err());
 }
+
+#[test]
+fn test_missing_admin_and_hub_are_typed_errors() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // A game that is ready to settle when the hub goes missing
+    client.start_game(&91u32, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    client.make_guess(&91u32, &player1, &3);
+    client.make_guess(&91u32, &player2, &7);
+
+    // Simulate an instance that lost its configured addresses
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::Admin);
+        env.storage().instance().remove(&DataKey::GameHubAddress);
+    });
+
+    assert_number_guess_error(&client.try_get_admin(), Error::AdminNotSet);
+    assert_number_guess_error(&client.try_set_admin(&player1), Error::AdminNotSet);
+    assert_number_guess_error(&client.try_set_hub(&player1), Error::AdminNotSet);
+    let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+    assert_number_guess_error(&client.try_upgrade(&new_wasm_hash), Error::AdminNotSet);
+    assert_number_guess_error(&client.try_get_hub(), Error::GameHubNotSet);
+    assert_number_guess_error(&client.try_reveal_winner(&91u32, &player1), Error::GameHubNotSet);
+}
+
+// ============================================================================
+// Best-of-N Tests
+// ============================================================================
+
+#[test]
+fn test_best_of_match_plays_until_majority() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 50u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &Some(3), &None);
+
+    let mut game = client.get_game(&session_id);
+    while game.winner.is_none() {
+        client.make_guess(&session_id, &player1, &3);
+        client.make_guess(&session_id, &player2, &8);
+        client.reveal_winner(&session_id, &player1);
+        game = client.get_game(&session_id);
+    }
+
+    // Someone took two rounds, in two or three rounds
+    let winner = game.winner.clone().unwrap();
+    let (winner_wins, loser_wins) = if winner == player1 {
+        (game.player1_wins, game.player2_wins)
+    } else {
+        (game.player2_wins, game.player1_wins)
+    };
+    assert_eq!(winner_wins, 2);
+    assert!(loser_wins <= 1);
+    assert_eq!(game.rounds.len(), winner_wins + loser_wins);
+    for (index, round) in game.rounds.iter().enumerate() {
+        assert_eq!(round.round, index as u32 + 1);
+        let player1_won = round.winning_number.abs_diff(3) <= round.winning_number.abs_diff(8);
+        assert_eq!(round.player1_won, player1_won);
+    }
+    assert_eq!(game.rounds.last().unwrap().player1_won, winner == player1);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.best_of, 3);
+    assert_eq!(archived.player1_wins, game.player1_wins);
+    assert_eq!(archived.player2_wins, game.player2_wins);
+}
+
+#[test]
+fn test_guesses_reset_between_rounds() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let session_id = 51u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &Some(5), &None);
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+    let round_winner = client.reveal_winner(&session_id, &player1);
+
+    // One round cannot decide a best-of-5
+    let game = client.get_game(&session_id);
+    assert!(game.winner.is_none());
+    assert_eq!(game.round, 2);
+    assert_eq!(game.player1_guess, None);
+    assert_eq!(game.player2_guess, None);
+    assert_eq!(game.rounds.len(), 1);
+    assert_eq!(game.rounds.get(0).unwrap().player1_won, round_winner == player1);
+
+    // The next round needs fresh guesses from both players
+    client.make_guess(&session_id, &player1, &5);
+    let result = client.try_reveal_winner(&session_id, &player1);
+    assert_number_guess_error(&result, Error::BothPlayersNotGuessed);
+    let result = client.try_get_match(&session_id);
+    assert_number_guess_error(&result, Error::MatchNotFound);
+}
+
+#[test]
+fn test_invalid_best_of_rejected() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    for best_of in [0u32, 2, 17] {
+        let points = 100_0000000;
+        let best_of = Some(best_of);
+        let result =
+            client.try_start_game(&52u32, &player1, &player2, &points, &points, &best_of, &None);
+        assert_number_guess_error(&result, Error::InvalidBestOf);
+    }
+}
+
+// ============================================================================
+// Guess Rules Tests
+// ============================================================================
+
+/// Start a single-round session with custom rules
+fn start_with_rules(
+    client: &NumberGuessContractClient<'static>,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    best_of: Option<u32>,
+    rules: GuessRules,
+) {
+    let points = 100_0000000;
+    let rules = Some(rules);
+    client.start_game(&session_id, player1, player2, &points, &points, &best_of, &rules);
+}
+
+#[test]
+fn test_custom_range() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let rules = GuessRules {
+        min: 100,
+        max: 200,
+        scoring: GuessScoring::Closest,
+    };
+    start_with_rules(&client, 60, &player1, &player2, None, rules.clone());
+    assert_eq!(client.get_game(&60u32).rules, rules);
+
+    // The default range no longer applies
+    let result = client.try_make_guess(&60u32, &player1, &5);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
+    let result = client.try_make_guess(&60u32, &player1, &201);
+    assert_number_guess_error(&result, Error::GuessOutOfRange);
+
+    client.make_guess(&60u32, &player1, &100);
+    client.make_guess(&60u32, &player2, &200);
+    client.reveal_winner(&60u32, &player1);
+    let winning_number = client.get_game(&60u32).winning_number.unwrap();
+    assert!((100..=200).contains(&winning_number));
+}
+
+#[test]
+fn test_jackpot_ends_match_early() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    // With only two numbers, one of the guesses always hits
+    let rules = GuessRules {
+        min: 1,
+        max: 2,
+        scoring: GuessScoring::Jackpot,
+    };
+    start_with_rules(&client, 61, &player1, &player2, Some(5), rules);
+    client.make_guess(&61u32, &player1, &1);
+    client.make_guess(&61u32, &player2, &2);
+    let winner = client.reveal_winner(&61u32, &player1);
+
+    let game = client.get_game(&61u32);
+    assert_eq!(game.winner, Some(winner.clone()));
+    assert_eq!(game.rounds.len(), 1);
+    assert_eq!(game.player1_wins + game.player2_wins, 1);
+    assert_eq!(client.get_match(&61u32).winner, winner);
+}
+
+#[test]
+fn test_price_is_right_never_goes_over() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let rules = GuessRules {
+        min: 1,
+        max: 10,
+        scoring: GuessScoring::PriceIsRight,
+    };
+    start_with_rules(&client, 62, &player1, &player2, None, rules);
+    client.make_guess(&62u32, &player1, &1);
+    client.make_guess(&62u32, &player2, &10);
+    let winner = client.reveal_winner(&62u32, &player1);
+
+    // Player 2 went over unless the number is exactly 10
+    let winning_number = client.get_game(&62u32).winning_number.unwrap();
+    let expected = if winning_number == 10 { &player2 } else { &player1 };
+    assert_eq!(&winner, expected);
+}
+
+#[test]
+fn test_invalid_rules_rejected() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+
+    let points = 100_0000000;
+    for (min, max) in [(5, 5), (10, 1)] {
+        let rules = Some(GuessRules {
+            min,
+            max,
+            scoring: GuessScoring::Closest,
+        });
+        let result =
+            client.try_start_game(&63u32, &player1, &player2, &points, &points, &None, &rules);
+        assert_number_guess_error(&result, Error::InvalidRules);
+    }
+}
+
+// ============================================================================
+// Storage TTL Tests
+// ============================================================================
+
+/// TTL a freshly created entry gets in the TTL tests
+const SHORT_TTL: u32 = 100;
+
+/// Advance the ledger sequence by `ledgers`
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_move_extends_game_ttl() {
+    let (env, client, _hub, player1, player2) = setup_test_with_min_ttl(SHORT_TTL);
+
+    let session_id = 100u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+
+    // Move close to the end of the original 30-day TTL, then go past it
+    advance_ledgers(&env, 518_400 - 100);
+    client.make_guess(&session_id, &player1, &5);
+    advance_ledgers(&env, 1_000);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1, player1);
+}
+
+// ============================================================================
+// Match History Tests
+// ============================================================================
+
+/// Play a game to completion and return the winner
+fn finish_game(
+    client: &NumberGuessContractClient,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+) -> Address {
+    client.make_guess(&session_id, player1, &3);
+    client.make_guess(&session_id, player2, &8);
+    client.reveal_winner(&session_id, player1)
+}
+
+#[test]
+fn test_match_archived_after_game_end() {
+    let (env, client, _hub, player1, player2) = setup_test_with_min_ttl(SHORT_TTL);
+
+    let session_id = 200u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &50_0000000, &None, &None);
+
+    let result = client.try_get_match(&session_id);
+    assert_number_guess_error(&result, Error::MatchNotFound);
+
+    let winner = finish_game(&client, session_id, &player1, &player2);
+    let game = client.get_game(&session_id);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.session_id, session_id);
+    assert_eq!(archived.player1, player1);
+    assert_eq!(archived.player2, player2);
+    assert_eq!(archived.player1_points, 100_0000000);
+    assert_eq!(archived.player2_points, 50_0000000);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.player1_won, winner == player1);
+    assert_eq!(archived.end_ledger, env.ledger().sequence());
+    let expected_hash: BytesN<32> = env.crypto().keccak256(&game.to_xdr(&env)).into();
+    assert_eq!(archived.state_hash, expected_hash);
+
+    // The archive outlives the temporary game entry
+    advance_ledgers(&env, 518_400 + 1);
+    let result = client.try_get_game(&session_id);
+    assert_number_guess_error(&result, Error::GameNotFound);
+    assert_eq!(client.get_match(&session_id), archived);
+}
+
+#[test]
+fn test_player_match_history_pagination() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+
+    for (session_id, opponent) in [(201u32, &player2), (202, &player3), (203, &player2)] {
+        let points = 100_0000000;
+        client.start_game(&session_id, &player1, opponent, &points, &points, &None, &None);
+        finish_game(&client, session_id, &player1, opponent);
+    }
+
+    assert_eq!(client.get_player_match_count(&player1), 3);
+    assert_eq!(client.get_player_match_count(&player2), 2);
+    assert_eq!(client.get_player_match_count(&player3), 1);
+
+    let page = client.get_player_matches(&player1, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().session_id, 201);
+    assert_eq!(page.get(1).unwrap().session_id, 202);
+
+    let page = client.get_player_matches(&player1, &2, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().session_id, 203);
+
+    let page = client.get_player_matches(&player2, &5, &10);
+    assert_eq!(page.len(), 0);
+}
+
+// ============================================================================
+// Settlement Tests
+// ============================================================================
+
+#[test]
+fn test_participants_only_settlement() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    assert_eq!(client.get_settlement_config().mode, SettlementMode::Participants);
+
+    let session_id = 300u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+
+    // Outsiders cannot settle, however long they wait
+    let stranger = Address::generate(&env);
+    advance_ledgers(&env, 10_000);
+    let result = client.try_reveal_winner(&session_id, &stranger);
+    assert_number_guess_error(&result, Error::NotPlayer);
+
+    let winner = client.reveal_winner(&session_id, &player2);
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.keeper, None);
+    assert_eq!(archived.keeper_reward, 0);
+}
+
+#[test]
+fn test_keeper_settlement_earns_reward() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let keeper = Address::generate(&env);
+
+    // Nobody escrows a game started straight through the hub, so nobody
+    // could pay its keeper
+    let direct_id = 300u32;
+    client.start_game(&direct_id, &player1, &player2, &100_0000000, &50_0000000, &None, &None);
+    client.make_guess(&direct_id, &player1, &3);
+    client.make_guess(&direct_id, &player2, &8);
+    advance_ledgers(&env, 10);
+    let result = client.try_reveal_winner(&direct_id, &keeper);
+    assert_number_guess_error(&result, Error::NotPlayer);
+
+    let session_id = 301u32;
+    client.start_matched_game(&lobby, &session_id, &player1, &player2, &100_0000000, &50_0000000);
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+
+    // The players get a head start after the last guess
+    advance_ledgers(&env, 9);
+    let result = client.try_reveal_winner(&session_id, &keeper);
+    assert_number_guess_error(&result, Error::SettlementTooEarly);
+
+    advance_ledgers(&env, 1);
+    let winner = client.reveal_winner(&session_id, &keeper);
+
+    // 1% of the matched 100 point pot, taken out of the winnings
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.keeper, Some(keeper.clone()));
+    assert_eq!(archived.keeper_reward, 1_0000000);
+    let payouts = (archived.player1_payout, archived.player2_payout);
+    if archived.player1_won {
+        assert_eq!(payouts, (149_0000000, 0));
+    } else {
+        assert_eq!(payouts, (50_0000000, 99_0000000));
+    }
+    assert_eq!(client.get_keeper_earnings(&keeper), 1_0000000);
+
+    // Settling again is a no-op and pays nothing more
+    assert_eq!(client.reveal_winner(&session_id, &keeper), winner);
+    assert_eq!(client.get_keeper_earnings(&keeper), 1_0000000);
+}
+
+#[test]
+fn test_player_settles_free_in_keeper_mode() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    client.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+
+    let session_id = 302u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    finish_game(&client, session_id, &player1, &player2);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.keeper, None);
+    assert_eq!(archived.keeper_reward, 0);
+    assert_eq!(client.get_keeper_earnings(&player1), 0);
+}
+
+#[test]
+fn test_set_settlement_config_rejects_large_reward() {
+    let (_env, client, _hub, _player1, _player2) = setup_test();
+
+    let result = client.try_set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 501,
+        keeper_delay: 0,
+    });
+    assert_number_guess_error(&result, Error::InvalidSettlementConfig);
+}
+
+// ============================================================================
+// Matchmaking Tests
+// ============================================================================
+
+#[test]
+fn test_matched_game_plays_with_default_rules() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let lobby = Address::generate(&env);
+
+    assert!(!client.is_matchmaker(&lobby));
+    client.set_matchmaker(&lobby, &true);
+    assert!(client.is_matchmaker(&lobby));
+
+    let session_id = 100u32;
+    let points = 100_0000000;
+    client.start_matched_game(&lobby, &session_id, &player1, &player2, &points, &points);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.best_of, 1);
+    assert_eq!(game.rules.scoring, GuessScoring::Closest);
+    assert_eq!(client.get_winner(&session_id), None);
+
+    client.make_guess(&session_id, &player1, &3);
+    client.make_guess(&session_id, &player2, &8);
+    let winner = client.reveal_winner(&session_id, &player1);
+    assert_eq!(client.get_winner(&session_id), Some(winner));
+}
+
+#[test]
+fn test_unregistered_matchmaker_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let lobby = Address::generate(&env);
+    let points = 100_0000000;
+
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player2, &points, &points);
+    assert_number_guess_error(&result, Error::NotMatchmaker);
+
+    // Removing a matchmaker revokes it
+    client.set_matchmaker(&lobby, &true);
+    client.set_matchmaker(&lobby, &false);
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player2, &points, &points);
+    assert_number_guess_error(&result, Error::NotMatchmaker);
+
+    client.set_matchmaker(&lobby, &true);
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player1, &points, &points);
+    assert_number_guess_error(&result, Error::SelfPlay);
+}
+
+// ============================================================================
+// Session Info Tests
+// ============================================================================
+
+#[test]
+fn test_session_info_tracks_betting_phase() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let session_id = 400u32;
+    let result = client.try_get_session_info(&session_id);
+    assert_number_guess_error(&result, Error::GameNotFound);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    let info = client.get_session_info(&session_id);
+    assert_eq!(info.player1, player1);
+    assert_eq!(info.player2, player2);
+    assert!(info.awaiting_first_move);
+    assert_eq!(info.winner, None);
+
+    // The first guess closes the betting phase
+    client.make_guess(&session_id, &player2, &4);
+    assert!(!client.get_session_info(&session_id).awaiting_first_move);
+
+    client.make_guess(&session_id, &player1, &7);
+    let winner = client.reveal_winner(&session_id, &player1);
+    let info = client.get_session_info(&session_id);
+    assert!(!info.awaiting_first_move);
+    assert_eq!(info.winner, Some(winner));
+}
+
+// ============================================================================
+// Provable Fairness Tests
+// ============================================================================
+
+#[test]
+fn test_winning_numbers_match_native_recomputation() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let points = 100_0000000;
+
+    for session_id in 500u32..540 {
+        let rules = GuessRules {
+            min: session_id % 3,
+            max: [10, 100, 1_000][session_id as usize % 3],
+            scoring: GuessScoring::Closest,
+        };
+        let (best_of, custom) = (Some(3), Some(rules.clone()));
+        client.start_game(&session_id, &player1, &player2, &points, &points, &best_of, &custom);
+
+        let material = client.get_seed_material(&session_id);
+        assert_eq!(material.session_id, session_id);
+        assert_eq!(material.player1, player1.to_string());
+        assert_eq!(material.player2, player2.to_string());
+        let material1 = material.player1.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+        let material2 = material.player2.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+        let session = Session {
+            session_id: material.session_id,
+            player1: material1.as_slice(),
+            player2: material2.as_slice(),
+        };
+
+        let mut game = client.get_game(&session_id);
+        while game.winner.is_none() {
+            let guess = |offset: u32| rules.min + (session_id * 7 + game.round + offset) % 10;
+            client.make_guess(&session_id, &player1, &guess(0));
+            client.make_guess(&session_id, &player2, &guess(3));
+            client.reveal_winner(&session_id, &player1);
+            game = client.get_game(&session_id);
+        }
+        for round in game.rounds.iter() {
+            let seed = number_guess::round_seed(
+                &NativeKeccak,
+                &session,
+                round.round,
+                round.player1_guess,
+                round.player2_guess,
+            );
+            let number = number_guess::winning_number(&seed, rules.min, rules.max);
+            assert_eq!(round.winning_number, number);
+        }
+    }
+}
+
+#[test]
+fn test_seed_material_requires_game() {
+    let (_env, client, _hub, _player1, _player2) = setup_test();
+    assert_number_guess_error(&client.try_get_seed_material(&560u32), Error::GameNotFound);
+}
+
+// ============================================================================
+// Session Key Tests
+// ============================================================================
+
+#[test]
+fn test_session_key_signs_guesses() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 600u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+
+    let key = Address::generate(&env);
+    let scope = Vec::from_array(&env, [Symbol::new(&env, "make_guess")]);
+    client.register_session_key(&session_id, &player1, &key, &scope, &None);
+    assert_eq!(
+        client.get_session_key(&session_id, &player1),
+        Some(SessionKey {
+            player: player1.clone(),
+            key: key.clone(),
+            functions: scope,
+            expires_at: None,
+        })
+    );
+
+    // The key signs in place of the player, and the guess counts as theirs
+    client.make_guess(&session_id, &key, &5);
+    assert_eq!(env.auths()[0].0, key);
+    assert_eq!(client.get_game(&session_id).player1_guess, Some(5));
+    assert_number_guess_error(
+        &client.try_make_guess(&session_id, &player1, &6),
+        Error::AlreadyGuessed,
+    );
+}
+
+#[test]
+fn test_session_key_expiry_and_revocation() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 610u32;
+    let best_of = Some(3u32);
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &best_of, &None);
+    let none = Vec::new(&env);
+
+    let expiring = Address::generate(&env);
+    let expires_at = Some(env.ledger().sequence() + 10);
+    client.register_session_key(&session_id, &player1, &expiring, &none, &expires_at);
+    advance_ledgers(&env, 10);
+    assert_number_guess_error(
+        &client.try_make_guess(&session_id, &expiring, &5),
+        Error::SessionKeyExpired,
+    );
+
+    // A new key replaces the expired one until it is revoked
+    let key = Address::generate(&env);
+    client.register_session_key(&session_id, &player1, &key, &none, &None);
+    assert_number_guess_error(&client.try_make_guess(&session_id, &expiring, &5), Error::NotPlayer);
+    client.revoke_session_key(&session_id, &player1);
+    assert_eq!(client.get_session_key(&session_id, &player1), None);
+    assert_number_guess_error(&client.try_make_guess(&session_id, &key, &5), Error::NotPlayer);
+    assert_number_guess_error(
+        &client.try_revoke_session_key(&session_id, &player1),
+        Error::SessionKeyNotFound,
+    );
+}
+
+#[test]
+fn test_invalid_session_keys_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 620u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000, &None, &None);
+    let none = Vec::new(&env);
+    let now = Some(env.ledger().sequence());
+
+    let key = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let result = client.try_register_session_key(&session_id, &outsider, &key, &none, &None);
+    assert_number_guess_error(&result, Error::NotPlayer);
+    let result = client.try_register_session_key(&session_id, &player1, &player2, &none, &None);
+    assert_number_guess_error(&result, Error::InvalidSessionKey);
+    let result = client.try_register_session_key(&session_id, &player1, &key, &none, &now);
+    assert_number_guess_error(&result, Error::InvalidSessionKey);
+    let scope = Vec::from_array(&env, [Symbol::new(&env, "reveal_winner")]);
+    let result = client.try_register_session_key(&session_id, &player1, &key, &scope, &None);
+    assert_number_guess_error(&result, Error::InvalidSessionKey);
+
+    // A key signs for one player only
+    client.register_session_key(&session_id, &player1, &key, &none, &None);
+    let result = client.try_register_session_key(&session_id, &player2, &key, &none, &None);
+    assert_number_guess_error(&result, Error::InvalidSessionKey);
+}
+
+// ============================================================================
+// Rematch Tests
+// ============================================================================
+
+#[test]
+fn test_rematch_series() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let rules = GuessRules { min: 1, max: 100, scoring: GuessScoring::OverUnder };
+    let guess_rules = Some(rules.clone());
+    client.start_game(&700, &player1, &player2, &100_0000000, &50_0000000, &None, &guess_rules);
+    assert_number_guess_error(
+        &client.try_propose_rematch(&700, &player1, &701, &false),
+        Error::GameNotEnded,
+    );
+    let first = finish_game(&client, 700, &player1, &player2);
+
+    // Either player proposes; only the other one can accept
+    client.propose_rematch(&700, &player2, &701, &false);
+    assert_eq!(
+        client.get_rematch(&700),
+        Some(RematchOffer { proposer: player2.clone(), new_session_id: 701, double_stakes: false })
+    );
+    assert_number_guess_error(&client.try_accept_rematch(&700, &player2), Error::RematchNotFound);
+    assert_eq!(client.accept_rematch(&700, &player1), 701);
+
+    // Stakes and rules carry over
+    let rematch = client.get_game(&701);
+    assert_eq!((rematch.player1_points, rematch.player2_points), (100_0000000, 50_0000000));
+    assert_eq!(rematch.rules, rules);
+
+    let second = finish_game(&client, 701, &player1, &player2);
+    let wins = |player: &Address| u32::from(first == *player) + u32::from(second == *player);
+    assert_eq!(
+        client.get_series(&701),
+        Some(Series {
+            player1: player1.clone(),
+            player2: player2.clone(),
+            sessions: Vec::from_array(&env, [700, 701]),
+            player1_wins: wins(&player1),
+            player2_wins: wins(&player2),
+        })
+    );
+    assert_number_guess_error(
+        &client.try_propose_rematch(&700, &player1, &702, &false),
+        Error::RematchAlreadyPlayed,
+    );
+}
+
+// ============================================================================
+// Stake Tests
+// ============================================================================
+
+#[test]
+fn test_non_positive_stakes_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    for (points1, points2) in [(0, 10), (10, 0), (-5, 10), (10, -1)] {
+        let result =
+            client.try_start_game(&800, &player1, &player2, &points1, &points2, &None, &None);
+        assert_number_guess_error(&result, Error::InvalidStake);
+    }
+
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let result = client.try_start_matched_game(&lobby, &800, &player1, &player2, &0, &10);
+    assert_number_guess_error(&result, Error::InvalidStake);
+}
+
+#[test]
+fn test_stake_limits() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let default_limits = StakeLimits { min_stake: 1, max_stake: i128::MAX / 2 };
+    assert_eq!(client.get_stake_limits(), default_limits);
+
+    let limits = StakeLimits { min_stake: 10, max_stake: 1_000 };
+    client.set_stake_limits(&limits);
+    assert_eq!(client.get_stake_limits(), limits);
+    for (points1, points2) in [(9, 100), (100, 1_001)] {
+        let result =
+            client.try_start_game(&810, &player1, &player2, &points1, &points2, &None, &None);
+        assert_number_guess_error(&result, Error::StakeOutOfRange);
+    }
+    client.start_game(&810, &player1, &player2, &10, &1_000, &None, &None);
+
+    for (min_stake, max_stake) in [(0, 100), (101, 100), (1, i128::MAX / 2 + 1)] {
+        let result = client.try_set_stake_limits(&StakeLimits { min_stake, max_stake });
+        assert_number_guess_error(&result, Error::InvalidStakeLimits);
+    }
+}
@@ -0,0 +1,435 @@
+#![no_std]
+
+//! # Side Bets
+//!
+//! Spectators back player 1 or player 2 of a live game session with a token
+//! stake. Bets are pooled per session and only accepted while the game reports
+//! that nobody has moved yet, so betting closes before any roll, guess, card
+//! decision or plot can reveal anything about the outcome.
+//!
+//! That only protects games whose outcome is still hidden before the first
+//! move. Dice Duel, Number Guess and Twenty-One draw their dice, numbers and
+//! cards from public seed material (`get_seed_material`), so anyone can
+//! compute them with the `provably-fair` crate as soon as the session starts.
+//! Games flag such sessions as `predetermined` and the pool refuses bets on
+//! them. Dice Duel's `Pig` sessions are not: every roll also takes a secret
+//! from each player.
+//!
+//! Once the game records a winner, the pool is settled pari-mutuel: everyone
+//! who backed the winner shares the whole pool in proportion to their stake.
+//! If the session is cancelled or expires without a winner, or nobody backed
+//! the winner, the pool is void and every bet is refunded.
+//!
+//! **Game Integration:**
+//! Works with every game exposing `get_session_info` (the `SessionGame`
+//! interface of the matchmaking crate). The pool never calls
+//! into the game's state-changing entry points and needs no registration;
+//! bettors choose the game contract, so they trust it to report the winner.
+
+use matchmaking::{SessionGameClient, escrow};
+use soroban_sdk::{
+    Address, BytesN, Env, contract, contracterror, contractimpl, contracttype, token
+};
+
+pub use matchmaking::SessionInfo;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors returned by the contract.
+///
+/// Codes are stable: clients match on them, so existing variants are never
+/// renumbered or reused and new failures get the next free code.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// Nobody has bet on that session yet
+    PoolNotFound = 1,
+    /// The bet amount is zero or negative
+    InvalidAmount = 2,
+    /// The game has no two-player session with that id
+    SessionNotFound = 3,
+    /// The session's first move was made, or the pool was already settled
+    BettingClosed = 4,
+    /// The session's own players cannot bet on it
+    PlayerCannotBet = 5,
+    /// The session is still being played, so the pool cannot be settled yet
+    SessionInProgress = 6,
+    /// The bettor has no winning or refundable stake in the pool
+    NothingToClaim = 7,
+    /// The bettor already claimed their payout
+    AlreadyClaimed = 8,
+    /// The bet token address is missing from instance storage
+    TokenNotSet = 9,
+    /// The admin address is missing from instance storage
+    AdminNotSet = 10,
+    /// The game's outcome can be computed from public seed material, so it takes no bets
+    OutcomePredetermined = 11,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PoolStatus {
+    /// Taking bets until the first move, then waiting for the winner
+    Open = 0,
+    /// The game has a winner; its backers can claim their share
+    Settled = 1,
+    /// No winner to pay (cancelled, expired or unbacked); every bet is refunded
+    Void = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pool {
+    /// Game contract the session is played on
+    pub game: Address,
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    /// Tokens staked on player 1 winning
+    pub total_on_player1: i128,
+    /// Tokens staked on player 2 winning
+    pub total_on_player2: i128,
+    pub status: PoolStatus,
+    /// Session winner (set once settled)
+    pub winner: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Bet {
+    /// Tokens this bettor staked on player 1
+    pub on_player1: i128,
+    /// Tokens this bettor staked on player 2
+    pub on_player2: i128,
+    pub claimed: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    Pool(Address, u32),            // (game, session_id) -> Pool
+    Bet(Address, u32, Address),    // (game, session_id, bettor) -> Bet
+}
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// Pools and bets hold escrowed tokens, so they live in persistent storage and
+// must outlive the sessions they are on: a session that expires voids its pool,
+// and the refunds are claimed after that
+
+/// TTL for pools and bets (60 days in ledgers, ~5 seconds per ledger), twice
+/// the games' default 30-day session TTL
+/// 60 days = 60 * 24 * 60 * 60 / 5 = 1,036,800 ledgers
+const POOL_TTL_LEDGERS: u32 = 1_036_800;
+
+/// Store a pool and extend its TTL
+fn save_pool(env: &Env, pool: &Pool) {
+    let key = DataKey::Pool(pool.game.clone(), pool.session_id);
+    escrow::save(env, &key, pool, POOL_TTL_LEDGERS);
+}
+
+/// Store a bet and extend its TTL
+fn save_bet(env: &Env, key: &DataKey, bet: &Bet) {
+    escrow::save(env, key, bet, POOL_TTL_LEDGERS);
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn load_pool(env: &Env, game: &Address, session_id: u32) -> Result<Pool, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Pool(game.clone(), session_id))
+        .ok_or(Error::PoolNotFound)
+}
+
+fn bet_token(env: &Env) -> Result<token::Client<'_>, Error> {
+    escrow::token(env).ok_or(Error::TokenNotSet)
+}
+
+/// Ask the game about a session. Any failure (no such session, a session the
+/// game no longer keeps, a multi-player session) reads as `None`.
+fn session_info(env: &Env, game: &Address, session_id: u32) -> Option<SessionInfo> {
+    match SessionGameClient::new(env, game).try_get_session_info(&session_id) {
+        Ok(Ok(info)) => Some(info),
+        _ => None,
+    }
+}
+
+/// Tokens staked on `player`'s side of the pool
+fn side_total(pool: &Pool, player: &Address) -> i128 {
+    if *player == pool.player1 {
+        pool.total_on_player1
+    } else {
+        pool.total_on_player2
+    }
+}
+
+/// Settle an open pool from the game's result. A session that is gone, or that
+/// now belongs to other players (its id was reused after it expired), voids
+/// the pool, as does a winner nobody backed.
+fn settle_pool(env: &Env, pool: &mut Pool) -> Result<(), Error> {
+    if pool.status != PoolStatus::Open {
+        return Ok(());
+    }
+
+    pool.status = match session_info(env, &pool.game, pool.session_id) {
+        Some(info) if info.player1 == pool.player1 && info.player2 == pool.player2 => {
+            let winner = info.winner.ok_or(Error::SessionInProgress)?;
+            if side_total(pool, &winner) == 0 {
+                PoolStatus::Void
+            } else {
+                pool.winner = Some(winner);
+                PoolStatus::Settled
+            }
+        }
+        _ => PoolStatus::Void,
+    };
+    save_pool(env, pool);
+    Ok(())
+}
+
+/// Tokens owed to a bet from a closed pool: a pro-rata share of the whole pool
+/// for stakes on the winner (rounded down), or both stakes back if it is void
+fn payout(pool: &Pool, bet: &Bet) -> i128 {
+    match (&pool.status, &pool.winner) {
+        (PoolStatus::Void, _) => bet.on_player1 + bet.on_player2,
+        (PoolStatus::Settled, Some(winner)) => {
+            let stake = if *winner == pool.player1 {
+                bet.on_player1
+            } else {
+                bet.on_player2
+            };
+            let total = pool.total_on_player1 + pool.total_on_player2;
+            stake * total / side_total(pool, winner)
+        }
+        _ => 0,
+    }
+}
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct SideBetsContract;
+
+#[contractimpl]
+impl SideBetsContract {
+    /// Initialize the contract with an admin and the bet token
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can upgrade contract)
+    /// * `token` - Token contract bets are escrowed in
+    pub fn __constructor(env: Env, admin: Address, token: Address) {
+        escrow::initialize(&env, &admin, &token);
+    }
+
+    /// Back one player of a session that has not seen its first move yet.
+    /// Repeated bets from the same bettor add up, on either side. Sessions of
+    /// games whose outcome follows from public seed material are refused.
+    ///
+    /// # Arguments
+    /// * `bettor` - Spectator placing the bet
+    /// * `game` - Game contract the session is played on
+    /// * `session_id` - The session to bet on
+    /// * `on_player1` - `true` to back player 1, `false` to back player 2
+    /// * `amount` - Tokens to stake
+    pub fn place_bet(
+        env: Env,
+        bettor: Address,
+        game: Address,
+        session_id: u32,
+        on_player1: bool,
+        amount: i128,
+    ) -> Result<(), Error> {
+        bettor.require_auth();
+        escrow::extend_instance_ttl(&env);
+
+        if amount <= 0 {
+            return Err(Error::InvalidAmount);
+        }
+        let info = session_info(&env, &game, session_id).ok_or(Error::SessionNotFound)?;
+        if bettor == info.player1 || bettor == info.player2 {
+            return Err(Error::PlayerCannotBet);
+        }
+        if info.predetermined {
+            return Err(Error::OutcomePredetermined);
+        }
+        if !info.awaiting_first_move || info.winner.is_some() {
+            return Err(Error::BettingClosed);
+        }
+
+        let mut pool = match load_pool(&env, &game, session_id) {
+            Ok(pool) => {
+                if pool.status != PoolStatus::Open
+                    || pool.player1 != info.player1
+                    || pool.player2 != info.player2
+                {
+                    return Err(Error::BettingClosed);
+                }
+                pool
+            }
+            Err(_) => Pool {
+                game: game.clone(),
+                session_id,
+                player1: info.player1,
+                player2: info.player2,
+                total_on_player1: 0,
+                total_on_player2: 0,
+                status: PoolStatus::Open,
+                winner: None,
+            },
+        };
+
+        bet_token(&env)?.transfer(&bettor, env.current_contract_address(), &amount);
+
+        let bet_key = DataKey::Bet(game, session_id, bettor);
+        let mut bet: Bet = env.storage().persistent().get(&bet_key).unwrap_or(Bet {
+            on_player1: 0,
+            on_player2: 0,
+            claimed: false,
+        });
+        if on_player1 {
+            bet.on_player1 += amount;
+            pool.total_on_player1 += amount;
+        } else {
+            bet.on_player2 += amount;
+            pool.total_on_player2 += amount;
+        }
+        save_bet(&env, &bet_key, &bet);
+        save_pool(&env, &pool);
+
+        Ok(())
+    }
+
+    /// Settle a pool from the game's result. Anyone may call this once the
+    /// session has a winner, or has been cancelled or expired.
+    ///
+    /// # Arguments
+    /// * `game` - Game contract the session is played on
+    /// * `session_id` - The session the pool is for
+    ///
+    /// # Returns
+    /// * `PoolStatus` - `Settled` if the winner's backers are paid, `Void` if
+    ///   every bet is refunded
+    pub fn settle(env: Env, game: Address, session_id: u32) -> Result<PoolStatus, Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut pool = load_pool(&env, &game, session_id)?;
+        settle_pool(&env, &mut pool)?;
+        Ok(pool.status)
+    }
+
+    /// Pay a bettor their share of a closed pool, settling it first if needed.
+    /// Anyone may call this; the payout always goes to the bettor.
+    ///
+    /// # Arguments
+    /// * `bettor` - Spectator who placed the bets
+    /// * `game` - Game contract the session is played on
+    /// * `session_id` - The session the pool is for
+    ///
+    /// # Returns
+    /// * `i128` - Tokens paid to the bettor
+    pub fn claim(env: Env, bettor: Address, game: Address, session_id: u32) -> Result<i128, Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut pool = load_pool(&env, &game, session_id)?;
+        settle_pool(&env, &mut pool)?;
+
+        let bet_key = DataKey::Bet(game, session_id, bettor.clone());
+        let mut bet: Bet = env
+            .storage()
+            .persistent()
+            .get(&bet_key)
+            .ok_or(Error::NothingToClaim)?;
+        if bet.claimed {
+            return Err(Error::AlreadyClaimed);
+        }
+        let amount = payout(&pool, &bet);
+        if amount == 0 {
+            return Err(Error::NothingToClaim);
+        }
+
+        bet_token(&env)?.transfer(&env.current_contract_address(), &bettor, &amount);
+        bet.claimed = true;
+        save_bet(&env, &bet_key, &bet);
+
+        Ok(amount)
+    }
+
+    /// Get the pool of a session
+    ///
+    /// # Arguments
+    /// * `game` - Game contract the session is played on
+    /// * `session_id` - The session the pool is for
+    pub fn get_pool(env: Env, game: Address, session_id: u32) -> Result<Pool, Error> {
+        load_pool(&env, &game, session_id)
+    }
+
+    /// Get a bettor's stakes in a session's pool, if they bet on it
+    ///
+    /// # Arguments
+    /// * `game` - Game contract the session is played on
+    /// * `session_id` - The session the pool is for
+    /// * `bettor` - Spectator who placed the bets
+    pub fn get_bet(env: Env, game: Address, session_id: u32, bettor: Address) -> Option<Bet> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Bet(game, session_id, bettor))
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        escrow::admin(&env).ok_or(Error::AdminNotSet)
+    }
+
+    /// Set a new admin address
+    ///
+    /// # Arguments
+    /// * `new_admin` - The new admin address
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        escrow::require_admin(&env).ok_or(Error::AdminNotSet)?;
+        escrow::set_admin(&env, &new_admin);
+
+        Ok(())
+    }
+
+    /// Get the token bets are escrowed in
+    pub fn get_token(env: Env) -> Result<Address, Error> {
+        escrow::token_address(&env).ok_or(Error::TokenNotSet)
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        escrow::require_admin(&env).ok_or(Error::AdminNotSet)?;
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,302 @@
+#![cfg(test)]
+
+// Unit tests for the side-bets contract, betting on a mock game whose outcome
+// stays hidden until the players move, with a Stellar asset as the bet token.
+// A real dice-duel contract with a simple mock GameHub shows why predetermined
+// games are refused.
+
+use crate::{Error, PoolStatus, SessionInfo, SideBetsContract, SideBetsContractClient};
+use dice_duel::{DiceDuelContract, DiceDuelContractClient};
+use provably_fair::{dice_duel as dice, NativeKeccak, Session, MAX_STRKEY_LEN};
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+// ============================================================================
+// Mock Session Game for Unit Testing
+// ============================================================================
+
+/// Reports whatever session state a test sets, like a game with secret moves
+/// (e.g. Stellar Dynasties' committed plots)
+#[contract]
+pub struct MockSessionGame;
+
+#[contractimpl]
+impl MockSessionGame {
+    pub fn set_session(env: Env, session_id: u32, info: SessionInfo) {
+        env.storage().persistent().set(&session_id, &info);
+    }
+
+    pub fn remove_session(env: Env, session_id: u32) {
+        env.storage().persistent().remove(&session_id);
+    }
+
+    pub fn get_session_info(env: Env, session_id: u32) -> SessionInfo {
+        env.storage().persistent().get(&session_id).expect("no such session")
+    }
+}
+
+// ============================================================================
+// Mock GameHub for Unit Testing
+// ============================================================================
+
+#[contract]
+pub struct MockGameHub;
+
+#[contractimpl]
+impl MockGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+        // Mock implementation - does nothing
+    }
+
+    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+        // Mock implementation - does nothing
+    }
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const UNIT: i128 = 1_0000000;
+const BALANCE: i128 = 1_000 * UNIT;
+const SESSION: u32 = 1;
+
+struct Setup {
+    env: Env,
+    admin: Address,
+    bets: SideBetsContractClient<'static>,
+    game: MockSessionGameClient<'static>,
+    token: TokenClient<'static>,
+    player1: Address,
+    player2: Address,
+}
+
+fn setup_test() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let game_addr = env.register(MockSessionGame, ());
+    let game = MockSessionGameClient::new(&env, &game_addr);
+
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let bets_addr = env.register(SideBetsContract, (&admin, &token_addr));
+    let bets = SideBetsContractClient::new(&env, &bets_addr);
+    let token = TokenClient::new(&env, &token_addr);
+
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+    let s = Setup {
+        env,
+        admin,
+        bets,
+        game,
+        token,
+        player1,
+        player2,
+    };
+    set_session(&s, true, None);
+    s
+}
+
+/// Set the mock session's betting phase and winner
+fn set_session(s: &Setup, awaiting_first_move: bool, winner: Option<&Address>) {
+    let info = SessionInfo {
+        player1: s.player1.clone(),
+        player2: s.player2.clone(),
+        awaiting_first_move,
+        predetermined: false,
+        winner: winner.cloned(),
+    };
+    s.game.set_session(&SESSION, &info);
+}
+
+/// A spectator with `BALANCE` tokens
+fn bettor(s: &Setup) -> Address {
+    let bettor = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&bettor, &BALANCE);
+    bettor
+}
+
+/// A player has moved; the session is in play
+fn first_move(s: &Setup) {
+    set_session(s, false, None);
+}
+
+/// End the session with `winner`
+fn finish_game(s: &Setup, winner: &Address) {
+    set_session(s, false, Some(winner));
+}
+
+// ============================================================================
+// Betting Tests
+// ============================================================================
+
+#[test]
+fn test_winner_backers_share_pool() {
+    let s = setup_test();
+    let game = &s.game.address;
+    let (alice, bob, carol) = (bettor(&s), bettor(&s), bettor(&s));
+
+    s.bets.place_bet(&alice, game, &SESSION, &true, &(100 * UNIT));
+    s.bets.place_bet(&bob, game, &SESSION, &true, &(200 * UNIT));
+    s.bets.place_bet(&bob, game, &SESSION, &true, &(100 * UNIT));
+    s.bets.place_bet(&carol, game, &SESSION, &false, &(200 * UNIT));
+
+    let pool = s.bets.get_pool(game, &SESSION);
+    assert_eq!(pool.player1, s.player1);
+    assert_eq!(pool.total_on_player1, 400 * UNIT);
+    assert_eq!(pool.total_on_player2, 200 * UNIT);
+    assert_eq!(s.bets.get_bet(game, &SESSION, &bob).unwrap().on_player1, 300 * UNIT);
+    assert_eq!(s.token.balance(&s.bets.address), 600 * UNIT);
+
+    // Nothing to settle while the session is in play
+    first_move(&s);
+    assert_eq!(s.bets.try_settle(game, &SESSION), Err(Ok(Error::SessionInProgress)));
+    finish_game(&s, &s.player1);
+
+    // Claiming settles the pool; player 1's backers split all 600 in
+    // proportion to their stakes and player 2's get nothing
+    assert_eq!(s.bets.claim(&alice, game, &SESSION), 150 * UNIT);
+    assert_eq!(s.bets.claim(&bob, game, &SESSION), 450 * UNIT);
+    assert_eq!(s.token.balance(&bob), BALANCE + 150 * UNIT);
+    let result = s.bets.try_claim(&carol, game, &SESSION);
+    assert_eq!(result, Err(Ok(Error::NothingToClaim)));
+    let result = s.bets.try_claim(&alice, game, &SESSION);
+    assert_eq!(result, Err(Ok(Error::AlreadyClaimed)));
+
+    let pool = s.bets.get_pool(game, &SESSION);
+    assert_eq!(pool.status, PoolStatus::Settled);
+    assert_eq!(pool.winner, Some(s.player1.clone()));
+    assert_eq!(s.token.balance(&s.bets.address), 0);
+}
+
+#[test]
+fn test_betting_closes_at_first_move() {
+    let s = setup_test();
+    let game = &s.game.address;
+    let alice = bettor(&s);
+
+    first_move(&s);
+    let result = s.bets.try_place_bet(&alice, game, &SESSION, &true, &UNIT);
+    assert_eq!(result, Err(Ok(Error::BettingClosed)));
+
+    finish_game(&s, &s.player1);
+    let result = s.bets.try_place_bet(&alice, game, &SESSION, &false, &UNIT);
+    assert_eq!(result, Err(Ok(Error::BettingClosed)));
+
+    assert_eq!(s.bets.try_get_pool(game, &SESSION), Err(Ok(Error::PoolNotFound)));
+    assert_eq!(s.token.balance(&alice), BALANCE);
+}
+
+#[test]
+fn test_invalid_bets_rejected() {
+    let s = setup_test();
+    let game = &s.game.address;
+    let alice = bettor(&s);
+
+    let result = s.bets.try_place_bet(&alice, game, &SESSION, &true, &0);
+    assert_eq!(result, Err(Ok(Error::InvalidAmount)));
+    let result = s.bets.try_place_bet(&alice, game, &99u32, &true, &UNIT);
+    assert_eq!(result, Err(Ok(Error::SessionNotFound)));
+    let result = s.bets.try_place_bet(&s.player1, game, &SESSION, &true, &UNIT);
+    assert_eq!(result, Err(Ok(Error::PlayerCannotBet)));
+    let result = s.bets.try_place_bet(&s.player2, game, &SESSION, &true, &UNIT);
+    assert_eq!(result, Err(Ok(Error::PlayerCannotBet)));
+
+    assert_eq!(s.bets.try_settle(game, &SESSION), Err(Ok(Error::PoolNotFound)));
+    assert_eq!(s.bets.get_bet(game, &SESSION, &alice), None);
+}
+
+// ============================================================================
+// Refund Tests
+// ============================================================================
+
+#[test]
+fn test_expired_session_refunds_both_sides() {
+    let s = setup_test();
+    let game = &s.game.address;
+    let alice = bettor(&s);
+
+    s.bets.place_bet(&alice, game, &SESSION, &true, &(30 * UNIT));
+    s.bets.place_bet(&alice, game, &SESSION, &false, &(20 * UNIT));
+    assert_eq!(s.bets.try_settle(game, &SESSION), Err(Ok(Error::SessionInProgress)));
+
+    // The session is abandoned and the game no longer keeps it
+    s.game.remove_session(&SESSION);
+
+    assert_eq!(s.bets.settle(game, &SESSION), PoolStatus::Void);
+    assert_eq!(s.bets.claim(&alice, game, &SESSION), 50 * UNIT);
+    assert_eq!(s.token.balance(&alice), BALANCE);
+    let result = s.bets.try_claim(&alice, game, &SESSION);
+    assert_eq!(result, Err(Ok(Error::AlreadyClaimed)));
+}
+
+#[test]
+fn test_unbacked_winner_voids_pool() {
+    let s = setup_test();
+    let game = &s.game.address;
+    let (alice, bob) = (bettor(&s), bettor(&s));
+
+    // Everyone backs player 1
+    s.bets.place_bet(&alice, game, &SESSION, &true, &(10 * UNIT));
+    s.bets.place_bet(&bob, game, &SESSION, &true, &(30 * UNIT));
+    finish_game(&s, &s.player2);
+
+    // Nobody backed the winner, so the pool is refunded
+    assert_eq!(s.bets.settle(game, &SESSION), PoolStatus::Void);
+    assert_eq!(s.bets.claim(&alice, game, &SESSION), 10 * UNIT);
+    assert_eq!(s.bets.claim(&bob, game, &SESSION), 30 * UNIT);
+    assert_eq!(s.token.balance(&s.bets.address), 0);
+}
+
+// ============================================================================
+// Predetermined Game Tests
+// ============================================================================
+
+#[test]
+fn test_predetermined_game_refused() {
+    let s = setup_test();
+    let hub_addr = s.env.register(MockGameHub, ());
+    let game_addr = s.env.register(DiceDuelContract, (&s.admin, &hub_addr));
+    let game = DiceDuelContractClient::new(&s.env, &game_addr);
+    let points = 100 * UNIT;
+    game.start_game(&SESSION, &s.player1, &s.player2, &points, &points, &None, &None);
+    assert!(game.get_session_info(&SESSION).awaiting_first_move);
+
+    // Before anyone rolls, a bettor recomputes both players' dice from the
+    // public seed material and knows who will win
+    let material = game.get_seed_material(&SESSION);
+    let player1 = material.player1.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let player2 = material.player2.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let session = Session {
+        session_id: material.session_id,
+        player1: player1.as_slice(),
+        player2: player2.as_slice(),
+    };
+    let seed = dice::round_seed(&NativeKeccak, &session, material.round);
+    let total = |player| dice::roll(&NativeKeccak, &seed, player, 2, 6).sum::<u32>();
+    let player1_wins = total(1) >= total(2);
+
+    // The pool refuses the sure bet
+    let alice = bettor(&s);
+    let result = s.bets.try_place_bet(&alice, &game_addr, &SESSION, &player1_wins, &UNIT);
+    assert_eq!(result, Err(Ok(Error::OutcomePredetermined)));
+    assert_eq!(s.token.balance(&alice), BALANCE);
+
+    game.roll(&SESSION, &s.player1);
+    game.roll(&SESSION, &s.player2);
+    let winner = game.reveal_winner(&SESSION, &s.player1);
+    assert_eq!(winner == s.player1, player1_wins);
+}
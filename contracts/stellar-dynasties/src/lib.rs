@@ -2,19 +2,60 @@
 
 //! # Stellar Dynasties: ZK-Intrigue
 //!
-//! A two-player medieval strategy game where players secretly plot against each other
-//! using Zero-Knowledge proofs. Players commit to secret plots (assassination, bribery,
-//! rebellion) and prove their validity with ZK proofs without revealing their targets.
+//! A medieval strategy game for two to six players who secretly plot against each other.
+//! Players commit to secret plots (assassination, bribery, rebellion) with a Pedersen
+//! commitment, the same one the Noir circuit in `circuits/` proves knowledge of. A plot
+//! stays hidden only until its dynasty reveals it: `verify_plot` takes the plot in
+//! plaintext and recomputes the commitment (see the `pedersen` module), so nobody can
+//! reveal a plot they did not commit, but every revealed plot is public from then on.
+//!
+//! The round's first reveal starts a reveal deadline. Dynasties that have not
+//! revealed by then cannot commit or reveal any more and forfeit: `resolve_round`
+//! eliminates them, so waiting to read the other plots cannot stall the game.
+//!
+//! Each dynasty owns four holdings (heir, treasury, castle, army). Every round a
+//! player plots against one holding of a rival dynasty and secretly guards one of
+//! their own; a plot that hits the guarded holding is neutralized, and a winning
+//! plot takes the targeted holding. Instead of plotting, a player may `Defend` a
+//! holding with counter-intelligence, foiling (and penalizing) a plot against it.
+//!
+//! Dynasties collect gold, troops and influence every round. Each plot spends
+//! one of them (bribery gold, rebellion troops, assassination and defence
+//! influence); the amount is committed with the plot and, when two plots clash,
+//! the one backed by more resources wins. Resolution lives in the pure `rules`
+//! module.
+//!
+//! Dynasties can propose and accept alliances, which pay both allies prestige
+//! every round they hold. Plotting against an ally requires a betrayal flag
+//! committed with the plot; a betrayal ignores the ally's guard and ends the
+//! alliance at resolution.
+//!
+//! A dynasty that loses all its holdings or all its prestige is eliminated and
+//! drops out of later rounds. The game ends when one dynasty is left or the
+//! round limit is reached; dynasties are then ranked and the pot is paid out
+//! by finishing place.
 //!
 //! **Game Hub Integration:**
 //! This game is Game Hub-aware and calls `start_game` / `end_game` on the
-//! hackathon's Game Hub contract.
+//! hackathon's Game Hub contract for two-player sessions, and the multi-player
+//! variant `start_multi_game` / `end_multi_game` for larger ones.
 
+use game_kit::rematch;
+use game_kit::session_keys::{self, authorize_move};
+use game_kit::settlement::{self, credit_keeper, settlement_config};
+use game_kit::stakes;
+use game_kit::ttl::{self, extend_game_ttl, extend_instance_ttl, ttl_config, HISTORY_TTL_LEDGERS};
+use matchmaking::registry;
 use soroban_sdk::{
     contract, contractclient, contracterror, contractimpl, contracttype,
-    vec, Address, Bytes, BytesN, Env, IntoVal,
+    vec, xdr::ToXdr, Address, BytesN, Env, IntoVal, Symbol, Vec,
 };
 
+pub mod pedersen;
+pub mod rules;
+
+use rules::Plot;
+
 // ============================================================================
 // Game Hub Contract Interface (Required by Stellar Hacks)
 // ============================================================================
@@ -32,31 +73,148 @@ pub trait GameHub {
     );
 
     fn end_game(env: Env, session_id: u32, player1_won: bool);
+
+    fn start_multi_game(
+        env: Env,
+        game_id: Address,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+    );
+
+    fn end_multi_game(env: Env, session_id: u32, ranking: Vec<Address>, payouts: Vec<i128>);
 }
 
 // ============================================================================
 // Errors
 // ============================================================================
 
+/// Errors returned by the contract.
+///
+/// Codes are stable: clients match on them, so existing variants are never
+/// renumbered or reused and new failures get the next free code.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
+    /// No session exists for the ID (never started, or expired)
     GameNotFound = 1,
+    /// The address is not a dynasty in the session, or may not settle it
+    /// (keepers only settle sessions whose stakes a matchmaker escrows)
     NotPlayer = 2,
+    /// The dynasty already committed (or revealed) a plot this round
     AlreadyCommitted = 3,
+    /// A plot was revealed without a prior commitment
     PlotNotCommitted = 4,
+    /// The session already has a winner
     GameAlreadyEnded = 5,
+    /// The revealed plot does not match the stored commitment
     InvalidProof = 6,
+    /// The round was resolved before every dynasty revealed and before the
+    /// reveal deadline passed
     BothPlayersNotReady = 7,
+    /// The plot action is not a `PlotAction`
     InvalidAction = 8,
+    /// The same address was given twice (two seats, or a dynasty as its own ally)
     SamePlayer = 9,
+    /// A TTL threshold exceeds its extension target, or a target exceeds the
+    /// network's maximum TTL
+    InvalidTtlConfig = 10,
+    /// No archived result exists for the session
+    MatchNotFound = 11,
+    /// The `GameConfig` would make the session unplayable
+    InvalidConfig = 12,
+    /// The targeted holding or dynasty does not exist or is already lost
+    InvalidTarget = 13,
+    /// The dynasty cannot afford the plot's spend
+    InsufficientResources = 14,
+    /// Too few or too many dynasties, or points do not match players
+    InvalidPlayerCount = 15,
+    /// The dynasty has been eliminated
+    PlayerEliminated = 16,
+    /// The two dynasties are already allied
+    AlreadyAllied = 17,
+    /// No matching alliance proposal is pending
+    ProposalNotFound = 18,
+    /// The two dynasties are not allied
+    NotAllied = 19,
+    /// The betrayal flag does not match plotting against an ally
+    InvalidBetrayal = 20,
+    /// No recorded round has that number
+    RoundNotFound = 21,
+    /// A keeper tried to settle before `keeper_delay` ledgers passed
+    SettlementTooEarly = 22,
+    /// The keeper reward exceeds 500 basis points
+    InvalidSettlementConfig = 23,
+    /// The GameHub address is missing from instance storage
+    GameHubNotSet = 24,
+    /// The admin address is missing from instance storage
+    AdminNotSet = 25,
+    /// The caller of `start_matched_game` is not a registered matchmaker
+    NotMatchmaker = 26,
+    /// A session key is a player of the session, expires in the past, is
+    /// scoped to an unknown entry point or already signs for another player
+    InvalidSessionKey = 27,
+    /// The session key's `expires_at` ledger has been reached
+    SessionKeyExpired = 28,
+    /// The session key is not scoped to this entry point
+    SessionKeyNotAllowed = 29,
+    /// The player has no session key registered for the session
+    SessionKeyNotFound = 30,
+    /// A rematch was proposed before the game ended
+    GameNotEnded = 31,
+    /// No rematch was offered to the player for the session
+    RematchNotFound = 32,
+    /// The session was already rematched; only the latest session of a series can be
+    RematchAlreadyPlayed = 33,
+    /// The session ID is already used by another game
+    SessionIdTaken = 34,
+    /// Doubling the stakes overflows
+    StakeOverflow = 35,
+    /// A player committed zero or negative points
+    InvalidStake = 36,
+    /// A player's points are outside the admin's stake limits
+    StakeOutOfRange = 37,
+    /// The minimum stake is below 1 or above the maximum, or the maximum is
+    /// above `i128::MAX / 8`
+    InvalidStakeLimits = 38,
+    /// The round's reveal deadline has passed
+    RevealDeadlinePassed = 39,
+    /// A plot was committed after a dynasty revealed this round
+    RevealsStarted = 40,
+}
+
+impl From<game_kit::Error> for Error {
+    fn from(error: game_kit::Error) -> Self {
+        match error {
+            game_kit::Error::InvalidTtlConfig => Error::InvalidTtlConfig,
+            game_kit::Error::InvalidSessionKey => Error::InvalidSessionKey,
+            game_kit::Error::SessionKeyExpired => Error::SessionKeyExpired,
+            game_kit::Error::SessionKeyNotAllowed => Error::SessionKeyNotAllowed,
+            game_kit::Error::RematchNotFound => Error::RematchNotFound,
+            game_kit::Error::RematchAlreadyPlayed => Error::RematchAlreadyPlayed,
+            game_kit::Error::StakeOverflow => Error::StakeOverflow,
+            game_kit::Error::InvalidStake => Error::InvalidStake,
+            game_kit::Error::StakeOutOfRange => Error::StakeOutOfRange,
+            game_kit::Error::InvalidStakeLimits => Error::InvalidStakeLimits,
+            game_kit::Error::NotPlayer => Error::NotPlayer,
+            game_kit::Error::SettlementTooEarly => Error::SettlementTooEarly,
+            game_kit::Error::InvalidSettlementConfig => Error::InvalidSettlementConfig,
+        }
+    }
 }
 
 // ============================================================================
 // Data Types
 // ============================================================================
 
+pub use game_kit::rematch::{RematchOffer, Series};
+pub use game_kit::session_keys::SessionKey;
+pub use game_kit::settlement::{SettlementConfig, SettlementMode};
+pub use game_kit::stakes::StakeLimits;
+pub use game_kit::ttl::TtlConfig;
+pub use matchmaking::{KeeperReward, SessionInfo, Settlement};
+
 /// Represents an intrigue action type
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -65,61 +223,778 @@ pub enum PlotAction {
     Assassination = 0,
     Bribery = 1,
     Rebellion = 2,
+    /// Counter-intelligence on one of your own holdings instead of plotting
+    Defend = 3,
+}
+
+/// A holding a dynasty can plot against or guard (the circuit's `target_id`)
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Target {
+    Heir = 1,
+    Treasury = 2,
+    Castle = 3,
+    Army = 4,
+}
+
+/// State of one holding
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Holding {
+    pub target: u32,
+    /// False once an opponent's plot has taken it
+    pub intact: bool,
+}
+
+/// A dynasty's stockpile, spent on plots
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Resources {
+    /// Spent on bribery
+    pub gold: u32,
+    /// Spent on rebellion
+    pub troops: u32,
+    /// Spent on assassination and counter-intelligence
+    pub influence: u32,
+}
+
+/// Balance parameters for a session. The contract-wide default lives in
+/// instance storage; each session keeps its own snapshot in `GameState`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameConfig {
+    /// Maximum rounds per game
+    pub max_rounds: u32,
+    /// Prestige each dynasty starts with
+    pub starting_prestige: i128,
+    /// Prestige gained for successful assassination
+    pub assassination_prestige: i128,
+    /// Prestige gained for successful bribery
+    pub bribery_prestige: i128,
+    /// Prestige gained for successful rebellion
+    pub rebellion_prestige: i128,
+    /// Prestige penalty for failed plot (opponent had counter)
+    pub failed_plot_penalty: i128,
+    /// Prestige both dynasties gain when their plots are the same
+    pub draw_prestige: i128,
+    /// Publicly name the plotter when counter-intelligence foils their plot
+    pub reveal_foiled_plotter: bool,
+    /// Resources each dynasty starts with
+    pub starting_resources: Resources,
+    /// Resources each dynasty collects after every round
+    pub income: Resources,
+    /// Smallest spend a plot (or defence) may commit
+    pub min_plot_spend: u32,
+    /// Strength multiplier for the action that counters the other in a clash
+    pub counter_multiplier: u32,
+    /// Prestige each ally gains for every round an alliance holds
+    pub alliance_prestige: i128,
+}
+
+/// A pact between two dynasties (by seat), or a proposal for one
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Alliance {
+    pub proposer: u32,
+    pub ally: u32,
+}
+
+/// One dynasty's seat in a session
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Dynasty {
+    pub player: Address,
+    pub points: i128,
+    /// Prestige score (determines the ranking)
+    pub prestige: i128,
+    /// Committed plot hash (Pedersen hash of the plot, see `commit_plot`)
+    pub plot_hash: Option<BytesN<32>>,
+    /// Whether the plot has been revealed against its commitment. The reveal
+    /// is stored apart from the game state (see `RevealedPlot`) but is public.
+    pub plot_verified: bool,
+    /// Holdings, indexed by `target - 1`
+    pub holdings: Vec<Holding>,
+    /// Remaining resources
+    pub resources: Resources,
+    /// Round in which the dynasty was knocked out
+    pub eliminated_in: Option<u32>,
+}
+
+/// A plot as revealed to `verify_plot`, held apart from `GameState` until
+/// `resolve_round` applies it. Like the reveal transaction, it can be read by
+/// anyone.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RevealedPlot {
+    /// The `PlotAction` (0=Assassination, 1=Bribery, 2=Rebellion, 3=Defend)
+    pub action: u32,
+    /// Seat of the dynasty the plot is aimed at (own seat when defending)
+    pub target_dynasty: u32,
+    /// Holding the plot is aimed at, or the own holding defended
+    pub target: u32,
+    /// Own holding guarded this round (equal to `target` when defending)
+    pub guard: u32,
+    /// Resources committed to the plot
+    pub spend: u32,
+    /// The plot betrays an ally
+    pub betrayal: bool,
+}
+
+/// What one dynasty did in a resolved round
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundPlay {
+    pub player: Address,
+    pub action: u32,
+    pub target_dynasty: u32,
+    pub target: u32,
+    /// Own holding guarded this round
+    pub guard: u32,
+    pub spend: u32,
+    pub betrayal: bool,
+    /// Prestige change over the round (after the floor at 0)
+    pub prestige_delta: i128,
+    /// The plot took the targeted holding
+    pub took: bool,
+    /// The plot was foiled by counter-intelligence
+    pub foiled: bool,
+}
+
+/// Public record of a resolved round, for replays and disputes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RoundOutcome {
+    pub round: u32,
+    /// Ledger sequence at which the round was resolved
+    pub ledger: u32,
+    /// Revealed plots of every dynasty still in the game, in seat order
+    pub plays: Vec<RoundPlay>,
+    /// Dynasties knocked out this round
+    pub eliminated: Vec<Address>,
+    /// Dynasties that had not revealed by the reveal deadline (also in `eliminated`)
+    pub forfeited: Vec<Address>,
 }
 
 /// Game state stored in temporary storage
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct GameState {
-    pub player1: Address,
-    pub player2: Address,
-    pub player1_points: i128,
-    pub player2_points: i128,
-    /// Player prestige scores (determines winner)
-    pub player1_prestige: i128,
-    pub player2_prestige: i128,
-    /// Committed plot hashes (Pedersen hash of target+secret)
-    pub player1_plot_hash: Option<BytesN<32>>,
-    pub player2_plot_hash: Option<BytesN<32>>,
-    /// Whether each player's plot has been verified via ZK proof
-    pub player1_plot_verified: bool,
-    pub player2_plot_verified: bool,
-    /// Plot action types (revealed after ZK verification)
-    pub player1_action: Option<u32>,
-    pub player2_action: Option<u32>,
+    /// Dynasties by seat (2 to 6)
+    pub players: Vec<Dynasty>,
+    /// Plotters caught by counter-intelligence last round (if the config reveals them)
+    pub exposed_plotters: Vec<Address>,
+    /// Standing alliances
+    pub alliances: Vec<Alliance>,
+    /// Alliance proposals waiting for the ally to accept
+    pub proposals: Vec<Alliance>,
+    /// Dynasties that betrayed an ally last round
+    pub betrayers: Vec<Address>,
     /// Current game round
     pub round: u32,
     /// Whether the game has ended
     pub ended: bool,
     /// Winner address (set when game ends)
     pub winner: Option<Address>,
+    /// Final standings, best first (set when game ends)
+    pub ranking: Vec<Address>,
+    /// Every resolved round, oldest first
+    pub rounds: Vec<RoundOutcome>,
+    /// Ledger of the last plot commitment or verification (keepers wait
+    /// `keeper_delay` after it)
+    pub last_move_ledger: u32,
+    /// Ledger after which dynasties that have not revealed forfeit the round
+    /// (set by the round's first reveal)
+    pub reveal_deadline: Option<u32>,
+    /// Whether a matchmaker (lobby or tournament) escrows the stakes and pays
+    /// keeper rewards; keepers may only settle such sessions
+    pub escrowed: bool,
+    /// Keeper rewards taken out of the pot so far
+    pub keeper_rewards: i128,
+    /// Rules snapshot taken at session start
+    pub config: GameConfig,
+}
+
+/// Archived result of a finished game, kept in persistent storage
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchResult {
+    pub session_id: u32,
+    /// Players by seat
+    pub players: Vec<Address>,
+    /// Points committed by each seat
+    pub points: Vec<i128>,
+    /// Final standings, best first
+    pub ranking: Vec<Address>,
+    /// Points paid to each place in `ranking`: its share of the matched pot
+    /// plus the excess of its stake over the smallest one
+    pub payouts: Vec<i128>,
+    pub winner: Address,
+    /// Keccak-256 of the final game state (XDR encoded)
+    pub state_hash: BytesN<32>,
+    /// Ledger sequence at which the game ended
+    pub end_ledger: u32,
+    /// Points paid to keepers for resolving rounds
+    pub keeper_rewards: i128,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub enum DataKey {
     Game(u32),       // session_id -> GameState
+    Plot(u32, u32),  // (session_id, seat) -> RevealedPlot for the current round
     GameHubAddress,
     Admin,
     SessionCounter,
+    GameConfig,
+    MatchResult(u32),              // session_id -> MatchResult
+    PlayerMatchCount(Address),     // player -> number of archived matches
+    PlayerMatch(Address, u32),     // (player, index) -> session_id
 }
 
 // ============================================================================
 // Configuration
 // ============================================================================
 
-/// TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
-const GAME_TTL_LEDGERS: u32 = 518_400;
-/// Maximum rounds per game
+/// Default maximum rounds per game
 const MAX_ROUNDS: u32 = 3;
-/// Prestige gained for successful assassination
+/// Default starting prestige
+const STARTING_PRESTIGE: i128 = 50;
+/// Default prestige gained for successful assassination
 const ASSASSINATION_PRESTIGE: i128 = 30;
-/// Prestige gained for successful bribery
+/// Default prestige gained for successful bribery
 const BRIBERY_PRESTIGE: i128 = 15;
-/// Prestige gained for successful rebellion
+/// Default prestige gained for successful rebellion
 const REBELLION_PRESTIGE: i128 = 20;
-/// Prestige penalty for failed plot (opponent had counter)
+/// Default prestige penalty for failed plot (opponent had counter)
 const FAILED_PLOT_PENALTY: i128 = 10;
+/// Default prestige bonus when both plots are the same
+const DRAW_PRESTIGE: i128 = 5;
+/// Default amount of each resource a dynasty starts with
+const STARTING_RESOURCES: u32 = 10;
+/// Default amount of each resource collected per round
+const RESOURCE_INCOME: u32 = 3;
+/// Default smallest spend per plot
+const MIN_PLOT_SPEND: u32 = 1;
+/// Default strength multiplier for the countering action
+const COUNTER_MULTIPLIER: u32 = 2;
+/// Default prestige per ally per round of alliance
+const ALLIANCE_PRESTIGE: i128 = 5;
+/// Smallest number of dynasties in a session
+const MIN_DYNASTIES: u32 = 2;
+/// Ledgers the other dynasties have to reveal after a round's first reveal
+/// (1 day = 24 * 60 * 60 / 5 = 17,280 ledgers)
+pub const REVEAL_TIMEOUT_LEDGERS: u32 = 17_280;
+
+// ============================================================================
+// Holding Helpers
+// ============================================================================
+
+/// All four holdings, intact
+fn new_holdings(env: &Env) -> Vec<Holding> {
+    let mut holdings = Vec::new(env);
+    for target in [Target::Heir, Target::Treasury, Target::Castle, Target::Army] {
+        holdings.push_back(Holding {
+            target: target as u32,
+            intact: true,
+        });
+    }
+    holdings
+}
+
+/// Index of a holding in a dynasty's holdings
+fn holding_index(holdings: &Vec<Holding>, target: u32) -> Result<u32, Error> {
+    match target.checked_sub(1) {
+        Some(index) if index < holdings.len() => Ok(index),
+        _ => Err(Error::InvalidTarget),
+    }
+}
+
+/// Index of a holding that can still be plotted against
+fn intact_holding(holdings: &Vec<Holding>, target: u32) -> Result<u32, Error> {
+    let index = holding_index(holdings, target)?;
+    if holdings.get_unchecked(index).intact {
+        Ok(index)
+    } else {
+        Err(Error::InvalidTarget)
+    }
+}
+
+/// Mark a holding as taken by the opponent
+fn take_holding(holdings: &mut Vec<Holding>, target: u32) {
+    if let Some(index) = target.checked_sub(1) {
+        if let Some(mut holding) = holdings.get(index) {
+            holding.intact = false;
+            holdings.set(index, holding);
+        }
+    }
+}
+
+/// A dynasty with no holdings left is knocked out
+fn all_taken(holdings: &Vec<Holding>) -> bool {
+    holdings.iter().all(|holding| !holding.intact)
+}
+
+/// Seat of a player in a session
+fn seat_of(game: &GameState, player: &Address) -> Result<u32, Error> {
+    game.players
+        .iter()
+        .position(|dynasty| dynasty.player == *player)
+        .map(|seat| seat as u32)
+        .ok_or(Error::NotPlayer)
+}
+
+/// Index of the pact between two seats, in either direction
+fn alliance_index(pacts: &Vec<Alliance>, a: u32, b: u32) -> Option<u32> {
+    pacts
+        .iter()
+        .position(|pact| {
+            (pact.proposer == a && pact.ally == b) || (pact.proposer == b && pact.ally == a)
+        })
+        .map(|index| index as u32)
+}
+
+/// Number of pacts a seat is part of
+fn alliance_count(pacts: &Vec<Alliance>, seat: u32) -> u32 {
+    pacts
+        .iter()
+        .filter(|pact| pact.proposer == seat || pact.ally == seat)
+        .count() as u32
+}
+
+/// Drop pacts with a dynasty that has been eliminated
+fn retain_active_pacts(pacts: &mut Vec<Alliance>, players: &Vec<Dynasty>) {
+    let active = |seat: u32| players.get_unchecked(seat).eliminated_in.is_none();
+    let mut index = 0;
+    while index < pacts.len() {
+        let pact = pacts.get_unchecked(index);
+        if active(pact.proposer) && active(pact.ally) {
+            index += 1;
+        } else {
+            pacts.remove(index);
+        }
+    }
+}
+
+/// Load a running session with the seats of a player and a would-be ally,
+/// both still in the game
+fn load_pact_parties(
+    env: &Env,
+    session_id: u32,
+    player: &Address,
+    ally: &Address,
+) -> Result<(GameState, u32, u32), Error> {
+    let game: GameState = env
+        .storage()
+        .temporary()
+        .get(&DataKey::Game(session_id))
+        .ok_or(Error::GameNotFound)?;
+    if game.ended {
+        return Err(Error::GameAlreadyEnded);
+    }
+    if player == ally {
+        return Err(Error::SamePlayer);
+    }
+
+    let seat = seat_of(&game, player)?;
+    let ally_seat = seat_of(&game, ally)?;
+    for party in [seat, ally_seat] {
+        if game.players.get_unchecked(party).eliminated_in.is_some() {
+            return Err(Error::PlayerEliminated);
+        }
+    }
+    Ok((game, seat, ally_seat))
+}
+
+/// Whether `a` finishes ahead of `b`, where `a` sits in the lower seat:
+/// survivors first, then later eliminations, then prestige
+fn ranks_ahead(a: &Dynasty, b: &Dynasty) -> bool {
+    let survival = |d: &Dynasty| d.eliminated_in.map_or(u32::MAX, |round| round);
+    (survival(a), a.prestige) >= (survival(b), b.prestige)
+}
+
+/// Seats ordered by final standing, best first
+fn rank_seats(env: &Env, players: &Vec<Dynasty>) -> Vec<u32> {
+    let mut ranking: Vec<u32> = Vec::new(env);
+    for seat in 0..players.len() {
+        let dynasty = players.get_unchecked(seat);
+        // Insert after every seat that ranks ahead (stable on ties)
+        let mut place = 0;
+        while place < ranking.len()
+            && ranks_ahead(&players.get_unchecked(ranking.get_unchecked(place)), &dynasty)
+        {
+            place += 1;
+        }
+        ranking.insert(place, seat);
+    }
+    ranking
+}
+
+/// Validate the holdings of a revealed plot. For `Defend`, `target` is the own
+/// holding under watch and must equal `guard`.
+fn check_plot_holdings(
+    own: &Vec<Holding>,
+    enemy: &Vec<Holding>,
+    action: u32,
+    target: u32,
+    guard: u32,
+) -> Result<(), Error> {
+    if action == PlotAction::Defend as u32 {
+        if target != guard {
+            return Err(Error::InvalidTarget);
+        }
+        intact_holding(own, target)?;
+    } else {
+        // Plots must be aimed at an intact enemy holding
+        intact_holding(enemy, target)?;
+        holding_index(own, guard)?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Game Config Helpers
+// ============================================================================
+
+/// Built-in balance parameters, used when no config was given
+pub fn default_game_config() -> GameConfig {
+    GameConfig {
+        max_rounds: MAX_ROUNDS,
+        starting_prestige: STARTING_PRESTIGE,
+        assassination_prestige: ASSASSINATION_PRESTIGE,
+        bribery_prestige: BRIBERY_PRESTIGE,
+        rebellion_prestige: REBELLION_PRESTIGE,
+        failed_plot_penalty: FAILED_PLOT_PENALTY,
+        draw_prestige: DRAW_PRESTIGE,
+        reveal_foiled_plotter: true,
+        starting_resources: Resources {
+            gold: STARTING_RESOURCES,
+            troops: STARTING_RESOURCES,
+            influence: STARTING_RESOURCES,
+        },
+        income: Resources {
+            gold: RESOURCE_INCOME,
+            troops: RESOURCE_INCOME,
+            influence: RESOURCE_INCOME,
+        },
+        min_plot_spend: MIN_PLOT_SPEND,
+        counter_multiplier: COUNTER_MULTIPLIER,
+        alliance_prestige: ALLIANCE_PRESTIGE,
+    }
+}
+
+/// Reject configs that would make a session unplayable
+fn validate_game_config(config: &GameConfig) -> Result<(), Error> {
+    if config.max_rounds == 0
+        || config.starting_prestige <= 0
+        || config.assassination_prestige < 0
+        || config.bribery_prestige < 0
+        || config.rebellion_prestige < 0
+        || config.failed_plot_penalty < 0
+        || config.draw_prestige < 0
+        || config.counter_multiplier == 0
+        || config.alliance_prestige < 0
+    {
+        return Err(Error::InvalidConfig);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Match History
+// ============================================================================
+// Finished games are archived in persistent storage so results outlive the
+// temporary game entry (disputes, statistics)
+
+/// Maximum number of results returned by one `get_player_matches` call
+const MAX_HISTORY_PAGE: u32 = 50;
+
+/// Archive the final state of a game and index it under every player.
+/// Must be called once, after the ranking has been set.
+fn archive_match(env: &Env, session_id: u32, game: &GameState, payouts: &Vec<i128>) {
+    let mut players = Vec::new(env);
+    let mut points = Vec::new(env);
+    for dynasty in game.players.iter() {
+        players.push_back(dynasty.player);
+        points.push_back(dynasty.points);
+    }
+    let result = MatchResult {
+        session_id,
+        players: players.clone(),
+        points,
+        ranking: game.ranking.clone(),
+        payouts: payouts.clone(),
+        winner: game.ranking.get_unchecked(0),
+        state_hash: env.crypto().keccak256(&game.clone().to_xdr(env)).into(),
+        end_ledger: env.ledger().sequence(),
+        keeper_rewards: game.keeper_rewards,
+    };
+
+    let key = DataKey::MatchResult(session_id);
+    env.storage().persistent().set(&key, &result);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+
+    for player in players.iter() {
+        index_player_match(env, &player, session_id);
+    }
+    rematch::record_series_result(env, session_id, result.winner == players.get_unchecked(0));
+}
+
+/// Append a session to a player's match index
+fn index_player_match(env: &Env, player: &Address, session_id: u32) {
+    let count_key = DataKey::PlayerMatchCount(player.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let entry_key = DataKey::PlayerMatch(player.clone(), count);
+    env.storage().persistent().set(&entry_key, &session_id);
+    env.storage().persistent().set(&count_key, &(count + 1));
+
+    env.storage()
+        .persistent()
+        .extend_ttl(&entry_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+}
+
+// ============================================================================
+// Settlement
+// ============================================================================
+// Dynasties can always resolve their own rounds. In keeper mode anyone else
+// can too, once the dynasties have had `keeper_delay` ledgers to do it
+// themselves, and earns a share of the pot for each round resolved, paid out
+// by the contract escrowing the stakes (see `game_kit::settlement`).
+
+/// Check that `caller` may resolve the current round of `game` now.
+/// Returns the keeper to reward, or `None` when one of the dynasties resolves.
+fn authorize_settlement(
+    env: &Env,
+    config: &SettlementConfig,
+    game: &GameState,
+    caller: &Address,
+) -> Result<Option<Address>, Error> {
+    let is_player = seat_of(game, caller).is_ok();
+    settlement::authorize_settlement(
+        env,
+        config,
+        caller,
+        is_player,
+        game.escrowed,
+        game.last_move_ledger,
+    )
+    .map_err(Error::from)
+}
+
+/// Keeper reward for resolving one round: the configured share of the
+/// matched pot, spread over the session's round limit
+fn keeper_reward(config: &SettlementConfig, game: &GameState) -> i128 {
+    let pot = matched_stake(&game.players) * i128::from(game.players.len());
+    settlement::keeper_reward(config, pot, game.config.max_rounds)
+}
+
+// ============================================================================
+// Stakes
+// ============================================================================
+// Every dynasty must commit a positive number of points within the admin's
+// limits. Unequal stakes are matched at the smallest one: only that much of
+// each stake is at play and split by finishing place, and the excess goes back
+// to its owner whatever their place.
+
+/// Largest stake the admin can allow, so that six stakes always add up
+const MAX_STAKE: i128 = i128::MAX / 8;
+
+/// Check every dynasty's points against the stake limits
+fn validate_stakes(env: &Env, points: &Vec<i128>) -> Result<(), Error> {
+    let limits = stakes::stake_limits(env, MAX_STAKE);
+    for stake in points.iter() {
+        stakes::validate_stake(&limits, stake)?;
+    }
+    Ok(())
+}
+
+/// The amount of each stake at play: the smallest stake in the session
+fn matched_stake(players: &Vec<Dynasty>) -> i128 {
+    players.iter().map(|dynasty| dynasty.points).min().unwrap_or(0)
+}
+
+// ============================================================================
+// Session Helpers
+// ============================================================================
+
+/// Plots are committed in secret and revealed later, so nothing about the
+/// outcome is known before the first move (see `SessionInfo::predetermined`)
+const OUTCOME_PREDETERMINED: bool = false;
+
+/// Check the seat count, that no player holds two seats and the stakes
+fn validate_players(env: &Env, players: &Vec<Address>, points: &Vec<i128>) -> Result<(), Error> {
+    let count = players.len();
+    if count < MIN_DYNASTIES || count > rules::MAX_DYNASTIES as u32 || points.len() != count {
+        return Err(Error::InvalidPlayerCount);
+    }
+    // Prevent self-play
+    for seat in 0..count {
+        let player = players.get_unchecked(seat);
+        if players.iter().skip(seat as usize + 1).any(|other| other == player) {
+            return Err(Error::SamePlayer);
+        }
+    }
+    validate_stakes(env, points)
+}
+
+/// Validate the players of a new session and collect their authorization.
+/// A per-session config is part of what they sign, so nobody can swap the
+/// rules on them.
+fn authorize_players(
+    env: &Env,
+    session_id: u32,
+    players: &Vec<Address>,
+    points: &Vec<i128>,
+    config: &Option<GameConfig>,
+) -> Result<(), Error> {
+    validate_players(env, players, points)?;
+
+    for (player, player_points) in players.iter().zip(points.iter()) {
+        let mut args = vec![env, session_id.into_val(env), player_points.into_val(env)];
+        if let Some(config) = config {
+            args.push_back(config.into_val(env));
+        }
+        player.require_auth_for_args(args);
+    }
+    Ok(())
+}
+
+/// Require `matchmaker`'s authorization and check the admin registered it.
+/// Registered matchmakers (a lobby, a tournament) collect the players' consent
+/// themselves, so matched sessions skip `authorize_players`.
+fn authorize_matchmaker(env: &Env, matchmaker: &Address) -> Result<(), Error> {
+    if !registry::authorize_matchmaker(env, matchmaker) {
+        return Err(Error::NotMatchmaker);
+    }
+    Ok(())
+}
+
+/// Snapshot the rules so later admin changes never affect a session
+fn session_config(env: &Env, config: Option<GameConfig>) -> Result<GameConfig, Error> {
+    match config {
+        Some(config) => {
+            validate_game_config(&config)?;
+            Ok(config)
+        }
+        None => Ok(StellarDynasties::get_config(env.clone())),
+    }
+}
+
+/// Create and store the initial state of a session; `escrowed` marks
+/// sessions a registered matchmaker started
+fn create_session(
+    env: &Env,
+    session_id: u32,
+    players: &Vec<Address>,
+    points: &Vec<i128>,
+    config: GameConfig,
+    escrowed: bool,
+) {
+    let mut dynasties = Vec::new(env);
+    for (player, player_points) in players.iter().zip(points.iter()) {
+        dynasties.push_back(Dynasty {
+            player,
+            points: player_points,
+            prestige: config.starting_prestige,
+            plot_hash: None,
+            plot_verified: false,
+            holdings: new_holdings(env),
+            resources: config.starting_resources.clone(),
+            eliminated_in: None,
+        });
+    }
+
+    let game = GameState {
+        players: dynasties,
+        exposed_plotters: Vec::new(env),
+        alliances: Vec::new(env),
+        proposals: Vec::new(env),
+        betrayers: Vec::new(env),
+        round: 1,
+        ended: false,
+        winner: None,
+        ranking: Vec::new(env),
+        rounds: Vec::new(env),
+        last_move_ledger: env.ledger().sequence(),
+        reveal_deadline: None,
+        escrowed,
+        keeper_rewards: 0,
+        config,
+    };
+
+    // Store in temporary storage with 30-day TTL
+    let game_key = DataKey::Game(session_id);
+    env.storage().temporary().set(&game_key, &game);
+    extend_game_ttl(env, &game_key);
+}
+
+fn game_hub(env: &Env) -> Result<GameHubClient<'_>, Error> {
+    let game_hub_addr: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .ok_or(Error::GameHubNotSet)?;
+    Ok(GameHubClient::new(env, &game_hub_addr))
+}
+
+// ============================================================================
+// Session Keys
+// ============================================================================
+// A player may register an ephemeral key for one session so their frontend can
+// sign moves without a wallet prompt each time (see `game_kit::session_keys`).
+// Move entry points resolve a key to its player with `authorize_move`.
+
+/// Entry points a session key may sign (`commit_plot` and `verify_plot`)
+const SESSION_KEY_MOVES: [&str; 2] = ["commit_plot", "verify_plot"];
+
+// ============================================================================
+// Rematches
+// ============================================================================
+// Once a two-player session has ended either player may offer a rematch,
+// which the other accepts without a fresh two-party start. The rematch keeps
+// the seats and the session's config and carries the stakes over (or doubles
+// them). Accepted rematches link their sessions into a series whose cumulative
+// score is kept in persistent storage next to the match history.
+
+/// Whether a session, live or archived, already uses `session_id`
+fn session_id_taken(env: &Env, session_id: u32) -> bool {
+    env.storage().temporary().has(&DataKey::Game(session_id))
+        || env.storage().persistent().has(&DataKey::MatchResult(session_id))
+}
+
+/// Load an ended two-player session and check `player` holds one of its seats
+fn load_rematch_game(env: &Env, session_id: u32, player: &Address) -> Result<GameState, Error> {
+    let game: GameState = env
+        .storage()
+        .temporary()
+        .get(&DataKey::Game(session_id))
+        .ok_or(Error::GameNotFound)?;
+    if game.players.len() != MIN_DYNASTIES {
+        return Err(Error::InvalidPlayerCount);
+    }
+    seat_of(&game, player)?;
+    if !game.ended {
+        return Err(Error::GameNotEnded);
+    }
+    Ok(game)
+}
+
+/// Stakes of a rematch of `game` in seat order, checked against the current
+/// stake limits
+fn rematch_stakes(env: &Env, game: &GameState, double_stakes: bool) -> Result<Vec<i128>, Error> {
+    let mut points = Vec::new(env);
+    for dynasty in game.players.iter() {
+        points.push_back(stakes::rematch_stake(dynasty.points, double_stakes)?);
+    }
+    validate_stakes(env, &points)?;
+    Ok(points)
+}
 
 // ============================================================================
 // Contract Definition
@@ -130,12 +1005,21 @@ pub struct StellarDynasties;
 
 #[contractimpl]
 impl StellarDynasties {
-    /// Initialize the contract with admin and Game Hub address.
+    /// Initialize the contract with admin, Game Hub address and default rules.
     ///
     /// # Arguments
     /// * `admin` - Admin address (can upgrade contract)
     /// * `game_hub` - Address of the Game Hub contract
-    pub fn __constructor(env: Env, admin: Address, game_hub: Address) {
+    /// * `config` - Default game config (built-in defaults if `None`)
+    pub fn __constructor(
+        env: Env,
+        admin: Address,
+        game_hub: Address,
+        config: Option<GameConfig>,
+    ) -> Result<(), Error> {
+        let config = config.unwrap_or_else(default_game_config);
+        validate_game_config(&config)?;
+
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage()
             .instance()
@@ -143,93 +1027,404 @@ impl StellarDynasties {
         env.storage()
             .instance()
             .set(&DataKey::SessionCounter, &0u32);
+        env.storage().instance().set(&DataKey::GameConfig, &config);
+        extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    // ========================================================================
+    // Game Lifecycle
+    // ========================================================================
+
+    /// Start a new intrigue session between two players.
+    /// Calls Game Hub `start_game()` as required by the hackathon.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier
+    /// * `player1` - Address of the first player (e.g., Duke)
+    /// * `player2` - Address of the second player (e.g., Baron)
+    /// * `player1_points` - Points amount committed by player 1
+    /// * `player2_points` - Points amount committed by player 2
+    /// * `config` - Rules for this session only (contract default if `None`)
+    pub fn start_session(
+        env: Env,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+        config: Option<GameConfig>,
+    ) -> Result<(), Error> {
+        extend_instance_ttl(&env);
+        if session_id_taken(&env, session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+
+        let players = vec![&env, player1.clone(), player2.clone()];
+        let points = vec![&env, player1_points, player2_points];
+        authorize_players(&env, session_id, &players, &points, &config)?;
+        let config = session_config(&env, config)?;
+
+        // Call Game Hub start_game (REQUIRED by hackathon)
+        game_hub(&env)?.start_game(
+            &env.current_contract_address(),
+            &session_id,
+            &player1,
+            &player2,
+            &player1_points,
+            &player2_points,
+        );
+
+        create_session(&env, session_id, &players, &points, config, false);
+        Ok(())
+    }
+
+    /// Start a two-player session on behalf of a registered matchmaker (see
+    /// `set_matchmaker`). The matchmaker collected both players' consent
+    /// itself, so only its own authorization is required. Uses the contract's
+    /// default config.
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Registered matchmaker contract starting the session
+    /// * `session_id` - Unique session identifier
+    /// * `player1` - Address of the first player
+    /// * `player2` - Address of the second player
+    /// * `player1_points` - Points amount committed by player 1
+    /// * `player2_points` - Points amount committed by player 2
+    pub fn start_matched_game(
+        env: Env,
+        matchmaker: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Result<(), Error> {
+        extend_instance_ttl(&env);
+        if session_id_taken(&env, session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+
+        let players = vec![&env, player1.clone(), player2.clone()];
+        let points = vec![&env, player1_points, player2_points];
+        validate_players(&env, &players, &points)?;
+        authorize_matchmaker(&env, &matchmaker)?;
+        let config = session_config(&env, None)?;
+
+        game_hub(&env)?.start_game(
+            &env.current_contract_address(),
+            &session_id,
+            &player1,
+            &player2,
+            &player1_points,
+            &player2_points,
+        );
+
+        create_session(&env, session_id, &players, &points, config, true);
+        Ok(())
+    }
+
+    /// Start a new intrigue session between three to six dynasties.
+    /// Calls the Game Hub multi-player variant `start_multi_game()`.
+    ///
+    /// # Arguments
+    /// * `session_id` - Unique session identifier
+    /// * `players` - Addresses of the players, in seat order
+    /// * `points` - Points amount committed by each player
+    /// * `config` - Rules for this session only (contract default if `None`)
+    pub fn start_multi_session(
+        env: Env,
+        session_id: u32,
+        players: Vec<Address>,
+        points: Vec<i128>,
+        config: Option<GameConfig>,
+    ) -> Result<(), Error> {
+        extend_instance_ttl(&env);
+        if session_id_taken(&env, session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+
+        // Two-player sessions go through the regular hub interface
+        if players.len() <= MIN_DYNASTIES {
+            return Err(Error::InvalidPlayerCount);
+        }
+        authorize_players(&env, session_id, &players, &points, &config)?;
+        let config = session_config(&env, config)?;
+
+        game_hub(&env)?.start_multi_game(
+            &env.current_contract_address(),
+            &session_id,
+            &players,
+            &points,
+        );
+
+        create_session(&env, session_id, &players, &points, config, false);
+        Ok(())
     }
 
-    // ========================================================================
-    // Game Lifecycle
-    // ========================================================================
+    /// Offer the other player a rematch of an ended two-player session,
+    /// replacing any offer pending for it. The rematch is played under
+    /// `new_session_id` with the same seats and config.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - The player proposing the rematch
+    /// * `new_session_id` - Unused session ID for the rematch
+    /// * `double_stakes` - Double both players' points instead of carrying them over
+    pub fn propose_rematch(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        new_session_id: u32,
+        double_stakes: bool,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let game = load_rematch_game(&env, session_id, &player)?;
+        rematch::check_rematchable(&env, session_id)?;
+        if session_id_taken(&env, new_session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+        rematch_stakes(&env, &game, double_stakes)?;
+
+        let offer = RematchOffer {
+            proposer: player,
+            new_session_id,
+            double_stakes,
+        };
+        rematch::offer_rematch(&env, session_id, &offer);
+        extend_game_ttl(&env, &DataKey::Game(session_id));
+        Ok(())
+    }
+
+    /// Accept the rematch the other player offered, starting it in the Game Hub
+    /// with the stakes of the offer. Returns the session ID of the rematch.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - The player accepting the rematch
+    pub fn accept_rematch(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let game = load_rematch_game(&env, session_id, &player)?;
+        let offer = rematch::take_rematch(&env, session_id, &player)?;
+        if session_id_taken(&env, offer.new_session_id) {
+            return Err(Error::SessionIdTaken);
+        }
+        let points = rematch_stakes(&env, &game, offer.double_stakes)?;
+
+        let mut players = Vec::new(&env);
+        for dynasty in game.players.iter() {
+            players.push_back(dynasty.player);
+        }
+        game_hub(&env)?.start_game(
+            &env.current_contract_address(),
+            &offer.new_session_id,
+            &players.get_unchecked(0),
+            &players.get_unchecked(1),
+            &points.get_unchecked(0),
+            &points.get_unchecked(1),
+        );
+        create_session(
+            &env,
+            offer.new_session_id,
+            &players,
+            &points,
+            game.config.clone(),
+            false,
+        );
+        let player1 = players.get_unchecked(0);
+        let player1_won = game.winner.as_ref() == Some(&player1);
+        rematch::link_rematch(
+            &env,
+            session_id,
+            offer.new_session_id,
+            &player1,
+            &players.get_unchecked(1),
+            player1_won,
+        );
+
+        Ok(offer.new_session_id)
+    }
+
+    /// Propose an alliance to another dynasty in the session.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID
+    /// * `player` - The proposing player
+    /// * `ally` - The player invited into the alliance
+    pub fn propose_alliance(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        ally: Address,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let (mut game, seat, ally_seat) = load_pact_parties(&env, session_id, &player, &ally)?;
+        if alliance_index(&game.alliances, seat, ally_seat).is_some() {
+            return Err(Error::AlreadyAllied);
+        }
+
+        let proposal = Alliance {
+            proposer: seat,
+            ally: ally_seat,
+        };
+        if !game.proposals.contains(&proposal) {
+            game.proposals.push_back(proposal);
+        }
+
+        let key = DataKey::Game(session_id);
+        env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
+        Ok(())
+    }
+
+    /// Accept an alliance proposed by another dynasty.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID
+    /// * `player` - The invited player
+    /// * `proposer` - The player who proposed the alliance
+    pub fn accept_alliance(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        proposer: Address,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let (mut game, seat, proposer_seat) =
+            load_pact_parties(&env, session_id, &player, &proposer)?;
+        let proposal = Alliance {
+            proposer: proposer_seat,
+            ally: seat,
+        };
+        let index = game
+            .proposals
+            .first_index_of(&proposal)
+            .ok_or(Error::ProposalNotFound)?;
+        game.proposals.remove(index);
+
+        // A counter-proposal is settled by the same pact
+        if let Some(counter) = alliance_index(&game.proposals, seat, proposer_seat) {
+            game.proposals.remove(counter);
+        }
+        game.alliances.push_back(proposal);
 
-    /// Start a new intrigue session between two players.
-    /// Calls Game Hub `start_game()` as required by the hackathon.
+        let key = DataKey::Game(session_id);
+        env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
+        Ok(())
+    }
+
+    /// Openly break an alliance. Unlike a betrayal this costs nothing but the
+    /// alliance bonus.
     ///
     /// # Arguments
-    /// * `session_id` - Unique session identifier
-    /// * `player1` - Address of the first player (e.g., Duke)
-    /// * `player2` - Address of the second player (e.g., Baron)
-    /// * `player1_points` - Points amount committed by player 1
-    /// * `player2_points` - Points amount committed by player 2
-    pub fn start_session(
+    /// * `session_id` - The session ID
+    /// * `player` - The player leaving the alliance
+    /// * `ally` - The current ally
+    pub fn break_alliance(
         env: Env,
         session_id: u32,
-        player1: Address,
-        player2: Address,
-        player1_points: i128,
-        player2_points: i128,
+        player: Address,
+        ally: Address,
     ) -> Result<(), Error> {
-        // Prevent self-play
-        if player1 == player2 {
-            return Err(Error::SamePlayer);
-        }
+        player.require_auth();
+        extend_instance_ttl(&env);
 
-        // Require authentication from both players
-        player1.require_auth_for_args(
-            vec![&env, session_id.into_val(&env), player1_points.into_val(&env)],
-        );
-        player2.require_auth_for_args(
-            vec![&env, session_id.into_val(&env), player2_points.into_val(&env)],
-        );
+        let (mut game, seat, ally_seat) = load_pact_parties(&env, session_id, &player, &ally)?;
+        let index = alliance_index(&game.alliances, seat, ally_seat).ok_or(Error::NotAllied)?;
+        game.alliances.remove(index);
 
-        // Call Game Hub start_game (REQUIRED by hackathon)
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+        let key = DataKey::Game(session_id);
+        env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
+        Ok(())
+    }
 
-        // Create initial game state
-        let game = GameState {
-            player1: player1.clone(),
-            player2: player2.clone(),
-            player1_points,
-            player2_points,
-            player1_prestige: 50, // Starting prestige
-            player2_prestige: 50,
-            player1_plot_hash: None,
-            player2_plot_hash: None,
-            player1_plot_verified: false,
-            player2_plot_verified: false,
-            player1_action: None,
-            player2_action: None,
-            round: 1,
-            ended: false,
-            winner: None,
-        };
+    /// Register an ephemeral session key that may sign `player`'s moves in
+    /// this session, replacing any key registered before. The key's address is
+    /// then passed in place of the player's to the move entry points.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player delegating their moves
+    /// * `key` - Address of the session key
+    /// * `functions` - Entry points the key may call (`commit_plot`, `verify_plot`); empty for all
+    /// * `expires_at` - Ledger sequence from which the key is refused, or `None`
+    ///   to keep it for the whole session
+    pub fn register_session_key(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        key: Address,
+        functions: Vec<Symbol>,
+        expires_at: Option<u32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
 
-        // Store in temporary storage with 30-day TTL
-        let game_key = DataKey::Game(session_id);
-        env.storage().temporary().set(&game_key, &game);
-        env.storage()
+        let game: GameState = env
+            .storage()
             .temporary()
-            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if game.ended {
+            return Err(Error::GameAlreadyEnded);
+        }
+        seat_of(&game, &player)?;
+        let is_player = seat_of(&game, &key).is_ok();
+        session_keys::register_session_key(
+            &env,
+            session_id,
+            player,
+            key,
+            functions,
+            expires_at,
+            is_player,
+            &SESSION_KEY_MOVES,
+        )?;
+        Ok(())
+    }
+
+    /// Revoke `player`'s session key; moves must be signed by the player again.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player who registered the key
+    pub fn revoke_session_key(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
 
+        if !session_keys::remove_session_key(&env, session_id, &player) {
+            return Err(Error::SessionKeyNotFound);
+        }
         Ok(())
     }
 
+    /// Get the session key `player` registered for a session, if any.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    pub fn get_session_key(env: Env, session_id: u32, player: Address) -> Option<SessionKey> {
+        session_keys::session_key(&env, session_id, player)
+    }
+
     /// Commit a secret plot hash. The hash is a Pedersen hash of
-    /// (target_id, secret_key, action_type) generated off-chain.
+    /// (target_id, secret_key, action_type, guard_id, spend, target_dynasty, betrayal)
+    /// generated off-chain. Commits close with the round's first reveal, so no
+    /// dynasty can pick its plot after reading another's.
     ///
     /// # Arguments
     /// * `session_id` - The session ID
-    /// * `player` - The player committing the plot
+    /// * `player` - The player committing the plot, or their session key
     /// * `plot_hash` - 32-byte Keccak/Pedersen hash of the plot details
     pub fn commit_plot(
         env: Env,
@@ -237,7 +1432,8 @@ impl StellarDynasties {
         player: Address,
         plot_hash: BytesN<32>,
     ) -> Result<(), Error> {
-        player.require_auth();
+        let player = authorize_move(&env, session_id, player, "commit_plot")?;
+        extend_instance_ttl(&env);
 
         let key = DataKey::Game(session_id);
         let mut game: GameState = env
@@ -250,44 +1446,51 @@ impl StellarDynasties {
             return Err(Error::GameAlreadyEnded);
         }
 
-        // Set the plot hash for the appropriate player
-        if player == game.player1 {
-            if game.player1_plot_hash.is_some() {
-                return Err(Error::AlreadyCommitted);
-            }
-            game.player1_plot_hash = Some(plot_hash);
-        } else if player == game.player2 {
-            if game.player2_plot_hash.is_some() {
-                return Err(Error::AlreadyCommitted);
-            }
-            game.player2_plot_hash = Some(plot_hash);
-        } else {
-            return Err(Error::NotPlayer);
+        // Set the plot hash for the player's dynasty
+        let seat = seat_of(&game, &player)?;
+        let mut dynasty = game.players.get_unchecked(seat);
+        if dynasty.eliminated_in.is_some() {
+            return Err(Error::PlayerEliminated);
         }
+        if dynasty.plot_hash.is_some() {
+            return Err(Error::AlreadyCommitted);
+        }
+        if game.reveal_deadline.is_some() {
+            return Err(Error::RevealsStarted);
+        }
+        dynasty.plot_hash = Some(plot_hash);
+        game.players.set(seat, dynasty);
+        game.last_move_ledger = env.ledger().sequence();
 
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
         Ok(())
     }
 
-    /// Verify a ZK proof for a committed plot.
-    /// In production, this would call the Ultrahonk verifier. For the hackathon
-    /// prototype, we verify the proof data matches the commitment.
+    /// Reveal a committed plot. The contract recomputes the Pedersen hash of the
+    /// revealed fields and the secret key (see `pedersen::plot_commitment`) and
+    /// refuses the reveal unless it matches the hash stored by `commit_plot`, so
+    /// a dynasty can only reveal the plot it committed to before seeing anyone
+    /// else's. The revealed plot is public, but its guard, spend and target only
+    /// take effect when `resolve_round` applies every plot at once.
+    ///
+    /// The round's first reveal sets a deadline `REVEAL_TIMEOUT_LEDGERS` away;
+    /// reveals after it are refused and their dynasties forfeit.
     ///
     /// # Arguments
     /// * `session_id` - The session ID
-    /// * `player` - The player verifying their plot
-    /// * `action_type` - The plot action type (0=Assassination, 1=Bribery, 2=Rebellion)
-    /// * `proof_data` - ZK proof bytes (from Noir prover)
-    /// * `commitment` - The public commitment hash
+    /// * `player` - The player revealing their plot, or their session key
+    /// * `plot` - The committed plot; `betrayal` is required to plot against an ally
+    /// * `secret_key` - The secret the plot was committed with, as a big-endian field element
     pub fn verify_plot(
         env: Env,
         session_id: u32,
         player: Address,
-        action_type: u32,
-        proof_data: Bytes,
-        commitment: BytesN<32>,
+        plot: RevealedPlot,
+        secret_key: BytesN<32>,
     ) -> Result<bool, Error> {
-        player.require_auth();
+        let player = authorize_move(&env, session_id, player, "verify_plot")?;
+        extend_instance_ttl(&env);
 
         let key = DataKey::Game(session_id);
         let mut game: GameState = env
@@ -300,58 +1503,115 @@ impl StellarDynasties {
             return Err(Error::GameAlreadyEnded);
         }
 
-        if action_type > 2 {
+        let RevealedPlot {
+            action: action_type,
+            target_dynasty,
+            target: target_id,
+            guard: guard_id,
+            spend,
+            betrayal,
+        } = plot.clone();
+        if action_type > PlotAction::Defend as u32 {
             return Err(Error::InvalidAction);
         }
 
-        // Verify the commitment matches what was stored
-        if player == game.player1 {
-            let stored_hash = game.player1_plot_hash.as_ref().ok_or(Error::PlotNotCommitted)?;
-
-            // Verify: hash the proof_data + action to produce a verification hash
-            // In production: call env.crypto().bn254_verify() or Ultrahonk verifier
-            // For prototype: verify commitment matches stored hash
-            let mut verify_input = Bytes::new(&env);
-            verify_input.append(&proof_data);
-            let _computed_hash = env.crypto().keccak256(&verify_input);
+        let seat = seat_of(&game, &player)?;
+        let mut dynasty = game.players.get_unchecked(seat);
+        if dynasty.eliminated_in.is_some() {
+            return Err(Error::PlayerEliminated);
+        }
+        let stored_hash = dynasty.plot_hash.as_ref().ok_or(Error::PlotNotCommitted)?;
+        if dynasty.plot_verified {
+            return Err(Error::AlreadyCommitted);
+        }
+        if game.reveal_deadline.is_some_and(|deadline| env.ledger().sequence() > deadline) {
+            return Err(Error::RevealDeadlinePassed);
+        }
 
-            // The commitment must match the stored plot hash
-            if *stored_hash != commitment {
-                return Err(Error::InvalidProof);
+        // Defences watch the own dynasty; plots must be aimed at a rival still in the game
+        let enemy_holdings = if action_type == PlotAction::Defend as u32 {
+            if target_dynasty != seat {
+                return Err(Error::InvalidTarget);
             }
+            dynasty.holdings.clone()
+        } else {
+            match game.players.get(target_dynasty) {
+                Some(rival) if target_dynasty != seat && rival.eliminated_in.is_none() => {
+                    rival.holdings
+                }
+                _ => return Err(Error::InvalidTarget),
+            }
+        };
+        // Allies can only be plotted against by committing to betray them
+        let allied = alliance_index(&game.alliances, seat, target_dynasty).is_some();
+        let plots = action_type != PlotAction::Defend as u32;
+        if betrayal != (plots && allied) {
+            return Err(Error::InvalidBetrayal);
+        }
+        check_plot_holdings(
+            &dynasty.holdings,
+            &enemy_holdings,
+            action_type,
+            target_id,
+            guard_id,
+        )?;
+        // Resources are only paid at resolution, when every plot is applied
+        rules::pay_for_plot(&game.config, &dynasty.resources, action_type, spend)
+            .ok_or(Error::InsufficientResources)?;
 
-            game.player1_plot_verified = true;
-            game.player1_action = Some(action_type);
-        } else if player == game.player2 {
-            let stored_hash = game.player2_plot_hash.as_ref().ok_or(Error::PlotNotCommitted)?;
-
-            let mut verify_input = Bytes::new(&env);
-            verify_input.append(&proof_data);
-            let _computed_hash = env.crypto().keccak256(&verify_input);
+        // The plot must open the stored commitment: no other plot can be revealed
+        let opened = pedersen::plot_commitment(
+            target_id,
+            &secret_key.to_array(),
+            action_type,
+            guard_id,
+            spend,
+            target_dynasty,
+            betrayal,
+        );
+        if opened != Some(stored_hash.to_array()) {
+            return Err(Error::InvalidProof);
+        }
 
-            if *stored_hash != commitment {
-                return Err(Error::InvalidProof);
-            }
+        let plot_key = DataKey::Plot(session_id, seat);
+        env.storage().temporary().set(&plot_key, &plot);
+        extend_game_ttl(&env, &plot_key);
 
-            game.player2_plot_verified = true;
-            game.player2_action = Some(action_type);
-        } else {
-            return Err(Error::NotPlayer);
+        dynasty.plot_verified = true;
+        game.players.set(seat, dynasty);
+        game.last_move_ledger = env.ledger().sequence();
+        if game.reveal_deadline.is_none() {
+            game.reveal_deadline = Some(env.ledger().sequence() + REVEAL_TIMEOUT_LEDGERS);
         }
 
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
         Ok(true)
     }
 
-    /// Resolve the current round after both players have verified their plots.
-    /// Determines prestige changes based on action matchups:
-    ///   - Assassination beats Bribery
-    ///   - Bribery beats Rebellion
-    ///   - Rebellion beats Assassination
+    /// Resolve the current round after every remaining dynasty has verified its
+    /// plot, or once the reveal deadline has passed: dynasties that have not
+    /// revealed by then forfeit, losing their prestige and being eliminated.
+    /// A plot aimed at a holding its target guarded this round is neutralized.
+    /// Two dynasties plotting against each other clash: if only one plot lands,
+    /// it succeeds and the other counts as failed; if both land, the plot backed
+    /// by more resources wins, with the countering action (see `rules::beats`)
+    /// weighing `counter_multiplier` times its spend. A plot against a dynasty
+    /// busy elsewhere succeeds if it lands.
+    ///
+    /// A successful plot takes the targeted holding. Dynasties left without
+    /// holdings or prestige are eliminated; the others collect their income.
+    ///
+    /// Who may call this depends on the `SettlementConfig`: the dynasties always
+    /// can; in keeper mode anyone else can too after `keeper_delay` ledgers, and
+    /// is paid a share of the pot for the round.
     ///
     /// # Arguments
     /// * `session_id` - The session ID
-    pub fn resolve_round(env: Env, session_id: u32) -> Result<GameState, Error> {
+    /// * `caller` - Address resolving the round (dynasty or keeper)
+    pub fn resolve_round(env: Env, session_id: u32, caller: Address) -> Result<GameState, Error> {
+        extend_instance_ttl(&env);
+
         let key = DataKey::Game(session_id);
         let mut game: GameState = env
             .storage()
@@ -363,85 +1623,211 @@ impl StellarDynasties {
             return Err(Error::GameAlreadyEnded);
         }
 
-        // Both players must have verified plots
-        if !game.player1_plot_verified || !game.player2_plot_verified {
-            return Err(Error::BothPlayersNotReady);
+        // Every remaining dynasty must have revealed its plot, unless the
+        // deadline passed: then the ones that did not forfeit
+        let deadline_passed = game
+            .reveal_deadline
+            .is_some_and(|deadline| env.ledger().sequence() > deadline);
+        let mut reveals: [Option<RevealedPlot>; rules::MAX_DYNASTIES] = Default::default();
+        let mut forfeits = [false; rules::MAX_DYNASTIES];
+        for (seat, dynasty) in game.players.iter().enumerate() {
+            if dynasty.eliminated_in.is_some() {
+                continue;
+            }
+            let plot_key = DataKey::Plot(session_id, seat as u32);
+            reveals[seat] = env.storage().temporary().get(&plot_key);
+            if reveals[seat].is_none() {
+                if !deadline_passed {
+                    return Err(Error::BothPlayersNotReady);
+                }
+                forfeits[seat] = true;
+            }
+        }
+        for seat in 0..game.players.len() {
+            env.storage().temporary().remove(&DataKey::Plot(session_id, seat));
+        }
+
+        // Guards only take effect now that every plot is revealed
+        let mut plots = [None; rules::MAX_DYNASTIES];
+        for (seat, reveal) in reveals.iter().enumerate() {
+            let Some(reveal) = reveal else { continue };
+            // A betrayed ally trusted the betrayer and did not guard against them
+            let target_guarded = !reveal.betrayal
+                && reveals
+                    .get(reveal.target_dynasty as usize)
+                    .and_then(Option::as_ref)
+                    .is_some_and(|rival| rival.guard == reveal.target);
+            plots[seat] = Some(rules::SeatPlot {
+                target_dynasty: reveal.target_dynasty,
+                plot: Plot {
+                    action: reveal.action,
+                    target: reveal.target,
+                    spend: reveal.spend,
+                    target_guarded,
+                },
+            });
+        }
+        let settlement = settlement_config(&env);
+        if let Some(keeper) = authorize_settlement(&env, &settlement, &game, &caller)? {
+            let reward = keeper_reward(&settlement, &game);
+            game.keeper_rewards += reward;
+            credit_keeper(&env, session_id, &keeper, reward);
         }
 
-        let p1_action = game.player1_action.unwrap_or(0);
-        let p2_action = game.player2_action.unwrap_or(0);
+        let results = rules::resolve_all(&game.config, &plots[..game.players.len() as usize]);
 
-        // Rock-Paper-Scissors style resolution
-        // 0=Assassination, 1=Bribery, 2=Rebellion
-        // Assassination(0) beats Bribery(1)
-        // Bribery(1) beats Rebellion(2)
-        // Rebellion(2) beats Assassination(0)
-        let (p1_prestige_delta, p2_prestige_delta) = if p1_action == p2_action {
-            // Draw: both get small bonus
-            (5i128, 5i128)
-        } else if (p1_action == 0 && p2_action == 1)
-            || (p1_action == 1 && p2_action == 2)
-            || (p1_action == 2 && p2_action == 0)
-        {
-            // Player 1 wins this round
-            let bonus = match p1_action {
-                0 => ASSASSINATION_PRESTIGE,
-                1 => BRIBERY_PRESTIGE,
-                2 => REBELLION_PRESTIGE,
-                _ => 10,
-            };
-            (bonus, -FAILED_PLOT_PENALTY)
-        } else {
-            // Player 2 wins this round
-            let bonus = match p2_action {
-                0 => ASSASSINATION_PRESTIGE,
-                1 => BRIBERY_PRESTIGE,
-                2 => REBELLION_PRESTIGE,
-                _ => 10,
-            };
-            (-FAILED_PLOT_PENALTY, bonus)
+        // Betrayals end their alliances before the survivors are paid
+        game.betrayers = Vec::new(&env);
+        for (seat, reveal) in reveals.iter().enumerate() {
+            if let Some(reveal) = reveal.as_ref().filter(|reveal| reveal.betrayal) {
+                let betrayed = reveal.target_dynasty;
+                if let Some(index) = alliance_index(&game.alliances, seat as u32, betrayed) {
+                    game.alliances.remove(index);
+                }
+                game.betrayers.push_back(game.players.get_unchecked(seat as u32).player);
+            }
+        }
+
+        // Counter-intelligence may expose whoever it caught
+        game.exposed_plotters = Vec::new(&env);
+        let mut prestige_before = [0i128; rules::MAX_DYNASTIES];
+        for (seat, result) in results.iter().enumerate().take(game.players.len() as usize) {
+            let mut dynasty = game.players.get_unchecked(seat as u32);
+            prestige_before[seat] = dynasty.prestige;
+            if result.foiled && game.config.reveal_foiled_plotter {
+                game.exposed_plotters.push_back(dynasty.player.clone());
+            }
+
+            // Apply prestige changes, including the alliance bonus (floor at 0)
+            let pacts = alliance_count(&game.alliances, seat as u32);
+            let alliance_bonus = game.config.alliance_prestige * i128::from(pacts);
+            dynasty.prestige = (dynasty.prestige + result.delta + alliance_bonus).max(0);
+            game.players.set(seat as u32, dynasty);
+        }
+
+        // Successful plots take the targeted holdings
+        for (seat, plot) in plots.iter().enumerate() {
+            if let Some(plot) = plot {
+                if results[seat].took {
+                    let mut rival = game.players.get_unchecked(plot.target_dynasty);
+                    take_holding(&mut rival.holdings, plot.plot.target);
+                    game.players.set(plot.target_dynasty, rival);
+                }
+            }
+        }
+
+        // Record the round, reset for the next one and knock out fallen dynasties
+        let mut outcome = RoundOutcome {
+            round: game.round,
+            ledger: env.ledger().sequence(),
+            plays: Vec::new(&env),
+            eliminated: Vec::new(&env),
+            forfeited: Vec::new(&env),
         };
+        let mut remaining = 0;
+        for seat in 0..game.players.len() {
+            let mut dynasty = game.players.get_unchecked(seat);
+            if let Some(reveal) = &reveals[seat as usize] {
+                let result = &results[seat as usize];
+                outcome.plays.push_back(RoundPlay {
+                    player: dynasty.player.clone(),
+                    action: reveal.action,
+                    target_dynasty: reveal.target_dynasty,
+                    target: reveal.target,
+                    guard: reveal.guard,
+                    spend: reveal.spend,
+                    betrayal: reveal.betrayal,
+                    prestige_delta: dynasty.prestige - prestige_before[seat as usize],
+                    took: result.took,
+                    foiled: result.foiled,
+                });
+                // Affordable since the reveal: nothing else spends resources
+                let config = &game.config;
+                if let Some(left) =
+                    rules::pay_for_plot(config, &dynasty.resources, reveal.action, reveal.spend)
+                {
+                    dynasty.resources = left;
+                }
+            }
+
+            dynasty.plot_hash = None;
+            dynasty.plot_verified = false;
+            if forfeits[seat as usize] {
+                dynasty.prestige = 0;
+                outcome.forfeited.push_back(dynasty.player.clone());
+            }
+            if dynasty.eliminated_in.is_none() {
+                if dynasty.prestige == 0 || all_taken(&dynasty.holdings) {
+                    dynasty.eliminated_in = Some(game.round);
+                    outcome.eliminated.push_back(dynasty.player.clone());
+                } else {
+                    dynasty.resources = rules::collect_income(&game.config, &dynasty.resources);
+                    remaining += 1;
+                }
+            }
+            game.players.set(seat, dynasty);
+        }
 
-        // Apply prestige changes (floor at 0)
-        game.player1_prestige = (game.player1_prestige + p1_prestige_delta).max(0);
-        game.player2_prestige = (game.player2_prestige + p2_prestige_delta).max(0);
+        game.rounds.push_back(outcome);
 
-        // Reset for next round
-        game.player1_plot_hash = None;
-        game.player2_plot_hash = None;
-        game.player1_plot_verified = false;
-        game.player2_plot_verified = false;
-        game.player1_action = None;
-        game.player2_action = None;
+        retain_active_pacts(&mut game.alliances, &game.players);
+        retain_active_pacts(&mut game.proposals, &game.players);
 
-        // Check if game should end (max rounds reached or prestige knockout)
-        if game.round >= MAX_ROUNDS || game.player1_prestige == 0 || game.player2_prestige == 0 {
+        // Check if game should end (max rounds reached or one dynasty left)
+        if game.round >= game.config.max_rounds || remaining <= 1 {
             game.ended = true;
 
-            // Determine winner
-            let player1_won = game.player1_prestige >= game.player2_prestige;
-            game.winner = if player1_won {
-                Some(game.player1.clone())
-            } else {
-                Some(game.player2.clone())
-            };
+            // Rank the dynasties, split what keepers left of the matched pot by
+            // finishing place and refund each stake's excess over the matched one
+            let seats = rank_seats(&env, &game.players);
+            let count = game.players.len();
+            let matched = matched_stake(&game.players);
+            let pot = matched * i128::from(count) - game.keeper_rewards;
+            let mut payouts = Vec::new(&env);
+            for (rank, seat) in seats.iter().enumerate() {
+                let dynasty = game.players.get_unchecked(seat);
+                let refund = dynasty.points - matched;
+                payouts.push_back(rules::payout(pot, count, rank as u32) + refund);
+                game.ranking.push_back(dynasty.player);
+            }
+            game.winner = game.ranking.first();
 
             // Call Game Hub end_game (REQUIRED by hackathon)
-            let game_hub_addr: Address = env
-                .storage()
-                .instance()
-                .get(&DataKey::GameHubAddress)
-                .expect("GameHub address not set");
-            let game_hub = GameHubClient::new(&env, &game_hub_addr);
-            game_hub.end_game(&session_id, &player1_won);
+            let hub = game_hub(&env)?;
+            if count == MIN_DYNASTIES {
+                hub.end_game(&session_id, &(seats.get_unchecked(0) == 0));
+            } else {
+                hub.end_multi_game(&session_id, &game.ranking, &payouts);
+            }
+
+            // Archive the result so it outlives the temporary session entry
+            archive_match(&env, session_id, &game, &payouts);
         } else {
             game.round += 1;
+            game.reveal_deadline = None;
         }
 
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
         Ok(game)
     }
 
+    /// Extend the TTL of a session and of the contract instance.
+    /// Anyone may call this to keep a long-running match from being archived.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID
+    pub fn bump(env: Env, session_id: u32) -> Result<(), Error> {
+        extend_instance_ttl(&env);
+
+        let key = DataKey::Game(session_id);
+        if !env.storage().temporary().has(&key) {
+            return Err(Error::GameNotFound);
+        }
+        extend_game_ttl(&env, &key);
+        Ok(())
+    }
+
     // ========================================================================
     // Query Functions
     // ========================================================================
@@ -455,12 +1841,180 @@ impl StellarDynasties {
             .ok_or(Error::GameNotFound)
     }
 
+    /// Get the public record of a resolved round.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID
+    /// * `round` - Round number (starting at 1)
+    pub fn get_round(env: Env, session_id: u32, round: u32) -> Result<RoundOutcome, Error> {
+        let game = Self::get_game(env, session_id)?;
+        round
+            .checked_sub(1)
+            .and_then(|index| game.rounds.get(index))
+            .ok_or(Error::RoundNotFound)
+    }
+
     /// Get the Game Hub contract address.
-    pub fn get_hub(env: Env) -> Address {
+    pub fn get_hub(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set")
+            .ok_or(Error::GameHubNotSet)
+    }
+
+    /// Get the archived result of a finished game.
+    /// Available after the temporary game entry has expired.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_match(env: Env, session_id: u32) -> Result<MatchResult, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))
+            .ok_or(Error::MatchNotFound)
+    }
+
+    /// Get the winner of a finished session, for matchmakers settling stakes.
+    /// `None` until the session is archived.
+    pub fn get_winner(env: Env, session_id: u32) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get::<_, MatchResult>(&DataKey::MatchResult(session_id))
+            .map(|result| result.winner)
+    }
+
+    /// Get how a finished two-player session's stakes are split between the
+    /// dynasties and the keepers that resolved its rounds, for matchmakers
+    /// paying out escrowed stakes. Multi-dynasty sessions have none.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID
+    pub fn get_settlement(env: Env, session_id: u32) -> Option<Settlement> {
+        let result: MatchResult = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))?;
+        if result.players.len() != MIN_DYNASTIES {
+            return None;
+        }
+        let payout = |seat: u32| {
+            let place = result.ranking.first_index_of(result.players.get_unchecked(seat))?;
+            result.payouts.get(place)
+        };
+        Some(Settlement {
+            player1_payout: payout(0)?,
+            player2_payout: payout(1)?,
+            winner: result.winner,
+            keeper_rewards: settlement::session_keepers(&env, session_id),
+        })
+    }
+
+    /// Get the rematch offered for an ended session, if one is pending.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    pub fn get_rematch(env: Env, session_id: u32) -> Option<RematchOffer> {
+        rematch::rematch_offer(&env, session_id)
+    }
+
+    /// Get the series a session belongs to, with its cumulative score.
+    /// Sessions that were never rematched have none.
+    ///
+    /// # Arguments
+    /// * `session_id` - Any session of the series
+    pub fn get_series(env: Env, session_id: u32) -> Option<Series> {
+        rematch::session_series(&env, session_id).map(|(_, series)| series)
+    }
+
+    /// Get the players, betting phase and winner of a two-player session, for
+    /// spectators and side-bet pools. Multi-dynasty sessions have no
+    /// player1/player2 sides and return `InvalidPlayerCount`.
+    pub fn get_session_info(env: Env, session_id: u32) -> Result<SessionInfo, Error> {
+        let archived: Option<MatchResult> =
+            env.storage().persistent().get(&DataKey::MatchResult(session_id));
+        if let Some(result) = archived {
+            if result.players.len() != MIN_DYNASTIES {
+                return Err(Error::InvalidPlayerCount);
+            }
+            return Ok(SessionInfo {
+                player1: result.players.get_unchecked(0),
+                player2: result.players.get_unchecked(1),
+                awaiting_first_move: false,
+                predetermined: OUTCOME_PREDETERMINED,
+                winner: Some(result.winner),
+            });
+        }
+
+        let game: GameState = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if game.players.len() != MIN_DYNASTIES {
+            return Err(Error::InvalidPlayerCount);
+        }
+        Ok(SessionInfo {
+            player1: game.players.get_unchecked(0).player,
+            player2: game.players.get_unchecked(1).player,
+            predetermined: OUTCOME_PREDETERMINED,
+            awaiting_first_move: !game.ended
+                && game.round == 1
+                && game.players.iter().all(|dynasty| dynasty.plot_hash.is_none())
+                && game.proposals.is_empty()
+                && game.alliances.is_empty(),
+            winner: game.winner,
+        })
+    }
+
+    /// Get the number of archived matches a player took part in.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    pub fn get_player_match_count(env: Env, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerMatchCount(player))
+            .unwrap_or(0)
+    }
+
+    /// Get a page of a player's archived matches, oldest first.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    /// * `start` - Index of the first match to return
+    /// * `limit` - Maximum number of matches to return (capped at 50)
+    pub fn get_player_matches(
+        env: Env,
+        player: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<MatchResult> {
+        let count = Self::get_player_match_count(env.clone(), player.clone());
+        let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE)).min(count);
+
+        let mut results = Vec::new(&env);
+        for index in start..end {
+            let session_id: Option<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PlayerMatch(player.clone(), index));
+            if let Some(result) = session_id.and_then(|id| {
+                env.storage()
+                    .persistent()
+                    .get::<_, MatchResult>(&DataKey::MatchResult(id))
+            }) {
+                results.push_back(result);
+            }
+        }
+        results
+    }
+
+    /// Get the total keeper rewards an address has earned resolving rounds.
+    ///
+    /// # Arguments
+    /// * `keeper` - Address of the keeper
+    pub fn get_keeper_earnings(env: Env, keeper: Address) -> i128 {
+        settlement::keeper_earnings(&env, keeper)
     }
 
     // ========================================================================
@@ -468,46 +2022,156 @@ impl StellarDynasties {
     // ========================================================================
 
     /// Get the current admin address.
-    pub fn get_admin(env: Env) -> Address {
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set")
+            .ok_or(Error::AdminNotSet)
     }
 
     /// Set a new admin address.
-    pub fn set_admin(env: Env, new_admin: Address) {
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Set a new Game Hub contract address.
-    pub fn set_hub(env: Env, new_hub: Address) {
+    pub fn set_hub(env: Env, new_hub: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
         env.storage()
             .instance()
             .set(&DataKey::GameHubAddress, &new_hub);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Get the default game config applied to new sessions.
+    pub fn get_config(env: Env) -> GameConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::GameConfig)
+            .unwrap_or_else(default_game_config)
+    }
+
+    /// Set the default game config. Running sessions keep their snapshot.
+    pub fn set_config(env: Env, config: GameConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        validate_game_config(&config)?;
+        env.storage().instance().set(&DataKey::GameConfig, &config);
+        extend_instance_ttl(&env);
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds.
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl_config(&env)
+    }
+
+    /// Set the storage TTL thresholds. Each threshold must not exceed its
+    /// extension target, and no target may exceed the network's maximum TTL.
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        ttl::set_ttl_config(&env, &config)?;
+        Ok(())
+    }
+
+    /// Get the settlement rules for `resolve_round`.
+    pub fn get_settlement_config(env: Env) -> SettlementConfig {
+        settlement_config(&env)
+    }
+
+    /// Set the settlement rules for `resolve_round`. The keeper reward may not
+    /// exceed 500 basis points.
+    pub fn set_settlement_config(env: Env, config: SettlementConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        settlement::set_settlement_config(&env, &config)?;
+        Ok(())
+    }
+
+    /// Get the stake limits new sessions are checked against
+    pub fn get_stake_limits(env: Env) -> StakeLimits {
+        stakes::stake_limits(&env, MAX_STAKE)
+    }
+
+    /// Set the stake limits for new sessions; running sessions keep their stakes
+    ///
+    /// # Arguments
+    /// * `limits` - New limits; the minimum must be at least 1 and at most the
+    ///   maximum, which may not exceed `i128::MAX / 8`
+    pub fn set_stake_limits(env: Env, limits: StakeLimits) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        stakes::set_stake_limits(&env, &limits, MAX_STAKE)?;
+        Ok(())
+    }
+
+    /// Check whether a contract may call `start_matched_game`.
+    pub fn is_matchmaker(env: Env, matchmaker: Address) -> bool {
+        registry::is_matchmaker(&env, &matchmaker)
+    }
+
+    /// Register (`allowed`) or remove a matchmaker contract.
+    pub fn set_matchmaker(env: Env, matchmaker: Address, allowed: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        registry::set_matchmaker(&env, matchmaker, allowed);
+        extend_instance_ttl(&env);
+        Ok(())
     }
 
     /// Upgrade the contract WASM.
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
         env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
     }
 }
 
@@ -517,3 +2181,9 @@ impl StellarDynasties {
 
 #[cfg(test)]
 mod test;
+
+#[cfg(test)]
+mod rules_test;
+
+#[cfg(test)]
+mod pedersen_test;
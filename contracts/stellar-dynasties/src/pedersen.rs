@@ -0,0 +1,389 @@
+//! The plot commitment, computed on-chain.
+//!
+//! `verify_plot` recomputes Noir's `std::hash::pedersen_hash` over the seven
+//! plot fields exactly as `circuits/src/main.nr` does and compares it with the
+//! hash stored by `commit_plot`, so a dynasty can only reveal the plot it
+//! committed to. The hash is the x coordinate of
+//! `sum(inputs[i] * G[i]) + 7 * H` on the Grumpkin curve `y^2 = x^3 - 17`,
+//! whose coordinates live in the BN254 scalar field (Noir's `Field`).
+//!
+//! Soroban has no host functions for Grumpkin, so the field and curve
+//! arithmetic lives here. Like `rules`, everything is pure; the generators are
+//! the ones `tools/plot-commitment` derives, and `pedersen_test.rs` checks the
+//! result against that crate and the circuit's pinned vectors.
+
+// Limb arithmetic walks several arrays by the same index
+#![allow(clippy::needless_range_loop)]
+
+/// BN254 scalar field modulus, little-endian limbs
+const MODULUS: [u64; 4] = [
+    0x43e1f593f0000001,
+    0x2833e84879b97091,
+    0xb85045b68181585d,
+    0x30644e72e131a029,
+];
+
+/// `-MODULUS^-1 mod 2^64`, for Montgomery reduction
+const INV: u64 = 0xc2e1f593efffffff;
+
+/// `2^512 mod MODULUS`, to move integers into Montgomery form
+const R2: [u64; 4] = [
+    0x1bb8e645ae216da7,
+    0x53fe3ab1e35c59e3,
+    0x8c49833d53bb8085,
+    0x0216d0b17f4e44a5,
+];
+
+/// Number of fields in a plot commitment
+pub const PLOT_FIELDS: usize = 7;
+
+/// Affine `(x, y)` of the default-domain generators `G[0..7]`, big-endian hex
+const GENERATORS: [(&str, &str); PLOT_FIELDS] = [
+    (
+        "083e7911d835097629f0067531fc15cafd79a89beecb39903f69572c636f4a5a",
+        "1a7f5efaad7f315c25a918f30cc8d7333fccab7ad7c90f14de81bcc528f9935d",
+    ),
+    (
+        "054aa86a73cb8a34525e5bbed6e43ba1198e860f5f3950268f71df4591bde402",
+        "209dcfbf2cfb57f9f6046f44d71ac6faf87254afc7407c04eb621a6287cac126",
+    ),
+    (
+        "1c44f2a5207c81c28a8321a5815ce8b1311024bbed131819bbdaf5a2ada84748",
+        "03aaee36e6422a1d0191632ac6599ae9eba5ac2c17a8c920aa3caf8b89c5f8a8",
+    ),
+    (
+        "26d8b1160c6821a30c65f6cb47124afe01c29f4338f44d4a12c9fccf22fb6fb2",
+        "05c70c3b9c0d25a4c100e3a27bf3cc375f8af8cdd9498ec4089a823d7464caff",
+    ),
+    (
+        "20ed9c6a1d27271c4498bfce0578d59db1adbeaa8734f7facc097b9b994fcf6e",
+        "29cd7d370938b358c62c4a00f73a0d10aba7e5aaa04704a0713f891ebeb92371",
+    ),
+    (
+        "0224a8abc6c8b8d50373d64cd2a1ab1567bf372b3b1f7b861d7f01257052d383",
+        "2358629b90eafb299d6650a311e79914b0215eb0a790810b26da5a826726d711",
+    ),
+    (
+        "0f106f6d46bc904a5290542490b2f238775ff3c445b2f8f704c466655f460a2a",
+        "29ab84d472f1d33f42fe09c47b8f7710f01920d6155250126731e486877bcf27",
+    ),
+];
+
+/// Affine `(x, y)` of the generator `H` that commits to the input length
+const LENGTH_GENERATOR: (&str, &str) = (
+    "2df8b940e5890e4e1377e05373fae69a1d754f6935e6a780b666947431f2cdcd",
+    "2ecd88d15967bc53b885912e0d16866154acb6aac2d3f85e27ca7eefb2c19083",
+);
+
+// ============================================================================
+// Field Arithmetic
+// ============================================================================
+
+/// `a + b + carry`, returning the low word and the carry
+const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + b as u128 + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+/// `a - b - borrow`, returning the low word and the borrow (0 or 1)
+const fn sbb(a: u64, b: u64, borrow: u64) -> (u64, u64) {
+    let diff = (a as u128).wrapping_sub(b as u128 + borrow as u128);
+    (diff as u64, ((diff >> 64) as u64) & 1)
+}
+
+/// `a + b * c + carry`, returning the low word and the carry
+const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let sum = a as u128 + (b as u128) * (c as u128) + carry as u128;
+    (sum as u64, (sum >> 64) as u64)
+}
+
+/// `a - MODULUS` if `a` is at least `MODULUS`
+fn reduce(a: [u64; 4]) -> [u64; 4] {
+    let mut out = [0u64; 4];
+    let mut borrow = 0;
+    for i in 0..4 {
+        (out[i], borrow) = sbb(a[i], MODULUS[i], borrow);
+    }
+    if borrow == 0 {
+        out
+    } else {
+        a
+    }
+}
+
+/// A field element in Montgomery form
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+struct Fe([u64; 4]);
+
+impl Fe {
+    const ZERO: Fe = Fe([0; 4]);
+
+    /// The integer `limbs`, which must be below `MODULUS`
+    fn from_limbs(limbs: [u64; 4]) -> Fe {
+        Fe(limbs).mul(&Fe(R2))
+    }
+
+    /// The canonical integer, leaving Montgomery form
+    fn to_limbs(self) -> [u64; 4] {
+        self.mul(&Fe([1, 0, 0, 0])).0
+    }
+
+    fn is_zero(&self) -> bool {
+        self.0 == [0; 4]
+    }
+
+    fn add(&self, rhs: &Fe) -> Fe {
+        let mut out = [0u64; 4];
+        let mut carry = 0;
+        for i in 0..4 {
+            (out[i], carry) = adc(self.0[i], rhs.0[i], carry);
+        }
+        // Both sides are below MODULUS < 2^254, so the sum cannot overflow
+        Fe(reduce(out))
+    }
+
+    fn sub(&self, rhs: &Fe) -> Fe {
+        let mut out = [0u64; 4];
+        let mut borrow = 0;
+        for i in 0..4 {
+            (out[i], borrow) = sbb(self.0[i], rhs.0[i], borrow);
+        }
+        if borrow != 0 {
+            let mut carry = 0;
+            for i in 0..4 {
+                (out[i], carry) = adc(out[i], MODULUS[i], carry);
+            }
+        }
+        Fe(out)
+    }
+
+    fn double(&self) -> Fe {
+        self.add(self)
+    }
+
+    /// Montgomery multiplication (CIOS)
+    fn mul(&self, rhs: &Fe) -> Fe {
+        let mut t = [0u64; 6];
+        for i in 0..4 {
+            let mut carry = 0;
+            for j in 0..4 {
+                (t[j], carry) = mac(t[j], self.0[j], rhs.0[i], carry);
+            }
+            (t[4], t[5]) = adc(t[4], carry, 0);
+
+            let m = t[0].wrapping_mul(INV);
+            let (_, mut carry) = mac(t[0], m, MODULUS[0], 0);
+            for j in 1..4 {
+                (t[j - 1], carry) = mac(t[j], m, MODULUS[j], carry);
+            }
+            let (low, high) = adc(t[4], carry, 0);
+            t[3] = low;
+            t[4] = t[5] + high;
+        }
+        Fe(reduce([t[0], t[1], t[2], t[3]]))
+    }
+
+    fn square(&self) -> Fe {
+        self.mul(self)
+    }
+
+    /// `self^(MODULUS - 2)`, the inverse of a non-zero element
+    fn invert(&self) -> Fe {
+        let mut exponent = MODULUS;
+        exponent[0] -= 2;
+        let mut out = Fe::from_limbs([1, 0, 0, 0]);
+        for bit in (0..256).rev() {
+            out = out.square();
+            if (exponent[bit / 64] >> (bit % 64)) & 1 == 1 {
+                out = out.mul(self);
+            }
+        }
+        out
+    }
+}
+
+/// Parse 32 big-endian bytes, refusing integers at or above `MODULUS`
+fn limbs_from_be_bytes(bytes: &[u8; 32]) -> Option<[u64; 4]> {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+        let start = 32 - 8 * (i + 1);
+        let mut word = [0u8; 8];
+        word.copy_from_slice(&bytes[start..start + 8]);
+        *limb = u64::from_be_bytes(word);
+    }
+    (reduce(limbs) == limbs).then_some(limbs)
+}
+
+fn limbs_to_be_bytes(limbs: [u64; 4]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, limb) in limbs.iter().enumerate() {
+        let start = 32 - 8 * (i + 1);
+        bytes[start..start + 8].copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+}
+
+/// Parse one of the hex constants above
+fn fe_from_hex(hex: &str) -> Fe {
+    let digits = hex.as_bytes();
+    let mut bytes = [0u8; 32];
+    for (i, byte) in bytes.iter_mut().enumerate() {
+        let nibble = |c: u8| match c {
+            b'0'..=b'9' => c - b'0',
+            _ => c - b'a' + 10,
+        };
+        *byte = (nibble(digits[2 * i]) << 4) | nibble(digits[2 * i + 1]);
+    }
+    Fe::from_limbs(limbs_from_be_bytes(&bytes).unwrap_or([0; 4]))
+}
+
+// ============================================================================
+// Curve Arithmetic
+// ============================================================================
+
+/// A Grumpkin point in Jacobian coordinates `(X / Z^2, Y / Z^3)`; `Z = 0` is
+/// the point at infinity
+#[derive(Clone, Copy, Debug)]
+struct Point {
+    x: Fe,
+    y: Fe,
+    z: Fe,
+}
+
+impl Point {
+    const INFINITY: Point = Point {
+        x: Fe::ZERO,
+        y: Fe::ZERO,
+        z: Fe::ZERO,
+    };
+
+    fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    /// Doubling for `a = 0` curves (dbl-2009-l)
+    fn double(&self) -> Point {
+        if self.is_infinity() || self.y.is_zero() {
+            return Point::INFINITY;
+        }
+        let a = self.x.square();
+        let b = self.y.square();
+        let c = b.square();
+        let d = self.x.add(&b).square().sub(&a).sub(&c).double();
+        let e = a.double().add(&a);
+        let f = e.square();
+        let x = f.sub(&d.double());
+        let c8 = c.double().double().double();
+        Point {
+            x,
+            y: e.mul(&d.sub(&x)).sub(&c8),
+            z: self.y.mul(&self.z).double(),
+        }
+    }
+
+    /// Add an affine point (madd-2007-bl)
+    fn add_affine(&self, (x2, y2): &(Fe, Fe)) -> Point {
+        let one = Fe::from_limbs([1, 0, 0, 0]);
+        if self.is_infinity() {
+            return Point {
+                x: *x2,
+                y: *y2,
+                z: one,
+            };
+        }
+        let z1z1 = self.z.square();
+        let u2 = x2.mul(&z1z1);
+        let s2 = y2.mul(&self.z).mul(&z1z1);
+        let h = u2.sub(&self.x);
+        let r = s2.sub(&self.y).double();
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double()
+            } else {
+                Point::INFINITY
+            };
+        }
+        let hh = h.square();
+        let i = hh.double().double();
+        let j = h.mul(&i);
+        let v = self.x.mul(&i);
+        let x = r.square().sub(&j).sub(&v.double());
+        Point {
+            x,
+            y: r.mul(&v.sub(&x)).sub(&self.y.mul(&j).double()),
+            z: self.z.add(&h).square().sub(&z1z1).sub(&hh),
+        }
+    }
+
+    /// The affine x coordinate, 0 for the point at infinity (as in Noir)
+    fn affine_x(&self) -> Fe {
+        if self.is_infinity() {
+            return Fe::ZERO;
+        }
+        self.x.mul(&self.z.square().invert())
+    }
+}
+
+// ============================================================================
+// Pedersen Hash
+// ============================================================================
+
+/// `pedersen_hash(inputs)` for field elements given as big-endian bytes, or
+/// `None` if one of them is not below the field modulus.
+///
+/// All products share one double-and-add pass over the scalar bits.
+pub fn pedersen_hash(inputs: &[[u8; 32]; PLOT_FIELDS]) -> Option<[u8; 32]> {
+    let mut scalars = [[0u64; 4]; PLOT_FIELDS + 1];
+    for (scalar, input) in scalars.iter_mut().zip(inputs) {
+        *scalar = limbs_from_be_bytes(input)?;
+    }
+    scalars[PLOT_FIELDS] = [PLOT_FIELDS as u64, 0, 0, 0];
+
+    let affine = |(x, y): (&str, &str)| (fe_from_hex(x), fe_from_hex(y));
+    let mut generators = [(Fe::ZERO, Fe::ZERO); PLOT_FIELDS + 1];
+    for (generator, hex) in generators.iter_mut().zip(GENERATORS) {
+        *generator = affine(hex);
+    }
+    generators[PLOT_FIELDS] = affine(LENGTH_GENERATOR);
+
+    let mut acc = Point::INFINITY;
+    for bit in (0..256).rev() {
+        acc = acc.double();
+        for (scalar, generator) in scalars.iter().zip(&generators) {
+            if (scalar[bit / 64] >> (bit % 64)) & 1 == 1 {
+                acc = acc.add_affine(generator);
+            }
+        }
+    }
+    Some(limbs_to_be_bytes(acc.affine_x().to_limbs()))
+}
+
+/// A small integer as a big-endian field element
+fn field_bytes(value: u32) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes[28..].copy_from_slice(&value.to_be_bytes());
+    bytes
+}
+
+/// The circuit's `action_commitment`: `pedersen_hash([target_id, secret_key,
+/// action_type, guard_id, spend, target_dynasty, betrayal])`, or `None` if
+/// `secret_key` is not below the field modulus
+pub fn plot_commitment(
+    target_id: u32,
+    secret_key: &[u8; 32],
+    action_type: u32,
+    guard_id: u32,
+    spend: u32,
+    target_dynasty: u32,
+    betrayal: bool,
+) -> Option<[u8; 32]> {
+    pedersen_hash(&[
+        field_bytes(target_id),
+        *secret_key,
+        field_bytes(action_type),
+        field_bytes(guard_id),
+        field_bytes(spend),
+        field_bytes(target_dynasty),
+        field_bytes(u32::from(betrayal)),
+    ])
+}
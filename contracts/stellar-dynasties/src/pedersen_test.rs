@@ -0,0 +1,125 @@
+#![cfg(test)]
+
+//! The on-chain plot commitment must agree with `tools/plot-commitment` and
+//! with the vectors `circuits/src/main.nr` pins. No `Env` is needed here.
+
+extern crate std;
+
+use ark_ff::{BigInteger, PrimeField};
+use plot_commitment::{Field, Plot};
+
+use crate::pedersen::{pedersen_hash, plot_commitment, PLOT_FIELDS};
+
+fn secret_bytes(secret: &Field) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(&secret.into_bigint().to_bytes_be());
+    bytes
+}
+
+fn native(plot: &Plot) -> [u8; 32] {
+    plot.commitment_bytes()
+}
+
+fn on_chain(plot: &Plot) -> [u8; 32] {
+    plot_commitment(
+        plot.target_id,
+        &secret_bytes(&plot.secret_key),
+        plot.action_type,
+        plot.guard_id,
+        plot.spend,
+        plot.target_dynasty,
+        plot.betrayal,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_circuit_vectors() {
+    let plot = Plot {
+        target_id: 1,
+        secret_key: Field::from(12345u64),
+        action_type: 0,
+        guard_id: 4,
+        spend: 3,
+        target_dynasty: 1,
+        betrayal: false,
+    };
+    let expected = [
+        0x12, 0x26, 0x6d, 0x1e, 0xba, 0x5f, 0x91, 0xaa, 0xf4, 0x80, 0xb9, 0xc8, 0xb4, 0x83, 0x1b,
+        0xa6, 0x7c, 0x40, 0x52, 0x5c, 0x0d, 0xca, 0x55, 0x6a, 0x88, 0x7f, 0xe7, 0x6a, 0x5c, 0xd1,
+        0xc2, 0x4e,
+    ];
+    assert_eq!(on_chain(&plot), expected);
+
+    let bribery = Plot {
+        target_id: 2,
+        secret_key: Field::from(99999u64),
+        action_type: 1,
+        guard_id: 2,
+        ..plot
+    };
+    assert_eq!(on_chain(&bribery), native(&bribery));
+}
+
+#[test]
+fn test_matches_native_commitment() {
+    // Full-width secrets exercise every bit of the scalar multiplication
+    let secrets = [
+        Field::from(1u64),
+        Field::from(u64::MAX),
+        -Field::from(1u64),
+        Field::from(0x1234_5678_9abc_def0u64) * Field::from(u64::MAX) * Field::from(977u64),
+    ];
+    for (i, secret_key) in secrets.into_iter().enumerate() {
+        let i = i as u32;
+        let plot = Plot {
+            target_id: 1 + i % 4,
+            secret_key,
+            action_type: i % 4,
+            guard_id: 4 - i % 4,
+            spend: u32::MAX - i,
+            target_dynasty: i % 6,
+            betrayal: i % 2 == 1,
+        };
+        assert_eq!(on_chain(&plot), native(&plot));
+    }
+}
+
+#[test]
+fn test_every_field_is_bound() {
+    let plot = Plot {
+        target_id: 3,
+        secret_key: Field::from(777u64),
+        action_type: 2,
+        guard_id: 1,
+        spend: 5,
+        target_dynasty: 2,
+        betrayal: false,
+    };
+    let hash = on_chain(&plot);
+    let changed = [
+        Plot { target_id: 4, ..plot },
+        Plot { secret_key: Field::from(778u64), ..plot },
+        Plot { action_type: 1, ..plot },
+        Plot { guard_id: 2, ..plot },
+        Plot { spend: 6, ..plot },
+        Plot { target_dynasty: 1, ..plot },
+        Plot { betrayal: true, ..plot },
+    ];
+    for other in changed {
+        assert_ne!(on_chain(&other), hash);
+    }
+}
+
+#[test]
+fn test_non_canonical_field_rejected() {
+    // The field modulus itself and anything above it are not field elements
+    let mut modulus = secret_bytes(&(-Field::from(1u64)));
+    modulus[31] += 1;
+    assert_eq!(plot_commitment(1, &modulus, 0, 1, 1, 1, false), None);
+    assert_eq!(plot_commitment(1, &[0xff; 32], 0, 1, 1, 1, false), None);
+
+    let mut inputs = [[0u8; 32]; PLOT_FIELDS];
+    inputs[6] = [0xff; 32];
+    assert_eq!(pedersen_hash(&inputs), None);
+}
@@ -0,0 +1,271 @@
+//! Round resolution rules for Stellar Dynasties.
+//!
+//! Everything here is pure: no storage, no auth and no `Env`, only the
+//! revealed plots and the session's `GameConfig`. The contract feeds the
+//! revealed plots in and applies the returned results, so balance changes can
+//! be unit tested on their own (see `rules_test.rs`) and analyzed off-chain
+//! with the very same code (see `tools/balance`).
+
+use crate::{GameConfig, PlotAction, Resources};
+
+/// One dynasty's revealed plot for a round
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Plot {
+    /// `PlotAction` as u32
+    pub action: u32,
+    /// Enemy holding plotted against, or own holding defended
+    pub target: u32,
+    /// Resources committed to the action (see `plot_resource`)
+    pub spend: u32,
+    /// Whether the opponent guarded the holding this plot is aimed at
+    pub target_guarded: bool,
+}
+
+/// Outcome of resolving one round
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct RoundResult {
+    /// Some(true) = player 1 won the round, Some(false) = player 2, None = nobody
+    pub player1_wins: Option<bool>,
+    pub player1_delta: i128,
+    pub player2_delta: i128,
+    /// Plot caught by the opponent's counter-intelligence
+    pub player1_foiled: bool,
+    pub player2_foiled: bool,
+}
+
+/// Largest number of dynasties in one session
+pub const MAX_DYNASTIES: usize = 6;
+
+/// A revealed plot together with the seat of the dynasty it is aimed at
+/// (the plotter's own seat for `Defend`)
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SeatPlot {
+    pub target_dynasty: u32,
+    pub plot: Plot,
+}
+
+/// What one dynasty gets out of a multi-way round
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct SeatResult {
+    /// Sum of prestige changes from every clash this dynasty took part in
+    pub delta: i128,
+    /// Its plot was caught by counter-intelligence
+    pub foiled: bool,
+    /// Its plot succeeded and takes the targeted holding
+    pub took: bool,
+}
+
+/// Which resource an action spends
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Resource {
+    Gold,
+    Troops,
+    Influence,
+}
+
+/// Bribery buys loyalty with gold, rebellion raises troops, assassination and
+/// counter-intelligence run on influence at court
+pub fn plot_resource(action: u32) -> Resource {
+    match action {
+        1 => Resource::Gold,
+        2 => Resource::Troops,
+        _ => Resource::Influence,
+    }
+}
+
+/// Deduct a plot's spend from a dynasty's resources.
+/// Returns `None` when the spend is below the config minimum or not affordable.
+pub fn pay_for_plot(
+    config: &GameConfig,
+    resources: &Resources,
+    action: u32,
+    spend: u32,
+) -> Option<Resources> {
+    if spend < config.min_plot_spend {
+        return None;
+    }
+    let mut left = resources.clone();
+    let pool = match plot_resource(action) {
+        Resource::Gold => &mut left.gold,
+        Resource::Troops => &mut left.troops,
+        Resource::Influence => &mut left.influence,
+    };
+    *pool = pool.checked_sub(spend)?;
+    Some(left)
+}
+
+/// Resources after collecting one round of income
+pub fn collect_income(config: &GameConfig, resources: &Resources) -> Resources {
+    Resources {
+        gold: resources.gold.saturating_add(config.income.gold),
+        troops: resources.troops.saturating_add(config.income.troops),
+        influence: resources.influence.saturating_add(config.income.influence),
+    }
+}
+
+/// Rock-Paper-Scissors matchups:
+///   - Assassination(0) beats Bribery(1)
+///   - Bribery(1) beats Rebellion(2)
+///   - Rebellion(2) beats Assassination(0)
+pub fn beats(action: u32, other: u32) -> bool {
+    (action == 0 && other == 1) || (action == 1 && other == 2) || (action == 2 && other == 0)
+}
+
+/// Prestige gained by a plot that succeeds
+pub fn plot_bonus(config: &GameConfig, action: u32) -> i128 {
+    match action {
+        0 => config.assassination_prestige,
+        1 => config.bribery_prestige,
+        2 => config.rebellion_prestige,
+        _ => 10,
+    }
+}
+
+/// Weight of a plot in a clash: its spend, multiplied when it counters the other
+fn strength(config: &GameConfig, plot: &Plot, other: &Plot) -> u64 {
+    let spend = u64::from(plot.spend);
+    if beats(plot.action, other.action) {
+        spend * u64::from(config.counter_multiplier)
+    } else {
+        spend
+    }
+}
+
+/// Resolve one round.
+///
+/// A plot aimed at a guarded holding is neutralized; one aimed at the holding
+/// the opponent defends is also foiled and penalized. If only one plot lands it
+/// succeeds and the other counts as failed. If both land, the stronger plot wins:
+/// strength is the resources spent, multiplied by `counter_multiplier` for the
+/// action that beats the other. Equal strength is a draw.
+pub fn resolve(config: &GameConfig, p1: &Plot, p2: &Plot) -> RoundResult {
+    // Defending players do not plot this round
+    let defend = PlotAction::Defend as u32;
+    let p1_plots = p1.action != defend;
+    let p2_plots = p2.action != defend;
+
+    let p1_lands = p1_plots && !p1.target_guarded;
+    let p2_lands = p2_plots && !p2.target_guarded;
+
+    let p1_foiled = p1_plots && p2.action == defend && p1.target == p2.target;
+    let p2_foiled = p2_plots && p1.action == defend && p2.target == p1.target;
+
+    let player1_wins = match (p1_lands, p2_lands) {
+        (true, true) => {
+            let (s1, s2) = (strength(config, p1, p2), strength(config, p2, p1));
+            if s1 == s2 {
+                None
+            } else {
+                Some(s1 > s2)
+            }
+        }
+        (true, false) => Some(true),
+        (false, true) => Some(false),
+        (false, false) => None,
+    };
+
+    // Only actual plots can fail; a defender that is outplotted just loses the holding
+    let p1_failed = if p1_plots { -config.failed_plot_penalty } else { 0 };
+    let p2_failed = if p2_plots { -config.failed_plot_penalty } else { 0 };
+    let (player1_delta, player2_delta) = match player1_wins {
+        Some(true) => (plot_bonus(config, p1.action), p2_failed),
+        Some(false) => (p1_failed, plot_bonus(config, p2.action)),
+        // Draw: both get small bonus
+        None if p1_lands => (config.draw_prestige, config.draw_prestige),
+        // Plots neutralized: only those foiled by counter-intelligence are punished
+        None => (
+            if p1_foiled { p1_failed } else { 0 },
+            if p2_foiled { p2_failed } else { 0 },
+        ),
+    };
+
+    RoundResult {
+        player1_wins,
+        player1_delta,
+        player2_delta,
+        player1_foiled: p1_foiled,
+        player2_foiled: p2_foiled,
+    }
+}
+
+/// Stand-in for a dynasty that neither plots against nor defends the holding
+/// an uncontested plot is aimed at
+fn bystander() -> Plot {
+    Plot {
+        action: PlotAction::Defend as u32,
+        target: 0,
+        spend: 0,
+        target_guarded: false,
+    }
+}
+
+/// Resolve a round between any number of dynasties (indexed by seat;
+/// `None` for eliminated seats).
+///
+/// Two dynasties plotting against each other clash exactly as in `resolve`.
+/// A plot whose target is busy elsewhere is uncontested: it lands unless the
+/// holding was guarded, and is foiled if the target defends that holding.
+pub fn resolve_all(
+    config: &GameConfig,
+    plots: &[Option<SeatPlot>],
+) -> [SeatResult; MAX_DYNASTIES] {
+    let mut results = [SeatResult::default(); MAX_DYNASTIES];
+    let defend = PlotAction::Defend as u32;
+    let seats = plots.len().min(MAX_DYNASTIES);
+
+    for i in 0..seats {
+        let Some(mine) = plots[i] else { continue };
+        if mine.plot.action == defend {
+            continue;
+        }
+        let j = mine.target_dynasty as usize;
+        let Some(theirs) = plots.get(j).copied().flatten() else {
+            continue;
+        };
+
+        let mutual = theirs.plot.action != defend && theirs.target_dynasty as usize == i;
+        if mutual && j < i {
+            // Already resolved from the other side
+            continue;
+        }
+        let response = if mutual || theirs.plot.action == defend {
+            theirs.plot
+        } else {
+            bystander()
+        };
+
+        let result = resolve(config, &mine.plot, &response);
+        results[i].delta += result.player1_delta;
+        results[j].delta += result.player2_delta;
+        results[i].foiled |= result.player1_foiled;
+        results[j].foiled |= result.player2_foiled;
+        match result.player1_wins {
+            Some(true) => results[i].took = true,
+            Some(false) => results[j].took = true,
+            None => {}
+        }
+    }
+    results
+}
+
+/// Share of the pot (in basis points) for each finishing place
+fn payout_shares(dynasties: u32) -> &'static [u32] {
+    match dynasties {
+        0..=2 => &[10_000],
+        3 => &[7_000, 3_000],
+        4 => &[6_000, 3_000, 1_000],
+        _ => &[5_000, 3_000, 2_000],
+    }
+}
+
+/// Points paid to the dynasty finishing at `rank` (0 = winner) out of `pot`.
+/// Rounding dust goes to the winner so the payouts always add up to the pot.
+pub fn payout(pot: i128, dynasties: u32, rank: u32) -> i128 {
+    let shares = payout_shares(dynasties);
+    let share = |place: usize| pot * i128::from(shares[place]) / 10_000;
+    match rank as usize {
+        0 => pot - (1..shares.len()).map(share).sum::<i128>(),
+        place if place < shares.len() => share(place),
+        _ => 0,
+    }
+}
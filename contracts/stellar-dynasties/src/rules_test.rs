@@ -0,0 +1,249 @@
+#![cfg(test)]
+
+//! Unit tests for the pure resolution rules. No `Env` is needed here.
+
+use crate::rules::{self, Plot, Resource, SeatPlot, SeatResult};
+use crate::{default_game_config, GameConfig, PlotAction, Resources};
+
+const ASSASSINATION: u32 = PlotAction::Assassination as u32;
+const BRIBERY: u32 = PlotAction::Bribery as u32;
+const REBELLION: u32 = PlotAction::Rebellion as u32;
+const DEFEND: u32 = PlotAction::Defend as u32;
+
+fn plot(action: u32, target: u32, spend: u32) -> Plot {
+    Plot {
+        action,
+        target,
+        spend,
+        target_guarded: false,
+    }
+}
+
+fn guarded(action: u32, target: u32, spend: u32) -> Plot {
+    Plot {
+        target_guarded: true,
+        ..plot(action, target, spend)
+    }
+}
+
+#[test]
+fn test_counter_wins_equal_spend() {
+    let config = default_game_config();
+    for (winner, loser) in [
+        (ASSASSINATION, BRIBERY),
+        (BRIBERY, REBELLION),
+        (REBELLION, ASSASSINATION),
+    ] {
+        let result = rules::resolve(&config, &plot(winner, 1, 3), &plot(loser, 1, 3));
+        assert_eq!(result.player1_wins, Some(true));
+        assert_eq!(result.player1_delta, rules::plot_bonus(&config, winner));
+        assert_eq!(result.player2_delta, -config.failed_plot_penalty);
+
+        let result = rules::resolve(&config, &plot(loser, 1, 3), &plot(winner, 1, 3));
+        assert_eq!(result.player1_wins, Some(false));
+    }
+}
+
+#[test]
+fn test_spend_overcomes_counter() {
+    let config = default_game_config();
+
+    // Bribery backed by 7 gold outweighs an assassination doubled from 3 to 6
+    let result = rules::resolve(&config, &plot(ASSASSINATION, 1, 3), &plot(BRIBERY, 2, 7));
+    assert_eq!(result.player1_wins, Some(false));
+    assert_eq!(result.player2_delta, config.bribery_prestige);
+
+    // 6 against 6 is a draw
+    let result = rules::resolve(&config, &plot(ASSASSINATION, 1, 3), &plot(BRIBERY, 2, 6));
+    assert_eq!(result.player1_wins, None);
+    assert_eq!(result.player1_delta, config.draw_prestige);
+    assert_eq!(result.player2_delta, config.draw_prestige);
+}
+
+#[test]
+fn test_same_action_decided_by_spend() {
+    let config = default_game_config();
+
+    let result = rules::resolve(&config, &plot(REBELLION, 1, 4), &plot(REBELLION, 1, 5));
+    assert_eq!(result.player1_wins, Some(false));
+
+    let result = rules::resolve(&config, &plot(REBELLION, 1, 5), &plot(REBELLION, 1, 5));
+    assert_eq!(result.player1_wins, None);
+}
+
+#[test]
+fn test_guarded_plot_loses_to_landing_plot() {
+    let config = default_game_config();
+
+    // However much is spent, a plot on a guarded holding does not land
+    let result = rules::resolve(&config, &guarded(ASSASSINATION, 1, 9), &plot(BRIBERY, 2, 1));
+    assert_eq!(result.player1_wins, Some(false));
+    assert_eq!(result.player1_delta, -config.failed_plot_penalty);
+
+    let result = rules::resolve(&config, &guarded(ASSASSINATION, 1, 9), &guarded(BRIBERY, 2, 1));
+    assert_eq!(result.player1_wins, None);
+    assert_eq!((result.player1_delta, result.player2_delta), (0, 0));
+}
+
+#[test]
+fn test_defend_foils_plot_on_watched_holding() {
+    let config = default_game_config();
+
+    // The defended holding is guarded, so the plot also counts as guarded
+    let result = rules::resolve(&config, &guarded(BRIBERY, 3, 5), &plot(DEFEND, 3, 1));
+    assert!(result.player1_foiled);
+    assert!(!result.player2_foiled);
+    assert_eq!(result.player1_wins, None);
+    assert_eq!(result.player1_delta, -config.failed_plot_penalty);
+    assert_eq!(result.player2_delta, 0);
+
+    // Defending elsewhere: the plot lands, and the defender is not penalized
+    let result = rules::resolve(&config, &plot(BRIBERY, 2, 5), &plot(DEFEND, 3, 1));
+    assert!(!result.player1_foiled);
+    assert_eq!(result.player1_wins, Some(true));
+    assert_eq!(result.player2_delta, 0);
+}
+
+#[test]
+fn test_counter_multiplier_from_config() {
+    let config = GameConfig {
+        counter_multiplier: 1,
+        ..default_game_config()
+    };
+
+    // Without a counter bonus the bigger spend wins regardless of matchup
+    let result = rules::resolve(&config, &plot(ASSASSINATION, 1, 3), &plot(BRIBERY, 2, 4));
+    assert_eq!(result.player1_wins, Some(false));
+}
+
+#[test]
+fn test_plot_resources() {
+    assert_eq!(rules::plot_resource(ASSASSINATION), Resource::Influence);
+    assert_eq!(rules::plot_resource(BRIBERY), Resource::Gold);
+    assert_eq!(rules::plot_resource(REBELLION), Resource::Troops);
+    assert_eq!(rules::plot_resource(DEFEND), Resource::Influence);
+}
+
+#[test]
+fn test_pay_for_plot() {
+    let config = GameConfig {
+        min_plot_spend: 2,
+        ..default_game_config()
+    };
+    let resources = Resources {
+        gold: 4,
+        troops: 1,
+        influence: 0,
+    };
+
+    let left = rules::pay_for_plot(&config, &resources, BRIBERY, 3).unwrap();
+    assert_eq!(
+        left,
+        Resources {
+            gold: 1,
+            ..resources.clone()
+        }
+    );
+    assert_eq!(rules::pay_for_plot(&config, &resources, BRIBERY, 5), None);
+    assert_eq!(rules::pay_for_plot(&config, &resources, BRIBERY, 1), None);
+    assert_eq!(rules::pay_for_plot(&config, &resources, REBELLION, 2), None);
+    assert_eq!(rules::pay_for_plot(&config, &resources, DEFEND, 2), None);
+}
+
+#[test]
+fn test_collect_income() {
+    let config = default_game_config();
+    let resources = Resources {
+        gold: 0,
+        troops: 1,
+        influence: u32::MAX,
+    };
+
+    let after = rules::collect_income(&config, &resources);
+    assert_eq!(after.gold, config.income.gold);
+    assert_eq!(after.troops, 1 + config.income.troops);
+    assert_eq!(after.influence, u32::MAX);
+}
+
+fn seat_plot(target_dynasty: u32, plot: Plot) -> Option<SeatPlot> {
+    Some(SeatPlot {
+        target_dynasty,
+        plot,
+    })
+}
+
+#[test]
+fn test_resolve_all_mutual_matches_resolve() {
+    let config = default_game_config();
+    let (p1, p2) = (plot(ASSASSINATION, 1, 3), plot(BRIBERY, 2, 3));
+
+    let pair = rules::resolve(&config, &p1, &p2);
+    let results = rules::resolve_all(&config, &[seat_plot(1, p1), seat_plot(0, p2)]);
+    assert_eq!(results[0].delta, pair.player1_delta);
+    assert_eq!(results[1].delta, pair.player2_delta);
+    assert!(results[0].took);
+    assert!(!results[1].took);
+}
+
+#[test]
+fn test_resolve_all_uncontested_plots() {
+    let config = default_game_config();
+
+    // 0 and 1 clash over each other; 2 plots against 0 unopposed, 3 against 2
+    // but 2 guarded that holding
+    let results = rules::resolve_all(
+        &config,
+        &[
+            seat_plot(1, plot(REBELLION, 1, 1)),
+            seat_plot(0, plot(ASSASSINATION, 1, 1)),
+            seat_plot(0, plot(BRIBERY, 2, 1)),
+            seat_plot(2, guarded(BRIBERY, 3, 1)),
+        ],
+    );
+    assert!(results[0].took);
+    assert_eq!(results[0].delta, config.rebellion_prestige);
+    assert_eq!(results[1].delta, -config.failed_plot_penalty);
+    assert!(results[2].took);
+    assert_eq!(results[2].delta, config.bribery_prestige);
+    assert!(!results[3].took);
+    assert_eq!(results[3].delta, 0);
+}
+
+#[test]
+fn test_resolve_all_defence_and_eliminated_seats() {
+    let config = default_game_config();
+
+    // Two plotters walk into the same counter-intelligence; seat 3 is out
+    let results = rules::resolve_all(
+        &config,
+        &[
+            seat_plot(2, guarded(ASSASSINATION, 4, 2)),
+            seat_plot(2, guarded(BRIBERY, 4, 2)),
+            seat_plot(2, plot(DEFEND, 4, 1)),
+            None,
+        ],
+    );
+    for result in &results[..2] {
+        assert!(result.foiled);
+        assert_eq!(result.delta, -config.failed_plot_penalty);
+    }
+    assert_eq!(results[2].delta, 0);
+    assert_eq!(results[3], SeatResult::default());
+}
+
+#[test]
+fn test_payouts_add_up_to_pot() {
+    for dynasties in 2..=6u32 {
+        for pot in [0i128, 1, 99, 600, 1_000_001] {
+            let total: i128 = (0..dynasties)
+                .map(|rank| rules::payout(pot, dynasties, rank))
+                .sum();
+            assert_eq!(total, pot);
+        }
+    }
+    assert_eq!(rules::payout(300, 3, 0), 210);
+    assert_eq!(rules::payout(300, 3, 1), 90);
+    assert_eq!(rules::payout(300, 3, 2), 0);
+    assert_eq!(rules::payout(1000, 6, 2), 200);
+    assert_eq!(rules::payout(1000, 6, 3), 0);
+}
@@ -1,20 +1,28 @@
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
-use soroban_sdk::{testutils::Address as _, Env};
+use game_kit::ttl::GAME_TTL_LEDGERS;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    xdr::ToXdr,
+    Env,
+};
 
 fn setup_env() -> (Env, Address, Address, Address, Address) {
     let env = Env::default();
     env.mock_all_auths();
 
     // Register mock game hub
-    let game_hub_id = env.register(crate::test_mock_hub::MockGameHub, ());
+    let game_hub_id = env.register(test_mock_hub::MockGameHub, ());
     // Register our contract with constructor args
     let contract_id = env.register(
         StellarDynasties,
         (
             &Address::generate(&env), // admin
             &game_hub_id,             // game_hub
+            None::<GameConfig>,       // default config
         ),
     );
 
@@ -29,13 +37,13 @@ fn test_start_session() {
     let (env, contract_id, _, player1, player2) = setup_env();
     let client = StellarDynastiesClient::new(&env, &contract_id);
 
-    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128);
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
 
     let game = client.get_game(&1u32);
-    assert_eq!(game.player1, player1);
-    assert_eq!(game.player2, player2);
-    assert_eq!(game.player1_prestige, 50);
-    assert_eq!(game.player2_prestige, 50);
+    assert_eq!(dynasty(&game, 0).player, player1);
+    assert_eq!(dynasty(&game, 1).player, player2);
+    assert_eq!(dynasty(&game, 0).prestige, 50);
+    assert_eq!(dynasty(&game, 1).prestige, 50);
     assert_eq!(game.round, 1);
     assert!(!game.ended);
 }
@@ -45,15 +53,25 @@ fn test_commit_plot() {
     let (env, contract_id, _, player1, player2) = setup_env();
     let client = StellarDynastiesClient::new(&env, &contract_id);
 
-    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128);
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
 
-    // Create a plot hash
-    let plot_hash = BytesN::from_array(&env, &[1u8; 32]);
+    // Commit to the circuit's assassination test plot, whose commitment
+    // `circuits/src/main.nr` pins
+    let (heir, treasury) = (Target::Heir as u32, Target::Treasury as u32);
+    let army = Target::Army as u32;
+    let plot_hash = commitment(&env, 0, 1, heir, army, 3, false);
     client.commit_plot(&1u32, &player1, &plot_hash);
+    let expected = [
+        0x12, 0x26, 0x6d, 0x1e, 0xba, 0x5f, 0x91, 0xaa, 0xf4, 0x80, 0xb9, 0xc8, 0xb4, 0x83, 0x1b,
+        0xa6, 0x7c, 0x40, 0x52, 0x5c, 0x0d, 0xca, 0x55, 0x6a, 0x88, 0x7f, 0xe7, 0x6a, 0x5c, 0xd1,
+        0xc2, 0x4e,
+    ];
+    assert_eq!(plot_hash, BytesN::from_array(&env, &expected));
+    assert_ne!(plot_hash, commitment(&env, 0, 1, treasury, army, 3, false));
 
     let game = client.get_game(&1u32);
-    assert!(game.player1_plot_hash.is_some());
-    assert!(game.player2_plot_hash.is_none());
+    assert_eq!(dynasty(&game, 0).plot_hash, Some(plot_hash));
+    assert!(dynasty(&game, 1).plot_hash.is_none());
 }
 
 #[test]
@@ -61,30 +79,1247 @@ fn test_full_round() {
     let (env, contract_id, _, player1, player2) = setup_env();
     let client = StellarDynastiesClient::new(&env, &contract_id);
 
-    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128);
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
 
-    // Both players commit plots
-    let hash1 = BytesN::from_array(&env, &[1u8; 32]);
-    let hash2 = BytesN::from_array(&env, &[2u8; 32]);
+    // Both players commit plots (action 0 = assassination, action 1 = bribery),
+    // each aimed at the opponent's heir while guarding their own army
+    let heir = Target::Heir as u32;
+    let army = Target::Army as u32;
+    let hash1 = commitment(&env, 0, 1, heir, army, 1, false);
+    let hash2 = commitment(&env, 1, 0, heir, army, 1, false);
     client.commit_plot(&1u32, &player1, &hash1);
     client.commit_plot(&1u32, &player2, &hash2);
 
-    // Both players verify plots (action 0 = assassination, action 1 = bribery)
-    let proof1 = Bytes::from_array(&env, &[10u8; 64]);
-    let proof2 = Bytes::from_array(&env, &[20u8; 64]);
-    client.verify_plot(&1u32, &player1, &0u32, &proof1, &hash1);
-    client.verify_plot(&1u32, &player2, &1u32, &proof2, &hash2);
+    // Both players verify their plots
+    let secret = secret_key(&env);
+    client.verify_plot(&1u32, &player1, &reveal(0, 1, heir, army, 1, false), &secret);
+    client.verify_plot(&1u32, &player2, &reveal(1, 0, heir, army, 1, false), &secret);
 
     // Resolve round: Assassination(0) beats Bribery(1) => player1 wins
-    let game = client.resolve_round(&1u32);
+    let game = client.resolve_round(&1u32, &player1);
     assert_eq!(game.round, 2); // Advanced to round 2
-    assert!(game.player1_prestige > 50); // Player1 gained prestige
-    assert!(game.player2_prestige < 50); // Player2 lost prestige
+    assert!(dynasty(&game, 0).prestige > 50); // Player1 gained prestige
+    assert!(dynasty(&game, 1).prestige < 50); // Player2 lost prestige
+    assert!(!dynasty(&game, 1).holdings.get(0).unwrap().intact); // Player2 lost the heir
+    assert!(dynasty(&game, 0).holdings.iter().all(|h| h.intact));
+}
+
+/// Advance the ledger sequence by `ledgers`
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_move_extends_session_ttl() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    // Move late in the original TTL window, then go past its end
+    advance_ledgers(&env, GAME_TTL_LEDGERS - 100);
+    let hash1 = commitment(&env, 0, 1, Target::Heir as u32, Target::Army as u32, SPEND, false);
+    client.commit_plot(&1u32, &player1, &hash1);
+    advance_ledgers(&env, 1_000);
+
+    let game = client.get_game(&1u32);
+    assert_eq!(dynasty(&game, 0).plot_hash, Some(hash1));
+}
+
+/// Plot submitted by one player for a round: (action, target, guard)
+type Plot = (u32, u32, u32);
+
+/// Dynasty seated at `seat`
+fn dynasty(game: &GameState, seat: u32) -> Dynasty {
+    game.players.get(seat).unwrap()
+}
+
+/// Resources both players commit to each plot in `play_plots`
+const SPEND: u32 = 1;
+
+/// Secret key every test dynasty commits with
+const SECRET_KEY: u64 = 12345;
+
+/// `SECRET_KEY` as the big-endian field element `verify_plot` takes
+fn secret_key(env: &Env) -> BytesN<32> {
+    let mut bytes = [0u8; 32];
+    bytes[24..].copy_from_slice(&SECRET_KEY.to_be_bytes());
+    BytesN::from_array(env, &bytes)
+}
+
+/// A plot as `verify_plot` takes it
+fn reveal(
+    action: u32,
+    target_dynasty: u32,
+    target: u32,
+    guard: u32,
+    spend: u32,
+    betrayal: bool,
+) -> RevealedPlot {
+    RevealedPlot {
+        action,
+        target_dynasty,
+        target,
+        guard,
+        spend,
+        betrayal,
+    }
+}
+
+/// The Pedersen commitment to a plot, computed natively exactly as the circuit
+/// computes its `action_commitment`
+fn commitment(
+    env: &Env,
+    action: u32,
+    target_dynasty: u32,
+    target: u32,
+    guard: u32,
+    spend: u32,
+    betrayal: bool,
+) -> BytesN<32> {
+    let plot = plot_commitment::Plot {
+        target_id: target,
+        secret_key: plot_commitment::Field::from(SECRET_KEY),
+        action_type: action,
+        guard_id: guard,
+        spend,
+        target_dynasty,
+        betrayal,
+    };
+    BytesN::from_array(env, &plot.commitment_bytes())
+}
+
+/// Commit and verify both plots of a round, leaving it ready to resolve
+fn submit_plots(
+    env: &Env,
+    client: &StellarDynastiesClient,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    plot1: Plot,
+    plot2: Plot,
+) {
+    let secret = secret_key(env);
+    // Plots go against the other seat, defences watch the own one
+    let rival = |seat: u32, (action, _, _): Plot| {
+        if action == PlotAction::Defend as u32 {
+            seat
+        } else {
+            1 - seat
+        }
+    };
+    let hash = |seat: u32, plot @ (action, target, guard): Plot| {
+        commitment(env, action, rival(seat, plot), target, guard, SPEND, false)
+    };
+    let (hash1, hash2) = (hash(0, plot1), hash(1, plot2));
+    client.commit_plot(&session_id, player1, &hash1);
+    client.commit_plot(&session_id, player2, &hash2);
+    for (seat, player, plot @ (action, target, guard)) in
+        [(0u32, player1, plot1), (1, player2, plot2)]
+    {
+        let rival = rival(seat, plot);
+        client.verify_plot(
+            &session_id,
+            player,
+            &reveal(action, rival, target, guard, SPEND, false),
+            &secret,
+        );
+    }
+}
+
+/// Commit, verify and resolve one round with explicit plots
+fn play_plots(
+    env: &Env,
+    client: &StellarDynastiesClient,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    plot1: Plot,
+    plot2: Plot,
+) -> GameState {
+    submit_plots(env, client, session_id, player1, player2, plot1, plot2);
+    client.resolve_round(&session_id, player1)
+}
+
+/// Play one round where neither plot is guarded against, so the outcome
+/// depends only on the actions: both aim at the holding numbered like the
+/// round and guard their army.
+fn play_round(
+    env: &Env,
+    client: &StellarDynastiesClient,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    action1: u32,
+    action2: u32,
+) -> GameState {
+    let target = client.get_game(&session_id).round;
+    let army = Target::Army as u32;
+    play_plots(
+        env,
+        client,
+        session_id,
+        player1,
+        player2,
+        (action1, target, army),
+        (action2, target, army),
+    )
+}
+
+#[test]
+fn test_match_archived_after_game_end() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &500i128, &None);
+    assert_eq!(client.try_get_match(&1u32), Err(Ok(Error::MatchNotFound)));
+
+    // Rebellion(2) beats Assassination(0) every round => player2 wins
+    let mut game = client.get_game(&1u32);
+    while !game.ended {
+        game = play_round(&env, &client, 1, &player1, &player2, 0, 2);
+    }
+
+    let result = client.get_match(&1u32);
+    assert_eq!(result.session_id, 1);
+    assert_eq!(result.players, vec![&env, player1.clone(), player2.clone()]);
+    assert_eq!(result.points, vec![&env, 1000i128, 500]);
+    assert_eq!(result.ranking, vec![&env, player2.clone(), player1.clone()]);
+    assert_eq!(result.payouts, vec![&env, 1000i128, 500]);
+    assert_eq!(result.winner, player2);
+    assert_eq!(result.end_ledger, env.ledger().sequence());
+    let expected_hash: BytesN<32> = env.crypto().keccak256(&game.to_xdr(&env)).into();
+    assert_eq!(result.state_hash, expected_hash);
+
+    // The archive outlives the temporary session entry
+    advance_ledgers(&env, GAME_TTL_LEDGERS + 1);
+    assert_eq!(client.try_get_game(&1u32), Err(Ok(Error::GameNotFound)));
+    assert_eq!(client.get_match(&1u32), result);
+}
+
+#[test]
+fn test_player_match_history_pagination() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let player3 = Address::generate(&env);
+
+    for (session_id, opponent) in [(1u32, &player2), (2, &player3), (3, &player2)] {
+        client.start_session(&session_id, &player1, opponent, &100i128, &100i128, &None);
+        let mut game = client.get_game(&session_id);
+        while !game.ended {
+            game = play_round(&env, &client, session_id, &player1, opponent, 0, 1);
+        }
+    }
+
+    assert_eq!(client.get_player_match_count(&player1), 3);
+    assert_eq!(client.get_player_match_count(&player2), 2);
+    assert_eq!(client.get_player_match_count(&player3), 1);
+
+    let page = client.get_player_matches(&player1, &0u32, &2u32);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().session_id, 1);
+    assert_eq!(page.get(1).unwrap().session_id, 2);
+
+    let page = client.get_player_matches(&player1, &2u32, &2u32);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().session_id, 3);
+
+    let page = client.get_player_matches(&player2, &5u32, &10u32);
+    assert_eq!(page.len(), 0);
+}
+
+fn custom_config() -> GameConfig {
+    GameConfig {
+        max_rounds: 1,
+        starting_prestige: 100,
+        assassination_prestige: 40,
+        bribery_prestige: 25,
+        rebellion_prestige: 35,
+        failed_plot_penalty: 20,
+        draw_prestige: 0,
+        reveal_foiled_plotter: false,
+        starting_resources: Resources {
+            gold: 5,
+            troops: 5,
+            influence: 5,
+        },
+        income: Resources {
+            gold: 1,
+            troops: 1,
+            influence: 1,
+        },
+        min_plot_spend: 1,
+        counter_multiplier: 3,
+        alliance_prestige: 0,
+    }
+}
+
+#[test]
+fn test_default_config() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    let config = client.get_config();
+    assert_eq!(config.max_rounds, 3);
+    assert_eq!(config.starting_prestige, 50);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    assert_eq!(client.get_game(&1u32).config, config);
+}
+
+#[test]
+fn test_constructor_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let game_hub_id = env.register(test_mock_hub::MockGameHub, ());
+    let contract_id = env.register(
+        StellarDynasties,
+        (&Address::generate(&env), &game_hub_id, Some(custom_config())),
+    );
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let player1 = Address::generate(&env);
+    let player2 = Address::generate(&env);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let game = client.get_game(&1u32);
+    assert_eq!(dynasty(&game, 0).prestige, 100);
+
+    // One-round game: Assassination(0) beats Bribery(1) and ends the match
+    let game = play_round(&env, &client, 1, &player1, &player2, 0, 1);
+    assert!(game.ended);
+    assert_eq!(dynasty(&game, 0).prestige, 140);
+    assert_eq!(dynasty(&game, 1).prestige, 80);
+}
+
+#[test]
+fn test_session_config_override() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    let config = Some(custom_config());
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &config);
+    client.start_session(&2u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    assert_eq!(client.get_game(&1u32).config, custom_config());
+    assert_eq!(dynasty(&client.get_game(&2u32), 0).prestige, 50);
+
+    // Rebellion(2) beats Assassination(0) with the overridden payoff
+    let game = play_round(&env, &client, 1, &player1, &player2, 0, 2);
+    assert!(game.ended);
+    assert_eq!(dynasty(&game, 0).prestige, 80);
+    assert_eq!(dynasty(&game, 1).prestige, 135);
+}
+
+#[test]
+fn test_set_config_does_not_affect_running_sessions() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    client.set_config(&custom_config());
+    assert_eq!(client.get_config(), custom_config());
+
+    // Running session still plays three rounds with the old payoffs
+    let game = play_round(&env, &client, 1, &player1, &player2, 0, 1);
+    assert!(!game.ended);
+    assert_eq!(dynasty(&game, 0).prestige, 80);
+    assert_eq!(dynasty(&game, 1).prestige, 40);
+
+    // New sessions pick up the new default
+    client.start_session(&2u32, &player1, &player2, &1000i128, &1000i128, &None);
+    assert_eq!(client.get_game(&2u32).config, custom_config());
+}
+
+#[test]
+fn test_invalid_config_rejected() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    let zero_rounds = GameConfig {
+        max_rounds: 0,
+        ..custom_config()
+    };
+    assert_eq!(client.try_set_config(&zero_rounds), Err(Ok(Error::InvalidConfig)));
+
+    let negative_bonus = GameConfig {
+        bribery_prestige: -5,
+        ..custom_config()
+    };
+    assert_eq!(
+        client.try_start_session(
+            &1u32,
+            &player1,
+            &player2,
+            &1000i128,
+            &1000i128,
+            &Some(negative_bonus)
+        ),
+        Err(Ok(Error::InvalidConfig))
+    );
+}
+
+#[test]
+fn test_guarded_target_neutralizes_plot() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let heir = Target::Heir as u32;
+    let treasury = Target::Treasury as u32;
+    let castle = Target::Castle as u32;
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    // Player 1's assassination hits the guarded heir; player 2's bribery lands
+    // on the unguarded treasury and wins even though Assassination beats Bribery
+    let (plot1, plot2) = ((0, heir, castle), (1, treasury, heir));
+    let game = play_plots(&env, &client, 1, &player1, &player2, plot1, plot2);
+    assert_eq!(dynasty(&game, 0).prestige, 40);
+    assert_eq!(dynasty(&game, 1).prestige, 65);
+    assert!(!dynasty(&game, 0).holdings.get(1).unwrap().intact);
+    assert!(dynasty(&game, 1).holdings.iter().all(|h| h.intact));
+}
+
+#[test]
+fn test_reveal_must_open_commitment() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, castle, army) = (Target::Heir as u32, Target::Castle as u32, Target::Army as u32);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let hash1 = commitment(&env, 0, 1, heir, army, SPEND, false);
+    let hash2 = commitment(&env, 1, 0, heir, army, SPEND, false);
+    client.commit_plot(&1u32, &player1, &hash1);
+    client.commit_plot(&1u32, &player2, &hash2);
+    let secret = secret_key(&env);
+
+    // Any field that differs from the committed plot is refused
+    let reveals = [
+        (0u32, heir, castle, SPEND, &secret),
+        (0, castle, army, SPEND, &secret),
+        (1, heir, army, SPEND, &secret),
+        (0, heir, army, SPEND + 1, &secret),
+        (0, heir, army, SPEND, &BytesN::from_array(&env, &[1u8; 32])),
+    ];
+    for (action, target, guard, spend, secret) in reveals {
+        let result = client.try_verify_plot(
+            &1u32,
+            &player1,
+            &reveal(action, 1, target, guard, spend, false),
+            secret,
+        );
+        assert_eq!(result, Err(Ok(Error::InvalidProof)));
+    }
+    // ...and so is a secret key that is not a field element
+    let result = client.try_verify_plot(
+        &1u32,
+        &player1,
+        &reveal(0, 1, heir, army, SPEND, false),
+        &BytesN::from_array(&env, &[0xff; 32]),
+    );
+    assert_eq!(result, Err(Ok(Error::InvalidProof)));
+
+    // The reveal stays out of the game state until the round resolves
+    client.verify_plot(&1u32, &player1, &reveal(0, 1, heir, army, SPEND, false), &secret);
+    let before = client.get_game(&1u32);
+    assert!(dynasty(&before, 0).plot_verified);
+    assert_eq!(dynasty(&before, 0).holdings, dynasty(&before, 1).holdings);
+    assert_eq!(dynasty(&before, 0).resources, client.get_config().starting_resources);
+    let result = client
+        .try_verify_plot(&1u32, &player1, &reveal(0, 1, heir, army, SPEND, false), &secret);
+    assert_eq!(result, Err(Ok(Error::AlreadyCommitted)));
+
+    // Having seen player 1 reveal, player 2 still cannot switch to a guard on the heir
+    let result = client
+        .try_verify_plot(&1u32, &player2, &reveal(1, 0, heir, heir, SPEND, false), &secret);
+    assert_eq!(result, Err(Ok(Error::InvalidProof)));
+    client.verify_plot(&1u32, &player2, &reveal(1, 0, heir, army, SPEND, false), &secret);
+    let game = client.resolve_round(&1u32, &player1);
+    let plays = client.get_round(&1u32, &1u32).plays;
+    assert_eq!(plays.get(0).unwrap().guard, army);
+    assert!(!dynasty(&game, 1).holdings.get(0).unwrap().intact);
+}
+
+#[test]
+fn test_both_plots_neutralized() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    let game = play_plots(&env, &client, 1, &player1, &player2, (0, army, heir), (2, heir, army));
+    assert_eq!(dynasty(&game, 0).prestige, 50);
+    assert_eq!(dynasty(&game, 1).prestige, 50);
+    assert_eq!(game.round, 2);
+    assert!(dynasty(&game, 0).holdings.iter().all(|h| h.intact));
+    assert!(dynasty(&game, 1).holdings.iter().all(|h| h.intact));
+}
+
+#[test]
+fn test_invalid_targets_rejected() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let hash = commitment(&env, 0, 1, 0, army, SPEND, false);
+    let secret = secret_key(&env);
+    client.commit_plot(&1u32, &player1, &hash);
+
+    for (target, guard) in [(0u32, army), (5, army), (heir, 0), (heir, 5)] {
+        assert_eq!(
+            client.try_verify_plot(
+                &1u32,
+                &player1,
+                &reveal(0, 1, target, guard, SPEND, false),
+                &secret,
+            ),
+            Err(Ok(Error::InvalidTarget))
+        );
+    }
+
+    // A holding that has been taken can no longer be plotted against
+    client.start_session(&2u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let game = play_plots(&env, &client, 2, &player1, &player2, (0, heir, army), (1, heir, army));
+    assert!(!dynasty(&game, 1).holdings.get(0).unwrap().intact);
+    let hash = commitment(&env, 0, 1, heir, army, 1, false);
+    client.commit_plot(&2u32, &player1, &hash);
+    assert_eq!(
+        client.try_verify_plot(
+            &2u32,
+            &player1,
+            &reveal(0, 1, heir, army, 1, false),
+            &secret,
+        ),
+        Err(Ok(Error::InvalidTarget))
+    );
+}
+
+#[test]
+fn test_losing_all_holdings_ends_game() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    // Enough rounds and prestige that only holdings can decide the game
+    let config = GameConfig {
+        max_rounds: 10,
+        starting_prestige: 1_000,
+        ..client.get_config()
+    };
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &Some(config));
+
+    // Player 1 takes one holding per round; player 2 always guards the wrong one
+    // and aims at the army player 1 guards
+    for target in 1..=4u32 {
+        let wrong_guard = if target == 1 { 4 } else { target - 1 };
+        let game = play_plots(
+            &env,
+            &client,
+            1,
+            &player1,
+            &player2,
+            (0, target, Target::Army as u32),
+            (1, Target::Army as u32, wrong_guard),
+        );
+        assert_eq!(game.ended, target == 4);
+    }
+
+    let game = client.get_game(&1u32);
+    assert_eq!(game.winner, Some(player1.clone()));
+    assert!(dynasty(&game, 1).holdings.iter().all(|h| !h.intact));
+}
+
+#[test]
+fn test_defend_foils_plot() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (castle, army) = (Target::Castle as u32, Target::Army as u32);
+    let defend = PlotAction::Defend as u32;
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    // Player 2 watches the castle player 1 plots against
+    let game = play_plots(
+        &env,
+        &client,
+        1,
+        &player1,
+        &player2,
+        (0, castle, army),
+        (defend, castle, castle),
+    );
+    assert_eq!(dynasty(&game, 0).prestige, 40);
+    assert_eq!(dynasty(&game, 1).prestige, 50);
+    assert_eq!(game.exposed_plotters, vec![&env, player1.clone()]);
+    assert!(dynasty(&game, 1).holdings.iter().all(|h| h.intact));
+
+    // The exposure only lasts for the round it happened in
+    let game = play_round(&env, &client, 1, &player1, &player2, 0, 0);
+    assert_eq!(game.exposed_plotters.len(), 0);
+}
+
+#[test]
+fn test_defend_can_hide_plotter() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (castle, army) = (Target::Castle as u32, Target::Army as u32);
+    let defend = PlotAction::Defend as u32;
+
+    let config = GameConfig {
+        reveal_foiled_plotter: false,
+        ..client.get_config()
+    };
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &Some(config));
+
+    let game = play_plots(
+        &env,
+        &client,
+        1,
+        &player1,
+        &player2,
+        (defend, castle, castle),
+        (1, castle, army),
+    );
+    assert_eq!(dynasty(&game, 0).prestige, 50);
+    assert_eq!(dynasty(&game, 1).prestige, 40);
+    assert_eq!(game.exposed_plotters.len(), 0);
+}
+
+#[test]
+fn test_defend_wrong_holding() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, castle, army) = (Target::Heir as u32, Target::Castle as u32, Target::Army as u32);
+    let defend = PlotAction::Defend as u32;
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    // Player 2 watches the castle but player 1 takes the heir; the defender
+    // loses the holding without a failed-plot penalty
+    let game = play_plots(
+        &env,
+        &client,
+        1,
+        &player1,
+        &player2,
+        (0, heir, army),
+        (defend, castle, castle),
+    );
+    assert_eq!(dynasty(&game, 0).prestige, 80);
+    assert_eq!(dynasty(&game, 1).prestige, 50);
+    assert_eq!(game.exposed_plotters.len(), 0);
+    assert!(!dynasty(&game, 1).holdings.get(0).unwrap().intact);
+
+    // Both defending: a quiet round
+    let game = play_plots(
+        &env,
+        &client,
+        1,
+        &player1,
+        &player2,
+        (defend, army, army),
+        (defend, army, army),
+    );
+    assert_eq!(dynasty(&game, 0).prestige, 80);
+    assert_eq!(dynasty(&game, 1).prestige, 50);
+    assert_eq!(game.round, 3);
+}
+
+#[test]
+fn test_defend_must_guard_own_target() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (castle, army) = (Target::Castle as u32, Target::Army as u32);
+    let defend = PlotAction::Defend as u32;
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let hash = commitment(&env, defend, 0, castle, army, 1, false);
+    let secret = secret_key(&env);
+    client.commit_plot(&1u32, &player1, &hash);
+
+    assert_eq!(
+        client.try_verify_plot(
+            &1u32,
+            &player1,
+            &reveal(defend, 0, castle, army, 1, false),
+            &secret,
+        ),
+        Err(Ok(Error::InvalidTarget))
+    );
+    assert_eq!(
+        client.try_verify_plot(
+            &1u32,
+            &player1,
+            &reveal(4, 1, castle, castle, 1, false),
+            &secret,
+        ),
+        Err(Ok(Error::InvalidAction))
+    );
+}
+
+#[test]
+fn test_plots_spend_resources_and_collect_income() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let game = client.get_game(&1u32);
+    assert_eq!(dynasty(&game, 0).resources, client.get_config().starting_resources);
+
+    // Player 1 bribes with 4 gold, player 2 rebels with 2 troops
+    let hash1 = commitment(&env, 1, 1, heir, army, 4, false);
+    let hash2 = commitment(&env, 2, 0, heir, army, 2, false);
+    let secret = secret_key(&env);
+    client.commit_plot(&1u32, &player1, &hash1);
+    client.commit_plot(&1u32, &player2, &hash2);
+    client.verify_plot(&1u32, &player1, &reveal(1, 1, heir, army, 4, false), &secret);
+    client.verify_plot(&1u32, &player2, &reveal(2, 0, heir, army, 2, false), &secret);
+
+    // Nothing is paid until the round resolves, so the spend stays private
+    let game = client.get_game(&1u32);
+    assert_eq!(dynasty(&game, 0).resources, client.get_config().starting_resources);
+    assert_eq!(dynasty(&game, 1).resources, client.get_config().starting_resources);
+
+    // After resolution both collect 3 of each resource
+    let game = client.resolve_round(&1u32, &player1);
+    assert_eq!(
+        dynasty(&game, 0).resources,
+        Resources {
+            gold: 9,
+            troops: 13,
+            influence: 13,
+        }
+    );
+    assert_eq!(dynasty(&game, 1).resources.troops, 11);
+}
+
+#[test]
+fn test_bigger_spend_beats_counter() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    // Assassination beats Bribery, but 2 influence (x2) loses to 5 gold
+    let hash1 = commitment(&env, 0, 1, heir, army, 2, false);
+    let hash2 = commitment(&env, 1, 0, heir, army, 5, false);
+    let secret = secret_key(&env);
+    client.commit_plot(&1u32, &player1, &hash1);
+    client.commit_plot(&1u32, &player2, &hash2);
+    client.verify_plot(&1u32, &player1, &reveal(0, 1, heir, army, 2, false), &secret);
+    client.verify_plot(&1u32, &player2, &reveal(1, 0, heir, army, 5, false), &secret);
+
+    let game = client.resolve_round(&1u32, &player1);
+    assert_eq!(dynasty(&game, 0).prestige, 40);
+    assert_eq!(dynasty(&game, 1).prestige, 65);
+    assert!(!dynasty(&game, 0).holdings.get(0).unwrap().intact);
+}
+
+#[test]
+fn test_insufficient_resources_rejected() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let hash = commitment(&env, 1, 1, heir, army, 11, false);
+    let secret = secret_key(&env);
+    client.commit_plot(&1u32, &player1, &hash);
+
+    // More gold than the treasury holds, and a spend below the minimum
+    for spend in [11u32, 0] {
+        assert_eq!(
+            client.try_verify_plot(
+                &1u32,
+                &player1,
+                &reveal(1, 1, heir, army, spend, false),
+                &secret,
+            ),
+            Err(Ok(Error::InsufficientResources))
+        );
+    }
+    assert!(!dynasty(&client.get_game(&1u32), 0).plot_verified);
+}
+
+/// Plot submitted by one dynasty in a multi-player round:
+/// (action, target dynasty, target holding, guard)
+type SeatPlot = (u32, u32, u32, u32);
+
+/// Start a session between `count` new players with 100 points each
+fn start_multi(
+    env: &Env,
+    client: &StellarDynastiesClient,
+    session_id: u32,
+    count: usize,
+    config: Option<GameConfig>,
+) -> std::vec::Vec<Address> {
+    let players: std::vec::Vec<Address> = (0..count).map(|_| Address::generate(env)).collect();
+    let points = Vec::from_array(env, [100i128; 6]).slice(0..count as u32);
+    let mut seats = Vec::new(env);
+    for player in &players {
+        seats.push_back(player.clone());
+    }
+    client.start_multi_session(&session_id, &seats, &points, &config);
+    players
+}
+
+/// Commit and verify one round of plots; `None` for eliminated seats.
+/// Plots against an ally are committed as betrayals.
+fn submit_multi(
+    env: &Env,
+    client: &StellarDynastiesClient,
+    session_id: u32,
+    players: &[Address],
+    plots: &[Option<SeatPlot>],
+) {
+    let secret = secret_key(env);
+    let alliances = client.get_game(&session_id).alliances;
+    let betrays = |seat: u32, (action, rival, _, _): SeatPlot| {
+        action != PlotAction::Defend as u32
+            && alliances.iter().any(|pact| {
+                (pact.proposer, pact.ally) == (seat, rival)
+                    || (pact.proposer, pact.ally) == (rival, seat)
+            })
+    };
+    // Every plot is committed before the first reveal closes commits
+    for (seat, (player, plot)) in players.iter().zip(plots).enumerate() {
+        if let Some(plot @ (action, rival, target, guard)) = *plot {
+            let betrayal = betrays(seat as u32, plot);
+            let hash = commitment(env, action, rival, target, guard, SPEND, betrayal);
+            client.commit_plot(&session_id, player, &hash);
+        }
+    }
+    for (seat, (player, plot)) in players.iter().zip(plots).enumerate() {
+        if let Some(plot @ (action, rival, target, guard)) = *plot {
+            let betrayal = betrays(seat as u32, plot);
+            client.verify_plot(
+                &session_id,
+                player,
+                &reveal(action, rival, target, guard, SPEND, betrayal),
+                &secret,
+            );
+        }
+    }
+}
+
+/// Commit, verify and resolve one round (see `submit_multi`)
+fn play_multi(
+    env: &Env,
+    client: &StellarDynastiesClient,
+    session_id: u32,
+    players: &[Address],
+    plots: &[Option<SeatPlot>],
+) -> GameState {
+    submit_multi(env, client, session_id, players, plots);
+    client.resolve_round(&session_id, &players[0])
+}
+
+#[test]
+fn test_start_multi_session() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    let players = start_multi(&env, &client, 1, 4, None);
+    let game = client.get_game(&1u32);
+    assert_eq!(game.players.len(), 4);
+    for (seat, player) in players.iter().enumerate() {
+        let dynasty = dynasty(&game, seat as u32);
+        assert_eq!(dynasty.player, *player);
+        assert_eq!(dynasty.points, 100);
+        assert_eq!(dynasty.prestige, 50);
+    }
+}
+
+#[test]
+fn test_multi_session_player_count() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    for count in [2u32, 7] {
+        let mut players = Vec::new(&env);
+        let mut points = Vec::new(&env);
+        for _ in 0..count {
+            players.push_back(Address::generate(&env));
+            points.push_back(100i128);
+        }
+        assert_eq!(
+            client.try_start_multi_session(&1u32, &players, &points, &None),
+            Err(Ok(Error::InvalidPlayerCount))
+        );
+    }
+
+    let players = vec![&env, player1.clone(), player2.clone(), Address::generate(&env)];
+    assert_eq!(
+        client.try_start_multi_session(&1u32, &players, &vec![&env, 100i128, 100], &None),
+        Err(Ok(Error::InvalidPlayerCount))
+    );
+    let players = vec![&env, player1.clone(), player2, player1];
+    assert_eq!(
+        client.try_start_multi_session(&1u32, &players, &vec![&env, 100i128, 100, 100], &None),
+        Err(Ok(Error::SamePlayer))
+    );
+}
+
+#[test]
+fn test_three_way_plots_all_land() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+
+    // Each dynasty plots against the next one around the table; nobody is
+    // plotted against by their own target, so every plot is uncontested
+    let players = start_multi(&env, &client, 1, 3, None);
+    let game = play_multi(
+        &env,
+        &client,
+        1,
+        &players,
+        &[Some((0, 1, heir, army)), Some((1, 2, heir, army)), Some((2, 0, heir, army))],
+    );
+
+    assert_eq!(dynasty(&game, 0).prestige, 80);
+    assert_eq!(dynasty(&game, 1).prestige, 65);
+    assert_eq!(dynasty(&game, 2).prestige, 70);
+    for seat in 0..3 {
+        assert!(!dynasty(&game, seat).holdings.get(0).unwrap().intact);
+    }
+    assert_eq!(game.round, 2);
+}
+
+#[test]
+fn test_eliminated_dynasty_drops_out() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, castle, army) = (Target::Heir as u32, Target::Castle as u32, Target::Army as u32);
+    let defend = PlotAction::Defend as u32;
+
+    let config = GameConfig {
+        max_rounds: 5,
+        starting_prestige: 10,
+        ..client.get_config()
+    };
+    let players = start_multi(&env, &client, 1, 3, Some(config));
+
+    // Seats 0 and 1 clash; seat 0's assassination beats seat 1's bribery and
+    // the failed plot wipes out seat 1's prestige
+    let game = play_multi(
+        &env,
+        &client,
+        1,
+        &players,
+        &[Some((0, 1, heir, army)), Some((1, 0, heir, army)), Some((defend, 2, castle, castle))],
+    );
+    assert_eq!(dynasty(&game, 1).eliminated_in, Some(1));
+    assert_eq!(client.get_round(&1u32, &1u32).eliminated, vec![&env, players[1].clone()]);
+    assert!(!game.ended);
+
+    // The eliminated dynasty can no longer plot or be plotted against
+    let hash = commitment(&env, 2, 2, castle, army, SPEND, false);
+    assert_eq!(
+        client.try_commit_plot(&1u32, &players[1], &hash),
+        Err(Ok(Error::PlayerEliminated))
+    );
+    let secret = secret_key(&env);
+    client.commit_plot(&1u32, &players[0], &hash);
+    let hash2 = commitment(&env, 0, 0, heir, army, SPEND, false);
+    client.commit_plot(&1u32, &players[2], &hash2);
+    assert_eq!(
+        client.try_verify_plot(
+            &1u32,
+            &players[0],
+            &reveal(0, 1, castle, army, SPEND, false),
+            &secret,
+        ),
+        Err(Ok(Error::InvalidTarget))
+    );
+
+    // Rebellion beats assassination: seat 2 falls as well and the game ends
+    client.verify_plot(
+        &1u32,
+        &players[0],
+        &reveal(2, 2, castle, army, SPEND, false),
+        &secret,
+    );
+    client.verify_plot(&1u32, &players[2], &reveal(0, 0, heir, army, SPEND, false), &secret);
+    let game = client.resolve_round(&1u32, &players[0]);
+    assert!(game.ended);
+    assert_eq!(client.get_round(&1u32, &2u32).plays.len(), 2);
+    assert_eq!(dynasty(&game, 0).prestige, 60);
+    assert_eq!(dynasty(&game, 2).eliminated_in, Some(2));
+    assert_eq!(game.winner, Some(players[0].clone()));
+    assert_eq!(
+        game.ranking,
+        vec![&env, players[0].clone(), players[2].clone(), players[1].clone()]
+    );
+
+    // The pot is split by finishing place
+    let result = client.get_match(&1u32);
+    assert_eq!(result.payouts, vec![&env, 210i128, 90, 0]);
+    for player in &players {
+        assert_eq!(client.get_player_match_count(player), 1);
+    }
+}
+
+#[test]
+fn test_reveal_deadline_forfeits_non_revealer() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+    let secret = secret_key(&env);
+
+    client.start_session(&1u32, &player1, &player2, &100i128, &100i128, &None);
+    client.commit_plot(&1u32, &player1, &commitment(&env, 0, 1, heir, army, SPEND, false));
+    client.commit_plot(&1u32, &player2, &commitment(&env, 1, 0, heir, army, SPEND, false));
+
+    // The first reveal starts the clock; until it runs out the round waits
+    client.verify_plot(&1u32, &player1, &reveal(0, 1, heir, army, SPEND, false), &secret);
+    let deadline = env.ledger().sequence() + REVEAL_TIMEOUT_LEDGERS;
+    assert_eq!(client.get_game(&1u32).reveal_deadline, Some(deadline));
+    advance_ledgers(&env, REVEAL_TIMEOUT_LEDGERS);
+    assert_eq!(client.try_resolve_round(&1u32, &player1), Err(Ok(Error::BothPlayersNotReady)));
+
+    // Past the deadline the other plot can no longer be revealed and forfeits
+    advance_ledgers(&env, 1);
+    let late = client.try_verify_plot(
+        &1u32,
+        &player2,
+        &reveal(1, 0, heir, army, SPEND, false),
+        &secret,
+    );
+    assert_eq!(late, Err(Ok(Error::RevealDeadlinePassed)));
+    let game = client.resolve_round(&1u32, &player1);
+    assert!(game.ended);
+    assert_eq!(game.winner, Some(player1.clone()));
+    assert_eq!(dynasty(&game, 1).prestige, 0);
+    assert_eq!(dynasty(&game, 1).eliminated_in, Some(1));
+
+    let round = client.get_round(&1u32, &1u32);
+    assert_eq!(round.forfeited, vec![&env, player2.clone()]);
+    assert_eq!(round.eliminated, vec![&env, player2]);
+    assert_eq!(round.plays.len(), 1);
+}
+
+#[test]
+fn test_commits_close_with_first_reveal() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+    let secret = secret_key(&env);
+    let players = start_multi(&env, &client, 1, 3, None);
+
+    // Seat 2 cannot wait for seat 0's plot and then pick an answer to it
+    client.commit_plot(&1u32, &players[0], &commitment(&env, 0, 1, heir, army, SPEND, false));
+    client.commit_plot(&1u32, &players[1], &commitment(&env, 1, 0, heir, army, SPEND, false));
+    client.verify_plot(&1u32, &players[0], &reveal(0, 1, heir, army, SPEND, false), &secret);
+    let hash = commitment(&env, 2, 0, heir, army, SPEND, false);
+    assert_eq!(client.try_commit_plot(&1u32, &players[2], &hash), Err(Ok(Error::RevealsStarted)));
+
+    // Once the deadline passes, the dynasty that never committed forfeits too
+    client.verify_plot(&1u32, &players[1], &reveal(1, 0, heir, army, SPEND, false), &secret);
+    advance_ledgers(&env, REVEAL_TIMEOUT_LEDGERS + 1);
+    let game = client.resolve_round(&1u32, &players[0]);
+    assert_eq!(client.get_round(&1u32, &1u32).forfeited, vec![&env, players[2].clone()]);
+    assert_eq!(dynasty(&game, 2).eliminated_in, Some(1));
+    assert_eq!(game.reveal_deadline, None);
+
+    // The next round starts with commits open again
+    client.commit_plot(&1u32, &players[0], &commitment(&env, 0, 1, heir, army, SPEND, false));
+}
+
+#[test]
+fn test_alliance_lifecycle() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    let players = start_multi(&env, &client, 1, 3, None);
+    let (p0, p1, p2) = (&players[0], &players[1], &players[2]);
+    client.propose_alliance(&1u32, p0, p1);
+    assert_eq!(client.get_game(&1u32).proposals.len(), 1);
+
+    // Only the invited dynasty can accept, and only an existing proposal
+    assert_eq!(
+        client.try_accept_alliance(&1u32, p2, p0),
+        Err(Ok(Error::ProposalNotFound))
+    );
+    assert_eq!(
+        client.try_accept_alliance(&1u32, p0, p1),
+        Err(Ok(Error::ProposalNotFound))
+    );
+    assert_eq!(
+        client.try_propose_alliance(&1u32, p0, p0),
+        Err(Ok(Error::SamePlayer))
+    );
+
+    client.accept_alliance(&1u32, p1, p0);
+    let game = client.get_game(&1u32);
+    assert_eq!(game.alliances, vec![&env, Alliance { proposer: 0, ally: 1 }]);
+    assert_eq!(game.proposals.len(), 0);
+    assert_eq!(
+        client.try_propose_alliance(&1u32, p1, p0),
+        Err(Ok(Error::AlreadyAllied))
+    );
+
+    // Either ally can break the alliance openly
+    client.break_alliance(&1u32, p1, p0);
+    assert_eq!(client.get_game(&1u32).alliances.len(), 0);
+    assert_eq!(
+        client.try_break_alliance(&1u32, p1, p0),
+        Err(Ok(Error::NotAllied))
+    );
+}
+
+#[test]
+fn test_alliance_pays_prestige() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (castle, army) = (Target::Castle as u32, Target::Army as u32);
+    let defend = PlotAction::Defend as u32;
+
+    let players = start_multi(&env, &client, 1, 3, None);
+    client.propose_alliance(&1u32, &players[0], &players[1]);
+    client.accept_alliance(&1u32, &players[1], &players[0]);
+
+    // A quiet round: everybody defends, only the allies gain prestige
+    let game = play_multi(
+        &env,
+        &client,
+        1,
+        &players,
+        &[
+            Some((defend, 0, army, army)),
+            Some((defend, 1, castle, castle)),
+            Some((defend, 2, army, army)),
+        ],
+    );
+    assert_eq!(dynasty(&game, 0).prestige, 55);
+    assert_eq!(dynasty(&game, 1).prestige, 55);
+    assert_eq!(dynasty(&game, 2).prestige, 50);
+}
+
+#[test]
+fn test_betrayal_must_be_committed() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+
+    let players = start_multi(&env, &client, 1, 3, None);
+    client.propose_alliance(&1u32, &players[0], &players[1]);
+    client.accept_alliance(&1u32, &players[1], &players[0]);
+
+    let hash = commitment(&env, 0, 1, heir, army, SPEND, true);
+    let secret = secret_key(&env);
+    client.commit_plot(&1u32, &players[0], &hash);
+
+    // Plotting against an ally needs the betrayal flag, and only an ally can be betrayed
+    for (rival, betrayal) in [(1u32, false), (2, true)] {
+        assert_eq!(
+            client.try_verify_plot(
+                &1u32,
+                &players[0],
+                &reveal(0, rival, heir, army, SPEND, betrayal),
+                &secret,
+            ),
+            Err(Ok(Error::InvalidBetrayal))
+        );
+    }
+}
+
+#[test]
+fn test_betrayal_ignores_guard_and_ends_alliance() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, castle, army) = (Target::Heir as u32, Target::Castle as u32, Target::Army as u32);
+    let defend = PlotAction::Defend as u32;
+
+    let players = start_multi(&env, &client, 1, 3, None);
+    client.propose_alliance(&1u32, &players[0], &players[1]);
+    client.accept_alliance(&1u32, &players[1], &players[0]);
+
+    // Seat 1 guards its heir, but not against its ally
+    let game = play_multi(
+        &env,
+        &client,
+        1,
+        &players,
+        &[
+            Some((0, 1, heir, army)),
+            Some((2, 2, army, heir)),
+            Some((defend, 2, castle, castle)),
+        ],
+    );
+    assert!(!dynasty(&game, 1).holdings.get(0).unwrap().intact);
+    assert_eq!(game.betrayers, vec![&env, players[0].clone()]);
+    assert_eq!(game.alliances.len(), 0);
+
+    // No alliance bonus in the round of the betrayal
+    assert_eq!(dynasty(&game, 0).prestige, 80);
+    assert_eq!(dynasty(&game, 1).prestige, 70);
+}
+
+#[test]
+fn test_round_history() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let (heir, castle, army) = (Target::Heir as u32, Target::Castle as u32, Target::Army as u32);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    assert_eq!(client.try_get_round(&1u32, &1u32), Err(Ok(Error::RoundNotFound)));
+
+    play_plots(&env, &client, 1, &player1, &player2, (0, heir, castle), (1, heir, army));
+    advance_ledgers(&env, 10);
+    let game = play_round(&env, &client, 1, &player1, &player2, 2, 2);
+    assert_eq!(game.rounds.len(), 2);
+
+    // Round 1: the assassination beat the bribery and took the heir
+    let round = client.get_round(&1u32, &1u32);
+    assert_eq!(round.round, 1);
+    assert_eq!(round.eliminated.len(), 0);
+    assert_eq!(
+        round.plays,
+        vec![
+            &env,
+            RoundPlay {
+                player: player1.clone(),
+                action: 0,
+                target_dynasty: 1,
+                target: heir,
+                guard: castle,
+                spend: SPEND,
+                betrayal: false,
+                prestige_delta: 30,
+                took: true,
+                foiled: false,
+            },
+            RoundPlay {
+                player: player2.clone(),
+                action: 1,
+                target_dynasty: 0,
+                target: heir,
+                guard: army,
+                spend: SPEND,
+                betrayal: false,
+                prestige_delta: -10,
+                took: false,
+                foiled: false,
+            },
+        ]
+    );
+
+    // Round 2 was a draw, resolved ten ledgers later
+    let round = client.get_round(&1u32, &2u32);
+    assert_eq!(round.ledger, client.get_round(&1u32, &1u32).ledger + 10);
+    assert!(round.plays.iter().all(|play| play.prestige_delta == 5 && !play.took));
+
+    for missing in [0u32, 3] {
+        assert_eq!(client.try_get_round(&1u32, &missing), Err(Ok(Error::RoundNotFound)));
+    }
 }
 
 // Minimal mock for testing
 mod test_mock_hub {
-    use soroban_sdk::{contract, contractimpl, Address, Env};
+    use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
 
     #[contract]
     pub struct MockGameHub;
@@ -103,5 +1338,443 @@ mod test_mock_hub {
         }
 
         pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {}
+
+        pub fn start_multi_game(
+            _env: Env,
+            _game_id: Address,
+            _session_id: u32,
+            _players: Vec<Address>,
+            _points: Vec<i128>,
+        ) {
+        }
+
+        pub fn end_multi_game(
+            _env: Env,
+            _session_id: u32,
+            _ranking: Vec<Address>,
+            _payouts: Vec<i128>,
+        ) {
+        }
     }
 }
+
+// ============================================================================
+// Settlement Tests
+// ============================================================================
+
+#[test]
+fn test_participants_only_resolution() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    assert_eq!(client.get_settlement_config().mode, SettlementMode::Participants);
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let army = Target::Army as u32;
+    submit_plots(&env, &client, 1, &player1, &player2, (0, 1, army), (1, 1, army));
+
+    // Outsiders cannot resolve, however long they wait
+    let stranger = Address::generate(&env);
+    advance_ledgers(&env, 10_000);
+    assert_eq!(client.try_resolve_round(&1u32, &stranger), Err(Ok(Error::NotPlayer)));
+
+    let game = client.resolve_round(&1u32, &player2);
+    assert_eq!(game.round, 2);
+    assert_eq!(game.keeper_rewards, 0);
+}
+
+#[test]
+fn test_keeper_resolution_paid_from_pot() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    client.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 300,
+        keeper_delay: 10,
+    });
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let army = Target::Army as u32;
+    let defend = (PlotAction::Defend as u32, army, army);
+    let keeper = Address::generate(&env);
+
+    // Nobody escrows a session started straight through the hub, so nobody
+    // could pay its keeper
+    client.start_session(&2u32, &player1, &player2, &150i128, &150i128, &None);
+    submit_plots(&env, &client, 2, &player1, &player2, defend, defend);
+    advance_ledgers(&env, 10);
+    assert_eq!(client.try_resolve_round(&2u32, &keeper), Err(Ok(Error::NotPlayer)));
+
+    client.start_matched_game(&lobby, &1u32, &player1, &player2, &150i128, &150i128);
+    submit_plots(&env, &client, 1, &player1, &player2, defend, defend);
+
+    // The dynasties get a head start after the last verification
+    advance_ledgers(&env, 9);
+    assert_eq!(client.try_resolve_round(&1u32, &keeper), Err(Ok(Error::SettlementTooEarly)));
+
+    // 3% of the 300 point pot over 3 rounds: 3 points per round
+    advance_ledgers(&env, 1);
+    let mut game = client.resolve_round(&1u32, &keeper);
+    assert_eq!(game.round, 2);
+    assert_eq!(game.keeper_rewards, 3);
+    assert_eq!(client.get_keeper_earnings(&keeper), 3);
+
+    // Dynasties resolving themselves pay nothing
+    while !game.ended {
+        game = play_round(&env, &client, 1, &player1, &player2, 0, 2);
+    }
+    assert_eq!(client.get_keeper_earnings(&keeper), 3);
+
+    // The keeper's cut comes out of the pot before it is split
+    let result = client.get_match(&1u32);
+    assert_eq!(result.keeper_rewards, 3);
+    assert_eq!(result.payouts.iter().sum::<i128>(), 297);
+}
+
+#[test]
+fn test_set_settlement_config_rejects_large_reward() {
+    let (env, contract_id, _, _, _) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    let result = client.try_set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 501,
+        keeper_delay: 0,
+    });
+    assert_eq!(result, Err(Ok(Error::InvalidSettlementConfig)));
+}
+
+// ============================================================================
+// Missing Instance Storage Tests
+// ============================================================================
+
+#[test]
+fn test_missing_admin_and_hub_are_typed_errors() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    // Simulate an instance that lost its configured addresses
+    env.as_contract(&contract_id, || {
+        env.storage().instance().remove(&DataKey::Admin);
+        env.storage().instance().remove(&DataKey::GameHubAddress);
+    });
+
+    assert_eq!(client.try_get_admin(), Err(Ok(Error::AdminNotSet)));
+    assert_eq!(client.try_set_admin(&player1), Err(Ok(Error::AdminNotSet)));
+    assert_eq!(client.try_set_hub(&player1), Err(Ok(Error::AdminNotSet)));
+    let config = client.get_config();
+    assert_eq!(client.try_set_config(&config), Err(Ok(Error::AdminNotSet)));
+    let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+    assert_eq!(client.try_upgrade(&new_wasm_hash), Err(Ok(Error::AdminNotSet)));
+    assert_eq!(client.try_get_hub(), Err(Ok(Error::GameHubNotSet)));
+    let result = client.try_start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    assert_eq!(result, Err(Ok(Error::GameHubNotSet)));
+}
+
+// ============================================================================
+// Matchmaking Tests
+// ============================================================================
+
+#[test]
+fn test_matched_session_plays_with_default_config() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let lobby = Address::generate(&env);
+
+    assert!(!client.is_matchmaker(&lobby));
+    client.set_matchmaker(&lobby, &true);
+    assert!(client.is_matchmaker(&lobby));
+
+    client.start_matched_game(&lobby, &1u32, &player1, &player2, &1000i128, &500i128);
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, lobby);
+
+    let mut game = client.get_game(&1u32);
+    assert_eq!(game.config, client.get_config());
+    assert_eq!(client.get_winner(&1u32), None);
+
+    // Rebellion(2) beats Assassination(0) every round => player2 wins
+    while !game.ended {
+        game = play_round(&env, &client, 1, &player1, &player2, 0, 2);
+    }
+    assert_eq!(client.get_winner(&1u32), Some(player2));
+}
+
+#[test]
+fn test_unregistered_matchmaker_rejected() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let lobby = Address::generate(&env);
+
+    let result = client.try_start_matched_game(&lobby, &1u32, &player1, &player2, &10, &10);
+    assert_eq!(result, Err(Ok(Error::NotMatchmaker)));
+
+    // Removing a matchmaker revokes it
+    client.set_matchmaker(&lobby, &true);
+    client.set_matchmaker(&lobby, &false);
+    let result = client.try_start_matched_game(&lobby, &1u32, &player1, &player2, &10, &10);
+    assert_eq!(result, Err(Ok(Error::NotMatchmaker)));
+
+    client.set_matchmaker(&lobby, &true);
+    let result = client.try_start_matched_game(&lobby, &1u32, &player1, &player1, &10, &10);
+    assert_eq!(result, Err(Ok(Error::SamePlayer)));
+}
+
+// ============================================================================
+// Session Info Tests
+// ============================================================================
+
+#[test]
+fn test_session_info_tracks_betting_phase() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    assert_eq!(client.try_get_session_info(&1u32), Err(Ok(Error::GameNotFound)));
+
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let info = client.get_session_info(&1u32);
+    assert_eq!(info.player1, player1);
+    assert_eq!(info.player2, player2);
+    assert!(info.awaiting_first_move);
+    assert_eq!(info.winner, None);
+
+    // The first committed plot closes the betting phase
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+    client.commit_plot(&1u32, &player2, &commitment(&env, 2, 0, heir, army, SPEND, false));
+    assert!(!client.get_session_info(&1u32).awaiting_first_move);
+
+    // Rebellion(2) beats Assassination(0) every round => player2 wins
+    client.start_session(&2u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let mut game = client.get_game(&2u32);
+    while !game.ended {
+        game = play_round(&env, &client, 2, &player1, &player2, 0, 2);
+    }
+    let info = client.get_session_info(&2u32);
+    assert!(!info.awaiting_first_move);
+    assert_eq!(info.winner, Some(player2));
+
+    // Multi-dynasty sessions have no two sides to back
+    start_multi(&env, &client, 3, 3, None);
+    assert_eq!(client.try_get_session_info(&3u32), Err(Ok(Error::InvalidPlayerCount)));
+}
+
+// ============================================================================
+// Session Key Tests
+// ============================================================================
+
+#[test]
+fn test_session_key_signs_plots() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    let key1 = Address::generate(&env);
+    let key2 = Address::generate(&env);
+    client.register_session_key(&1u32, &player1, &key1, &Vec::new(&env), &None);
+    client.register_session_key(&1u32, &player2, &key2, &Vec::new(&env), &None);
+    assert_eq!(
+        client.get_session_key(&1u32, &player1),
+        Some(SessionKey {
+            player: player1.clone(),
+            key: key1.clone(),
+            functions: Vec::new(&env),
+            expires_at: None,
+        })
+    );
+
+    // The keys commit and verify for their players, who settle the round
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+    submit_plots(&env, &client, 1, &key1, &key2, (0, heir, army), (1, heir, army));
+    let game = client.resolve_round(&1u32, &player1);
+    assert_eq!(game.round, 2);
+    assert!(dynasty(&game, 0).prestige > 50);
+    assert!(dynasty(&game, 1).prestige < 50);
+
+    client.commit_plot(&1u32, &key1, &commitment(&env, 0, 1, heir, army, SPEND, false));
+    assert_eq!(env.auths()[0].0, key1);
+    let hash = commitment(&env, 0, 1, heir, army, SPEND, false);
+    assert_eq!(client.try_commit_plot(&1u32, &player1, &hash), Err(Ok(Error::AlreadyCommitted)));
+}
+
+#[test]
+fn test_session_key_scope_expiry_and_revocation() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+
+    // A key scoped to commits cannot verify
+    let key = Address::generate(&env);
+    let scope = Vec::from_array(&env, [Symbol::new(&env, "commit_plot")]);
+    let expires_at = env.ledger().sequence() + 10;
+    client.register_session_key(&1u32, &player1, &key, &scope, &Some(expires_at));
+    let (heir, army) = (Target::Heir as u32, Target::Army as u32);
+    let hash = commitment(&env, 0, 1, heir, army, SPEND, false);
+    client.commit_plot(&1u32, &key, &hash);
+    let secret = secret_key(&env);
+    let (action, rival) = (0u32, 1u32);
+    let result = client
+        .try_verify_plot(&1u32, &key, &reveal(action, rival, heir, army, SPEND, false), &secret);
+    assert_eq!(result, Err(Ok(Error::SessionKeyNotAllowed)));
+
+    // Once the expiry ledger is reached the key is refused
+    client.start_session(&2u32, &player1, &player2, &1000i128, &1000i128, &None);
+    client.register_session_key(&2u32, &player1, &key, &scope, &Some(expires_at));
+    advance_ledgers(&env, 10);
+    let result = client.try_commit_plot(&2u32, &key, &hash);
+    assert_eq!(result, Err(Ok(Error::SessionKeyExpired)));
+
+    // A revoked key is no longer resolved to the player
+    client.revoke_session_key(&2u32, &player1);
+    assert_eq!(client.get_session_key(&2u32, &player1), None);
+    assert_eq!(client.try_commit_plot(&2u32, &key, &hash), Err(Ok(Error::NotPlayer)));
+    let result = client.try_revoke_session_key(&2u32, &player1);
+    assert_eq!(result, Err(Ok(Error::SessionKeyNotFound)));
+}
+
+#[test]
+fn test_invalid_session_keys_rejected() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    client.start_session(&1u32, &player1, &player2, &1000i128, &1000i128, &None);
+    let none = Vec::new(&env);
+    let key = Address::generate(&env);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_register_session_key(&1u32, &outsider, &key, &none, &None);
+    assert_eq!(result, Err(Ok(Error::NotPlayer)));
+    let result = client.try_register_session_key(&1u32, &player1, &player2, &none, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidSessionKey)));
+    let now = Some(env.ledger().sequence());
+    let result = client.try_register_session_key(&1u32, &player1, &key, &none, &now);
+    assert_eq!(result, Err(Ok(Error::InvalidSessionKey)));
+    let scope = Vec::from_array(&env, [Symbol::new(&env, "resolve_round")]);
+    let result = client.try_register_session_key(&1u32, &player1, &key, &scope, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidSessionKey)));
+
+    // A key signs for one player only
+    client.register_session_key(&1u32, &player1, &key, &none, &None);
+    let result = client.try_register_session_key(&1u32, &player2, &key, &none, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidSessionKey)));
+}
+
+// ============================================================================
+// Rematch Tests
+// ============================================================================
+
+#[test]
+fn test_rematch_series() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+    let config = custom_config();
+    client.start_session(&1u32, &player1, &player2, &1000i128, &500i128, &Some(config.clone()));
+    let result = client.try_propose_rematch(&1u32, &player1, &2u32, &false);
+    assert_eq!(result, Err(Ok(Error::GameNotEnded)));
+
+    // Rebellion(2) beats Assassination(0) every round => player2 wins
+    let mut game = client.get_game(&1u32);
+    while !game.ended {
+        game = play_round(&env, &client, 1, &player1, &player2, 0, 2);
+    }
+    client.propose_rematch(&1u32, &player1, &2u32, &true);
+    assert_eq!(client.try_accept_rematch(&1u32, &player1), Err(Ok(Error::RematchNotFound)));
+    assert_eq!(client.accept_rematch(&1u32, &player2), 2);
+    assert_eq!(client.get_rematch(&1u32), None);
+
+    // The rematch keeps the seats and config at doubled stakes
+    let rematch = client.get_game(&2u32);
+    assert_eq!(rematch.config, config);
+    assert_eq!((dynasty(&rematch, 0).player, dynasty(&rematch, 0).points), (player1.clone(), 2000));
+    assert_eq!((dynasty(&rematch, 1).player, dynasty(&rematch, 1).points), (player2.clone(), 1000));
+
+    // Assassination(0) beats Bribery(1) => player1 levels the series
+    game = rematch;
+    while !game.ended {
+        game = play_round(&env, &client, 2, &player1, &player2, 0, 1);
+    }
+    assert_eq!(
+        client.get_series(&1u32),
+        Some(Series {
+            player1: player1.clone(),
+            player2: player2.clone(),
+            sessions: vec![&env, 1, 2],
+            player1_wins: 1,
+            player2_wins: 1,
+        })
+    );
+    let result = client.try_propose_rematch(&1u32, &player2, &3u32, &false);
+    assert_eq!(result, Err(Ok(Error::RematchAlreadyPlayed)));
+
+    // Multi-dynasty sessions cannot be rematched
+    let players = start_multi(&env, &client, 4, 3, None);
+    let result = client.try_propose_rematch(&4u32, &players[0], &5u32, &false);
+    assert_eq!(result, Err(Ok(Error::InvalidPlayerCount)));
+}
+
+// ============================================================================
+// Stake Tests
+// ============================================================================
+
+#[test]
+fn test_invalid_stakes_rejected() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    let result = client.try_start_session(&1u32, &player1, &player2, &0i128, &1000i128, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidStake)));
+    let players = vec![&env, player1.clone(), player2.clone(), Address::generate(&env)];
+    let points = vec![&env, 100i128, -1, 100];
+    let result = client.try_start_multi_session(&1u32, &players, &points, &None);
+    assert_eq!(result, Err(Ok(Error::InvalidStake)));
+
+    let default_limits = StakeLimits { min_stake: 1, max_stake: i128::MAX / 8 };
+    assert_eq!(client.get_stake_limits(), default_limits);
+    client.set_stake_limits(&StakeLimits { min_stake: 100, max_stake: 1000 });
+    let result = client.try_start_session(&1u32, &player1, &player2, &99i128, &1000i128, &None);
+    assert_eq!(result, Err(Ok(Error::StakeOutOfRange)));
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let result = client.try_start_matched_game(&lobby, &1u32, &player1, &player2, &100, &1001);
+    assert_eq!(result, Err(Ok(Error::StakeOutOfRange)));
+    client.start_session(&1u32, &player1, &player2, &100i128, &1000i128, &None);
+
+    for (min_stake, max_stake) in [(0, 100), (101, 100), (1, i128::MAX / 8 + 1)] {
+        let result = client.try_set_stake_limits(&StakeLimits { min_stake, max_stake });
+        assert_eq!(result, Err(Ok(Error::InvalidStakeLimits)));
+    }
+}
+
+#[test]
+fn test_asymmetric_stakes_matched_payouts() {
+    let (env, contract_id, _, player1, player2) = setup_env();
+    let client = StellarDynastiesClient::new(&env, &contract_id);
+
+    // Only 500 of player 1's 1000 are matched: the loser keeps the excess
+    client.start_session(&1u32, &player1, &player2, &1000i128, &500i128, &None);
+    let mut game = client.get_game(&1u32);
+    while !game.ended {
+        game = play_round(&env, &client, 1, &player1, &player2, 0, 2);
+    }
+    let result = client.get_match(&1u32);
+    assert_eq!(result.ranking, vec![&env, player2.clone(), player1.clone()]);
+    assert_eq!(result.payouts, vec![&env, 1000i128, 500]);
+
+    // ...and the winner takes the matched 500 on top of their whole stake
+    client.start_session(&2u32, &player1, &player2, &1000i128, &500i128, &None);
+    game = client.get_game(&2u32);
+    while !game.ended {
+        game = play_round(&env, &client, 2, &player1, &player2, 2, 0);
+    }
+    assert_eq!(client.get_match(&2u32).payouts, vec![&env, 1500i128, 0]);
+
+    // With more dynasties only the matched pot is split by place
+    let players: std::vec::Vec<Address> = (0..3).map(|_| Address::generate(&env)).collect();
+    let seats = Vec::from_array(&env, [players[0].clone(), players[1].clone(), players[2].clone()]);
+    let points = vec![&env, 300i128, 100, 100];
+    client.start_multi_session(&3u32, &seats, &points, &Some(custom_config()));
+    let army = Target::Army as u32;
+    let defend = |seat: u32| Some((PlotAction::Defend as u32, seat, army, army));
+    play_multi(&env, &client, 3, &players, &[defend(0), defend(1), defend(2)]);
+    let result = client.get_match(&3u32);
+    let rank = result.ranking.first_index_of(&players[0]).unwrap();
+    assert_eq!(result.payouts.iter().sum::<i128>(), 500);
+    assert_eq!(result.payouts.get(rank).unwrap(), rules::payout(300, 3, rank) + 200);
+}
@@ -0,0 +1,73 @@
+//! Bracket and schedule arithmetic for tournaments.
+//!
+//! Everything here is pure: no storage, no auth and no `Env`, only entrant
+//! counts, slots and basis points. The contract keeps the players and the
+//! matches, so the seeding, the round-robin schedule and the prize split can be
+//! unit tested on their own (see `bracket_test.rs`).
+
+/// Most entrants a single-elimination tournament may register
+pub const MAX_ENTRANTS: u32 = 16;
+
+/// Most entrants a round-robin tournament may register (every pair plays, so
+/// the number of matches grows quadratically)
+pub const MAX_ROUND_ROBIN_ENTRANTS: u32 = 8;
+
+/// Basis points in a whole prize pool
+pub const TOTAL_BPS: u32 = 10_000;
+
+/// Number of slots in the first round of a single-elimination bracket: the
+/// smallest power of two that seats every entrant. Empty slots are byes.
+pub fn bracket_size(entrants: u32) -> u32 {
+    entrants.next_power_of_two().max(2)
+}
+
+/// Seed (0 = top seed) placed in a first-round bracket slot.
+///
+/// Slots `2k` and `2k + 1` meet in the first round. The order is the usual one
+/// where seeds in each pair add up to `size - 1`, so the top seeds meet as late
+/// as possible and any byes (seeds past the entrant count) go to the top seeds.
+pub fn slot_seed(size: u32, slot: u32) -> u32 {
+    if size <= 1 {
+        return 0;
+    }
+    let seed = slot_seed(size / 2, slot / 2);
+    if slot.is_multiple_of(2) {
+        seed
+    } else {
+        size - 1 - seed
+    }
+}
+
+/// Number of matches in a round robin, where every pair of entrants plays once
+pub fn round_robin_matches(entrants: u32) -> u32 {
+    entrants * entrants.saturating_sub(1) / 2
+}
+
+/// Seeds playing the `index`th round-robin match (pairs in lexicographic order)
+pub fn round_robin_pair(entrants: u32, index: u32) -> (u32, u32) {
+    let mut remaining = index;
+    let mut first = 0;
+    while first + 1 < entrants {
+        let opponents = entrants - first - 1;
+        if remaining < opponents {
+            return (first, first + 1 + remaining);
+        }
+        remaining -= opponents;
+        first += 1;
+    }
+    (first, first)
+}
+
+/// Whether a prize split can be used: every place is paid something, no more
+/// places than `MAX_ENTRANTS`, and the shares add up to the whole pool
+pub fn is_valid_payout(payout_bps: &[u32]) -> bool {
+    !payout_bps.is_empty()
+        && payout_bps.len() as u32 <= MAX_ENTRANTS
+        && payout_bps.iter().all(|bps| *bps > 0)
+        && payout_bps.iter().sum::<u32>() == TOTAL_BPS
+}
+
+/// Share of `pool` paid for `bps` basis points, rounded down
+pub fn prize(pool: i128, bps: u32) -> i128 {
+    pool * bps as i128 / TOTAL_BPS as i128
+}
@@ -0,0 +1,75 @@
+#![cfg(test)]
+
+//! Unit tests for the pure bracket arithmetic. No `Env` is needed here.
+
+use crate::bracket;
+
+#[test]
+fn test_bracket_size_fits_entrants() {
+    assert_eq!(bracket::bracket_size(2), 2);
+    assert_eq!(bracket::bracket_size(3), 4);
+    assert_eq!(bracket::bracket_size(5), 8);
+    assert_eq!(bracket::bracket_size(8), 8);
+    assert_eq!(bracket::bracket_size(16), 16);
+}
+
+#[test]
+fn test_slot_seeds_follow_standard_order() {
+    let order: [u32; 8] = core::array::from_fn(|slot| bracket::slot_seed(8, slot as u32));
+    // 1v8, 4v5, 2v7, 3v6
+    assert_eq!(order, [0, 7, 3, 4, 1, 6, 2, 5]);
+
+    let order: [u32; 4] = core::array::from_fn(|slot| bracket::slot_seed(4, slot as u32));
+    assert_eq!(order, [0, 3, 1, 2]);
+}
+
+#[test]
+fn test_byes_go_to_top_seeds() {
+    // With 5 entrants in an 8-slot bracket, seeds 5-7 are byes and each faces a
+    // different top seed, so no first-round pair is two byes
+    for pair in 0..4 {
+        let a = bracket::slot_seed(8, 2 * pair);
+        let b = bracket::slot_seed(8, 2 * pair + 1);
+        assert!(a < 5 || b < 5);
+        if a >= 5 || b >= 5 {
+            assert!(a.min(b) < 3);
+        }
+    }
+}
+
+#[test]
+fn test_round_robin_covers_every_pair_once() {
+    let entrants = 5;
+    let matches = bracket::round_robin_matches(entrants);
+    assert_eq!(matches, 10);
+
+    let mut seen = [[false; 5]; 5];
+    for index in 0..matches {
+        let (a, b) = bracket::round_robin_pair(entrants, index);
+        assert!(a < b && b < entrants);
+        assert!(!seen[a as usize][b as usize]);
+        seen[a as usize][b as usize] = true;
+    }
+    assert_eq!(bracket::round_robin_pair(entrants, 0), (0, 1));
+    assert_eq!(bracket::round_robin_pair(entrants, 9), (3, 4));
+}
+
+#[test]
+fn test_payout_validation() {
+    assert!(bracket::is_valid_payout(&[10_000]));
+    assert!(bracket::is_valid_payout(&[6_000, 3_000, 1_000]));
+    assert!(!bracket::is_valid_payout(&[]));
+    assert!(!bracket::is_valid_payout(&[6_000, 3_000]));
+    assert!(!bracket::is_valid_payout(&[10_000, 0]));
+    // Seventeen places are more than any tournament seats
+    let mut too_many = [588; 17];
+    too_many[0] = 592;
+    assert!(!bracket::is_valid_payout(&too_many));
+}
+
+#[test]
+fn test_prize_rounds_down() {
+    assert_eq!(bracket::prize(1_000, 6_000), 600);
+    assert_eq!(bracket::prize(1_001, 3_333), 333);
+    assert_eq!(bracket::prize(0, 10_000), 0);
+}
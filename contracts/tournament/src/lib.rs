@@ -0,0 +1,858 @@
+#![no_std]
+
+//! # Tournament
+//!
+//! Runs single-elimination and round-robin tournaments on any of the studio's
+//! games. Players register by paying the entry fee into the prize pool; when
+//! the organizer starts the tournament the entrants are seeded from a shuffle
+//! keyed by a secret the organizer committed to at creation, and every
+//! pairing is started as a session on the
+//! chosen game through its `start_matched_game` entry point (which calls
+//! `GameHub::start_game`).
+//!
+//! `advance` reads finished sessions through the game's `get_settlement`, pays
+//! any keepers that settled them out of the prize pool, moves winners on to the
+//! next round of a bracket, and once the last match is decided ranks the
+//! entrants and pays out what is left of the prize pool. A round that is still
+//! undecided `ROUND_DEADLINE_LEDGERS` after it started can be ended by anyone
+//! with `refund_stale`, which refunds the pool to every entrant. So can a
+//! tournament the organizer neither started nor cancelled within
+//! `REGISTRATION_DEADLINE_LEDGERS` of its creation, refunding every entry fee.
+//! Bracket seeding, the round-robin schedule and the prize split live in
+//! `bracket.rs`.
+//!
+//! **Game Integration:**
+//! The game's admin must register the tournament contract with
+//! `set_matchmaker(tournament, true)`. Entrants consent to play when they
+//! register, so they do not sign the individual session starts.
+
+use matchmaking::{MatchedGameClient, Settlement, escrow};
+use soroban_sdk::{
+    Address, Bytes, BytesN, Env, Vec, contract, contracterror, contractimpl, contracttype,
+    token
+};
+
+mod bracket;
+
+// ============================================================================
+// Errors
+// ============================================================================
+
+/// Errors returned by the contract.
+///
+/// Codes are stable: clients match on them, so existing variants are never
+/// renumbered or reused and new failures get the next free code.
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum Error {
+    /// No tournament exists with that id
+    TournamentNotFound = 1,
+    /// The entry fee is zero or negative
+    InvalidEntryFee = 2,
+    /// Fewer than two seats, or more than the format allows
+    InvalidEntrantLimit = 3,
+    /// The prize split is empty, has unpaid places or does not add up to 10,000 bps
+    InvalidPayout = 4,
+    /// Registration has closed (the tournament started, was cancelled or
+    /// passed its registration deadline)
+    RegistrationClosed = 5,
+    /// Every seat is taken
+    TournamentFull = 6,
+    /// The player already registered
+    AlreadyRegistered = 7,
+    /// Only the organizer may start or cancel the tournament
+    NotOrganizer = 8,
+    /// `start` was called with fewer than two entrants
+    NotEnoughEntrants = 9,
+    /// `advance` was called on a tournament that is not running, or
+    /// `refund_stale` on one that already ended
+    NotRunning = 10,
+    /// The entry fee token address is missing from instance storage
+    TokenNotSet = 11,
+    /// The admin address is missing from instance storage
+    AdminNotSet = 12,
+    /// A game's settlement has a negative amount or does not add up to both entry fees
+    InvalidSettlement = 13,
+    /// The seed passed to `start` does not match the organizer's commitment
+    InvalidSeed = 14,
+    /// The game refused every session id drawn for a pairing (taken, or the
+    /// tournament is not a registered matchmaker)
+    SessionUnavailable = 15,
+    /// `refund_stale` was called before the round's deadline passed
+    RoundDeadlineNotPassed = 16,
+    /// `refund_stale` was called once every pairing is decided; advance instead
+    RoundFinished = 17,
+    /// `refund_stale` was called during registration before its deadline passed
+    RegistrationDeadlineNotPassed = 18,
+}
+
+// ============================================================================
+// Data Types
+// ============================================================================
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Format {
+    /// Winners advance through a seeded bracket; one loss and you are out
+    SingleElimination = 0,
+    /// Every entrant plays every other entrant once; most wins takes first place
+    RoundRobin = 1,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TournamentStatus {
+    /// Players may register
+    Registration = 0,
+    /// Matches are being played
+    Running = 1,
+    /// Every match is decided and the prize pool was paid out
+    Finished = 2,
+    /// The organizer cancelled during registration and entry fees were refunded
+    Cancelled = 3,
+    /// The tournament missed its registration or a round deadline and the prize
+    /// pool was refunded to every entrant
+    Refunded = 4,
+}
+
+/// One pairing, played as a session on the tournament's game
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Pairing {
+    /// Bracket round (always 1 in a round robin)
+    pub round: u32,
+    /// Position in the round: bracket pair, or index in the round-robin schedule
+    pub slot: u32,
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    /// Set by `advance` once the game reports a winner
+    pub winner: Option<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tournament {
+    pub tournament_id: u32,
+    pub organizer: Address,
+    /// Game contract every pairing is played on
+    pub game: Address,
+    pub format: Format,
+    /// Tokens each entrant pays into the prize pool, also committed as game points
+    pub entry_fee: i128,
+    pub max_entrants: u32,
+    /// Last ledger for the organizer to start the tournament (set at creation)
+    pub registration_deadline: u32,
+    /// Share of the prize pool for each place in basis points, first place first
+    pub payout_bps: Vec<u32>,
+    /// Players in registration order
+    pub entrants: Vec<Address>,
+    /// Players in seeded order, top seed first (set by `start`)
+    pub seeds: Vec<Address>,
+    /// Keccak-256 of the organizer's secret seed, committed at creation
+    pub seed_commit: BytesN<32>,
+    /// The organizer's secret seed, revealed by `start`
+    pub seed: Option<BytesN<32>>,
+    /// Randomness the seeding was shuffled with (set by `start`)
+    pub seeding_hash: Option<BytesN<32>>,
+    /// Current bracket round
+    pub round: u32,
+    /// Last ledger for the current round's pairings to finish (set when it starts)
+    pub round_deadline: u32,
+    /// Players in the current round's bracket slots; `None` is a bye
+    pub lineup: Vec<Option<Address>>,
+    /// Every pairing so far, in the order they were started
+    pub pairings: Vec<Pairing>,
+    /// Tokens paid out of the prize pool to keepers that settled pairings
+    pub keeper_rewards: i128,
+    /// Final standings, best first (set when finished)
+    pub ranking: Vec<Address>,
+    /// Prize paid to each place in `ranking`
+    pub payouts: Vec<i128>,
+    pub status: TournamentStatus,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    TournamentCounter,
+    Tournament(u32),               // tournament_id -> Tournament
+}
+
+// ============================================================================
+// Storage TTL Management
+// ============================================================================
+// Tournaments hold entry fees, so they live in persistent storage and are kept
+// alive while they can still be played out
+
+/// TTL for tournaments (30 days in ledgers, ~5 seconds per ledger)
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+const TOURNAMENT_TTL_LEDGERS: u32 = 518_400;
+
+/// How long a round has to finish before the pool can be refunded (7 days in ledgers)
+pub const ROUND_DEADLINE_LEDGERS: u32 = 120_960;
+
+/// How long registration stays open before the entry fees can be refunded
+/// (7 days in ledgers)
+pub const REGISTRATION_DEADLINE_LEDGERS: u32 = 120_960;
+
+/// Session ids drawn for a pairing before giving up on the game
+pub const MAX_SESSION_ATTEMPTS: u32 = 8;
+
+/// Store a tournament and extend its TTL
+fn save_tournament(env: &Env, tournament: &Tournament) {
+    let key = DataKey::Tournament(tournament.tournament_id);
+    escrow::save(env, &key, tournament, TOURNAMENT_TTL_LEDGERS);
+}
+
+// ============================================================================
+// Helper Functions
+// ============================================================================
+
+fn load_tournament(env: &Env, tournament_id: u32) -> Result<Tournament, Error> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::Tournament(tournament_id))
+        .ok_or(Error::TournamentNotFound)
+}
+
+fn fee_token(env: &Env) -> Result<token::Client<'_>, Error> {
+    escrow::token(env).ok_or(Error::TokenNotSet)
+}
+
+/// Registration is open until the tournament starts, is cancelled or passes
+/// its registration deadline
+fn check_registration_open(env: &Env, tournament: &Tournament) -> Result<(), Error> {
+    if tournament.status != TournamentStatus::Registration
+        || env.ledger().sequence() > tournament.registration_deadline
+    {
+        return Err(Error::RegistrationClosed);
+    }
+    Ok(())
+}
+
+/// Pay every entrant's entry fee back
+fn refund_entry_fees(env: &Env, tournament: &Tournament) -> Result<(), Error> {
+    let token = fee_token(env)?;
+    for entrant in tournament.entrants.iter() {
+        token.transfer(&env.current_contract_address(), &entrant, &tournament.entry_fee);
+    }
+    Ok(())
+}
+
+fn check_organizer(tournament: &Tournament, organizer: &Address) -> Result<(), Error> {
+    organizer.require_auth();
+    if tournament.organizer != *organizer {
+        return Err(Error::NotOrganizer);
+    }
+    Ok(())
+}
+
+/// Shuffle the entrants into seeded order (Fisher-Yates).
+///
+/// The randomness is a hash of the tournament id, the organizer's revealed
+/// seed and the entrants. Entrants register without knowing the seed and the
+/// organizer committed to it before anyone registered, so neither side can
+/// grind the order; it is still identical between simulation and submission,
+/// and anyone can recompute it from the stored `seed` and `seeding_hash`.
+fn seed_entrants(env: &Env, tournament: &mut Tournament, seed: BytesN<32>) {
+    let mut seed_bytes = Bytes::new(env);
+    seed_bytes.append(&Bytes::from_array(env, &tournament.tournament_id.to_be_bytes()));
+    seed_bytes.append(&Bytes::from(seed.clone()));
+    for entrant in tournament.entrants.iter() {
+        seed_bytes.append(&entrant.to_string().to_bytes());
+    }
+    let seeding_hash: BytesN<32> = env.crypto().keccak256(&seed_bytes).into();
+
+    let mut seeds = tournament.entrants.clone();
+    for i in (1..seeds.len()).rev() {
+        let mut draw_bytes = Bytes::from(seeding_hash.clone());
+        draw_bytes.append(&Bytes::from_array(env, &i.to_be_bytes()));
+        let draw: BytesN<32> = env.crypto().keccak256(&draw_bytes).into();
+        let draw = draw.to_array();
+        let j = u32::from_be_bytes([draw[0], draw[1], draw[2], draw[3]]) % (i + 1);
+
+        let at_i = seeds.get_unchecked(i);
+        seeds.set(i, seeds.get_unchecked(j));
+        seeds.set(j, at_i);
+    }
+
+    tournament.seeds = seeds;
+    tournament.seed = Some(seed);
+    tournament.seeding_hash = Some(seeding_hash);
+}
+
+/// Session id drawn for the pairing at `index` on its `attempt`th try
+pub(crate) fn session_candidate(
+    env: &Env,
+    seeding_hash: &BytesN<32>,
+    index: u32,
+    attempt: u32,
+) -> u32 {
+    let mut bytes = Bytes::from(seeding_hash.clone());
+    bytes.append(&Bytes::from_array(env, &index.to_be_bytes()));
+    bytes.append(&Bytes::from_array(env, &attempt.to_be_bytes()));
+    let hash: BytesN<32> = env.crypto().keccak256(&bytes).into();
+    let hash = hash.to_array();
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]])
+}
+
+/// Record a pairing and start its session on the game.
+///
+/// Session ids are drawn from the seeding hash, so nobody knows them before
+/// `start` reveals the seed. Once it is revealed anyone can work out a later
+/// round's ids and occupy them on the game first, so an id the game refuses
+/// is skipped for the next draw, up to `MAX_SESSION_ATTEMPTS` times.
+fn start_pairing(
+    env: &Env,
+    tournament: &mut Tournament,
+    slot: u32,
+    player1: Address,
+    player2: Address,
+) -> Result<(), Error> {
+    let seeding_hash = tournament.seeding_hash.clone().ok_or(Error::NotRunning)?;
+    let index = tournament.pairings.len();
+    let game = MatchedGameClient::new(env, &tournament.game);
+
+    for attempt in 0..MAX_SESSION_ATTEMPTS {
+        let session_id = session_candidate(env, &seeding_hash, index, attempt);
+        let started = game.try_start_matched_game(
+            &env.current_contract_address(),
+            &session_id,
+            &player1,
+            &player2,
+            &tournament.entry_fee,
+            &tournament.entry_fee,
+        );
+        if matches!(started, Ok(Ok(()))) {
+            tournament.pairings.push_back(Pairing {
+                round: tournament.round,
+                slot,
+                session_id,
+                player1,
+                player2,
+                winner: None,
+            });
+            return Ok(());
+        }
+    }
+    Err(Error::SessionUnavailable)
+}
+
+/// Start the pairings of the current bracket round. Byes play nothing.
+fn open_round(env: &Env, tournament: &mut Tournament) -> Result<(), Error> {
+    for slot in 0..tournament.lineup.len() / 2 {
+        if let (Some(player1), Some(player2)) = (
+            tournament.lineup.get_unchecked(2 * slot),
+            tournament.lineup.get_unchecked(2 * slot + 1),
+        ) {
+            start_pairing(env, tournament, slot, player1, player2)?;
+        }
+    }
+    Ok(())
+}
+
+/// Players advancing from the current bracket round, in slot order
+fn next_lineup(env: &Env, tournament: &Tournament) -> Vec<Option<Address>> {
+    let mut lineup = Vec::new(env);
+    for slot in 0..tournament.lineup.len() / 2 {
+        let advancing = match (
+            tournament.lineup.get_unchecked(2 * slot),
+            tournament.lineup.get_unchecked(2 * slot + 1),
+        ) {
+            (Some(_), Some(_)) => tournament
+                .pairings
+                .iter()
+                .find(|pairing| pairing.round == tournament.round && pairing.slot == slot)
+                .and_then(|pairing| pairing.winner),
+            (Some(player), None) | (None, Some(player)) => Some(player),
+            (None, None) => None,
+        };
+        lineup.push_back(advancing);
+    }
+    lineup
+}
+
+/// Round in which a player lost a bracket pairing (0 if they never lost)
+fn round_lost(tournament: &Tournament, player: &Address) -> u32 {
+    tournament
+        .pairings
+        .iter()
+        .find(|pairing| {
+            (pairing.player1 == *player || pairing.player2 == *player)
+                && pairing.winner.as_ref().is_some_and(|winner| winner != player)
+        })
+        .map(|pairing| pairing.round)
+        .unwrap_or(0)
+}
+
+/// Final standings, best first. Bracket entrants rank by how far they got,
+/// round-robin entrants by wins; ties go to the better seed.
+fn rank_entrants(env: &Env, tournament: &Tournament) -> Vec<Address> {
+    let mut ranking = Vec::new(env);
+    match tournament.format {
+        Format::SingleElimination => {
+            // Everyone but the champion lost exactly once; the champion counts
+            // as going one round past the final
+            let champion_round = tournament.round + 1;
+            for round in (1..=champion_round).rev() {
+                for player in tournament.seeds.iter() {
+                    let reached = match round_lost(tournament, &player) {
+                        0 => champion_round,
+                        lost => lost,
+                    };
+                    if reached == round {
+                        ranking.push_back(player);
+                    }
+                }
+            }
+        }
+        Format::RoundRobin => {
+            for wins in (0..tournament.seeds.len()).rev() {
+                for player in tournament.seeds.iter() {
+                    let won = tournament
+                        .pairings
+                        .iter()
+                        .filter(|pairing| pairing.winner.as_ref() == Some(&player))
+                        .count() as u32;
+                    if won == wins {
+                        ranking.push_back(player);
+                    }
+                }
+            }
+        }
+    }
+    ranking
+}
+
+/// Pay the keepers that settled a pairing's session out of the prize pool.
+/// The game's settlement must split exactly the two entry fees it was played for.
+fn pay_keepers(
+    env: &Env,
+    tournament: &mut Tournament,
+    settlement: &Settlement,
+) -> Result<(), Error> {
+    if settlement.total() != Some(tournament.entry_fee * 2) {
+        return Err(Error::InvalidSettlement);
+    }
+    let token = fee_token(env)?;
+    for reward in settlement.keeper_rewards.iter() {
+        if reward.amount > 0 {
+            token.transfer(&env.current_contract_address(), &reward.keeper, &reward.amount);
+            tournament.keeper_rewards += reward.amount;
+        }
+    }
+    Ok(())
+}
+
+/// Collect the winners of finished sessions, paying the keepers that settled
+/// them. Returns how many pairings are still undecided.
+fn collect_results(env: &Env, tournament: &mut Tournament) -> Result<u32, Error> {
+    let game = MatchedGameClient::new(env, &tournament.game);
+    let mut undecided = 0;
+    for index in 0..tournament.pairings.len() {
+        let mut pairing = tournament.pairings.get_unchecked(index);
+        if pairing.winner.is_some() {
+            continue;
+        }
+        match game.get_settlement(&pairing.session_id) {
+            Some(settlement) => {
+                pay_keepers(env, tournament, &settlement)?;
+                pairing.winner = Some(settlement.winner);
+                tournament.pairings.set(index, pairing);
+            }
+            None => undecided += 1,
+        }
+    }
+    Ok(undecided)
+}
+
+/// Prize pool left after keeper rewards
+fn prize_pool(tournament: &Tournament) -> i128 {
+    tournament.entry_fee * tournament.entrants.len() as i128 - tournament.keeper_rewards
+}
+
+/// Rank the entrants and pay out what keepers left of the prize pool.
+/// Rounding dust goes to first place.
+fn finish(env: &Env, tournament: &mut Tournament) -> Result<(), Error> {
+    let ranking = rank_entrants(env, tournament);
+    let pool = prize_pool(tournament);
+
+    let mut payouts = Vec::new(env);
+    let mut paid = 0;
+    for place in 0..ranking.len() {
+        let prize = match tournament.payout_bps.get(place) {
+            Some(bps) => bracket::prize(pool, bps),
+            None => 0,
+        };
+        payouts.push_back(prize);
+        paid += prize;
+    }
+    payouts.set(0, payouts.get_unchecked(0) + pool - paid);
+
+    let token = fee_token(env)?;
+    for (player, prize) in ranking.iter().zip(payouts.iter()) {
+        if prize > 0 {
+            token.transfer(&env.current_contract_address(), &player, &prize);
+        }
+    }
+
+    tournament.ranking = ranking;
+    tournament.payouts = payouts;
+    tournament.status = TournamentStatus::Finished;
+    Ok(())
+}
+
+// ============================================================================
+// Contract Definition
+// ============================================================================
+
+#[contract]
+pub struct TournamentContract;
+
+#[contractimpl]
+impl TournamentContract {
+    /// Initialize the contract with an admin and the entry fee token
+    ///
+    /// # Arguments
+    /// * `admin` - Admin address (can upgrade contract)
+    /// * `token` - Token contract entry fees and prizes are paid in
+    pub fn __constructor(env: Env, admin: Address, token: Address) {
+        escrow::initialize(&env, &admin, &token);
+    }
+
+    /// Create a tournament and open registration. The organizer has
+    /// `REGISTRATION_DEADLINE_LEDGERS` to start it; after that anyone can
+    /// refund the entry fees with `refund_stale`.
+    ///
+    /// # Arguments
+    /// * `organizer` - Address that will start (or cancel) the tournament
+    /// * `game` - Game contract to play on (must have registered this contract)
+    /// * `format` - Single elimination or round robin
+    /// * `entry_fee` - Tokens each entrant pays into the prize pool
+    /// * `max_entrants` - Seats available (up to 16, or 8 for a round robin)
+    /// * `payout_bps` - Share of the pool for each place in basis points, adding up to 10,000
+    /// * `seed_commit` - Keccak-256 of a secret seed the organizer reveals at `start`
+    ///
+    /// # Returns
+    /// * `u32` - The new tournament id
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_tournament(
+        env: Env,
+        organizer: Address,
+        game: Address,
+        format: Format,
+        entry_fee: i128,
+        max_entrants: u32,
+        payout_bps: Vec<u32>,
+        seed_commit: BytesN<32>,
+    ) -> Result<u32, Error> {
+        organizer.require_auth();
+        escrow::extend_instance_ttl(&env);
+
+        if entry_fee <= 0 {
+            return Err(Error::InvalidEntryFee);
+        }
+        let seat_limit = match format {
+            Format::SingleElimination => bracket::MAX_ENTRANTS,
+            Format::RoundRobin => bracket::MAX_ROUND_ROBIN_ENTRANTS,
+        };
+        if !(2..=seat_limit).contains(&max_entrants) {
+            return Err(Error::InvalidEntrantLimit);
+        }
+        if payout_bps.len() > bracket::MAX_ENTRANTS {
+            return Err(Error::InvalidPayout);
+        }
+        let mut split = [0u32; bracket::MAX_ENTRANTS as usize];
+        for (share, bps) in split.iter_mut().zip(payout_bps.iter()) {
+            *share = bps;
+        }
+        if !bracket::is_valid_payout(&split[..payout_bps.len() as usize]) {
+            return Err(Error::InvalidPayout);
+        }
+
+        let tournament_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::TournamentCounter)
+            .unwrap_or(0)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::TournamentCounter, &tournament_id);
+
+        save_tournament(
+            &env,
+            &Tournament {
+                tournament_id,
+                organizer,
+                game,
+                format,
+                entry_fee,
+                max_entrants,
+                registration_deadline: env.ledger().sequence() + REGISTRATION_DEADLINE_LEDGERS,
+                payout_bps,
+                entrants: Vec::new(&env),
+                seeds: Vec::new(&env),
+                seed_commit,
+                seed: None,
+                seeding_hash: None,
+                round: 0,
+                round_deadline: 0,
+                lineup: Vec::new(&env),
+                pairings: Vec::new(&env),
+                keeper_rewards: 0,
+                ranking: Vec::new(&env),
+                payouts: Vec::new(&env),
+                status: TournamentStatus::Registration,
+            },
+        );
+
+        Ok(tournament_id)
+    }
+
+    /// Register for a tournament and pay the entry fee into the prize pool.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to enter
+    /// * `player` - Entrant; consents to every pairing the tournament starts for them
+    pub fn register(env: Env, tournament_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        escrow::extend_instance_ttl(&env);
+
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        check_registration_open(&env, &tournament)?;
+        if tournament.entrants.contains(&player) {
+            return Err(Error::AlreadyRegistered);
+        }
+        if tournament.entrants.len() >= tournament.max_entrants {
+            return Err(Error::TournamentFull);
+        }
+
+        fee_token(&env)?.transfer(&player, env.current_contract_address(), &tournament.entry_fee);
+
+        tournament.entrants.push_back(player);
+        save_tournament(&env, &tournament);
+
+        Ok(())
+    }
+
+    /// Close registration, seed the entrants and start the first pairings.
+    /// A bracket starts its first round; a round robin starts every pairing.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to start
+    /// * `organizer` - The tournament's organizer
+    /// * `seed` - The secret seed whose hash was committed at creation
+    pub fn start(
+        env: Env,
+        tournament_id: u32,
+        organizer: Address,
+        seed: BytesN<32>,
+    ) -> Result<(), Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        check_organizer(&tournament, &organizer)?;
+        check_registration_open(&env, &tournament)?;
+        let entrants = tournament.entrants.len();
+        if entrants < 2 {
+            return Err(Error::NotEnoughEntrants);
+        }
+
+        let commit: BytesN<32> = env.crypto().keccak256(&Bytes::from(seed.clone())).into();
+        if commit != tournament.seed_commit {
+            return Err(Error::InvalidSeed);
+        }
+        seed_entrants(&env, &mut tournament, seed);
+        tournament.round = 1;
+        tournament.round_deadline = env.ledger().sequence() + ROUND_DEADLINE_LEDGERS;
+        tournament.status = TournamentStatus::Running;
+
+        match tournament.format {
+            Format::SingleElimination => {
+                let size = bracket::bracket_size(entrants);
+                let mut lineup = Vec::new(&env);
+                for slot in 0..size {
+                    lineup.push_back(tournament.seeds.get(bracket::slot_seed(size, slot)));
+                }
+                tournament.lineup = lineup;
+                open_round(&env, &mut tournament)?;
+            }
+            Format::RoundRobin => {
+                for index in 0..bracket::round_robin_matches(entrants) {
+                    let (a, b) = bracket::round_robin_pair(entrants, index);
+                    let player1 = tournament.seeds.get_unchecked(a);
+                    let player2 = tournament.seeds.get_unchecked(b);
+                    start_pairing(&env, &mut tournament, index, player1, player2)?;
+                }
+            }
+        }
+
+        save_tournament(&env, &tournament);
+        Ok(())
+    }
+
+    /// Collect finished sessions from the game, paying any keepers that
+    /// settled them out of the prize pool, and move the tournament on.
+    /// Once every pairing of a bracket round is decided the next round starts;
+    /// once the last pairing is decided the prize pool is paid out.
+    /// Anyone may call this.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to advance
+    ///
+    /// # Returns
+    /// * `TournamentStatus` - `Finished` once the prizes were paid, else `Running`
+    pub fn advance(env: Env, tournament_id: u32) -> Result<TournamentStatus, Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        if tournament.status != TournamentStatus::Running {
+            return Err(Error::NotRunning);
+        }
+
+        if collect_results(&env, &mut tournament)? == 0 {
+            match tournament.format {
+                Format::SingleElimination if tournament.lineup.len() > 2 => {
+                    tournament.lineup = next_lineup(&env, &tournament);
+                    tournament.round += 1;
+                    tournament.round_deadline = env.ledger().sequence() + ROUND_DEADLINE_LEDGERS;
+                    open_round(&env, &mut tournament)?;
+                }
+                _ => finish(&env, &mut tournament)?,
+            }
+        }
+
+        save_tournament(&env, &tournament);
+        Ok(tournament.status)
+    }
+
+    /// End a tournament whose current round missed its deadline and refund
+    /// the prize pool (less keeper rewards already paid) to every entrant in
+    /// equal shares, rounding dust to the first to register. Anyone may call
+    /// this once the deadline has passed, as long as a pairing is still
+    /// undecided.
+    ///
+    /// A tournament still in registration past its registration deadline is
+    /// ended the same way, refunding every entry fee in full.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to refund
+    pub fn refund_stale(env: Env, tournament_id: u32) -> Result<(), Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        if tournament.status == TournamentStatus::Registration {
+            if env.ledger().sequence() <= tournament.registration_deadline {
+                return Err(Error::RegistrationDeadlineNotPassed);
+            }
+            refund_entry_fees(&env, &tournament)?;
+            tournament.status = TournamentStatus::Refunded;
+            save_tournament(&env, &tournament);
+            return Ok(());
+        }
+        if tournament.status != TournamentStatus::Running {
+            return Err(Error::NotRunning);
+        }
+        if env.ledger().sequence() <= tournament.round_deadline {
+            return Err(Error::RoundDeadlineNotPassed);
+        }
+        // Sessions that finished late still pay their keepers
+        if collect_results(&env, &mut tournament)? == 0 {
+            return Err(Error::RoundFinished);
+        }
+
+        let pool = prize_pool(&tournament);
+        let share = pool / tournament.entrants.len() as i128;
+        let dust = pool - share * tournament.entrants.len() as i128;
+        let token = fee_token(&env)?;
+        for (index, entrant) in tournament.entrants.iter().enumerate() {
+            let refund = if index == 0 { share + dust } else { share };
+            if refund > 0 {
+                token.transfer(&env.current_contract_address(), &entrant, &refund);
+            }
+        }
+
+        tournament.status = TournamentStatus::Refunded;
+        save_tournament(&env, &tournament);
+        Ok(())
+    }
+
+    /// Cancel a tournament during registration and refund every entry fee.
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament to cancel
+    /// * `organizer` - The tournament's organizer
+    pub fn cancel(env: Env, tournament_id: u32, organizer: Address) -> Result<(), Error> {
+        escrow::extend_instance_ttl(&env);
+
+        let mut tournament = load_tournament(&env, tournament_id)?;
+        check_organizer(&tournament, &organizer)?;
+        if tournament.status != TournamentStatus::Registration {
+            return Err(Error::RegistrationClosed);
+        }
+        refund_entry_fees(&env, &tournament)?;
+
+        tournament.status = TournamentStatus::Cancelled;
+        save_tournament(&env, &tournament);
+        Ok(())
+    }
+
+    /// Get a tournament, including its seeding, pairings and (once finished) results
+    ///
+    /// # Arguments
+    /// * `tournament_id` - The tournament id returned by `create_tournament`
+    pub fn get_tournament(env: Env, tournament_id: u32) -> Result<Tournament, Error> {
+        load_tournament(&env, tournament_id)
+    }
+
+    // ========================================================================
+    // Admin Functions
+    // ========================================================================
+
+    /// Get the current admin address
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
+        escrow::admin(&env).ok_or(Error::AdminNotSet)
+    }
+
+    /// Set a new admin address
+    ///
+    /// # Arguments
+    /// * `new_admin` - The new admin address
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
+        escrow::require_admin(&env).ok_or(Error::AdminNotSet)?;
+        escrow::set_admin(&env, &new_admin);
+
+        Ok(())
+    }
+
+    /// Get the token entry fees and prizes are paid in
+    pub fn get_token(env: Env) -> Result<Address, Error> {
+        escrow::token_address(&env).ok_or(Error::TokenNotSet)
+    }
+
+    /// Update the contract WASM hash (upgrade contract)
+    ///
+    /// # Arguments
+    /// * `new_wasm_hash` - The hash of the new WASM binary
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
+        escrow::require_admin(&env).ok_or(Error::AdminNotSet)?;
+
+        env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod bracket_test;
+#[cfg(test)]
+mod test;
@@ -0,0 +1,484 @@
+#![cfg(test)]
+
+// Unit tests for the tournament contract, played on a real dice-duel contract
+// with a simple mock GameHub and a Stellar asset as the entry fee token.
+
+use crate::{Error, Format, TournamentContract, TournamentContractClient, TournamentStatus};
+use dice_duel::{DiceDuelContract, DiceDuelContractClient, SettlementConfig, SettlementMode};
+use soroban_sdk::testutils::{Address as _, Ledger as _};
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
+use soroban_sdk::{contract, contractimpl, vec, Address, Bytes, BytesN, Env, Vec};
+
+// ============================================================================
+// Mock GameHub for Unit Testing
+// ============================================================================
+
+#[contract]
+pub struct MockGameHub;
+
+#[contractimpl]
+impl MockGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+        // Mock implementation - does nothing
+    }
+
+    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {
+        // Mock implementation - does nothing
+    }
+}
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+const ENTRY_FEE: i128 = 10_0000000;
+const BALANCE: i128 = 10 * ENTRY_FEE;
+
+struct Setup {
+    env: Env,
+    tournament: TournamentContractClient<'static>,
+    game: DiceDuelContractClient<'static>,
+    token: TokenClient<'static>,
+    organizer: Address,
+    /// The organizer's secret seed, committed to by every test tournament
+    seed: BytesN<32>,
+}
+
+fn setup_test() -> Setup {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let hub_addr = env.register(MockGameHub, ());
+    let game_addr = env.register(DiceDuelContract, (&admin, &hub_addr));
+    let game = DiceDuelContractClient::new(&env, &game_addr);
+
+    let token_addr = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let tournament_addr = env.register(TournamentContract, (&admin, &token_addr));
+    let tournament = TournamentContractClient::new(&env, &tournament_addr);
+
+    // The game trusts the tournament to start matches for its entrants
+    game.set_matchmaker(&tournament_addr, &true);
+
+    let token = TokenClient::new(&env, &token_addr);
+    let organizer = Address::generate(&env);
+    let seed = BytesN::from_array(&env, &[7; 32]);
+
+    Setup {
+        env,
+        tournament,
+        game,
+        token,
+        organizer,
+        seed,
+    }
+}
+
+/// Commitment to a secret seed, as passed to `create_tournament`
+fn commit(env: &Env, seed: &BytesN<32>) -> BytesN<32> {
+    env.crypto().keccak256(&Bytes::from(seed.clone())).into()
+}
+
+/// Create a tournament with `count` registered entrants
+fn create_with_entrants(
+    s: &Setup,
+    format: Format,
+    count: u32,
+    payout_bps: Vec<u32>,
+) -> (u32, Vec<Address>) {
+    let id = s.tournament.create_tournament(
+        &s.organizer,
+        &s.game.address,
+        &format,
+        &ENTRY_FEE,
+        &8,
+        &payout_bps,
+        &commit(&s.env, &s.seed),
+    );
+    let asset = StellarAssetClient::new(&s.env, &s.token.address);
+    let mut entrants = Vec::new(&s.env);
+    for _ in 0..count {
+        let player = Address::generate(&s.env);
+        asset.mint(&player, &BALANCE);
+        s.tournament.register(&id, &player);
+        entrants.push_back(player);
+    }
+    (id, entrants)
+}
+
+/// Play every pairing the game has not finished yet
+fn play_open_pairings(s: &Setup, id: u32) {
+    for pairing in s.tournament.get_tournament(&id).pairings.iter() {
+        if s.game.get_winner(&pairing.session_id).is_none() {
+            s.game.roll(&pairing.session_id, &pairing.player1);
+            s.game.roll(&pairing.session_id, &pairing.player2);
+            s.game.reveal_winner(&pairing.session_id, &pairing.player1);
+        }
+    }
+}
+
+/// Play and advance until the tournament finishes; returns the rounds played
+fn play_out(s: &Setup, id: u32) -> u32 {
+    let mut rounds = 0;
+    loop {
+        play_open_pairings(s, id);
+        rounds += 1;
+        if s.tournament.advance(&id) == TournamentStatus::Finished {
+            return rounds;
+        }
+    }
+}
+
+// ============================================================================
+// Single-Elimination Tests
+// ============================================================================
+
+#[test]
+fn test_bracket_with_byes_pays_out() {
+    let s = setup_test();
+    let payout = vec![&s.env, 7_000u32, 3_000];
+    let (id, entrants) = create_with_entrants(&s, Format::SingleElimination, 5, payout);
+    assert_eq!(s.token.balance(&s.tournament.address), 5 * ENTRY_FEE);
+
+    s.tournament.start(&id, &s.organizer, &s.seed);
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.status, TournamentStatus::Running);
+    assert_eq!(tournament.seeds.len(), 5);
+    for entrant in entrants.iter() {
+        assert!(tournament.seeds.contains(&entrant));
+    }
+    // 5 entrants in an 8-slot bracket: three byes, one first-round pairing
+    assert_eq!(tournament.lineup.len(), 8);
+    assert_eq!(tournament.pairings.len(), 1);
+    let first = tournament.pairings.get_unchecked(0);
+    assert_eq!(s.game.get_game(&first.session_id).player1, first.player1);
+
+    // Nothing advances until the game has a winner
+    assert_eq!(s.tournament.advance(&id), TournamentStatus::Running);
+    assert_eq!(s.tournament.get_tournament(&id).round, 1);
+
+    assert_eq!(play_out(&s, id), 3);
+
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.status, TournamentStatus::Finished);
+    assert_eq!(tournament.pairings.len(), 4);
+    let last = tournament.pairings.get_unchecked(3);
+    assert_eq!(last.round, 3);
+
+    // The final's winner and loser take first and second place
+    let champion = last.winner.clone().unwrap();
+    let runner_up = if champion == last.player1 { last.player2 } else { last.player1 };
+    assert_eq!(tournament.ranking.get_unchecked(0), champion);
+    assert_eq!(tournament.ranking.get_unchecked(1), runner_up);
+    assert_eq!(tournament.ranking.len(), 5);
+
+    let pool = 5 * ENTRY_FEE;
+    assert_eq!(tournament.payouts.get_unchecked(0), pool * 7 / 10);
+    assert_eq!(tournament.payouts.get_unchecked(1), pool * 3 / 10);
+    assert_eq!(s.token.balance(&champion), BALANCE - ENTRY_FEE + pool * 7 / 10);
+    assert_eq!(s.token.balance(&runner_up), BALANCE - ENTRY_FEE + pool * 3 / 10);
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+
+    assert_eq!(s.tournament.try_advance(&id), Err(Ok(Error::NotRunning)));
+}
+
+#[test]
+fn test_first_round_follows_seeding() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, _) = create_with_entrants(&s, Format::SingleElimination, 4, payout);
+    s.tournament.start(&id, &s.organizer, &s.seed);
+
+    let tournament = s.tournament.get_tournament(&id);
+    assert!(tournament.seeding_hash.is_some());
+    // Seeds 1v4 and 2v3 meet in the first round
+    let first = tournament.pairings.get_unchecked(0);
+    assert_eq!(first.player1, tournament.seeds.get_unchecked(0));
+    assert_eq!(first.player2, tournament.seeds.get_unchecked(3));
+    let second = tournament.pairings.get_unchecked(1);
+    assert_eq!(second.player1, tournament.seeds.get_unchecked(1));
+    assert_eq!(second.player2, tournament.seeds.get_unchecked(2));
+}
+
+#[test]
+fn test_start_requires_committed_seed() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, _) = create_with_entrants(&s, Format::RoundRobin, 3, payout);
+
+    let wrong = BytesN::from_array(&s.env, &[8; 32]);
+    let result = s.tournament.try_start(&id, &s.organizer, &wrong);
+    assert_eq!(result, Err(Ok(Error::InvalidSeed)));
+
+    // The revealed seed is recorded next to the hash it keyed
+    s.tournament.start(&id, &s.organizer, &s.seed);
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.seed, Some(s.seed.clone()));
+    assert_eq!(tournament.seed_commit, commit(&s.env, &s.seed));
+    assert!(tournament.seeding_hash.is_some());
+}
+
+#[test]
+fn test_occupied_session_ids_are_skipped() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, _) = create_with_entrants(&s, Format::SingleElimination, 4, payout);
+    s.tournament.start(&id, &s.organizer, &s.seed);
+
+    // With the seed revealed, anyone can work out the final's session id and
+    // start a game of their own on it first
+    let seeding_hash = s.tournament.get_tournament(&id).seeding_hash.unwrap();
+    let taken = crate::session_candidate(&s.env, &seeding_hash, 2, 0);
+    let squatter1 = Address::generate(&s.env);
+    let squatter2 = Address::generate(&s.env);
+    s.game.start_game(&taken, &squatter1, &squatter2, &1, &1, &None, &None);
+
+    play_open_pairings(&s, id);
+    assert_eq!(s.tournament.advance(&id), TournamentStatus::Running);
+
+    // The final moved on to the next id drawn instead of reverting
+    let last = s.tournament.get_tournament(&id).pairings.get_unchecked(2);
+    assert_eq!(last.round, 2);
+    assert_eq!(last.session_id, crate::session_candidate(&s.env, &seeding_hash, 2, 1));
+    assert_eq!(s.game.get_game(&last.session_id).player1, last.player1);
+    assert_eq!(play_out(&s, id), 1);
+}
+
+#[test]
+fn test_keeper_paid_from_pool() {
+    let s = setup_test();
+    s.game.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+    let payout = vec![&s.env, 10_000u32];
+    let (id, _) = create_with_entrants(&s, Format::SingleElimination, 2, payout);
+    s.tournament.start(&id, &s.organizer, &s.seed);
+
+    // The players leave the final unsettled, so a keeper settles it
+    let pairing = s.tournament.get_tournament(&id).pairings.get_unchecked(0);
+    s.game.roll(&pairing.session_id, &pairing.player1);
+    s.game.roll(&pairing.session_id, &pairing.player2);
+    let keeper = Address::generate(&s.env);
+    let sequence = s.env.ledger().sequence();
+    s.env.ledger().set_sequence_number(sequence + 10);
+    let champion = s.game.reveal_winner(&pairing.session_id, &keeper);
+    assert_eq!(s.tournament.advance(&id), TournamentStatus::Finished);
+
+    // 1% of the matched pot goes to the keeper, the rest of the pool to the winner
+    let reward = 2 * ENTRY_FEE / 100;
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.keeper_rewards, reward);
+    assert_eq!(s.token.balance(&keeper), reward);
+    assert_eq!(s.token.balance(&champion), BALANCE + ENTRY_FEE - reward);
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+}
+
+// ============================================================================
+// Round-Robin Tests
+// ============================================================================
+
+#[test]
+fn test_round_robin_ranks_by_wins() {
+    let s = setup_test();
+    let payout = vec![&s.env, 5_000u32, 3_000, 2_000];
+    let (id, _) = create_with_entrants(&s, Format::RoundRobin, 4, payout);
+    s.tournament.start(&id, &s.organizer, &s.seed);
+
+    // Every pair plays once, all started up front
+    assert_eq!(s.tournament.get_tournament(&id).pairings.len(), 6);
+    assert_eq!(play_out(&s, id), 1);
+
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.ranking.len(), 4);
+    let wins = |player: &Address| {
+        tournament
+            .pairings
+            .iter()
+            .filter(|pairing| pairing.winner.as_ref() == Some(player))
+            .count()
+    };
+    for place in 1..tournament.ranking.len() {
+        let better = tournament.ranking.get_unchecked(place - 1);
+        let worse = tournament.ranking.get_unchecked(place);
+        assert!(wins(&better) >= wins(&worse));
+    }
+
+    // Fourth place is unpaid and the whole pool is distributed
+    let paid: i128 = tournament.payouts.iter().sum();
+    assert_eq!(paid, 4 * ENTRY_FEE);
+    assert_eq!(tournament.payouts.get_unchecked(3), 0);
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+}
+
+// ============================================================================
+// Deadline Tests
+// ============================================================================
+
+#[test]
+fn test_stalled_round_refunds_pool_after_deadline() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, entrants) = create_with_entrants(&s, Format::SingleElimination, 3, payout);
+    let result = s.tournament.try_refund_stale(&id);
+    assert_eq!(result, Err(Ok(Error::RegistrationDeadlineNotPassed)));
+    s.tournament.start(&id, &s.organizer, &s.seed);
+
+    // One player of the only first-round pairing never rolls
+    let pairing = s.tournament.get_tournament(&id).pairings.get_unchecked(0);
+    s.game.roll(&pairing.session_id, &pairing.player1);
+    let deadline = s.tournament.get_tournament(&id).round_deadline;
+    assert_eq!(deadline, s.env.ledger().sequence() + crate::ROUND_DEADLINE_LEDGERS);
+
+    s.env.ledger().set_sequence_number(deadline);
+    let result = s.tournament.try_refund_stale(&id);
+    assert_eq!(result, Err(Ok(Error::RoundDeadlineNotPassed)));
+
+    s.env.ledger().set_sequence_number(deadline + 1);
+    s.tournament.refund_stale(&id);
+    for entrant in entrants.iter() {
+        assert_eq!(s.token.balance(&entrant), BALANCE);
+    }
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.status, TournamentStatus::Refunded);
+    assert_eq!(s.tournament.try_advance(&id), Err(Ok(Error::NotRunning)));
+}
+
+#[test]
+fn test_unstarted_tournament_refunds_entry_fees_after_deadline() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, entrants) = create_with_entrants(&s, Format::SingleElimination, 3, payout);
+    let deadline = s.tournament.get_tournament(&id).registration_deadline;
+    assert_eq!(deadline, s.env.ledger().sequence() + crate::REGISTRATION_DEADLINE_LEDGERS);
+
+    // The organizer never starts: fees stay locked until the deadline passes
+    s.env.ledger().set_sequence_number(deadline);
+    let result = s.tournament.try_refund_stale(&id);
+    assert_eq!(result, Err(Ok(Error::RegistrationDeadlineNotPassed)));
+
+    // Past it nobody can register or start, and anyone can refund every fee
+    s.env.ledger().set_sequence_number(deadline + 1);
+    let late = Address::generate(&s.env);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&late, &BALANCE);
+    assert_eq!(s.tournament.try_register(&id, &late), Err(Ok(Error::RegistrationClosed)));
+    let result = s.tournament.try_start(&id, &s.organizer, &s.seed);
+    assert_eq!(result, Err(Ok(Error::RegistrationClosed)));
+
+    s.tournament.refund_stale(&id);
+    for entrant in entrants.iter() {
+        assert_eq!(s.token.balance(&entrant), BALANCE);
+    }
+    assert_eq!(s.token.balance(&s.tournament.address), 0);
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.status, TournamentStatus::Refunded);
+    assert_eq!(s.tournament.try_refund_stale(&id), Err(Ok(Error::NotRunning)));
+}
+
+#[test]
+fn test_decided_round_not_refunded_after_deadline() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, _) = create_with_entrants(&s, Format::RoundRobin, 3, payout);
+    s.tournament.start(&id, &s.organizer, &s.seed);
+    play_open_pairings(&s, id);
+
+    // Every pairing finished, so the tournament is advanced, however late
+    let deadline = s.tournament.get_tournament(&id).round_deadline;
+    s.env.ledger().set_sequence_number(deadline + 1);
+    assert_eq!(s.tournament.try_refund_stale(&id), Err(Ok(Error::RoundFinished)));
+    assert_eq!(s.tournament.advance(&id), TournamentStatus::Finished);
+}
+
+// ============================================================================
+// Registration Tests
+// ============================================================================
+
+#[test]
+fn test_registration_rules() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, entrants) = create_with_entrants(&s, Format::SingleElimination, 1, payout);
+    let player = entrants.get_unchecked(0);
+
+    let result = s.tournament.try_register(&id, &player);
+    assert_eq!(result, Err(Ok(Error::AlreadyRegistered)));
+    let result = s.tournament.try_start(&id, &s.organizer, &s.seed);
+    assert_eq!(result, Err(Ok(Error::NotEnoughEntrants)));
+    let result = s.tournament.try_start(&id, &player, &s.seed);
+    assert_eq!(result, Err(Ok(Error::NotOrganizer)));
+
+    // Fill the remaining seven seats
+    let asset = StellarAssetClient::new(&s.env, &s.token.address);
+    for _ in 1..8 {
+        let other = Address::generate(&s.env);
+        asset.mint(&other, &BALANCE);
+        s.tournament.register(&id, &other);
+    }
+    let late = Address::generate(&s.env);
+    asset.mint(&late, &BALANCE);
+    assert_eq!(s.tournament.try_register(&id, &late), Err(Ok(Error::TournamentFull)));
+
+    s.tournament.start(&id, &s.organizer, &s.seed);
+    assert_eq!(s.tournament.get_tournament(&id).pairings.len(), 4);
+    let result = s.tournament.try_register(&id, &late);
+    assert_eq!(result, Err(Ok(Error::RegistrationClosed)));
+    let result = s.tournament.try_cancel(&id, &s.organizer);
+    assert_eq!(result, Err(Ok(Error::RegistrationClosed)));
+}
+
+#[test]
+fn test_cancel_refunds_entry_fees() {
+    let s = setup_test();
+    let payout = vec![&s.env, 10_000u32];
+    let (id, entrants) = create_with_entrants(&s, Format::RoundRobin, 3, payout);
+
+    s.tournament.cancel(&id, &s.organizer);
+    for entrant in entrants.iter() {
+        assert_eq!(s.token.balance(&entrant), BALANCE);
+    }
+    let tournament = s.tournament.get_tournament(&id);
+    assert_eq!(tournament.status, TournamentStatus::Cancelled);
+    let result = s.tournament.try_start(&id, &s.organizer, &s.seed);
+    assert_eq!(result, Err(Ok(Error::RegistrationClosed)));
+}
+
+#[test]
+fn test_invalid_tournaments_rejected() {
+    let s = setup_test();
+    let game = &s.game.address;
+    let elimination = Format::SingleElimination;
+    let full = vec![&s.env, 10_000u32];
+    let seed_commit = commit(&s.env, &s.seed);
+    let create = |format: &Format, fee: i128, seats: u32, split: &Vec<u32>| {
+        let organizer = &s.organizer;
+        s.tournament
+            .try_create_tournament(organizer, game, format, &fee, &seats, split, &seed_commit)
+    };
+
+    let result = create(&elimination, 0, 8, &full);
+    assert_eq!(result, Err(Ok(Error::InvalidEntryFee)));
+
+    let result = create(&elimination, ENTRY_FEE, 17, &full);
+    assert_eq!(result, Err(Ok(Error::InvalidEntrantLimit)));
+    let result = create(&Format::RoundRobin, ENTRY_FEE, 9, &full);
+    assert_eq!(result, Err(Ok(Error::InvalidEntrantLimit)));
+
+    let short = vec![&s.env, 6_000u32, 3_000];
+    let result = create(&elimination, ENTRY_FEE, 8, &short);
+    assert_eq!(result, Err(Ok(Error::InvalidPayout)));
+
+    assert_eq!(s.tournament.try_get_tournament(&9), Err(Ok(Error::TournamentNotFound)));
+}
@@ -10,9 +10,16 @@
 //! This game is Game Hub-aware and enforces all games to be played through the
 //! Game Hub contract. Games cannot be started or completed without points involvement.
 
+use game_kit::rematch;
+use game_kit::session_keys::{self, authorize_move};
+use game_kit::settlement::{self, credit_keeper, settlement_config};
+use game_kit::stakes;
+use game_kit::ttl::{self, extend_game_ttl, extend_instance_ttl, ttl_config, HISTORY_TTL_LEDGERS};
+use matchmaking::registry;
+use provably_fair::{twenty_one, Session, MAX_STRKEY_LEN};
 use soroban_sdk::{
-    Address, Bytes, BytesN, Env, IntoVal, contract, contractclient, contracterror,
-    contractimpl, contracttype, vec
+    Address, Bytes, BytesN, Env, IntoVal, String, Symbol, Vec, contract, contractclient,
+    contracterror, contractimpl, contracttype, vec, xdr::ToXdr
 };
 
 // Import GameHub contract interface
@@ -36,21 +43,98 @@ pub trait GameHub {
 // Errors
 // ============================================================================
 
+/// Errors returned by the contract.
+///
+/// Codes are stable: clients match on them, so existing variants are never
+/// renumbered or reused and new failures get the next free code.
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
 pub enum Error {
+    /// No game exists for the session (never started, or expired)
     GameNotFound = 1,
+    /// The address is not one of the two players, or may not settle this game
+    /// (keepers only settle games whose stakes a matchmaker escrows)
     NotPlayer = 2,
+    /// The player already stuck and cannot draw or stick again
     AlreadyStuck = 3,
+    /// The game already has a winner
     GameAlreadyEnded = 4,
+    /// No longer returned; kept so later codes stay stable
     PlayerBusted = 5,
+    /// `reveal_winner` was called before both players stuck
     BothPlayersNotStuck = 6,
+    /// No longer returned; kept so later codes stay stable
     OpponentNotStuck = 7,
+    /// No longer returned (a tie now deals a new round); kept so later codes stay stable
     Draw = 8,
+    /// Player 1 and player 2 are the same address
     SelfPlay = 9,
+    /// The round counter overflowed after repeated draws
     RoundOverflow = 10,
+    /// A stored hand could not be read or totalled
     InvalidHandData = 11,
+    /// A TTL threshold exceeds its extension target, or a target exceeds the
+    /// network's maximum TTL
+    InvalidTtlConfig = 12,
+    /// No archived result exists for the session
+    MatchNotFound = 13,
+    /// A keeper tried to settle before `keeper_delay` ledgers passed
+    SettlementTooEarly = 14,
+    /// The keeper reward exceeds 500 basis points
+    InvalidSettlementConfig = 15,
+    /// The GameHub address is missing from instance storage
+    GameHubNotSet = 16,
+    /// The admin address is missing from instance storage
+    AdminNotSet = 17,
+    /// The caller of `start_matched_game` is not a registered matchmaker
+    NotMatchmaker = 18,
+    /// A session key is a player of the session, expires in the past, is
+    /// scoped to an unknown entry point or already signs for the other player
+    InvalidSessionKey = 19,
+    /// The session key's `expires_at` ledger has been reached
+    SessionKeyExpired = 20,
+    /// The session key is not scoped to this entry point
+    SessionKeyNotAllowed = 21,
+    /// The player has no session key registered for the session
+    SessionKeyNotFound = 22,
+    /// A rematch was proposed before the game ended
+    GameNotEnded = 23,
+    /// No rematch was offered to the player for the session
+    RematchNotFound = 24,
+    /// The session was already rematched; only the latest session of a series can be
+    RematchAlreadyPlayed = 25,
+    /// The session ID is already used by another game
+    SessionIdTaken = 26,
+    /// Doubling the stakes overflows
+    StakeOverflow = 27,
+    /// A player committed zero or negative points
+    InvalidStake = 28,
+    /// A player's points are outside the admin's stake limits
+    StakeOutOfRange = 29,
+    /// The minimum stake is below 1 or above the maximum, or the maximum is
+    /// above `i128::MAX / 2`
+    InvalidStakeLimits = 30,
+}
+
+impl From<game_kit::Error> for Error {
+    fn from(error: game_kit::Error) -> Self {
+        match error {
+            game_kit::Error::InvalidTtlConfig => Error::InvalidTtlConfig,
+            game_kit::Error::InvalidSessionKey => Error::InvalidSessionKey,
+            game_kit::Error::SessionKeyExpired => Error::SessionKeyExpired,
+            game_kit::Error::SessionKeyNotAllowed => Error::SessionKeyNotAllowed,
+            game_kit::Error::RematchNotFound => Error::RematchNotFound,
+            game_kit::Error::RematchAlreadyPlayed => Error::RematchAlreadyPlayed,
+            game_kit::Error::StakeOverflow => Error::StakeOverflow,
+            game_kit::Error::InvalidStake => Error::InvalidStake,
+            game_kit::Error::StakeOutOfRange => Error::StakeOutOfRange,
+            game_kit::Error::InvalidStakeLimits => Error::InvalidStakeLimits,
+            game_kit::Error::NotPlayer => Error::NotPlayer,
+            game_kit::Error::SettlementTooEarly => Error::SettlementTooEarly,
+            game_kit::Error::InvalidSettlementConfig => Error::InvalidSettlementConfig,
+        }
+    }
 }
 
 // ============================================================================
@@ -68,6 +152,13 @@ pub enum Error {
 // Data Types
 // ============================================================================
 
+pub use game_kit::rematch::{RematchOffer, Series};
+pub use game_kit::session_keys::SessionKey;
+pub use game_kit::settlement::{SettlementConfig, SettlementMode};
+pub use game_kit::stakes::StakeLimits;
+pub use game_kit::ttl::TtlConfig;
+pub use matchmaking::{KeeperReward, SessionInfo, Settlement};
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct Game {
@@ -81,6 +172,50 @@ pub struct Game {
     pub player2_stuck: bool,
     pub winner: Option<Address>,
     pub round: u32,
+    /// Ledger of the last start, hit or stick (keepers wait `keeper_delay` after it)
+    pub last_move_ledger: u32,
+    /// Whether a matchmaker (lobby or tournament) escrows the stakes and pays
+    /// keeper rewards; keepers may only settle such games
+    pub escrowed: bool,
+}
+
+/// Archived result of a finished game, kept in persistent storage
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchResult {
+    pub session_id: u32,
+    pub player1: Address,
+    pub player2: Address,
+    pub player1_points: i128,
+    pub player2_points: i128,
+    pub player1_won: bool,
+    pub winner: Address,
+    /// Points paid back to each player under the matched-stakes rule, less the
+    /// keeper rewards; with them they add up to both stakes. Informational only:
+    /// the Game Hub is told just the winner and settles the points itself.
+    pub player1_payout: i128,
+    pub player2_payout: i128,
+    /// Keccak-256 of the final game state (XDR encoded)
+    pub state_hash: BytesN<32>,
+    /// Ledger sequence at which the game ended
+    pub end_ledger: u32,
+    /// Third party that settled the game, if it was not one of the players
+    pub keeper: Option<Address>,
+    /// Points credited to the keeper for settling
+    pub keeper_reward: i128,
+}
+
+/// Public inputs a session's cards are derived from, for recomputing them with
+/// the `provably-fair` crate (see `get_seed_material`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SeedMaterial {
+    pub session_id: u32,
+    /// Player addresses as the strkey text the seeds hash
+    pub player1: String,
+    pub player2: String,
+    /// Current round; the hands in `Game` were dealt from its deck
+    pub round: u32,
 }
 
 #[contracttype]
@@ -89,18 +224,11 @@ pub enum DataKey {
     Game(u32),
     GameHubAddress,
     Admin,
+    MatchResult(u32),              // session_id -> MatchResult
+    PlayerMatchCount(Address),     // player -> number of archived matches
+    PlayerMatch(Address, u32),     // (player, index) -> session_id
 }
 
-// ============================================================================
-// Storage TTL Management
-// ============================================================================
-// TTL (Time To Live) ensures game data doesn't expire unexpectedly
-// Games are stored in temporary storage with a minimum 30-day retention
-
-/// TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
-/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
-const GAME_TTL_LEDGERS: u32 = 518_400;
-
 // ============================================================================
 // Helper Functions
 // ============================================================================
@@ -127,11 +255,299 @@ fn calculate_hand_value(hand: &Bytes) -> Result<u32, Error> {
     Ok(total)
 }
 
-/// Deal a card (1-13) using deterministic PRNG
-/// The seed is passed in (as Hash from keccak256)
-fn deal_card(env: &Env, seed: BytesN<32>) -> u8 {
-    env.prng().seed(seed.into());
-    env.prng().gen_range::<u64>(1..=13) as u8
+/// Cards are dealt from public seed material, so every deal of a session can be
+/// computed before the first decision (see `SessionInfo::predetermined`)
+const OUTCOME_PREDETERMINED: bool = true;
+
+/// keccak256 on the host, for the outcome derivations of `provably_fair`
+struct HostKeccak<'a>(&'a Env);
+
+impl provably_fair::Keccak256 for HostKeccak<'_> {
+    fn hash(&self, parts: &[&[u8]]) -> [u8; 32] {
+        let mut preimage = Bytes::new(self.0);
+        for part in parts {
+            preimage.extend_from_slice(part);
+        }
+        self.0.crypto().keccak256(&preimage).to_array()
+    }
+}
+
+/// Deck seed of a round (see `provably_fair::twenty_one`).
+///
+/// Seed components (all deterministic and identical between sim/submit):
+/// 1. Session ID - unique per game
+/// 2. Player addresses - both players contribute
+/// 3. Round number - a fresh deck after every draw
+///
+/// Note: We do NOT include ledger sequence or timestamp because those differ
+/// between simulation and submission.
+fn deal_seed(
+    env: &Env,
+    session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    round: u32,
+) -> [u8; 32] {
+    let player1 = player1.to_string().to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let player2 = player2.to_string().to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let session = Session {
+        session_id,
+        player1: player1.as_slice(),
+        player2: player2.as_slice(),
+    };
+    twenty_one::deal_seed(&HostKeccak(env), &session, round)
+}
+
+/// Deal the opening hand of `player` (1 or 2) from a round's deck
+fn deal_hand(env: &Env, seed: &[u8; 32], player: u8) -> Bytes {
+    let mut hand = Bytes::new(env);
+    for card in twenty_one::hand(&HostKeccak(env), seed, player, twenty_one::OPENING_CARDS) {
+        hand.push_back(card);
+    }
+    hand
+}
+
+// ============================================================================
+// Match History
+// ============================================================================
+// Finished games are archived in persistent storage so results outlive the
+// temporary game entry (disputes, statistics)
+
+/// Maximum number of results returned by one `get_player_matches` call
+const MAX_HISTORY_PAGE: u32 = 50;
+
+/// Archive the final state of a game and index it under both players.
+/// Must be called once, after the winner has been set.
+fn archive_match(
+    env: &Env,
+    session_id: u32,
+    game: &Game,
+    player1_won: bool,
+    keeper: Option<Address>,
+    keeper_reward: i128,
+) {
+    let winner = if player1_won {
+        game.player1.clone()
+    } else {
+        game.player2.clone()
+    };
+    let (player1_payout, player2_payout) = stakes::settle_stakes(
+        game.player1_points,
+        game.player2_points,
+        player1_won,
+        keeper_reward,
+    );
+    let result = MatchResult {
+        session_id,
+        player1: game.player1.clone(),
+        player2: game.player2.clone(),
+        player1_points: game.player1_points,
+        player2_points: game.player2_points,
+        player1_won,
+        winner,
+        player1_payout,
+        player2_payout,
+        state_hash: env.crypto().keccak256(&game.clone().to_xdr(env)).into(),
+        end_ledger: env.ledger().sequence(),
+        keeper,
+        keeper_reward,
+    };
+
+    let key = DataKey::MatchResult(session_id);
+    env.storage().persistent().set(&key, &result);
+    env.storage()
+        .persistent()
+        .extend_ttl(&key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+
+    index_player_match(env, &game.player1, session_id);
+    index_player_match(env, &game.player2, session_id);
+    rematch::record_series_result(env, session_id, player1_won);
+}
+
+/// Append a session to a player's match index
+fn index_player_match(env: &Env, player: &Address, session_id: u32) {
+    let count_key = DataKey::PlayerMatchCount(player.clone());
+    let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+
+    let entry_key = DataKey::PlayerMatch(player.clone(), count);
+    env.storage().persistent().set(&entry_key, &session_id);
+    env.storage().persistent().set(&count_key, &(count + 1));
+
+    env.storage()
+        .persistent()
+        .extend_ttl(&entry_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+    env.storage()
+        .persistent()
+        .extend_ttl(&count_key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+}
+
+// ============================================================================
+// Settlement
+// ============================================================================
+// Players can always settle their own game. In keeper mode anyone else can
+// settle it too, once the players have had `keeper_delay` ledgers to do it
+// themselves, and earns a share of the pot that the contract escrowing the
+// stakes pays out (see `game_kit::settlement`).
+
+/// Check that `caller` may settle `game` now.
+/// Returns the keeper to reward, or `None` when one of the players settles.
+fn authorize_settlement(
+    env: &Env,
+    config: &SettlementConfig,
+    game: &Game,
+    caller: &Address,
+) -> Result<Option<Address>, Error> {
+    let is_player = *caller == game.player1 || *caller == game.player2;
+    settlement::authorize_settlement(
+        env,
+        config,
+        caller,
+        is_player,
+        game.escrowed,
+        game.last_move_ledger,
+    )
+    .map_err(Error::from)
+}
+
+/// Keeper reward for settling a game: the configured share of the matched pot
+fn keeper_reward(config: &SettlementConfig, game: &Game) -> i128 {
+    let pot = 2 * game.player1_points.min(game.player2_points);
+    settlement::keeper_reward(config, pot, 1)
+}
+
+// ============================================================================
+// Stakes
+// ============================================================================
+// Both players must commit a positive number of points within the admin's
+// limits. Unequal stakes are matched at the smaller one: the winner takes that
+// much from the loser less any keeper rewards, and the rest of the larger stake
+// goes back to its owner (see `game_kit::stakes`).
+
+/// Largest stake the admin can allow, so that two stakes always add up
+const MAX_STAKE: i128 = i128::MAX / 2;
+
+// ============================================================================
+// Matchmaking
+// ============================================================================
+// Contracts registered by the admin (a lobby, a tournament) may start games
+// for players who gave their consent to the matchmaker instead of signing the
+// start themselves. Both entry points share `open_game` once checks pass.
+
+/// Register the session with the GameHub and store the new game.
+/// Players must already be validated and authorized;
+/// the stakes are checked against the stake limits here. `escrowed` marks
+/// games a registered matchmaker started.
+fn open_game(
+    env: &Env,
+    session_id: u32,
+    player1: Address,
+    player2: Address,
+    player1_points: i128,
+    player2_points: i128,
+    escrowed: bool,
+) -> Result<(), Error> {
+    if session_id_taken(env, session_id) {
+        return Err(Error::SessionIdTaken);
+    }
+    let limits = stakes::stake_limits(env, MAX_STAKE);
+    stakes::validate_stake(&limits, player1_points)?;
+    stakes::validate_stake(&limits, player2_points)?;
+
+    // Get GameHub address
+    let game_hub_addr: Address = env
+        .storage()
+        .instance()
+        .get(&DataKey::GameHubAddress)
+        .ok_or(Error::GameHubNotSet)?;
+
+    // Create GameHub client
+    let game_hub = GameHubClient::new(env, &game_hub_addr);
+
+    // Call the Game Hub to start the session and lock points
+    // This requires THIS contract's authorization (env.current_contract_address())
+    game_hub.start_game(
+        &env.current_contract_address(),
+        &session_id,
+        &player1,
+        &player2,
+        &player1_points,
+        &player2_points,
+    );
+
+    // Deal initial hands (2 cards each) from the first round's deck
+    let seed = deal_seed(env, session_id, &player1, &player2, 1);
+    let player1_hand = deal_hand(env, &seed, 1);
+    let player2_hand = deal_hand(env, &seed, 2);
+
+    // Create game
+    let game = Game {
+        player1: player1.clone(),
+        player2: player2.clone(),
+        player1_points,
+        player2_points,
+        player1_hand,
+        player2_hand,
+        player1_stuck: false,
+        player2_stuck: false,
+        winner: None,
+        round: 1,
+        last_move_ledger: env.ledger().sequence(),
+        escrowed,
+    };
+
+    // Store game in temporary storage with 30-day TTL
+    let game_key = DataKey::Game(session_id);
+    env.storage().temporary().set(&game_key, &game);
+
+    // Set TTL to ensure game is retained for at least 30 days
+    extend_game_ttl(env, &game_key);
+
+    // Event emitted by GameHub contract (GameStarted)
+
+    Ok(())
+}
+
+/// Require `matchmaker`'s authorization and check the admin registered it
+fn authorize_matchmaker(env: &Env, matchmaker: &Address) -> Result<(), Error> {
+    if !registry::authorize_matchmaker(env, matchmaker) {
+        return Err(Error::NotMatchmaker);
+    }
+    Ok(())
+}
+
+// ============================================================================
+// Session Keys
+// ============================================================================
+// A player may register an ephemeral key for one session so their frontend can
+// sign moves without a wallet prompt each time (see `game_kit::session_keys`).
+// Move entry points resolve a key to its player with `authorize_move`.
+
+/// Entry points a session key may sign (`hit` and `stick`)
+const SESSION_KEY_MOVES: [&str; 2] = ["hit", "stick"];
+
+// ============================================================================
+// Rematches
+// ============================================================================
+// Once a game has ended either player may offer a rematch, which the other
+// accepts without a fresh two-party start. The rematch keeps the seats and
+// carries the stakes over (or doubles them). Accepted rematches link their
+// sessions into a series whose cumulative score is kept in persistent storage
+// next to the match history.
+
+/// Whether a game, live or archived, already uses `session_id`
+fn session_id_taken(env: &Env, session_id: u32) -> bool {
+    env.storage().temporary().has(&DataKey::Game(session_id))
+        || env.storage().persistent().has(&DataKey::MatchResult(session_id))
+}
+
+/// Stakes of a rematch of `game`, checked against the current stake limits
+fn rematch_stakes(env: &Env, game: &Game, double_stakes: bool) -> Result<(i128, i128), Error> {
+    let player1_points = stakes::rematch_stake(game.player1_points, double_stakes)?;
+    let player2_points = stakes::rematch_stake(game.player2_points, double_stakes)?;
+    let limits = stakes::stake_limits(env, MAX_STAKE);
+    stakes::validate_stake(&limits, player1_points)?;
+    stakes::validate_stake(&limits, player2_points)?;
+    Ok((player1_points, player2_points))
 }
 
 // ============================================================================
@@ -154,6 +570,8 @@ impl TwentyOneContract {
         env.storage()
             .instance()
             .set(&DataKey::GameHubAddress, &game_hub);
+
+        extend_instance_ttl(&env);
     }
 
     /// Start a new game between two players with points.
@@ -182,103 +600,234 @@ impl TwentyOneContract {
             return Err(Error::SelfPlay);
         }
 
+        extend_instance_ttl(&env);
+
         // Require authentication from both players (they consent to committing points)
         player1.require_auth_for_args(vec![&env, session_id.into_val(&env), player1_points.into_val(&env)]);
         player2.require_auth_for_args(vec![&env, session_id.into_val(&env), player2_points.into_val(&env)]);
 
-        // Get GameHub address
-        let game_hub_addr: Address = env
-            .storage()
-            .instance()
-            .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+        open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            false,
+        )
+    }
 
-        // Create GameHub client
-        let game_hub = GameHubClient::new(&env, &game_hub_addr);
-
-        // Call the Game Hub to start the session and lock points
-        // This requires THIS contract's authorization (env.current_contract_address())
-        game_hub.start_game(
-            &env.current_contract_address(),
-            &session_id,
-            &player1,
-            &player2,
-            &player1_points,
-            &player2_points,
-        );
+    /// Start a game on behalf of a registered matchmaker (see `set_matchmaker`).
+    /// The matchmaker collected both players' consent itself, so only its own
+    /// authorization is required.
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Registered matchmaker contract starting the game
+    /// * `session_id` - Unique session identifier (u32)
+    /// * `player1` - Address of first player
+    /// * `player2` - Address of second player
+    /// * `player1_points` - Points amount committed by player 1
+    /// * `player2_points` - Points amount committed by player 2
+    pub fn start_matched_game(
+        env: Env,
+        matchmaker: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    ) -> Result<(), Error> {
+        if player1 == player2 {
+            return Err(Error::SelfPlay);
+        }
+
+        extend_instance_ttl(&env);
+        authorize_matchmaker(&env, &matchmaker)?;
+
+        open_game(
+            &env,
+            session_id,
+            player1,
+            player2,
+            player1_points,
+            player2_points,
+            true,
+        )
+    }
 
-        // Generate deterministic seed for card dealing
-        // Seed components (all deterministic and identical between sim/submit):
-        // 1. Session ID - unique per game
-        // 2. Player addresses - both players contribute
-        //
-        // Note: We do NOT include ledger sequence or timestamp because those differ
-        // between simulation and submission.
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-        seed_bytes.append(&player1.to_string().to_bytes());
-        seed_bytes.append(&player2.to_string().to_bytes());
-        let base_seed = env.crypto().keccak256(&seed_bytes);
-
-        // Deal initial hands (2 cards each)
-        // Use different seeds for each card to ensure variety
-        let mut player1_hand = Bytes::new(&env);
-        let mut player2_hand = Bytes::new(&env);
-
-        // Deal 2 cards to player1
-        for i in 0..2 {
-            let mut card_seed_bytes = Bytes::new(&env);
-            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-            card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
-            let card_seed = env.crypto().keccak256(&card_seed_bytes);
-            player1_hand.push_back(deal_card(&env, card_seed.into()));
+    /// Offer the other player a rematch of an ended game, replacing any offer
+    /// pending for it. The rematch is played under `new_session_id` with the same seats.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - Address of the player proposing the rematch
+    /// * `new_session_id` - Unused session ID for the rematch
+    /// * `double_stakes` - Double both players' points instead of carrying them over
+    pub fn propose_rematch(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        new_session_id: u32,
+        double_stakes: bool,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let game_key = DataKey::Game(session_id);
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&game_key)
+            .ok_or(Error::GameNotFound)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        if game.winner.is_none() {
+            return Err(Error::GameNotEnded);
+        }
+        rematch::check_rematchable(&env, session_id)?;
+        if session_id_taken(&env, new_session_id) {
+            return Err(Error::SessionIdTaken);
         }
+        rematch_stakes(&env, &game, double_stakes)?;
+
+        let offer = RematchOffer {
+            proposer: player,
+            new_session_id,
+            double_stakes,
+        };
+        rematch::offer_rematch(&env, session_id, &offer);
+        extend_game_ttl(&env, &game_key);
+
+        Ok(())
+    }
 
-        // Deal 2 cards to player2
-        for i in 0..2 {
-            let mut card_seed_bytes = Bytes::new(&env);
-            card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-            card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
-            let card_seed = env.crypto().keccak256(&card_seed_bytes);
-            player2_hand.push_back(deal_card(&env, card_seed.into()));
+    /// Accept the rematch the other player offered, starting it in the Game Hub
+    /// with the stakes of the offer. Returns the session ID of the rematch.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    /// * `player` - Address of the player accepting the rematch
+    pub fn accept_rematch(env: Env, session_id: u32, player: Address) -> Result<u32, Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let offer = rematch::take_rematch(&env, session_id, &player)?;
+        if session_id_taken(&env, offer.new_session_id) {
+            return Err(Error::SessionIdTaken);
         }
+        let (player1_points, player2_points) = rematch_stakes(&env, &game, offer.double_stakes)?;
 
-        // Create game
-        let game = Game {
-            player1: player1.clone(),
-            player2: player2.clone(),
+        open_game(
+            &env,
+            offer.new_session_id,
+            game.player1.clone(),
+            game.player2.clone(),
             player1_points,
             player2_points,
-            player1_hand,
-            player2_hand,
-            player1_stuck: false,
-            player2_stuck: false,
-            winner: None,
-            round: 1,
-        };
+            false,
+        )?;
+        let player1_won = game.winner.as_ref() == Some(&game.player1);
+        rematch::link_rematch(
+            &env,
+            session_id,
+            offer.new_session_id,
+            &game.player1,
+            &game.player2,
+            player1_won,
+        );
 
-        // Store game in temporary storage with 30-day TTL
-        let game_key = DataKey::Game(session_id);
-        env.storage().temporary().set(&game_key, &game);
+        Ok(offer.new_session_id)
+    }
 
-        // Set TTL to ensure game is retained for at least 30 days
-        env.storage()
+    /// Register an ephemeral session key that may sign `player`'s moves in
+    /// this session, replacing any key registered before. The key's address is
+    /// then passed in place of the player's to the move entry points.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player delegating their moves
+    /// * `key` - Address of the session key
+    /// * `functions` - Entry points the key may call (`hit`, `stick`); empty for all
+    /// * `expires_at` - Ledger sequence from which the key is refused, or `None`
+    ///   to keep it for the whole session
+    pub fn register_session_key(
+        env: Env,
+        session_id: u32,
+        player: Address,
+        key: Address,
+        functions: Vec<Symbol>,
+        expires_at: Option<u32>,
+    ) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
+
+        let game: Game = env
+            .storage()
             .temporary()
-            .extend_ttl(&game_key, GAME_TTL_LEDGERS, GAME_TTL_LEDGERS);
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        if game.winner.is_some() {
+            return Err(Error::GameAlreadyEnded);
+        }
+        if player != game.player1 && player != game.player2 {
+            return Err(Error::NotPlayer);
+        }
+        let is_player = key == game.player1 || key == game.player2;
+        session_keys::register_session_key(
+            &env,
+            session_id,
+            player,
+            key,
+            functions,
+            expires_at,
+            is_player,
+            &SESSION_KEY_MOVES,
+        )?;
+        Ok(())
+    }
 
-        // Event emitted by GameHub contract (GameStarted)
+    /// Revoke `player`'s session key; moves must be signed by the player again.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player who registered the key
+    pub fn revoke_session_key(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
+        player.require_auth();
+        extend_instance_ttl(&env);
 
+        if !session_keys::remove_session_key(&env, session_id, &player) {
+            return Err(Error::SessionKeyNotFound);
+        }
         Ok(())
     }
 
+    /// Get the session key `player` registered for a session, if any.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    /// * `player` - Address of the player
+    pub fn get_session_key(env: Env, session_id: u32, player: Address) -> Option<SessionKey> {
+        session_keys::session_key(&env, session_id, player)
+    }
+
     /// Player draws another card ("hit").
     /// If the player's hand value exceeds 21, they bust and lose immediately.
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player drawing a card
+    /// * `player` - Address of the player drawing a card, or of their session key
     pub fn hit(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
-        player.require_auth();
+        let player = authorize_move(&env, session_id, player, "hit")?;
+        extend_instance_ttl(&env);
 
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
@@ -300,6 +849,7 @@ impl TwentyOneContract {
         if !is_player1 && !is_player2 {
             return Err(Error::NotPlayer);
         }
+        game.last_move_ledger = env.ledger().sequence();
 
         if is_player1 && game.player1_stuck {
             return Err(Error::AlreadyStuck);
@@ -309,22 +859,14 @@ impl TwentyOneContract {
             return Err(Error::AlreadyStuck);
         }
 
-        // Generate seed for new card based on current hand size
-        let mut seed_bytes = Bytes::new(&env);
-        seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-        seed_bytes.append(&player.to_string().to_bytes());
-
-        let card_count = if is_player1 {
-            game.player1_hand.len()
+        // The next card of the player's hand in this round's deck
+        let (seat, card_count) = if is_player1 {
+            (1, game.player1_hand.len())
         } else {
-            game.player2_hand.len()
+            (2, game.player2_hand.len())
         };
-
-        seed_bytes.append(&Bytes::from_array(&env, &(card_count as u32).to_be_bytes()));
-        seed_bytes.append(&Bytes::from_array(&env, &game.round.to_be_bytes()));
-
-        let card_seed = env.crypto().keccak256(&seed_bytes);
-        let new_card = deal_card(&env, card_seed.into());
+        let seed = deal_seed(&env, session_id, &game.player1, &game.player2, game.round);
+        let new_card = twenty_one::card(&HostKeccak(&env), &seed, seat, card_count as u8);
 
         // Add card to player's hand
         if is_player1 {
@@ -340,6 +882,8 @@ impl TwentyOneContract {
                 // Only set winner AFTER GameHub succeeds
                 game.winner = Some(game.player2.clone());
                 env.storage().temporary().set(&key, &game);
+                extend_game_ttl(&env, &key);
+                archive_match(&env, session_id, &game, false, None, 0);
 
                 // Return Ok - caller should check game.winner to see if game ended
                 return Ok(());
@@ -357,6 +901,8 @@ impl TwentyOneContract {
                 // Only set winner AFTER GameHub succeeds
                 game.winner = Some(game.player1.clone());
                 env.storage().temporary().set(&key, &game);
+                extend_game_ttl(&env, &key);
+                archive_match(&env, session_id, &game, true, None, 0);
 
                 // Return Ok - caller should check game.winner to see if game ended
                 return Ok(());
@@ -365,6 +911,7 @@ impl TwentyOneContract {
 
         // Store updated game
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
 
         Ok(())
     }
@@ -374,9 +921,10 @@ impl TwentyOneContract {
     ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
-    /// * `player` - Address of the player sticking
+    /// * `player` - Address of the player sticking, or of their session key
     pub fn stick(env: Env, session_id: u32, player: Address) -> Result<(), Error> {
-        player.require_auth();
+        let player = authorize_move(&env, session_id, player, "stick")?;
+        extend_instance_ttl(&env);
 
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
@@ -405,9 +953,11 @@ impl TwentyOneContract {
         } else {
             return Err(Error::NotPlayer);
         }
+        game.last_move_ledger = env.ledger().sequence();
 
         // Store updated game
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
 
         Ok(())
     }
@@ -417,12 +967,24 @@ impl TwentyOneContract {
     /// This calculates hand values, determines the winner (closest to 21),
     /// and handles draws by dealing new hands.
     ///
+    /// Who may call this depends on the `SettlementConfig`: the players always can;
+    /// in keeper mode anyone else can too after `keeper_delay` ledgers, and earns
+    /// `keeper_reward_bps` of the matched pot.
+    ///
     /// # Arguments
     /// * `session_id` - The session ID of the game
+    /// * `caller` - Address settling the game (player or keeper)
     ///
     /// # Returns
-    /// * `Address` - Address of the winning player
-    pub fn reveal_winner(env: Env, session_id: u32) -> Result<Address, Error> {
+    /// * `Some(Address)` - Address of the winning player
+    /// * `None` - The hands tied and new hands were dealt for the next round
+    pub fn reveal_winner(
+        env: Env,
+        session_id: u32,
+        caller: Address,
+    ) -> Result<Option<Address>, Error> {
+        extend_instance_ttl(&env);
+
         // Get game from temporary storage
         let key = DataKey::Game(session_id);
         let mut game: Game = env
@@ -433,7 +995,7 @@ impl TwentyOneContract {
 
         // Check if game already ended (has a winner)
         if let Some(winner) = &game.winner {
-            return Ok(winner.clone());
+            return Ok(Some(winner.clone()));
         }
 
         // Check both players have stuck
@@ -441,6 +1003,9 @@ impl TwentyOneContract {
             return Err(Error::BothPlayersNotStuck);
         }
 
+        let settlement = settlement_config(&env);
+        let keeper = authorize_settlement(&env, &settlement, &game, &caller)?;
+
         // Calculate hand values
         let player1_value = calculate_hand_value(&game.player1_hand)?;
         let player2_value = calculate_hand_value(&game.player2_hand)?;
@@ -461,40 +1026,16 @@ impl TwentyOneContract {
             game.player1_stuck = false;
             game.player2_stuck = false;
 
-            // Clear hands
-            game.player1_hand = Bytes::new(&env);
-            game.player2_hand = Bytes::new(&env);
-
-            // Deal new hands (2 cards each)
-            let mut seed_bytes = Bytes::new(&env);
-            seed_bytes.append(&Bytes::from_array(&env, &session_id.to_be_bytes()));
-            seed_bytes.append(&game.player1.to_string().to_bytes());
-            seed_bytes.append(&game.player2.to_string().to_bytes());
-            seed_bytes.append(&Bytes::from_array(&env, &game.round.to_be_bytes()));
-            let base_seed = env.crypto().keccak256(&seed_bytes);
-
-            // Deal 2 cards to player1
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 1])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player1_hand.push_back(deal_card(&env, card_seed.into()));
-            }
-
-            // Deal 2 cards to player2
-            for i in 0..2 {
-                let mut card_seed_bytes = Bytes::new(&env);
-                card_seed_bytes.append(&Bytes::from(base_seed.clone()));
-                card_seed_bytes.append(&Bytes::from_array(&env, &[i, 2])); // [card_index, player]
-                let card_seed = env.crypto().keccak256(&card_seed_bytes);
-                game.player2_hand.push_back(deal_card(&env, card_seed.into()));
-            }
+            // Deal new hands (2 cards each) from the new round's deck
+            let seed = deal_seed(&env, session_id, &game.player1, &game.player2, game.round);
+            game.player1_hand = deal_hand(&env, &seed, 1);
+            game.player2_hand = deal_hand(&env, &seed, 2);
 
-            // Store updated game and return error to indicate draw
+            // Store the new round; an error here would roll the redeal back
             env.storage().temporary().set(&key, &game);
+            extend_game_ttl(&env, &key);
 
-            return Err(Error::Draw);
+            return Ok(None);
         };
 
         // Call GameHub FIRST (before setting winner)
@@ -504,8 +1045,37 @@ impl TwentyOneContract {
         // Only update game with winner AFTER GameHub succeeds
         game.winner = Some(winner.clone());
         env.storage().temporary().set(&key, &game);
+        extend_game_ttl(&env, &key);
+
+        // Pay the keeper out of the stake, if a third party settled
+        let reward = match &keeper {
+            Some(keeper) => {
+                let reward = keeper_reward(&settlement, &game);
+                credit_keeper(&env, session_id, keeper, reward);
+                reward
+            }
+            None => 0,
+        };
+        archive_match(&env, session_id, &game, player1_won, keeper, reward);
 
-        Ok(winner)
+        Ok(Some(winner))
+    }
+
+    /// Extend the TTL of a game and of the contract instance.
+    /// Anyone can call this to keep a long-running or idle game from being archived.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn bump(env: Env, session_id: u32) -> Result<(), Error> {
+        extend_instance_ttl(&env);
+
+        let key = DataKey::Game(session_id);
+        if !env.storage().temporary().has(&key) {
+            return Err(Error::GameNotFound);
+        }
+        extend_game_ttl(&env, &key);
+
+        Ok(())
     }
 
     /// Get game information.
@@ -548,6 +1118,174 @@ impl TwentyOneContract {
         }
     }
 
+    /// Get the archived result of a finished game.
+    /// Available after the temporary game entry has expired.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_match(env: Env, session_id: u32) -> Result<MatchResult, Error> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))
+            .ok_or(Error::MatchNotFound)
+    }
+
+    /// Get the winner of a finished game, for matchmakers settling stakes.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    ///
+    /// # Returns
+    /// * `Option<Address>` - The winner, or `None` until the game is archived
+    pub fn get_winner(env: Env, session_id: u32) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get::<_, MatchResult>(&DataKey::MatchResult(session_id))
+            .map(|result| result.winner)
+    }
+
+    /// Get how a finished game's stakes are split between the players and the
+    /// keepers that settled it, for matchmakers paying out escrowed stakes.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_settlement(env: Env, session_id: u32) -> Option<Settlement> {
+        let result: MatchResult = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MatchResult(session_id))?;
+        Some(Settlement {
+            winner: result.winner,
+            player1_payout: result.player1_payout,
+            player2_payout: result.player2_payout,
+            keeper_rewards: settlement::session_keepers(&env, session_id),
+        })
+    }
+
+    /// Get the rematch offered for an ended session, if one is pending.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the ended game
+    pub fn get_rematch(env: Env, session_id: u32) -> Option<RematchOffer> {
+        rematch::rematch_offer(&env, session_id)
+    }
+
+    /// Get the series a session belongs to, with its cumulative score.
+    /// Sessions that were never rematched have none.
+    ///
+    /// # Arguments
+    /// * `session_id` - Any session of the series
+    pub fn get_series(env: Env, session_id: u32) -> Option<Series> {
+        rematch::session_series(&env, session_id).map(|(_, series)| series)
+    }
+
+    /// Get the players, betting phase and winner of a session, for spectators
+    /// and side-bet pools.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_session_info(env: Env, session_id: u32) -> Result<SessionInfo, Error> {
+        let archived: Option<MatchResult> =
+            env.storage().persistent().get(&DataKey::MatchResult(session_id));
+        if let Some(result) = archived {
+            return Ok(SessionInfo {
+                player1: result.player1,
+                player2: result.player2,
+                awaiting_first_move: false,
+                predetermined: OUTCOME_PREDETERMINED,
+                winner: Some(result.winner),
+            });
+        }
+
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        Ok(SessionInfo {
+            predetermined: OUTCOME_PREDETERMINED,
+            awaiting_first_move: game.winner.is_none()
+                && game.round == 1
+                && !game.player1_stuck
+                && !game.player2_stuck
+                && game.player1_hand.len() == 2
+                && game.player2_hand.len() == 2,
+            player1: game.player1,
+            player2: game.player2,
+            winner: game.winner,
+        })
+    }
+
+    /// Get the public seed material a session's cards are derived from, so
+    /// anyone can recompute both hands with the `provably-fair` crate.
+    ///
+    /// # Arguments
+    /// * `session_id` - The session ID of the game
+    pub fn get_seed_material(env: Env, session_id: u32) -> Result<SeedMaterial, Error> {
+        let game: Game = env
+            .storage()
+            .temporary()
+            .get(&DataKey::Game(session_id))
+            .ok_or(Error::GameNotFound)?;
+        Ok(SeedMaterial {
+            session_id,
+            player1: game.player1.to_string(),
+            player2: game.player2.to_string(),
+            round: game.round,
+        })
+    }
+
+    /// Get the number of archived matches a player took part in.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    pub fn get_player_match_count(env: Env, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerMatchCount(player))
+            .unwrap_or(0)
+    }
+
+    /// Get a page of a player's archived matches, oldest first.
+    ///
+    /// # Arguments
+    /// * `player` - Address of the player
+    /// * `start` - Index of the first match to return
+    /// * `limit` - Maximum number of matches to return (capped at 50)
+    pub fn get_player_matches(
+        env: Env,
+        player: Address,
+        start: u32,
+        limit: u32,
+    ) -> Vec<MatchResult> {
+        let count = Self::get_player_match_count(env.clone(), player.clone());
+        let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE)).min(count);
+
+        let mut results = Vec::new(&env);
+        for index in start..end {
+            let session_id: Option<u32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PlayerMatch(player.clone(), index));
+            if let Some(result) = session_id.and_then(|id| {
+                env.storage()
+                    .persistent()
+                    .get::<_, MatchResult>(&DataKey::MatchResult(id))
+            }) {
+                results.push_back(result);
+            }
+        }
+        results
+    }
+
+    /// Get the total keeper rewards an address has earned settling games.
+    ///
+    /// # Arguments
+    /// * `keeper` - Address of the keeper
+    pub fn get_keeper_earnings(env: Env, keeper: Address) -> i128 {
+        settlement::keeper_earnings(&env, keeper)
+    }
+
     // ========================================================================
     // Internal Helper Functions
     // ========================================================================
@@ -559,7 +1297,7 @@ impl TwentyOneContract {
             .storage()
             .instance()
             .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set");
+            .ok_or(Error::GameHubNotSet)?;
 
         // Create GameHub client
         let game_hub = GameHubClient::new(env, &game_hub_addr);
@@ -580,69 +1318,178 @@ impl TwentyOneContract {
     ///
     /// # Returns
     /// * `Address` - The admin address
-    pub fn get_admin(env: Env) -> Address {
+    pub fn get_admin(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set")
+            .ok_or(Error::AdminNotSet)
     }
 
     /// Set a new admin address
     ///
     /// # Arguments
     /// * `new_admin` - The new admin address
-    pub fn set_admin(env: Env, new_admin: Address) {
+    pub fn set_admin(env: Env, new_admin: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.storage().instance().set(&DataKey::Admin, &new_admin);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Get the current GameHub contract address
     ///
     /// # Returns
     /// * `Address` - The GameHub contract address
-    pub fn get_hub(env: Env) -> Address {
+    pub fn get_hub(env: Env) -> Result<Address, Error> {
         env.storage()
             .instance()
             .get(&DataKey::GameHubAddress)
-            .expect("GameHub address not set")
+            .ok_or(Error::GameHubNotSet)
     }
 
     /// Set a new GameHub contract address
     ///
     /// # Arguments
     /// * `new_hub` - The new GameHub contract address
-    pub fn set_hub(env: Env, new_hub: Address) {
+    pub fn set_hub(env: Env, new_hub: Address) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.storage()
             .instance()
             .set(&DataKey::GameHubAddress, &new_hub);
+        extend_instance_ttl(&env);
+
+        Ok(())
+    }
+
+    /// Get the storage TTL thresholds
+    ///
+    /// # Returns
+    /// * `TtlConfig` - The configured thresholds (or the defaults)
+    pub fn get_ttl_config(env: Env) -> TtlConfig {
+        ttl_config(&env)
+    }
+
+    /// Set the storage TTL thresholds
+    ///
+    /// # Arguments
+    /// * `config` - New thresholds; each threshold must not exceed its extension target, and
+    ///   no target may exceed the network's maximum TTL
+    pub fn set_ttl_config(env: Env, config: TtlConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        ttl::set_ttl_config(&env, &config)?;
+
+        Ok(())
+    }
+
+    /// Get the settlement rules for `reveal_winner`
+    ///
+    /// # Returns
+    /// * `SettlementConfig` - The configured rules (or the participants-only default)
+    pub fn get_settlement_config(env: Env) -> SettlementConfig {
+        settlement_config(&env)
+    }
+
+    /// Set the settlement rules for `reveal_winner`
+    ///
+    /// # Arguments
+    /// * `config` - New rules; the keeper reward may not exceed 500 basis points
+    pub fn set_settlement_config(env: Env, config: SettlementConfig) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        settlement::set_settlement_config(&env, &config)?;
+
+        Ok(())
+    }
+
+    /// Get the stake limits new games are checked against
+    pub fn get_stake_limits(env: Env) -> StakeLimits {
+        stakes::stake_limits(&env, MAX_STAKE)
+    }
+
+    /// Set the stake limits for new games; running games keep their stakes
+    ///
+    /// # Arguments
+    /// * `limits` - New limits; the minimum must be at least 1 and at most the
+    ///   maximum, which may not exceed `i128::MAX / 2`
+    pub fn set_stake_limits(env: Env, limits: StakeLimits) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        stakes::set_stake_limits(&env, &limits, MAX_STAKE)?;
+
+        Ok(())
+    }
+
+    /// Check whether a contract may call `start_matched_game`
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Address of the contract
+    pub fn is_matchmaker(env: Env, matchmaker: Address) -> bool {
+        registry::is_matchmaker(&env, &matchmaker)
+    }
+
+    /// Register or remove a matchmaker contract
+    ///
+    /// # Arguments
+    /// * `matchmaker` - Address of the contract (a lobby, a tournament)
+    /// * `allowed` - Whether it may call `start_matched_game`
+    pub fn set_matchmaker(env: Env, matchmaker: Address, allowed: bool) -> Result<(), Error> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(Error::AdminNotSet)?;
+        admin.require_auth();
+
+        registry::set_matchmaker(&env, matchmaker, allowed);
+        extend_instance_ttl(&env);
+
+        Ok(())
     }
 
     /// Update the contract WASM hash (upgrade contract)
     ///
     /// # Arguments
     /// * `new_wasm_hash` - The hash of the new WASM binary
-    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) -> Result<(), Error> {
         let admin: Address = env
             .storage()
             .instance()
             .get(&DataKey::Admin)
-            .expect("Admin not set");
+            .ok_or(Error::AdminNotSet)?;
         admin.require_auth();
 
         env.deployer().update_current_contract_wasm(new_wasm_hash);
+
+        Ok(())
     }
 }
 
@@ -7,9 +7,15 @@
 // For full integration tests with the real GameHub contract, see:
 // contracts/game_hub/src/tests/twenty_one_integration.rs
 
-use crate::{Error, TwentyOneContract, TwentyOneContractClient};
+use crate::{
+    DataKey, Error, RematchOffer, Series, SessionKey, SettlementConfig, SettlementMode, StakeLimits,
+    TwentyOneContract, TwentyOneContractClient,
+};
+use provably_fair::{twenty_one, NativeKeccak, Session, MAX_STRKEY_LEN};
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env};
+use soroban_sdk::{
+    contract, contractimpl, xdr::ToXdr, Address, Bytes, BytesN, Env, Symbol, Vec,
+};
 
 // ============================================================================
 // Mock GameHub for Unit Testing
@@ -51,6 +57,20 @@ fn setup_test() -> (
     MockGameHubClient<'static>,
     Address,
     Address,
+) {
+    setup_test_with_min_ttl(u32::MAX / 2)
+}
+
+/// Same as `setup_test`, but new storage entries start with `min_ttl` ledgers
+/// so tests can observe TTL extension and expiry.
+fn setup_test_with_min_ttl(
+    min_ttl: u32,
+) -> (
+    Env,
+    TwentyOneContractClient<'static>,
+    MockGameHubClient<'static>,
+    Address,
+    Address,
 ) {
     let env = Env::default();
     env.mock_all_auths();
@@ -62,8 +82,8 @@ fn setup_test() -> (
         sequence_number: 100,
         network_id: Default::default(),
         base_reserve: 10,
-        min_temp_entry_ttl: u32::MAX / 2,
-        min_persistent_entry_ttl: u32::MAX / 2,
+        min_temp_entry_ttl: min_ttl,
+        min_persistent_entry_ttl: min_ttl,
         max_entry_ttl: u32::MAX / 2,
     });
 
@@ -156,6 +176,7 @@ fn calculate_hand_value_helper(hand: &Bytes) -> u32 {
 // ============================================================================
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_complete_game_simple() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
@@ -182,7 +203,7 @@ fn test_complete_game_simple() {
     client.stick(&session_id, &player2);
 
     // Reveal winner
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1).unwrap();
     assert!(winner == player1 || winner == player2);
 
     // Verify game is ended
@@ -192,6 +213,7 @@ fn test_complete_game_simple() {
 }
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_initial_cards_dealt() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
@@ -216,6 +238,7 @@ fn test_initial_cards_dealt() {
 }
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_get_hand_value() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
@@ -314,7 +337,7 @@ fn test_closer_to_21_wins() {
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
-    let winner = client.reveal_winner(&session_id);
+    let winner = client.reveal_winner(&session_id, &player1).unwrap();
 
     // Get final hand values
     let game = client.get_game(&session_id);
@@ -341,7 +364,7 @@ fn test_reveal_winner_requires_both_stuck() {
     client.stick(&session_id, &player1);
 
     // Try to reveal winner - should fail
-    let result = client.try_reveal_winner(&session_id);
+    let result = client.try_reveal_winner(&session_id, &player1);
     assert_twenty_one_error(&result, Error::BothPlayersNotStuck);
 }
 
@@ -350,6 +373,7 @@ fn test_reveal_winner_requires_both_stuck() {
 // ============================================================================
 
 #[test]
+#[allow(clippy::unnecessary_unwrap)]
 fn test_bust_detection() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
@@ -380,6 +404,7 @@ fn test_bust_detection() {
 }
 
 #[test]
+#[allow(clippy::unnecessary_unwrap)]
 fn test_cannot_hit_after_bust() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
@@ -412,35 +437,44 @@ fn test_cannot_hit_after_bust() {
 // ============================================================================
 
 #[test]
+#[allow(clippy::bool_assert_comparison)]
 fn test_draw_starts_new_round() {
     let (_env, client, _hub, player1, player2) = setup_test();
 
-    let session_id = 11u32;
-    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
-
-    // Both players stick immediately
+    // Deals are fixed by the session seed, so find a session whose opening
+    // hands tie and have both players stick on them
+    let session_id = (100u32..400)
+        .find(|&session_id| {
+            client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+            let game = client.get_game(&session_id);
+            calculate_hand_value_helper(&game.player1_hand)
+                == calculate_hand_value_helper(&game.player2_hand)
+        })
+        .expect("no tied deal found");
+    let tied = client.get_game(&session_id);
     client.stick(&session_id, &player1);
     client.stick(&session_id, &player2);
 
-    let game = client.get_game(&session_id);
-    let player1_value = calculate_hand_value_helper(&game.player1_hand);
-    let player2_value = calculate_hand_value_helper(&game.player2_hand);
-
-    // Only test draw behavior if hands are equal
-    if player1_value == player2_value {
-        // This should return Draw error
-        let result = client.try_reveal_winner(&session_id);
-        assert_twenty_one_error(&result, Error::Draw);
-
-        // Verify new round was created
-        let game_after = client.get_game(&session_id);
-        assert_eq!(game_after.round, 2); // Round should increment
-        assert_eq!(game_after.player1_stuck, false); // Flags reset
-        assert_eq!(game_after.player2_stuck, false);
-        assert_eq!(game_after.player1_hand.len(), 2); // New cards dealt
-        assert_eq!(game_after.player2_hand.len(), 2);
-    }
-    // If not a draw, test passes (no assertion needed)
+    // The tie is reported as a new round rather than an error, so the redeal sticks
+    let result = client.try_reveal_winner(&session_id, &player1);
+    assert_eq!(result, Ok(Ok(None)));
+
+    let game_after = client.get_game(&session_id);
+    assert_eq!(game_after.round, 2); // Round should increment
+    assert_eq!(game_after.player1_stuck, false); // Flags reset
+    assert_eq!(game_after.player2_stuck, false);
+    assert_eq!(game_after.player1_hand.len(), 2); // New cards dealt
+    assert_eq!(game_after.player2_hand.len(), 2);
+    assert_ne!(
+        (game_after.player1_hand, game_after.player2_hand),
+        (tied.player1_hand, tied.player2_hand)
+    );
+    assert_eq!(game_after.winner, None);
+
+    // The new round is played on as usual
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    assert_eq!(client.get_game(&session_id).round, 2);
 }
 
 // ============================================================================
@@ -521,7 +555,7 @@ fn test_cannot_stick_nonexistent_game() {
 fn test_cannot_reveal_nonexistent_game() {
     let (_env, client, _hub, _player1, _player2) = setup_test();
 
-    let result = client.try_reveal_winner(&999);
+    let result = client.try_reveal_winner(&999, &_player1);
     assert_twenty_one_error(&result, Error::GameNotFound);
 }
 
@@ -545,7 +579,7 @@ fn test_cannot_hit_after_game_ended() {
     client.stick(&session_id, &player2);
 
     // Reveal winner (may panic if draw, but that's okay)
-    let result = client.try_reveal_winner(&session_id);
+    let result = client.try_reveal_winner(&session_id, &player1);
     if result.is_ok() {
         // Game ended successfully
         // Try to hit after game ended
@@ -568,8 +602,8 @@ fn test_cannot_stick_after_game_ended() {
     client.stick(&session_id, &player2);
 
     // Reveal winner
-    let result = client.try_reveal_winner(&session_id);
-    if result.is_ok() {
+    let result = client.try_reveal_winner(&session_id, &player1);
+    if let Ok(Ok(Some(_))) = result {
         // Game ended, verify stuck flag is set
         let game = client.get_game(&session_id);
         assert!(game.player1_stuck);
@@ -591,13 +625,11 @@ fn test_reveal_winner_idempotent() {
     client.stick(&session_id, &player2);
 
     // First reveal
-    let result1 = client.try_reveal_winner(&session_id);
-    if result1.is_ok() {
-        let winner1 = result1.unwrap().unwrap();
-
+    let result1 = client.try_reveal_winner(&session_id, &player1);
+    if let Ok(Ok(Some(winner1))) = result1 {
         // Second reveal should return same winner (idempotent)
-        let winner2 = client.reveal_winner(&session_id);
-        assert_eq!(winner1, winner2);
+        let winner2 = client.reveal_winner(&session_id, &player1);
+        assert_eq!(Some(winner1), winner2);
     }
 }
 
@@ -680,11 +712,12 @@ fn test_asymmetric_points() {
     assert_eq!(game.player1_points, points1);
     assert_eq!(game.player2_points, points2);
 
-    client.stick(&session_id, &player1);
-    client.stick(&session_id, &player2);
-
-    // Try to reveal winner (may be draw)
-    let _ = client.try_reveal_winner(&session_id);
+    // Player 1 busts, so player 2 wins the matched 50 and player 1 keeps the
+    // unmatched 150 of their stake
+    assert_eq!(finish_game(&client, session_id, &player1, &player2), player2);
+    let result = client.get_match(&session_id);
+    assert_eq!(result.player1_payout, 150_0000000);
+    assert_eq!(result.player2_payout, 100_0000000);
 }
 
 // ============================================================================
@@ -692,6 +725,7 @@ fn test_asymmetric_points() {
 // ============================================================================
 
 #[test]
+#[allow(clippy::manual_range_contains)]
 fn test_face_cards_worth_10() {
     // This is a deterministic test of card value logic
     // We can't control what cards are dealt, but we can verify the hand value calculation
@@ -821,6 +855,27 @@ fn test_upgrade_function_exists() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_missing_admin_and_hub_are_typed_errors() {
+    let (env, client, _hub, player1, player2) = setup_test();
+
+    // Simulate an instance that lost its configured addresses
+    env.as_contract(&client.address, || {
+        env.storage().instance().remove(&DataKey::Admin);
+        env.storage().instance().remove(&DataKey::GameHubAddress);
+    });
+
+    assert_twenty_one_error(&client.try_get_admin(), Error::AdminNotSet);
+    assert_twenty_one_error(&client.try_set_admin(&player1), Error::AdminNotSet);
+    assert_twenty_one_error(&client.try_set_hub(&player1), Error::AdminNotSet);
+    let new_wasm_hash = BytesN::from_array(&env, &[0u8; 32]);
+    assert_twenty_one_error(&client.try_upgrade(&new_wasm_hash), Error::AdminNotSet);
+    assert_twenty_one_error(&client.try_get_hub(), Error::GameHubNotSet);
+    let points = 100_0000000;
+    let result = client.try_start_game(&90u32, &player1, &player2, &points, &points);
+    assert_twenty_one_error(&result, Error::GameHubNotSet);
+}
+
 // ============================================================================
 // Determinism Tests
 // ============================================================================
@@ -870,3 +925,549 @@ fn test_cannot_play_against_self() {
     let result = client.try_start_game(&session_id, &player1, &player1, &100_0000000, &100_0000000);
     assert_twenty_one_error(&result, Error::SelfPlay);
 }
+
+// ============================================================================
+// Storage TTL Tests
+// ============================================================================
+
+/// TTL a freshly created entry gets in the TTL tests
+const SHORT_TTL: u32 = 100;
+
+/// Advance the ledger sequence by `ledgers`
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+#[test]
+fn test_move_extends_game_ttl() {
+    let (env, client, _hub, player1, player2) = setup_test_with_min_ttl(SHORT_TTL);
+
+    let session_id = 100u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    // Move close to the end of the original 30-day TTL, then go past it
+    advance_ledgers(&env, 518_400 - 100);
+    client.stick(&session_id, &player1);
+    advance_ledgers(&env, 1_000);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1, player1);
+}
+
+// ============================================================================
+// Match History Tests
+// ============================================================================
+
+/// Play a game to completion and return the winner
+fn finish_game(
+    client: &TwentyOneContractClient,
+    session_id: u32,
+    player1: &Address,
+    _player2: &Address,
+) -> Address {
+    // Player 1 hits until busting, which ends the game
+    loop {
+        client.hit(&session_id, player1);
+        if let Some(winner) = client.get_game(&session_id).winner {
+            break winner;
+        }
+    }
+}
+
+#[test]
+fn test_match_archived_after_game_end() {
+    let (env, client, _hub, player1, player2) = setup_test_with_min_ttl(SHORT_TTL);
+
+    let session_id = 200u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &50_0000000);
+
+    let result = client.try_get_match(&session_id);
+    assert_twenty_one_error(&result, Error::MatchNotFound);
+
+    let winner = finish_game(&client, session_id, &player1, &player2);
+    let game = client.get_game(&session_id);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.session_id, session_id);
+    assert_eq!(archived.player1, player1);
+    assert_eq!(archived.player2, player2);
+    assert_eq!(archived.player1_points, 100_0000000);
+    assert_eq!(archived.player2_points, 50_0000000);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.player1_won, winner == player1);
+    assert_eq!(archived.end_ledger, env.ledger().sequence());
+    let expected_hash: BytesN<32> = env.crypto().keccak256(&game.to_xdr(&env)).into();
+    assert_eq!(archived.state_hash, expected_hash);
+
+    // The archive outlives the temporary game entry
+    advance_ledgers(&env, 518_400 + 1);
+    let result = client.try_get_game(&session_id);
+    assert_twenty_one_error(&result, Error::GameNotFound);
+    assert_eq!(client.get_match(&session_id), archived);
+}
+
+#[test]
+fn test_player_match_history_pagination() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let player3 = Address::generate(&env);
+
+    for (session_id, opponent) in [(201u32, &player2), (202, &player3), (203, &player2)] {
+        client.start_game(&session_id, &player1, opponent, &100_0000000, &100_0000000);
+        finish_game(&client, session_id, &player1, opponent);
+    }
+
+    assert_eq!(client.get_player_match_count(&player1), 3);
+    assert_eq!(client.get_player_match_count(&player2), 2);
+    assert_eq!(client.get_player_match_count(&player3), 1);
+
+    let page = client.get_player_matches(&player1, &0, &2);
+    assert_eq!(page.len(), 2);
+    assert_eq!(page.get(0).unwrap().session_id, 201);
+    assert_eq!(page.get(1).unwrap().session_id, 202);
+
+    let page = client.get_player_matches(&player1, &2, &2);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().session_id, 203);
+
+    let page = client.get_player_matches(&player2, &5, &10);
+    assert_eq!(page.len(), 0);
+}
+
+// ============================================================================
+// Settlement Tests
+// ============================================================================
+
+#[test]
+fn test_participants_only_settlement() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    assert_eq!(client.get_settlement_config().mode, SettlementMode::Participants);
+
+    let session_id = 300u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+
+    // Outsiders cannot settle (or force a redeal on a draw), however long they wait
+    let stranger = Address::generate(&env);
+    advance_ledgers(&env, 10_000);
+    let result = client.try_reveal_winner(&session_id, &stranger);
+    assert_twenty_one_error(&result, Error::NotPlayer);
+
+    let winner = client.reveal_winner(&session_id, &player2).unwrap();
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.keeper, None);
+    assert_eq!(archived.keeper_reward, 0);
+}
+
+#[test]
+fn test_keeper_settlement_earns_reward() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let keeper = Address::generate(&env);
+
+    // Nobody escrows a game started straight through the hub, so nobody
+    // could pay its keeper
+    let direct_id = 300u32;
+    client.start_game(&direct_id, &player1, &player2, &100_0000000, &50_0000000);
+    client.stick(&direct_id, &player1);
+    client.stick(&direct_id, &player2);
+    advance_ledgers(&env, 10);
+    let result = client.try_reveal_winner(&direct_id, &keeper);
+    assert_twenty_one_error(&result, Error::NotPlayer);
+
+    let session_id = 301u32;
+    client.start_matched_game(&lobby, &session_id, &player1, &player2, &100_0000000, &50_0000000);
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+
+    // The players get a head start after the last stick
+    advance_ledgers(&env, 9);
+    let result = client.try_reveal_winner(&session_id, &keeper);
+    assert_twenty_one_error(&result, Error::SettlementTooEarly);
+
+    advance_ledgers(&env, 1);
+    let winner = client.reveal_winner(&session_id, &keeper).unwrap();
+
+    // 1% of the matched 100 point pot, taken out of the winnings
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.winner, winner);
+    assert_eq!(archived.keeper, Some(keeper.clone()));
+    assert_eq!(archived.keeper_reward, 1_0000000);
+    let payouts = (archived.player1_payout, archived.player2_payout);
+    if archived.player1_won {
+        assert_eq!(payouts, (149_0000000, 0));
+    } else {
+        assert_eq!(payouts, (50_0000000, 99_0000000));
+    }
+    assert_eq!(client.get_keeper_earnings(&keeper), 1_0000000);
+
+    // Settling again is a no-op and pays nothing more
+    assert_eq!(client.reveal_winner(&session_id, &keeper), Some(winner));
+    assert_eq!(client.get_keeper_earnings(&keeper), 1_0000000);
+}
+
+#[test]
+fn test_bust_pays_no_keeper() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    client.set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 100,
+        keeper_delay: 10,
+    });
+
+    // A bust ends the game inside the player's own move
+    let session_id = 302u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    finish_game(&client, session_id, &player1, &player2);
+
+    let archived = client.get_match(&session_id);
+    assert_eq!(archived.keeper, None);
+    assert_eq!(archived.keeper_reward, 0);
+}
+
+#[test]
+fn test_set_settlement_config_rejects_large_reward() {
+    let (_env, client, _hub, _player1, _player2) = setup_test();
+
+    let result = client.try_set_settlement_config(&SettlementConfig {
+        mode: SettlementMode::Keeper,
+        keeper_reward_bps: 501,
+        keeper_delay: 0,
+    });
+    assert_twenty_one_error(&result, Error::InvalidSettlementConfig);
+}
+
+// ============================================================================
+// Matchmaking Tests
+// ============================================================================
+
+#[test]
+fn test_matched_game_starts_without_player_auth() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let lobby = Address::generate(&env);
+
+    assert!(!client.is_matchmaker(&lobby));
+    client.set_matchmaker(&lobby, &true);
+    assert!(client.is_matchmaker(&lobby));
+
+    let session_id = 100u32;
+    let points = 100_0000000;
+    client.start_matched_game(&lobby, &session_id, &player1, &player2, &points, &points);
+    // Only the matchmaker signs a matched start
+    let auths = env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, lobby);
+
+    let game = client.get_game(&session_id);
+    assert_eq!(game.player1_hand.len(), 2);
+    assert_eq!(client.get_winner(&session_id), None);
+
+    client.stick(&session_id, &player1);
+    client.stick(&session_id, &player2);
+    let winner = client.reveal_winner(&session_id, &player1).unwrap();
+    assert_eq!(client.get_winner(&session_id), Some(winner));
+}
+
+#[test]
+fn test_unregistered_matchmaker_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let lobby = Address::generate(&env);
+    let points = 100_0000000;
+
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player2, &points, &points);
+    assert_twenty_one_error(&result, Error::NotMatchmaker);
+
+    // Removing a matchmaker revokes it
+    client.set_matchmaker(&lobby, &true);
+    client.set_matchmaker(&lobby, &false);
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player2, &points, &points);
+    assert_twenty_one_error(&result, Error::NotMatchmaker);
+
+    client.set_matchmaker(&lobby, &true);
+    let result =
+        client.try_start_matched_game(&lobby, &101u32, &player1, &player1, &points, &points);
+    assert_twenty_one_error(&result, Error::SelfPlay);
+}
+
+// ============================================================================
+// Session Info Tests
+// ============================================================================
+
+#[test]
+fn test_session_info_tracks_betting_phase() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let session_id = 400u32;
+    let result = client.try_get_session_info(&session_id);
+    assert_twenty_one_error(&result, Error::GameNotFound);
+
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let info = client.get_session_info(&session_id);
+    assert_eq!(info.player1, player1);
+    assert_eq!(info.player2, player2);
+    assert!(info.awaiting_first_move);
+    assert_eq!(info.winner, None);
+
+    // The first move closes the betting phase
+    client.stick(&session_id, &player1);
+    assert!(!client.get_session_info(&session_id).awaiting_first_move);
+
+    client.stick(&session_id, &player2);
+    let winner = client.reveal_winner(&session_id, &player1).unwrap();
+    let info = client.get_session_info(&session_id);
+    assert!(!info.awaiting_first_move);
+    assert_eq!(info.winner, Some(winner));
+}
+
+// ============================================================================
+// Provable Fairness Tests
+// ============================================================================
+
+/// Assert both hands equal the cards recomputed natively from the session's
+/// public seed material
+fn assert_hands_match_native(client: &TwentyOneContractClient<'static>, session_id: u32) {
+    let game = client.get_game(&session_id);
+    let material = client.get_seed_material(&session_id);
+    let player1 = material.player1.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let player2 = material.player2.to_bytes().to_buffer::<MAX_STRKEY_LEN>();
+    let session = Session {
+        session_id: material.session_id,
+        player1: player1.as_slice(),
+        player2: player2.as_slice(),
+    };
+    let seed = twenty_one::deal_seed(&NativeKeccak, &session, material.round);
+    for (seat, hand) in [(1, &game.player1_hand), (2, &game.player2_hand)] {
+        let native = twenty_one::hand(&NativeKeccak, &seed, seat, hand.len() as u8);
+        assert!(hand.iter().eq(native));
+    }
+}
+
+#[test]
+fn test_hands_match_native_recomputation() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let points = 100_0000000;
+
+    for session_id in 500u32..540 {
+        client.start_game(&session_id, &player1, &player2, &points, &points);
+        let material = client.get_seed_material(&session_id);
+        assert_eq!(material.session_id, session_id);
+        assert_eq!(material.player1, player1.to_string());
+        assert_eq!(material.player2, player2.to_string());
+        assert_eq!(client.get_game(&session_id).player1_hand.len(), 2);
+        assert_hands_match_native(&client, session_id);
+
+        // Both players hit to 17 (unless someone busts), then stick
+        for player in [&player1, &player2] {
+            while client.get_game(&session_id).winner.is_none()
+                && client.get_hand_value(&session_id, player) < 17
+            {
+                client.hit(&session_id, player);
+                assert_hands_match_native(&client, session_id);
+            }
+        }
+        if client.get_game(&session_id).winner.is_none() {
+            client.stick(&session_id, &player1);
+            client.stick(&session_id, &player2);
+            let _ = client.try_reveal_winner(&session_id, &player1);
+        }
+        assert_hands_match_native(&client, session_id);
+    }
+}
+
+#[test]
+fn test_seed_material_requires_game() {
+    let (_env, client, _hub, _player1, _player2) = setup_test();
+    assert_twenty_one_error(&client.try_get_seed_material(&560u32), Error::GameNotFound);
+}
+
+// ============================================================================
+// Session Key Tests
+// ============================================================================
+
+#[test]
+fn test_session_key_signs_moves() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 600u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let key = Address::generate(&env);
+    client.register_session_key(&session_id, &player1, &key, &Vec::new(&env), &None);
+    assert_eq!(
+        client.get_session_key(&session_id, &player1),
+        Some(SessionKey {
+            player: player1.clone(),
+            key: key.clone(),
+            functions: Vec::new(&env),
+            expires_at: None,
+        })
+    );
+
+    // The key signs in place of the player, and the card is dealt to them
+    client.hit(&session_id, &key);
+    assert_eq!(env.auths()[0].0, key);
+    assert_eq!(client.get_game(&session_id).player1_hand.len(), 3);
+    assert_eq!(client.get_game(&session_id).player2_hand.len(), 2);
+}
+
+#[test]
+fn test_session_key_scope_and_expiry() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 610u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+
+    let key = Address::generate(&env);
+    let scope = Vec::from_array(&env, [Symbol::new(&env, "stick")]);
+    let expires_at = Some(env.ledger().sequence() + 10);
+    client.register_session_key(&session_id, &player2, &key, &scope, &expires_at);
+    assert_twenty_one_error(&client.try_hit(&session_id, &key), Error::SessionKeyNotAllowed);
+
+    advance_ledgers(&env, 10);
+    assert_twenty_one_error(&client.try_stick(&session_id, &key), Error::SessionKeyExpired);
+    assert!(!client.get_game(&session_id).player2_stuck);
+}
+
+#[test]
+fn test_revoked_session_key_is_refused() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 620u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let none = Vec::new(&env);
+
+    let old_key = Address::generate(&env);
+    let key = Address::generate(&env);
+    client.register_session_key(&session_id, &player1, &old_key, &none, &None);
+    client.register_session_key(&session_id, &player1, &key, &none, &None);
+    assert_twenty_one_error(&client.try_stick(&session_id, &old_key), Error::NotPlayer);
+
+    client.revoke_session_key(&session_id, &player1);
+    assert_twenty_one_error(&client.try_stick(&session_id, &key), Error::NotPlayer);
+    assert_twenty_one_error(
+        &client.try_revoke_session_key(&session_id, &player1),
+        Error::SessionKeyNotFound,
+    );
+    client.stick(&session_id, &player1);
+}
+
+#[test]
+fn test_invalid_session_keys_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    let session_id = 630u32;
+    client.start_game(&session_id, &player1, &player2, &100_0000000, &100_0000000);
+    let none = Vec::new(&env);
+    let now = Some(env.ledger().sequence());
+
+    let key = Address::generate(&env);
+    let outsider = Address::generate(&env);
+    let result = client.try_register_session_key(&session_id, &outsider, &key, &none, &None);
+    assert_twenty_one_error(&result, Error::NotPlayer);
+    let result = client.try_register_session_key(&session_id, &player1, &player2, &none, &None);
+    assert_twenty_one_error(&result, Error::InvalidSessionKey);
+    let result = client.try_register_session_key(&session_id, &player1, &key, &none, &now);
+    assert_twenty_one_error(&result, Error::InvalidSessionKey);
+    let scope = Vec::from_array(&env, [Symbol::new(&env, "reveal_winner")]);
+    let result = client.try_register_session_key(&session_id, &player1, &key, &scope, &None);
+    assert_twenty_one_error(&result, Error::InvalidSessionKey);
+
+    // A key signs for one player only
+    client.register_session_key(&session_id, &player1, &key, &none, &None);
+    let result = client.try_register_session_key(&session_id, &player2, &key, &none, &None);
+    assert_twenty_one_error(&result, Error::InvalidSessionKey);
+}
+
+// ============================================================================
+// Rematch Tests
+// ============================================================================
+
+#[test]
+fn test_rematch_series() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    client.start_game(&700, &player1, &player2, &100_0000000, &50_0000000);
+    assert_twenty_one_error(
+        &client.try_propose_rematch(&700, &player1, &701, &false),
+        Error::GameNotEnded,
+    );
+
+    // Player 1 busts, so player 2 wins the first game of the series
+    assert_eq!(finish_game(&client, 700, &player1, &player2), player2);
+    client.propose_rematch(&700, &player1, &701, &true);
+    assert_eq!(
+        client.get_rematch(&700),
+        Some(RematchOffer { proposer: player1.clone(), new_session_id: 701, double_stakes: true })
+    );
+    assert_twenty_one_error(&client.try_accept_rematch(&700, &player1), Error::RematchNotFound);
+    assert_eq!(client.accept_rematch(&700, &player2), 701);
+
+    // The rematch is dealt fresh hands at doubled stakes
+    let rematch = client.get_game(&701);
+    assert_eq!((rematch.player1_points, rematch.player2_points), (200_0000000, 100_0000000));
+    assert_eq!(rematch.player1_hand.len(), 2);
+    assert_eq!(rematch.winner, None);
+
+    assert_eq!(finish_game(&client, 701, &player1, &player2), player2);
+    let series = Series {
+        player1: player1.clone(),
+        player2: player2.clone(),
+        sessions: Vec::from_array(&env, [700, 701]),
+        player1_wins: 0,
+        player2_wins: 2,
+    };
+    assert_eq!(client.get_series(&700), Some(series.clone()));
+    assert_eq!(client.get_series(&701), Some(series));
+    assert_twenty_one_error(
+        &client.try_propose_rematch(&700, &player2, &702, &false),
+        Error::RematchAlreadyPlayed,
+    );
+    assert_twenty_one_error(
+        &client.try_propose_rematch(&701, &player2, &700, &false),
+        Error::SessionIdTaken,
+    );
+}
+
+// ============================================================================
+// Stake Tests
+// ============================================================================
+
+#[test]
+fn test_non_positive_stakes_rejected() {
+    let (env, client, _hub, player1, player2) = setup_test();
+    for (points1, points2) in [(0, 10), (10, 0), (-5, 10), (10, -1)] {
+        let result =
+            client.try_start_game(&800, &player1, &player2, &points1, &points2);
+        assert_twenty_one_error(&result, Error::InvalidStake);
+    }
+
+    let lobby = Address::generate(&env);
+    client.set_matchmaker(&lobby, &true);
+    let result = client.try_start_matched_game(&lobby, &800, &player1, &player2, &0, &10);
+    assert_twenty_one_error(&result, Error::InvalidStake);
+}
+
+#[test]
+fn test_stake_limits() {
+    let (_env, client, _hub, player1, player2) = setup_test();
+    let default_limits = StakeLimits { min_stake: 1, max_stake: i128::MAX / 2 };
+    assert_eq!(client.get_stake_limits(), default_limits);
+
+    let limits = StakeLimits { min_stake: 10, max_stake: 1_000 };
+    client.set_stake_limits(&limits);
+    assert_eq!(client.get_stake_limits(), limits);
+    for (points1, points2) in [(9, 100), (100, 1_001)] {
+        let result =
+            client.try_start_game(&810, &player1, &player2, &points1, &points2);
+        assert_twenty_one_error(&result, Error::StakeOutOfRange);
+    }
+    client.start_game(&810, &player1, &player2, &10, &1_000);
+
+    for (min_stake, max_stake) in [(0, 100), (101, 100), (1, i128::MAX / 2 + 1)] {
+        let result = client.try_set_stake_limits(&StakeLimits { min_stake, max_stake });
+        assert_twenty_one_error(&result, Error::InvalidStakeLimits);
+    }
+}
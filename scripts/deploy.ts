@@ -47,10 +47,15 @@ function usage() {
   console.log(`
 Usage: bun run deploy [contract-name...]
 
+Games are constructed with the admin and the Game Hub. The lobby, tournament and
+side-bets contracts are constructed with the admin and the token they escrow stakes
+in: TOKEN_CONTRACT_ID from the environment or .env, else the native XLM contract.
+
 Examples:
   bun run deploy
   bun run deploy number-guess
   bun run deploy twenty-one number-guess
+  TOKEN_CONTRACT_ID=C... bun run deploy lobby
 `);
 }
 
@@ -62,6 +67,9 @@ const RPC_URL = 'https://soroban-testnet.stellar.org';
 const NETWORK_PASSPHRASE = 'Test SDF Network ; September 2015';
 const EXISTING_GAME_HUB_TESTNET_CONTRACT_ID = 'CB4VZAT2U3UC6XFK3N23SKRF2NDCMP3QHJYMCHHFMZO7MRQO6DQ2EMYG';
 
+// Contracts constructed with `--admin --token` rather than `--admin --game-hub`
+const TOKEN_CONTRACTS = new Set(['lobby', 'tournament', 'side-bets']);
+
 async function testnetAccountExists(address: string): Promise<boolean> {
   const res = await fetch(`https://horizon-testnet.stellar.org/accounts/${address}`, { method: 'GET' });
   if (res.status === 404) return false;
@@ -130,7 +138,8 @@ if (!mock) {
   process.exit(1);
 }
 
-const needsMock = contracts.some((c) => !c.isMockHub);
+const needsMock = contracts.some((c) => !c.isMockHub && !TOKEN_CONTRACTS.has(c.packageName));
+const needsToken = contracts.some((c) => TOKEN_CONTRACTS.has(c.packageName));
 const deployMockRequested = contracts.some((c) => c.isMockHub);
 const shouldEnsureMock = deployMockRequested || needsMock;
 
@@ -288,6 +297,26 @@ if (shouldEnsureMock) {
   }
 }
 
+// Token the lobby, tournament and side-bets contracts escrow stakes in
+let tokenId = process.env.TOKEN_CONTRACT_ID || getEnvValue(existingEnv, 'TOKEN_CONTRACT_ID') || "";
+if (needsToken && !tokenId) {
+  try {
+    tokenId = (await $`stellar contract id asset --asset native --network ${NETWORK}`.text()).trim();
+    console.log(`✅ Using native XLM token contract: ${tokenId}\n`);
+  } catch (error) {
+    console.error("❌ Failed to resolve the native XLM token contract:", error);
+    console.error("Set TOKEN_CONTRACT_ID to the token the escrowing contracts should use.");
+    process.exit(1);
+  }
+}
+
+function constructorArgs(contract: { packageName: string }): string[] {
+  if (TOKEN_CONTRACTS.has(contract.packageName)) {
+    return ["--admin", adminAddress, "--token", tokenId];
+  }
+  return ["--admin", adminAddress, "--game-hub", mockGameHubId];
+}
+
 for (const contract of contracts) {
   if (contract.isMockHub) continue;
 
@@ -301,7 +330,7 @@ for (const contract of contracts) {
 
     console.log("  Deploying and initializing...");
     const deployResult =
-      await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- --admin ${adminAddress} --game-hub ${mockGameHubId}`.text();
+      await $`stellar contract deploy --wasm-hash ${wasmHash} --source-account ${adminSecret} --network ${NETWORK} -- ${constructorArgs(contract)}`.text();
     const contractId = deployResult.trim();
     deployed[contract.packageName] = contractId;
     console.log(`✅ ${contract.packageName} deployed: ${contractId}\n`);
@@ -332,6 +361,7 @@ const deploymentContracts = allContracts.reduce<Record<string, string>>((acc, co
 
 const deploymentInfo = {
   mockGameHubId,
+  tokenId,
   twentyOneId,
   numberGuessId,
   contracts: deploymentContracts,
@@ -361,6 +391,9 @@ VITE_SOROBAN_RPC_URL=${RPC_URL}
 VITE_NETWORK_PASSPHRASE=${NETWORK_PASSPHRASE}
 ${contractEnvLines}
 
+# Token the lobby, tournament and side-bets contracts escrow stakes in
+TOKEN_CONTRACT_ID=${tokenId}
+
 # Dev wallet addresses for testing
 VITE_DEV_ADMIN_ADDRESS=${walletAddresses.admin}
 VITE_DEV_PLAYER1_ADDRESS=${walletAddresses.player1}
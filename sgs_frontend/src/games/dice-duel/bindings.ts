@@ -41,24 +41,61 @@ export const networks = {
 
 export interface Game {
   player1: string;
-  player1_die1: Option<u32>;
-  player1_die2: Option<u32>;
+  player1_banked: u32;
+  player1_dice: Array<u32>;
   player1_points: i128;
+  player1_roll_hash: Option<Buffer>;
+  player1_roll_secret: Option<Buffer>;
   player1_rolled: boolean;
+  player1_turn: boolean;
   player2: string;
-  player2_die1: Option<u32>;
-  player2_die2: Option<u32>;
+  player2_banked: u32;
+  player2_dice: Array<u32>;
   player2_points: i128;
+  player2_roll_hash: Option<Buffer>;
+  player2_roll_secret: Option<Buffer>;
   player2_rolled: boolean;
+  reveal_deadline: Option<u32>;
+  rolls: u32;
+  rules: DiceRules;
+  turn_total: u32;
   winner: Option<string>;
 }
 
+export enum Scoring {
+  Sum = 0,
+  Highest = 1,
+  PokerDice = 2,
+  DoublesBonus = 3,
+  Pig = 4,
+}
+
+export interface DiceRules {
+  dice: u32;
+  scoring: Scoring;
+  sides: u32;
+  target: u32;
+}
+
 export const Errors = {
   1: {message:"GameNotFound"},
   2: {message:"NotPlayer"},
   3: {message:"AlreadyRolled"},
   4: {message:"BothPlayersNotRolled"},
-  5: {message:"GameAlreadyEnded"}
+  5: {message:"GameAlreadyEnded"},
+  6: {message:"InvalidTtlConfig"},
+  7: {message:"MatchNotFound"},
+  8: {message:"SettlementTooEarly"},
+  9: {message:"InvalidSettlementConfig"},
+  10: {message:"InvalidBestOf"},
+  11: {message:"InvalidRules"},
+  12: {message:"NotYourTurn"},
+  13: {message:"NothingToBank"},
+  14: {message:"WrongGameMode"},
+  15: {message:"SelfPlay"},
+  16: {message:"GameHubNotSet"},
+  17: {message:"AdminNotSet"},
+  18: {message:"NotMatchmaker"}
 }
 
 export type DataKey = {tag: "Game", values: readonly [u32]} | {tag: "GameHubAddress", values: void} | {tag: "Admin", values: void};
@@ -66,8 +103,8 @@ export type DataKey = {tag: "Game", values: readonly [u32]} | {tag: "GameHubAddr
 export interface Client {
   /**
    * Construct and simulate a roll transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * Commit a roll for the current game.
-   * Both players must roll before the winner can be revealed.
+   * Commit a roll for the current round.
+   * Both players must roll before the round can be revealed.
    * 
    * # Arguments
    * * `session_id` - The session ID of the game
@@ -146,22 +183,32 @@ export interface Client {
    * * `player2` - Address of second player
    * * `player1_points` - Points amount committed by player 1
    * * `player2_points` - Points amount committed by player 2
+   * * `best_of` - Odd number of rounds in the match, up to 15 (single roll if `None`)
+   * * `rules` - Dice count, sides and scoring (two six-sided dice, highest total if `None`)
    */
-  start_game: ({session_id, player1, player2, player1_points, player2_points}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+  start_game: ({session_id, player1, player2, player1_points, player2_points, best_of, rules}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128, best_of: Option<u32>, rules: Option<DiceRules>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
 
   /**
    * Construct and simulate a reveal_winner transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * Reveal the winner of the game and submit outcome to GameHub.
+   * Reveal the current round and, once a player has won the match, submit the
+   * outcome to GameHub.
    * Can only be called after both players have rolled.
-   * This generates dice rolls for both players, determines the winner, and ends the session.
+   * This generates dice rolls for both players and scores the round. If neither
+   * player has a majority of `best_of` yet, the rolls are reset for the next round;
+   * otherwise the session ends.
+   * 
+   * Who may call this depends on the `SettlementConfig`: the players always can;
+   * in keeper mode anyone else can too after `keeper_delay` ledgers, and earns
+   * `keeper_reward_bps` of the matched pot, spread over the match's rounds.
    * 
    * # Arguments
    * * `session_id` - The session ID of the game
+   * * `caller` - Address settling the round (player or keeper)
    * 
    * # Returns
-   * * `Address` - Address of the winning player
+   * * `Address` - Address of the round's winner; the match winner once `winner` is set
    */
-  reveal_winner: ({session_id}: {session_id: u32}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+  reveal_winner: ({session_id, caller}: {session_id: u32, caller: string}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
 
 }
 export class Client extends ContractClient {
@@ -186,16 +233,16 @@ export class Client extends ContractClient {
       new ContractSpec([ "AAAAAQAAAAAAAAAAAAAABEdhbWUAAAALAAAAAAAAAAdwbGF5ZXIxAAAAABMAAAAAAAAADHBsYXllcjFfZGllMQAAA+gAAAAEAAAAAAAAAAxwbGF5ZXIxX2RpZTIAAAPoAAAABAAAAAAAAAAOcGxheWVyMV9wb2ludHMAAAAAAAsAAAAAAAAADnBsYXllcjFfcm9sbGVkAAAAAAABAAAAAAAAAAdwbGF5ZXIyAAAAABMAAAAAAAAADHBsYXllcjJfZGllMQAAA+gAAAAEAAAAAAAAAAxwbGF5ZXIyX2RpZTIAAAPoAAAABAAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAAAAAAADnBsYXllcjJfcm9sbGVkAAAAAAABAAAAAAAAAAZ3aW5uZXIAAAAAA+gAAAAT",
         "AAAABAAAAAAAAAAAAAAABUVycm9yAAAAAAAABQAAAAAAAAAMR2FtZU5vdEZvdW5kAAAAAQAAAAAAAAAJTm90UGxheWVyAAAAAAAAAgAAAAAAAAANQWxyZWFkeVJvbGxlZAAAAAAAAAMAAAAAAAAAFEJvdGhQbGF5ZXJzTm90Um9sbGVkAAAABAAAAAAAAAAQR2FtZUFscmVhZHlFbmRlZAAAAAU=",
         "AAAAAgAAAAAAAAAAAAAAB0RhdGFLZXkAAAAAAwAAAAEAAAAAAAAABEdhbWUAAAABAAAABAAAAAAAAAAAAAAADkdhbWVIdWJBZGRyZXNzAAAAAAAAAAAAAAAAAAVBZG1pbgAAAA==",
-        "AAAAAAAAAMpDb21taXQgYSByb2xsIGZvciB0aGUgY3VycmVudCBnYW1lLgpCb3RoIHBsYXllcnMgbXVzdCByb2xsIGJlZm9yZSB0aGUgd2lubmVyIGNhbiBiZSByZXZlYWxlZC4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKKiBgcGxheWVyYCAtIEFkZHJlc3Mgb2YgdGhlIHBsYXllciByb2xsaW5nIHRoZSBkaWNlAAAAAAAEcm9sbAAAAAIAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAEAAAPpAAAD7QAAAAAAAAAD",
+        "AAAAAAAAAMpDb21taXQgYSByb2xsIGZvciB0aGUgY3VycmVudCByb3VuZC4KQm90aCBwbGF5ZXJzIG11c3Qgcm9sbCBiZWZvcmUgdGhlIHJvdW5kIGNhbiBiZSByZXZlYWxlZC4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKKiBgcGxheWVyYCAtIEFkZHJlc3Mgb2YgdGhlIHBsYXllciByb2xsaW5nIHRoZSBkaWNlAAAAAAAEcm9sbAAAAAIAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEw==",
         "AAAAAAAAAF5HZXQgdGhlIGN1cnJlbnQgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzCgojIFJldHVybnMKKiBgQWRkcmVzc2AgLSBUaGUgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzAAAAAAAHZ2V0X2h1YgAAAAAAAAAAAQAAABM=",
         "AAAAAAAAAF5TZXQgYSBuZXcgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzCgojIEFyZ3VtZW50cwoqIGBuZXdfaHViYCAtIFRoZSBuZXcgR2FtZUh1YiBjb250cmFjdCBhZGRyZXNzAAAAAAAHc2V0X2h1YgAAAAABAAAAAAAAAAduZXdfaHViAAAAABMAAAAA",
         "AAAAAAAAAHFVcGRhdGUgdGhlIGNvbnRyYWN0IFdBU00gaGFzaCAodXBncmFkZSBjb250cmFjdCkKCiMgQXJndW1lbnRzCiogYG5ld193YXNtX2hhc2hgIC0gVGhlIGhhc2ggb2YgdGhlIG5ldyBXQVNNIGJpbmFyeQAAAAAAAAd1cGdyYWRlAAAAAAEAAAAAAAAADW5ld193YXNtX2hhc2gAAAAAAAPuAAAAIAAAAAA=",
         "AAAAAAAAAJNHZXQgZ2FtZSBpbmZvcm1hdGlvbi4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKCiMgUmV0dXJucwoqIGBHYW1lYCAtIFRoZSBnYW1lIHN0YXRlIChpbmNsdWRlcyBkaWNlIGFmdGVyIGdhbWUgZW5kcykAAAAACGdldF9nYW1lAAAAAQAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAEAAAPpAAAH0AAAAARHYW1lAAAAAw==",
         "AAAAAAAAAEhHZXQgdGhlIGN1cnJlbnQgYWRtaW4gYWRkcmVzcwoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gVGhlIGFkbWluIGFkZHJlc3MAAAAJZ2V0X2FkbWluAAAAAAAAAAAAAAEAAAAT",
         "AAAAAAAAAEpTZXQgYSBuZXcgYWRtaW4gYWRkcmVzcwoKIyBBcmd1bWVudHMKKiBgbmV3X2FkbWluYCAtIFRoZSBuZXcgYWRtaW4gYWRkcmVzcwAAAAAACXNldF9hZG1pbgAAAAAAAAEAAAAAAAAACW5ld19hZG1pbgAAAAAAABMAAAAA",
-        "AAAAAAAAAipTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgAAAAAACnN0YXJ0X2dhbWUAAAAAAAUAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAABAAAD6QAAA+0AAAAAAAAAAw==",
+        "AAAAAAAAAtRTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgoqIGBiZXN0X29mYCAtIE9kZCBudW1iZXIgb2Ygcm91bmRzIGluIHRoZSBtYXRjaCwgdXAgdG8gMTUgKHNpbmdsZSByb2xsIGlmIGBOb25lYCkKKiBgcnVsZXNgIC0gRGljZSBjb3VudCwgc2lkZXMgYW5kIHNjb3JpbmcgKHR3byBzaXgtc2lkZWQgZGljZSwgaGlnaGVzdCB0b3RhbCBpZiBgTm9uZWApAAAACnN0YXJ0X2dhbWUAAAAAAAcAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAAAAAAAB2Jlc3Rfb2YAAAAD6AAAAAQAAAAAAAAABXJ1bGVzAAAAAAAD6AAAB9AAAAAJRGljZVJ1bGVzAAAA",
         "AAAAAAAAAKNJbml0aWFsaXplIHRoZSBjb250cmFjdCB3aXRoIEdhbWVIdWIgYWRkcmVzcyBhbmQgYWRtaW4KCiMgQXJndW1lbnRzCiogYGFkbWluYCAtIEFkbWluIGFkZHJlc3MgKGNhbiB1cGdyYWRlIGNvbnRyYWN0KQoqIGBnYW1lX2h1YmAgLSBBZGRyZXNzIG9mIHRoZSBHYW1lSHViIGNvbnRyYWN0AAAAAA1fX2NvbnN0cnVjdG9yAAAAAAAAAgAAAAAAAAAFYWRtaW4AAAAAAAATAAAAAAAAAAhnYW1lX2h1YgAAABMAAAAA",
-        "AAAAAAAAAThSZXZlYWwgdGhlIHdpbm5lciBvZiB0aGUgZ2FtZSBhbmQgc3VibWl0IG91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIHJvbGxlZC4KVGhpcyBnZW5lcmF0ZXMgZGljZSByb2xscyBmb3IgYm90aCBwbGF5ZXJzLCBkZXRlcm1pbmVzIHRoZSB3aW5uZXIsIGFuZCBlbmRzIHRoZSBzZXNzaW9uLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gQWRkcmVzcyBvZiB0aGUgd2lubmluZyBwbGF5ZXIAAAANcmV2ZWFsX3dpbm5lcgAAAAAAAAEAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAABAAAD6QAAABMAAAAD" ]),
+        "AAAAAAAAAv5SZXZlYWwgdGhlIGN1cnJlbnQgcm91bmQgYW5kLCBvbmNlIGEgcGxheWVyIGhhcyB3b24gdGhlIG1hdGNoLCBzdWJtaXQgdGhlCm91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIHJvbGxlZC4KVGhpcyBnZW5lcmF0ZXMgZGljZSByb2xscyBmb3IgYm90aCBwbGF5ZXJzIGFuZCBzY29yZXMgdGhlIHJvdW5kLiBJZiBuZWl0aGVyCnBsYXllciBoYXMgYSBtYWpvcml0eSBvZiBgYmVzdF9vZmAgeWV0LCB0aGUgcm9sbHMgYXJlIHJlc2V0IGZvciB0aGUgbmV4dCByb3VuZDsKb3RoZXJ3aXNlIHRoZSBzZXNzaW9uIGVuZHMuCgpXaG8gbWF5IGNhbGwgdGhpcyBkZXBlbmRzIG9uIHRoZSBgU2V0dGxlbWVudENvbmZpZ2A6IHRoZSBwbGF5ZXJzIGFsd2F5cyBjYW47CmluIGtlZXBlciBtb2RlIGFueW9uZSBlbHNlIGNhbiB0b28gYWZ0ZXIgYGtlZXBlcl9kZWxheWAgbGVkZ2VycywgYW5kIGVhcm5zCmBrZWVwZXJfcmV3YXJkX2Jwc2Agb2YgdGhlIGNvbWJpbmVkIHN0YWtlLCBzcHJlYWQgb3ZlciB0aGUgbWF0Y2gncyByb3VuZHMuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFRoZSBzZXNzaW9uIElEIG9mIHRoZSBnYW1lCiogYGNhbGxlcmAgLSBBZGRyZXNzIHNldHRsaW5nIHRoZSByb3VuZCAocGxheWVyIG9yIGtlZXBlcikKCiMgUmV0dXJucwoqIGBBZGRyZXNzYCAtIEFkZHJlc3Mgb2YgdGhlIHJvdW5kJ3Mgd2lubmVyOyB0aGUgbWF0Y2ggd2lubmVyIG9uY2UgYHdpbm5lcmAgaXMgc2V0AAAAAAANcmV2ZWFsX3dpbm5lcgAAAAAAAAIAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABmNhbGxlcgAAAAAAEw==" ]),
       options
     )
   }
@@ -86,6 +86,8 @@ export class DiceDuelService {
       player2,
       player1_points: player1Points,
       player2_points: player2Points,
+      best_of: undefined, // single-round session
+      rules: undefined, // two six-sided dice, highest total
     }, DEFAULT_METHOD_OPTIONS);
 
     const validUntilLedgerSeq = authTtlMinutes
@@ -134,6 +136,8 @@ export class DiceDuelService {
       player2,
       player1_points: player1Points,
       player2_points: player2Points,
+      best_of: undefined, // single-round session
+      rules: undefined, // two six-sided dice, highest total
     }, DEFAULT_METHOD_OPTIONS);
     // NOTE: Contract methods automatically simulate - no need to call tx.simulate() again!
     console.log('[prepareStartGame] Transaction built and simulated, extracting auth entries');
@@ -369,6 +373,8 @@ export class DiceDuelService {
       player2: player2Address,             // Provided by Player 2
       player1_points: gameParams.player1Points, // From auth entry
       player2_points: player2Points,         // Provided by Player 2
+      best_of: undefined, // single-round session
+      rules: undefined, // two six-sided dice, highest total
     }, DEFAULT_METHOD_OPTIONS);
     // NOTE: Contract methods automatically simulate - no need to call tx.simulate() again!
 
@@ -622,7 +628,7 @@ export class DiceDuelService {
     authTtlMinutes?: number
   ) {
     const client = this.createSigningClient(callerAddress, signer);
-    const tx = await client.reveal_winner({ session_id: sessionId }, DEFAULT_METHOD_OPTIONS);
+    const tx = await client.reveal_winner({ session_id: sessionId, caller: callerAddress }, DEFAULT_METHOD_OPTIONS);
     // NOTE: Contract methods automatically simulate - footprint already includes all required storage keys
     // (reveal_winner calls the Game Hub end_game() hook)
 
@@ -716,11 +716,18 @@ export function TwentyOneGame({
 
         const signer = getContractSigner();
         const winnerResult = await twentyOneService.revealWinner(sessionId, userAddress, signer);
+        const winner = (winnerResult as any).unwrap ? (winnerResult as any).unwrap() : winnerResult;
+
+        if (!winner) {
+          // Tied hands: new hands were dealt for the next round
+          await loadGameState();
+          setSuccess('Draw! New hands have been dealt.');
+          return;
+        }
 
         await waitForWinner();
         await loadGameState();
 
-        const winner = (winnerResult as any).unwrap ? (winnerResult as any).unwrap() : winnerResult;
         const isWinner = normalizeAddress(winner) === normalizedUserAddress;
         setSuccess(isWinner ? '🎉 You won!' : 'Game complete! Winner revealed.');
 
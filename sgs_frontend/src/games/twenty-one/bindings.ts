@@ -63,7 +63,14 @@ export const Errors = {
   8: {message:"Draw"},
   9: {message:"SelfPlay"},
   10: {message:"RoundOverflow"},
-  11: {message:"InvalidHandData"}
+  11: {message:"InvalidHandData"},
+  12: {message:"InvalidTtlConfig"},
+  13: {message:"MatchNotFound"},
+  14: {message:"SettlementTooEarly"},
+  15: {message:"InvalidSettlementConfig"},
+  16: {message:"GameHubNotSet"},
+  17: {message:"AdminNotSet"},
+  18: {message:"NotMatchmaker"}
 }
 
 export type DataKey = {tag: "Game", values: readonly [u32]} | {tag: "GameHubAddress", values: void} | {tag: "Admin", values: void};
@@ -173,13 +180,18 @@ export interface Client {
    * This calculates hand values, determines the winner (closest to 21),
    * and handles draws by dealing new hands.
    * 
+   * Who may call this depends on the `SettlementConfig`: the players always can;
+   * in keeper mode anyone else can too after `keeper_delay` ledgers, and earns
+   * `keeper_reward_bps` of the matched pot.
+   * 
    * # Arguments
    * * `session_id` - The session ID of the game
+   * * `caller` - Address settling the game (player or keeper)
    * 
    * # Returns
    * * `Address` - Address of the winning player
    */
-  reveal_winner: ({session_id}: {session_id: u32}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+  reveal_winner: ({session_id, caller}: {session_id: u32, caller: string}, options?: MethodOptions) => Promise<AssembledTransaction<Result<Option<string>>>>
 
   /**
    * Construct and simulate a get_hand_value transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
@@ -227,7 +239,7 @@ export class Client extends ContractClient {
         "AAAAAAAAAEpTZXQgYSBuZXcgYWRtaW4gYWRkcmVzcwoKIyBBcmd1bWVudHMKKiBgbmV3X2FkbWluYCAtIFRoZSBuZXcgYWRtaW4gYWRkcmVzcwAAAAAACXNldF9hZG1pbgAAAAAAAAEAAAAAAAAACW5ld19hZG1pbgAAAAAAABMAAAAA",
         "AAAAAAAAAlFTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgpFYWNoIHBsYXllciBpcyBkZWFsdCAyIGNhcmRzIHRvIHN0YXJ0LgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgAAAAAAAApzdGFydF9nYW1lAAAAAAAFAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAdwbGF5ZXIxAAAAABMAAAAAAAAAB3BsYXllcjIAAAAAEwAAAAAAAAAOcGxheWVyMV9wb2ludHMAAAAAAAsAAAAAAAAADnBsYXllcjJfcG9pbnRzAAAAAAALAAAAAQAAA+kAAAPtAAAAAAAAAAM=",
         "AAAAAAAAAKNJbml0aWFsaXplIHRoZSBjb250cmFjdCB3aXRoIEdhbWVIdWIgYWRkcmVzcyBhbmQgYWRtaW4KCiMgQXJndW1lbnRzCiogYGFkbWluYCAtIEFkbWluIGFkZHJlc3MgKGNhbiB1cGdyYWRlIGNvbnRyYWN0KQoqIGBnYW1lX2h1YmAgLSBBZGRyZXNzIG9mIHRoZSBHYW1lSHViIGNvbnRyYWN0AAAAAA1fX2NvbnN0cnVjdG9yAAAAAAAAAgAAAAAAAAAFYWRtaW4AAAAAAAATAAAAAAAAAAhnYW1lX2h1YgAAABMAAAAA",
-        "AAAAAAAAAUpSZXZlYWwgdGhlIHdpbm5lciBvZiB0aGUgZ2FtZSBhbmQgc3VibWl0IG91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIHN0dWNrLgpUaGlzIGNhbGN1bGF0ZXMgaGFuZCB2YWx1ZXMsIGRldGVybWluZXMgdGhlIHdpbm5lciAoY2xvc2VzdCB0byAyMSksCmFuZCBoYW5kbGVzIGRyYXdzIGJ5IGRlYWxpbmcgbmV3IGhhbmRzLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gQWRkcmVzcyBvZiB0aGUgd2lubmluZyBwbGF5ZXIAAAAAAA1yZXZlYWxfd2lubmVyAAAAAAAAAQAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAEAAAPpAAAAEwAAAAM=",
+        "AAAAAAAAAkhSZXZlYWwgdGhlIHdpbm5lciBvZiB0aGUgZ2FtZSBhbmQgc3VibWl0IG91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIHN0dWNrLgpUaGlzIGNhbGN1bGF0ZXMgaGFuZCB2YWx1ZXMsIGRldGVybWluZXMgdGhlIHdpbm5lciAoY2xvc2VzdCB0byAyMSksCmFuZCBoYW5kbGVzIGRyYXdzIGJ5IGRlYWxpbmcgbmV3IGhhbmRzLgoKV2hvIG1heSBjYWxsIHRoaXMgZGVwZW5kcyBvbiB0aGUgYFNldHRsZW1lbnRDb25maWdgOiB0aGUgcGxheWVycyBhbHdheXMgY2FuOwppbiBrZWVwZXIgbW9kZSBhbnlvbmUgZWxzZSBjYW4gdG9vIGFmdGVyIGBrZWVwZXJfZGVsYXlgIGxlZGdlcnMsIGFuZCBlYXJucwpga2VlcGVyX3Jld2FyZF9icHNgIG9mIHRoZSBjb21iaW5lZCBzdGFrZS4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKKiBgY2FsbGVyYCAtIEFkZHJlc3Mgc2V0dGxpbmcgdGhlIGdhbWUgKHBsYXllciBvciBrZWVwZXIpCgojIFJldHVybnMKKiBgQWRkcmVzc2AgLSBBZGRyZXNzIG9mIHRoZSB3aW5uaW5nIHBsYXllcgAAAA1yZXZlYWxfd2lubmVyAAAAAAAAAgAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAAAAAAGY2FsbGVyAAAAAAATAAAAAQAAA+kAAAATAAAAAw==",
         "AAAAAAAAAL5HZXQgdGhlIGN1cnJlbnQgaGFuZCB2YWx1ZSBmb3IgYSBwbGF5ZXIuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFRoZSBzZXNzaW9uIElEIG9mIHRoZSBnYW1lCiogYHBsYXllcmAgLSBBZGRyZXNzIG9mIHRoZSBwbGF5ZXIKCiMgUmV0dXJucwoqIGB1MzJgIC0gVGhlIHRvdGFsIHZhbHVlIG9mIHRoZSBwbGF5ZXIncyBoYW5kAAAAAAAOZ2V0X2hhbmRfdmFsdWUAAAAAAAIAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAEAAAPpAAAABAAAAAM=" ]),
       options
     )
@@ -242,7 +254,7 @@ export class Client extends ContractClient {
         get_admin: this.txFromJSON<string>,
         set_admin: this.txFromJSON<null>,
         start_game: this.txFromJSON<Result<void>>,
-        reveal_winner: this.txFromJSON<Result<string>>,
+        reveal_winner: this.txFromJSON<Result<Option<string>>>,
         get_hand_value: this.txFromJSON<Result<u32>>
   }
 }
\ No newline at end of file
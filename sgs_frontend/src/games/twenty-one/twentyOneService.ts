@@ -365,6 +365,7 @@ export class TwentyOneService {
     const client = this.createSigningClient(player, signer);
     const tx = await client.reveal_winner({
       session_id: sessionId,
+      caller: player,
     }, DEFAULT_METHOD_OPTIONS);
 
     const validUntilLedgerSeq = await calculateValidUntilLedger(RPC_URL, DEFAULT_AUTH_TTL_MINUTES);
@@ -264,7 +264,8 @@ export function StellarDynastiesGame({ userAddress }: Props) {
       addLog(`🌐 Verifying Player 1 proof on Soroban...`);
       const v1 = await sorobanService.verifyPlot(
         sessionId, p1kp, selectedAction,
-        p1Commit.proofDataBytes, p1Commit.commitmentBytes
+        p1Commit.proofDataBytes, p1Commit.commitmentBytes,
+        round, 4, // aim at a different enemy holding each round, guard the army
       );
       if (v1.success) {
         addLog(`✅ P1 proof verified on-chain! TX: ${v1.txHash?.slice(0, 8)}...`, true);
@@ -276,7 +277,8 @@ export function StellarDynastiesGame({ userAddress }: Props) {
       addLog(`🌐 Verifying Player 2 proof on Soroban...`);
       const v2 = await sorobanService.verifyPlot(
         sessionId, p2kp, enemyAction,
-        p2Commit.proofDataBytes, p2Commit.commitmentBytes
+        p2Commit.proofDataBytes, p2Commit.commitmentBytes,
+        round, 4, 1, 0, // player 2 plots against seat 0
       );
       if (v2.success) {
         addLog(`✅ P2 proof verified on-chain! TX: ${v2.txHash?.slice(0, 8)}...`, true);
@@ -330,7 +332,7 @@ export function StellarDynastiesGame({ userAddress }: Props) {
       if (rr.success) {
         addLog(`✅ Round resolved on-chain! TX: ${rr.txHash?.slice(0, 8)}...`, true);
         if (rr.data) {
-          addLog(`📊 On-chain state: P1 prestige=${rr.data.player1_prestige}, P2 prestige=${rr.data.player2_prestige}`);
+          addLog(`📊 On-chain state: P1 prestige=${rr.data.players?.[0]?.prestige}, P2 prestige=${rr.data.players?.[1]?.prestige}`);
         }
         setTxHash(rr.txHash || '');
       } else {
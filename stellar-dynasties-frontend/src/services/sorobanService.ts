@@ -17,18 +17,21 @@ import { Buffer } from 'buffer';
 import { SOROBAN_RPC_URL, NETWORK_PASSPHRASE, getContractId } from '../utils/constants';
 
 // ---------- Types ----------
+export interface OnChainDynasty {
+    player: string;
+    prestige: number;
+    plot_verified: boolean;
+    action: number | null;
+    eliminated_in: number | null;
+}
+
 export interface OnChainGameState {
-    player1: string;
-    player2: string;
-    player1_prestige: number;
-    player2_prestige: number;
-    player1_plot_verified: boolean;
-    player2_plot_verified: boolean;
-    player1_action: number | null;
-    player2_action: number | null;
+    /** Dynasties by seat */
+    players: OnChainDynasty[];
     round: number;
     ended: boolean;
     winner: string | null;
+    ranking: string[];
 }
 
 export interface TxResult {
@@ -206,6 +209,7 @@ class SorobanService {
             new Address(player2Keypair.publicKey()).toScVal(),
             nativeToScVal(player1Points, { type: 'i128' }),
             nativeToScVal(player2Points, { type: 'i128' }),
+            xdr.ScVal.scvVoid(), // config: use the contract default
         ];
 
         try {
@@ -282,16 +286,30 @@ class SorobanService {
         sessionId: number,
         playerKeypair: Keypair,
         actionType: number,
-        proofData: Uint8Array,
-        commitment: Uint8Array,
+        secretKey: Uint8Array,
+        targetId: number = 1,
+        guardId: number = 4,
+        spend: number = 1,
+        targetDynasty: number = 1,
+        betrayal: boolean = false,
     ): Promise<TxResult> {
         console.log(`[Soroban] Verifying plot for session ${sessionId}, action ${actionType}...`);
+        // RevealedPlot struct: a map keyed by field name, in sorted key order
+        const field = (name: string, val: xdr.ScVal) =>
+            new xdr.ScMapEntry({ key: xdr.ScVal.scvSymbol(name), val });
+        const plot = xdr.ScVal.scvMap([
+            field('action', nativeToScVal(actionType, { type: 'u32' })),
+            field('betrayal', nativeToScVal(betrayal, { type: 'bool' })),
+            field('guard', nativeToScVal(guardId, { type: 'u32' })),
+            field('spend', nativeToScVal(spend, { type: 'u32' })),
+            field('target', nativeToScVal(targetId, { type: 'u32' })),
+            field('target_dynasty', nativeToScVal(targetDynasty, { type: 'u32' })),
+        ]);
         const args = [
             nativeToScVal(sessionId, { type: 'u32' }),
             new Address(playerKeypair.publicKey()).toScVal(),
-            nativeToScVal(actionType, { type: 'u32' }),
-            nativeToScVal(Buffer.from(proofData), { type: 'bytes' }),
-            nativeToScVal(Buffer.from(commitment), { type: 'bytes' }),
+            plot,
+            nativeToScVal(Buffer.from(secretKey), { type: 'bytes' }),
         ];
         return this.submitTx(playerKeypair, 'verify_plot', args);
     }
@@ -303,6 +321,7 @@ class SorobanService {
         console.log(`[Soroban] Resolving round for session ${sessionId}...`);
         const args = [
             nativeToScVal(sessionId, { type: 'u32' }),
+            new Address(callerKeypair.publicKey()).toScVal(),
         ];
         return this.submitTx(callerKeypair, 'resolve_round', args);
     }
@@ -46,16 +46,41 @@ export interface Game {
   player2: string;
   player2_guess: Option<u32>;
   player2_points: i128;
+  rules: GuessRules;
   winner: Option<string>;
   winning_number: Option<u32>;
 }
 
+export enum GuessScoring {
+  Closest = 0,
+  Jackpot = 1,
+  OverUnder = 2,
+  PriceIsRight = 3,
+}
+
+export interface GuessRules {
+  max: u32;
+  min: u32;
+  scoring: GuessScoring;
+}
+
 export const Errors = {
   1: {message:"GameNotFound"},
   2: {message:"NotPlayer"},
   3: {message:"AlreadyGuessed"},
   4: {message:"BothPlayersNotGuessed"},
-  5: {message:"GameAlreadyEnded"}
+  5: {message:"GameAlreadyEnded"},
+  6: {message:"InvalidTtlConfig"},
+  7: {message:"MatchNotFound"},
+  8: {message:"SettlementTooEarly"},
+  9: {message:"InvalidSettlementConfig"},
+  10: {message:"InvalidBestOf"},
+  11: {message:"GuessOutOfRange"},
+  12: {message:"InvalidRules"},
+  13: {message:"SelfPlay"},
+  14: {message:"GameHubNotSet"},
+  15: {message:"AdminNotSet"},
+  16: {message:"NotMatchmaker"}
 }
 
 export type DataKey = {tag: "Game", values: readonly [u32]} | {tag: "GameHubAddress", values: void} | {tag: "Admin", values: void};
@@ -120,13 +145,13 @@ export interface Client {
 
   /**
    * Construct and simulate a make_guess transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * Make a guess for the current game.
-   * Players can guess a number between 1 and 10.
+   * Make a guess for the current round.
+   * Players guess a number in the session's range (1-10 by default).
    * 
    * # Arguments
    * * `session_id` - The session ID of the game
    * * `player` - Address of the player making the guess
-   * * `guess` - The guessed number (1-10)
+   * * `guess` - The guessed number, between the session's `min` and `max`
    */
   make_guess: ({session_id, player, guess}: {session_id: u32, player: string, guess: u32}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
 
@@ -144,22 +169,32 @@ export interface Client {
    * * `player2` - Address of second player
    * * `player1_points` - Points amount committed by player 1
    * * `player2_points` - Points amount committed by player 2
+   * * `best_of` - Odd number of rounds in the match, up to 15 (single guess if `None`)
+   * * `rules` - Guess range and scoring (1-10, closest wins if `None`)
    */
-  start_game: ({session_id, player1, player2, player1_points, player2_points}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
+  start_game: ({session_id, player1, player2, player1_points, player2_points, best_of, rules}: {session_id: u32, player1: string, player2: string, player1_points: i128, player2_points: i128, best_of: Option<u32>, rules: Option<GuessRules>}, options?: MethodOptions) => Promise<AssembledTransaction<Result<void>>>
 
   /**
    * Construct and simulate a reveal_winner transaction. Returns an `AssembledTransaction` object which will have a `result` field containing the result of the simulation. If this transaction changes contract state, you will need to call `signAndSend()` on the returned object.
-   * Reveal the winner of the game and submit outcome to GameHub.
+   * Reveal the current round and, once a player has won the match, submit the
+   * outcome to GameHub.
    * Can only be called after both players have made their guesses.
-   * This generates the winning number, determines the winner, and ends the session.
+   * This generates the winning number and scores the round. If neither player has
+   * a majority of `best_of` yet, the guesses are cleared for the next round;
+   * otherwise the session ends.
+   * 
+   * Who may call this depends on the `SettlementConfig`: the players always can;
+   * in keeper mode anyone else can too after `keeper_delay` ledgers, and earns
+   * `keeper_reward_bps` of the matched pot, spread over the match's rounds.
    * 
    * # Arguments
    * * `session_id` - The session ID of the game
+   * * `caller` - Address settling the round (player or keeper)
    * 
    * # Returns
-   * * `Address` - Address of the winning player
+   * * `Address` - Address of the round's winner; the match winner once `winner` is set
    */
-  reveal_winner: ({session_id}: {session_id: u32}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
+  reveal_winner: ({session_id, caller}: {session_id: u32, caller: string}, options?: MethodOptions) => Promise<AssembledTransaction<Result<string>>>
 
 }
 export class Client extends ContractClient {
@@ -190,10 +225,10 @@ export class Client extends ContractClient {
         "AAAAAAAAAJ1HZXQgZ2FtZSBpbmZvcm1hdGlvbi4KCiMgQXJndW1lbnRzCiogYHNlc3Npb25faWRgIC0gVGhlIHNlc3Npb24gSUQgb2YgdGhlIGdhbWUKCiMgUmV0dXJucwoqIGBHYW1lYCAtIFRoZSBnYW1lIHN0YXRlIChpbmNsdWRlcyB3aW5uaW5nIG51bWJlciBhZnRlciBnYW1lIGVuZHMpAAAAAAAACGdldF9nYW1lAAAAAQAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAEAAAPpAAAH0AAAAARHYW1lAAAAAw==",
         "AAAAAAAAAEhHZXQgdGhlIGN1cnJlbnQgYWRtaW4gYWRkcmVzcwoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gVGhlIGFkbWluIGFkZHJlc3MAAAAJZ2V0X2FkbWluAAAAAAAAAAAAAAEAAAAT",
         "AAAAAAAAAEpTZXQgYSBuZXcgYWRtaW4gYWRkcmVzcwoKIyBBcmd1bWVudHMKKiBgbmV3X2FkbWluYCAtIFRoZSBuZXcgYWRtaW4gYWRkcmVzcwAAAAAACXNldF9hZG1pbgAAAAAAAAEAAAAAAAAACW5ld19hZG1pbgAAAAAAABMAAAAA",
-        "AAAAAAAAAOJNYWtlIGEgZ3Vlc3MgZm9yIHRoZSBjdXJyZW50IGdhbWUuClBsYXllcnMgY2FuIGd1ZXNzIGEgbnVtYmVyIGJldHdlZW4gMSBhbmQgMTAuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFRoZSBzZXNzaW9uIElEIG9mIHRoZSBnYW1lCiogYHBsYXllcmAgLSBBZGRyZXNzIG9mIHRoZSBwbGF5ZXIgbWFraW5nIHRoZSBndWVzcwoqIGBndWVzc2AgLSBUaGUgZ3Vlc3NlZCBudW1iZXIgKDEtMTApAAAAAAAKbWFrZV9ndWVzcwAAAAAAAwAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAAAAAAGcGxheWVyAAAAAAATAAAAAAAAAAVndWVzcwAAAAAAAAQAAAABAAAD6QAAA+0AAAAAAAAAAw==",
-        "AAAAAAAAAipTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgAAAAAACnN0YXJ0X2dhbWUAAAAAAAUAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAAB3BsYXllcjEAAAAAEwAAAAAAAAAHcGxheWVyMgAAAAATAAAAAAAAAA5wbGF5ZXIxX3BvaW50cwAAAAAACwAAAAAAAAAOcGxheWVyMl9wb2ludHMAAAAAAAsAAAABAAAD6QAAA+0AAAAAAAAAAw==",
+        "AAAAAAAAARdNYWtlIGEgZ3Vlc3MgZm9yIHRoZSBjdXJyZW50IHJvdW5kLgpQbGF5ZXJzIGd1ZXNzIGEgbnVtYmVyIGluIHRoZSBzZXNzaW9uJ3MgcmFuZ2UgKDEtMTAgYnkgZGVmYXVsdCkuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFRoZSBzZXNzaW9uIElEIG9mIHRoZSBnYW1lCiogYHBsYXllcmAgLSBBZGRyZXNzIG9mIHRoZSBwbGF5ZXIgbWFraW5nIHRoZSBndWVzcwoqIGBndWVzc2AgLSBUaGUgZ3Vlc3NlZCBudW1iZXIsIGJldHdlZW4gdGhlIHNlc3Npb24ncyBgbWluYCBhbmQgYG1heGAAAAAACm1ha2VfZ3Vlc3MAAAAAAAMAAAAAAAAACnNlc3Npb25faWQAAAAAAAQAAAAAAAAABnBsYXllcgAAAAAAEwAAAAAAAAAFZ3Vlc3MAAAAAAAAE",
+        "AAAAAAAAAsBTdGFydCBhIG5ldyBnYW1lIGJldHdlZW4gdHdvIHBsYXllcnMgd2l0aCBwb2ludHMuClRoaXMgY3JlYXRlcyBhIHNlc3Npb24gaW4gdGhlIEdhbWUgSHViIGFuZCBsb2NrcyBwb2ludHMgYmVmb3JlIHN0YXJ0aW5nIHRoZSBnYW1lLgoKKipDUklUSUNBTDoqKiBUaGlzIG1ldGhvZCByZXF1aXJlcyBhdXRob3JpemF0aW9uIGZyb20gVEhJUyBjb250cmFjdCAobm90IHBsYXllcnMpLgpUaGUgR2FtZSBIdWIgd2lsbCBjYWxsIGBnYW1lX2lkLnJlcXVpcmVfYXV0aCgpYCB3aGljaCBjaGVja3MgdGhpcyBjb250cmFjdCdzIGFkZHJlc3MuCgojIEFyZ3VtZW50cwoqIGBzZXNzaW9uX2lkYCAtIFVuaXF1ZSBzZXNzaW9uIGlkZW50aWZpZXIgKHUzMikKKiBgcGxheWVyMWAgLSBBZGRyZXNzIG9mIGZpcnN0IHBsYXllcgoqIGBwbGF5ZXIyYCAtIEFkZHJlc3Mgb2Ygc2Vjb25kIHBsYXllcgoqIGBwbGF5ZXIxX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMQoqIGBwbGF5ZXIyX3BvaW50c2AgLSBQb2ludHMgYW1vdW50IGNvbW1pdHRlZCBieSBwbGF5ZXIgMgoqIGBiZXN0X29mYCAtIE9kZCBudW1iZXIgb2Ygcm91bmRzIGluIHRoZSBtYXRjaCwgdXAgdG8gMTUgKHNpbmdsZSBndWVzcyBpZiBgTm9uZWApCiogYHJ1bGVzYCAtIEd1ZXNzIHJhbmdlIGFuZCBzY29yaW5nICgxLTEwLCBjbG9zZXN0IHdpbnMgaWYgYE5vbmVgKQAAAApzdGFydF9nYW1lAAAAAAAHAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAAAAAAdwbGF5ZXIxAAAAABMAAAAAAAAAB3BsYXllcjIAAAAAEwAAAAAAAAAOcGxheWVyMV9wb2ludHMAAAAAAAsAAAAAAAAADnBsYXllcjJfcG9pbnRzAAAAAAALAAAAAAAAAAdiZXN0X29mAAAAA+gAAAAEAAAAAAAAAAVydWxlcwAAAAAAA+gAAAfQAAAACkd1ZXNzUnVsZXMAAA==",
         "AAAAAAAAAKNJbml0aWFsaXplIHRoZSBjb250cmFjdCB3aXRoIEdhbWVIdWIgYWRkcmVzcyBhbmQgYWRtaW4KCiMgQXJndW1lbnRzCiogYGFkbWluYCAtIEFkbWluIGFkZHJlc3MgKGNhbiB1cGdyYWRlIGNvbnRyYWN0KQoqIGBnYW1lX2h1YmAgLSBBZGRyZXNzIG9mIHRoZSBHYW1lSHViIGNvbnRyYWN0AAAAAA1fX2NvbnN0cnVjdG9yAAAAAAAAAgAAAAAAAAAFYWRtaW4AAAAAAAATAAAAAAAAAAhnYW1lX2h1YgAAABMAAAAA",
-        "AAAAAAAAATtSZXZlYWwgdGhlIHdpbm5lciBvZiB0aGUgZ2FtZSBhbmQgc3VibWl0IG91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIG1hZGUgdGhlaXIgZ3Vlc3Nlcy4KVGhpcyBnZW5lcmF0ZXMgdGhlIHdpbm5pbmcgbnVtYmVyLCBkZXRlcm1pbmVzIHRoZSB3aW5uZXIsIGFuZCBlbmRzIHRoZSBzZXNzaW9uLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoKIyBSZXR1cm5zCiogYEFkZHJlc3NgIC0gQWRkcmVzcyBvZiB0aGUgd2lubmluZyBwbGF5ZXIAAAAADXJldmVhbF93aW5uZXIAAAAAAAABAAAAAAAAAApzZXNzaW9uX2lkAAAAAAAEAAAAAQAAA+kAAAATAAAAAw==" ]),
+        "AAAAAAAAAwVSZXZlYWwgdGhlIGN1cnJlbnQgcm91bmQgYW5kLCBvbmNlIGEgcGxheWVyIGhhcyB3b24gdGhlIG1hdGNoLCBzdWJtaXQgdGhlCm91dGNvbWUgdG8gR2FtZUh1Yi4KQ2FuIG9ubHkgYmUgY2FsbGVkIGFmdGVyIGJvdGggcGxheWVycyBoYXZlIG1hZGUgdGhlaXIgZ3Vlc3Nlcy4KVGhpcyBnZW5lcmF0ZXMgdGhlIHdpbm5pbmcgbnVtYmVyIGFuZCBzY29yZXMgdGhlIHJvdW5kLiBJZiBuZWl0aGVyIHBsYXllciBoYXMKYSBtYWpvcml0eSBvZiBgYmVzdF9vZmAgeWV0LCB0aGUgZ3Vlc3NlcyBhcmUgY2xlYXJlZCBmb3IgdGhlIG5leHQgcm91bmQ7Cm90aGVyd2lzZSB0aGUgc2Vzc2lvbiBlbmRzLgoKV2hvIG1heSBjYWxsIHRoaXMgZGVwZW5kcyBvbiB0aGUgYFNldHRsZW1lbnRDb25maWdgOiB0aGUgcGxheWVycyBhbHdheXMgY2FuOwppbiBrZWVwZXIgbW9kZSBhbnlvbmUgZWxzZSBjYW4gdG9vIGFmdGVyIGBrZWVwZXJfZGVsYXlgIGxlZGdlcnMsIGFuZCBlYXJucwpga2VlcGVyX3Jld2FyZF9icHNgIG9mIHRoZSBjb21iaW5lZCBzdGFrZSwgc3ByZWFkIG92ZXIgdGhlIG1hdGNoJ3Mgcm91bmRzLgoKIyBBcmd1bWVudHMKKiBgc2Vzc2lvbl9pZGAgLSBUaGUgc2Vzc2lvbiBJRCBvZiB0aGUgZ2FtZQoqIGBjYWxsZXJgIC0gQWRkcmVzcyBzZXR0bGluZyB0aGUgcm91bmQgKHBsYXllciBvciBrZWVwZXIpCgojIFJldHVybnMKKiBgQWRkcmVzc2AgLSBBZGRyZXNzIG9mIHRoZSByb3VuZCdzIHdpbm5lcjsgdGhlIG1hdGNoIHdpbm5lciBvbmNlIGB3aW5uZXJgIGlzIHNldAAAAAAAAA1yZXZlYWxfd2lubmVyAAAAAAAAAgAAAAAAAAAKc2Vzc2lvbl9pZAAAAAAABAAAAAAAAAAGY2FsbGVyAAAAAAAT" ]),
       options
     )
   }
@@ -0,0 +1,268 @@
+//! Stellar Dynasties payoffs, extracted from the contract's own round rules.
+//!
+//! Holdings are interchangeable under the rules, so choosing targets and guards
+//! uniformly at random among intact holdings is optimal for both dynasties; the
+//! analysis averages over those choices and leaves the action as the decision.
+//! Both dynasties commit the same spend, so a clash is decided by the counter
+//! rule alone, and resources never run out.
+
+use std::collections::HashMap;
+
+use serde::Serialize;
+use stellar_dynasties::rules::{self, Plot, SeatPlot};
+use stellar_dynasties::{GameConfig, PlotAction, Target};
+
+use crate::solver::{self, Dominated, Equilibrium, Matrix};
+
+/// Strategy names, by `PlotAction` value
+pub const ACTIONS: [&str; 4] = ["assassination", "bribery", "rebellion", "defend"];
+
+/// Holdings each dynasty starts with
+const HOLDINGS: u32 = Target::Army as u32;
+
+/// State of a two-dynasty game at the start of a round
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Position {
+    pub round: u32,
+    pub prestige: [i128; 2],
+    /// Intact holdings of each dynasty
+    pub holdings: [u32; 2],
+}
+
+impl Position {
+    /// Opening position of a session played with `config`
+    pub fn start(config: &GameConfig) -> Self {
+        Position {
+            round: 1,
+            prestige: [config.starting_prestige; 2],
+            holdings: [HOLDINGS; 2],
+        }
+    }
+}
+
+/// One way a round plays out once both actions are chosen
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Branch {
+    pub probability: f64,
+    /// Prestige change of each dynasty
+    pub deltas: [i128; 2],
+    /// Each dynasty's plot took a rival holding
+    pub took: [bool; 2],
+}
+
+/// Targets and guards one dynasty can pick for `action`: plots aim at one of the
+/// rival's holdings and guard one of their own, a defence watches one of its own
+fn choices(action: u32, own: u32, rival: u32) -> Vec<(u32, u32)> {
+    if action == PlotAction::Defend as u32 {
+        (1..=own).map(|holding| (holding, holding)).collect()
+    } else {
+        (1..=rival)
+            .flat_map(|target| (1..=own).map(move |guard| (target, guard)))
+            .collect()
+    }
+}
+
+/// Every equally likely combination of targets and guards for a pair of
+/// actions, resolved with `rules::resolve_all` exactly as `resolve_round` does
+pub fn branches(config: &GameConfig, position: &Position, actions: [u32; 2]) -> Vec<Branch> {
+    let defend = PlotAction::Defend as u32;
+    let [h1, h2] = position.holdings;
+    let first = choices(actions[0], h1, h2);
+    let second = choices(actions[1], h2, h1);
+    let probability = 1.0 / (first.len() * second.len()) as f64;
+
+    let mut branches = Vec::new();
+    for &(target1, guard1) in &first {
+        for &(target2, guard2) in &second {
+            // A plot is guarded against when the rival guards its target; a
+            // defence always guards its own target
+            let seat_plot = |seat: u32, action: u32, target: u32, rival_guard: u32| SeatPlot {
+                target_dynasty: if action == defend { seat } else { 1 - seat },
+                plot: Plot {
+                    action,
+                    target,
+                    spend: config.min_plot_spend,
+                    target_guarded: action == defend || target == rival_guard,
+                },
+            };
+            let plots = [
+                Some(seat_plot(0, actions[0], target1, guard2)),
+                Some(seat_plot(1, actions[1], target2, guard1)),
+            ];
+            let results = rules::resolve_all(config, &plots);
+            branches.push(Branch {
+                probability,
+                deltas: [results[0].delta, results[1].delta],
+                took: [results[0].took, results[1].took],
+            });
+        }
+    }
+    branches
+}
+
+/// Expected prestige change of each dynasty for every pair of actions
+pub fn expected_deltas(config: &GameConfig, position: &Position) -> Vec<Vec<[f64; 2]>> {
+    action_pairs(|a1, a2| {
+        branches(config, position, [a1, a2])
+            .iter()
+            .fold([0.0, 0.0], |[d1, d2], branch| {
+                [
+                    d1 + branch.probability * branch.deltas[0] as f64,
+                    d2 + branch.probability * branch.deltas[1] as f64,
+                ]
+            })
+    })
+}
+
+/// One round on its own: payoff to player 1 is the prestige lead gained
+pub fn round_matrix(config: &GameConfig, position: &Position) -> Matrix {
+    expected_deltas(config, position)
+        .into_iter()
+        .map(|row| row.into_iter().map(|[d1, d2]| d1 - d2).collect())
+        .collect()
+}
+
+/// Build a 4x4 table indexed by player 1's and player 2's action
+fn action_pairs<T>(mut cell: impl FnMut(u32, u32) -> T) -> Vec<Vec<T>> {
+    (0..ACTIONS.len() as u32)
+        .map(|a1| (0..ACTIONS.len() as u32).map(|a2| cell(a1, a2)).collect())
+        .collect()
+}
+
+/// The whole game, played to the end with prestige floored at 0, knockouts and
+/// the contract's ranking. Payoff to player 1 is their probability of winning.
+pub struct FullGame<'a> {
+    config: &'a GameConfig,
+    /// Value of every position solved so far
+    values: HashMap<Position, f64>,
+}
+
+impl<'a> FullGame<'a> {
+    pub fn new(config: &'a GameConfig) -> Self {
+        FullGame {
+            config,
+            values: HashMap::new(),
+        }
+    }
+
+    /// Player 1's winning probability under optimal play from `position`
+    pub fn value(&mut self, position: &Position) -> f64 {
+        if let Some(value) = self.values.get(position) {
+            return *value;
+        }
+        let value = solver::solve(&self.stage_matrix(position)).value;
+        self.values.insert(*position, value);
+        value
+    }
+
+    /// Player 1's winning probability after each pair of actions in `position`,
+    /// with optimal play afterwards
+    pub fn stage_matrix(&mut self, position: &Position) -> Matrix {
+        let config = self.config;
+        action_pairs(|a1, a2| {
+            branches(config, position, [a1, a2])
+                .iter()
+                .map(|branch| branch.probability * self.after(position, branch))
+                .sum()
+        })
+    }
+
+    /// Player 1's winning probability once `branch` has been applied
+    fn after(&mut self, position: &Position, branch: &Branch) -> f64 {
+        let mut next = Position {
+            round: position.round + 1,
+            ..*position
+        };
+        for seat in 0..2 {
+            next.prestige[seat] = (position.prestige[seat] + branch.deltas[seat]).max(0);
+            if branch.took[seat] {
+                next.holdings[1 - seat] -= 1;
+            }
+        }
+
+        // Dynasties rank by the round they were knocked out in, survivors first
+        let survival: Vec<u32> = (0..2)
+            .map(|seat| {
+                let out = next.prestige[seat] == 0 || next.holdings[seat] == 0;
+                if out { position.round } else { u32::MAX }
+            })
+            .collect();
+        let remaining = survival.iter().filter(|round| **round == u32::MAX).count();
+        if position.round >= self.config.max_rounds || remaining <= 1 {
+            // Ties go to the lower seat
+            let player1_first =
+                (survival[0], next.prestige[0]) >= (survival[1], next.prestige[1]);
+            return if player1_first { 1.0 } else { 0.0 };
+        }
+        self.value(&next)
+    }
+}
+
+/// Equilibrium and dominated actions of one matrix game
+#[derive(Clone, Debug, Serialize)]
+pub struct StageAnalysis {
+    /// Payoff to player 1, indexed by player 1's then player 2's action
+    pub payoffs: Matrix,
+    pub equilibrium: Equilibrium,
+    /// Actions player 1 never needs, given what player 2 might do
+    pub dominated_player1: Vec<Dominated>,
+    /// Actions player 2 never needs, given what player 1 might do
+    pub dominated_player2: Vec<Dominated>,
+}
+
+impl StageAnalysis {
+    pub fn new(payoffs: Matrix) -> Self {
+        StageAnalysis {
+            equilibrium: solver::solve(&payoffs),
+            dominated_player1: solver::dominated_rows(&payoffs),
+            dominated_player2: solver::dominated_columns(&payoffs),
+            payoffs,
+        }
+    }
+}
+
+/// Balance constants the analysis was run with
+#[derive(Clone, Debug, Serialize)]
+pub struct Constants {
+    pub max_rounds: u32,
+    pub starting_prestige: i128,
+    pub assassination_prestige: i128,
+    pub bribery_prestige: i128,
+    pub rebellion_prestige: i128,
+    pub failed_plot_penalty: i128,
+    pub draw_prestige: i128,
+    pub counter_multiplier: u32,
+}
+
+/// Everything the analyzer reports for one config
+#[derive(Clone, Debug, Serialize)]
+pub struct Analysis {
+    pub actions: [&'static str; 4],
+    pub constants: Constants,
+    /// Expected prestige change of each dynasty in the opening round
+    pub expected_deltas: Vec<Vec<[f64; 2]>>,
+    /// The opening round alone, scored by prestige lead
+    pub one_round: StageAnalysis,
+    /// The opening round of the whole game, scored by winning probability
+    pub full_game: StageAnalysis,
+}
+
+pub fn analyze(config: &GameConfig) -> Analysis {
+    let start = Position::start(config);
+    Analysis {
+        actions: ACTIONS,
+        constants: Constants {
+            max_rounds: config.max_rounds,
+            starting_prestige: config.starting_prestige,
+            assassination_prestige: config.assassination_prestige,
+            bribery_prestige: config.bribery_prestige,
+            rebellion_prestige: config.rebellion_prestige,
+            failed_plot_penalty: config.failed_plot_penalty,
+            draw_prestige: config.draw_prestige,
+            counter_multiplier: config.counter_multiplier,
+        },
+        expected_deltas: expected_deltas(config, &start),
+        one_round: StageAnalysis::new(round_matrix(config, &start)),
+        full_game: StageAnalysis::new(FullGame::new(config).stage_matrix(&start)),
+    }
+}
@@ -0,0 +1,131 @@
+#![cfg(test)]
+
+//! Unit tests for payoff extraction and the full-game solution.
+
+use stellar_dynasties::{default_game_config, PlotAction};
+
+use crate::analysis::{analyze, branches, expected_deltas, round_matrix, FullGame, Position};
+use crate::solver::Dominated;
+
+const ASSASSINATION: u32 = PlotAction::Assassination as u32;
+const REBELLION: u32 = PlotAction::Rebellion as u32;
+const DEFEND: u32 = PlotAction::Defend as u32;
+
+#[test]
+fn test_branches_cover_every_target_and_guard() {
+    let config = default_game_config();
+    let start = Position::start(&config);
+
+    // Plots pick a target and a guard out of 4 holdings each, defences one holding
+    let plots = branches(&config, &start, [ASSASSINATION, REBELLION]);
+    assert_eq!(plots.len(), 16 * 16);
+    let defences = branches(&config, &start, [DEFEND, DEFEND]);
+    assert_eq!(defences.len(), 4 * 4);
+
+    let total: f64 = plots.iter().map(|branch| branch.probability).sum();
+    assert!((total - 1.0).abs() < 1e-12);
+    assert!(defences.iter().all(|branch| branch.deltas == [0, 0]));
+}
+
+#[test]
+fn test_expected_deltas_follow_the_rules() {
+    let config = default_game_config();
+    let deltas = expected_deltas(&config, &Position::start(&config));
+    let assassinate = ASSASSINATION as usize;
+    let defend = DEFEND as usize;
+
+    // The defender watches the targeted holding one time in four: the plot is
+    // foiled then and succeeds otherwise
+    let expected = 0.75 * config.assassination_prestige as f64
+        - 0.25 * config.failed_plot_penalty as f64;
+    assert_eq!(deltas[assassinate][defend], [expected, 0.0]);
+    assert_eq!(deltas[defend][assassinate], [0.0, expected]);
+}
+
+#[test]
+fn test_one_round_is_symmetric() {
+    let config = default_game_config();
+    let matrix = round_matrix(&config, &Position::start(&config));
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, payoff) in row.iter().enumerate() {
+            assert_eq!(*payoff, -matrix[j][i]);
+        }
+    }
+
+    let analysis = analyze(&config);
+    assert!(analysis.one_round.equilibrium.value.abs() < 1e-9);
+    let equilibrium = &analysis.one_round.equilibrium;
+    for (p, q) in equilibrium.row.iter().zip(&equilibrium.column) {
+        assert!((p - q).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_full_game_favours_player1_on_ties() {
+    // Level games go to the lower seat, so player 1 wins more than half
+    let config = default_game_config();
+    let value = FullGame::new(&config).value(&Position::start(&config));
+    assert!(value > 0.5 && value < 1.0);
+}
+
+#[test]
+fn test_full_game_ends_at_max_rounds() {
+    let config = default_game_config();
+    let mut game = FullGame::new(&config);
+
+    // No single round can make up a 190 prestige lead
+    let last_round = Position {
+        round: config.max_rounds,
+        prestige: [200, 10],
+        holdings: [4, 4],
+    };
+    assert_eq!(game.value(&last_round), 1.0);
+
+    // One failed plot knocks a dynasty this low on prestige out
+    let exposed = Position {
+        round: 1,
+        prestige: [50, 5],
+        holdings: [4, 4],
+    };
+    assert!(game.value(&exposed) > game.value(&Position::start(&config)));
+}
+
+#[test]
+fn test_last_holding_cannot_be_taken() {
+    // A dynasty down to one holding always guards it, so plots against it
+    // are neutralized and only prestige can knock it out
+    let config = default_game_config();
+    let cornered = Position {
+        round: 1,
+        prestige: [50, 50],
+        holdings: [4, 1],
+    };
+    for action in [ASSASSINATION, REBELLION] {
+        for branch in branches(&config, &cornered, [action, REBELLION]) {
+            assert!(!branch.took[0]);
+        }
+    }
+}
+
+#[test]
+fn test_free_plots_dominate_defence() {
+    // Without a penalty for failing, plotting never costs anything
+    let mut config = default_game_config();
+    config.failed_plot_penalty = 0;
+    let analysis = analyze(&config);
+
+    let defend = DEFEND as usize;
+    let by_assassination = Dominated {
+        strategy: defend,
+        by: ASSASSINATION as usize,
+        strictly: true,
+    };
+    assert_eq!(analysis.one_round.dominated_player1, vec![by_assassination.clone()]);
+    assert_eq!(analysis.one_round.dominated_player2, vec![by_assassination]);
+    assert_eq!(analysis.full_game.equilibrium.row[defend], 0.0);
+
+    // The default penalty keeps every action in play
+    let analysis = analyze(&default_game_config());
+    assert!(analysis.one_round.dominated_player1.is_empty());
+    assert!(analysis.full_game.dominated_player1.is_empty());
+}
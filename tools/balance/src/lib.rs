@@ -0,0 +1,15 @@
+//! Game-theory balance analysis for Stellar Dynasties.
+//!
+//! Payoff matrices are built from the contract's own `rules` module, so the
+//! analysis always reflects the code that resolves rounds on chain. Both the
+//! opening round alone and the whole game are solved for mixed-strategy Nash
+//! equilibria, and actions dominated by another action are flagged.
+
+pub mod analysis;
+pub mod solver;
+
+#[cfg(test)]
+mod analysis_test;
+
+#[cfg(test)]
+mod solver_test;
@@ -0,0 +1,173 @@
+//! Command line for the Stellar Dynasties balance analyzer: solves the game
+//! with the contract's default constants, or with any of them overridden.
+
+use std::fmt::Write as _;
+use std::process::ExitCode;
+
+use balance::analysis::{self, Analysis, StageAnalysis, ACTIONS};
+use balance::solver::Dominated;
+use stellar_dynasties::{default_game_config, GameConfig};
+
+const USAGE: &str = "\
+usage: balance [options]
+
+Every constant defaults to the contract's built-in game config.
+
+options:
+  --max-rounds N           rounds per game
+  --starting-prestige N    prestige each dynasty starts with
+  --assassination N        prestige for a successful assassination
+  --bribery N              prestige for a successful bribery
+  --rebellion N            prestige for a successful rebellion
+  --failed-penalty N       prestige lost by a failed plot
+  --draw N                 prestige both dynasties gain on a draw
+  --counter-multiplier N   strength multiplier of the countering action
+  --format F               text or json (default text)";
+
+/// Output format selected with `--format`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(GameConfig, Format), String> {
+    let mut config = default_game_config();
+    let mut format = Format::Text;
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(format!("missing value for {flag}"))?;
+        let number = || value.parse::<i128>().map_err(|_| format!("invalid {flag}: {value}"));
+        let count = || value.parse::<u32>().map_err(|_| format!("invalid {flag}: {value}"));
+        match flag.as_str() {
+            "--max-rounds" => config.max_rounds = count()?,
+            "--starting-prestige" => config.starting_prestige = number()?,
+            "--assassination" => config.assassination_prestige = number()?,
+            "--bribery" => config.bribery_prestige = number()?,
+            "--rebellion" => config.rebellion_prestige = number()?,
+            "--failed-penalty" => config.failed_plot_penalty = number()?,
+            "--draw" => config.draw_prestige = number()?,
+            "--counter-multiplier" => config.counter_multiplier = count()?,
+            "--format" => {
+                format = match value.as_str() {
+                    "text" => Format::Text,
+                    "json" => Format::Json,
+                    _ => return Err(format!("unknown format: {value}")),
+                }
+            }
+            _ => return Err(format!("unknown option: {flag}")),
+        }
+    }
+    if config.max_rounds == 0 || config.starting_prestige <= 0 {
+        return Err("max rounds and starting prestige must be positive".to_string());
+    }
+    Ok((config, format))
+}
+
+/// A table with one row per player 1 action and one column per player 2 action
+fn table(out: &mut String, cells: &[Vec<String>]) {
+    let _ = write!(out, "{:>15}", "");
+    for action in ACTIONS {
+        let _ = write!(out, "{action:>15}");
+    }
+    out.push('\n');
+    for (action, row) in ACTIONS.iter().zip(cells) {
+        let _ = write!(out, "{action:>15}");
+        for cell in row {
+            let _ = write!(out, "{cell:>15}");
+        }
+        out.push('\n');
+    }
+}
+
+fn mix(out: &mut String, player: &str, probabilities: &[f64]) {
+    let _ = write!(out, "  {player}:");
+    for (action, p) in ACTIONS.iter().zip(probabilities) {
+        let _ = write!(out, " {action} {p:.3}");
+    }
+    out.push('\n');
+}
+
+fn dominated(out: &mut String, player: &str, dominated: &[Dominated]) {
+    for d in dominated {
+        let how = if d.strictly { "strictly" } else { "weakly" };
+        let _ = writeln!(
+            out,
+            "  {player} {} is {how} dominated by {}",
+            ACTIONS[d.strategy], ACTIONS[d.by]
+        );
+    }
+}
+
+fn stage(out: &mut String, title: &str, stage: &StageAnalysis) {
+    let _ = writeln!(out, "\n{title}");
+    let cells: Vec<Vec<String>> = stage
+        .payoffs
+        .iter()
+        .map(|row| row.iter().map(|payoff| format!("{payoff:.3}")).collect())
+        .collect();
+    table(out, &cells);
+    let _ = writeln!(out, "Equilibrium value: {:.4}", stage.equilibrium.value);
+    mix(out, "player 1", &stage.equilibrium.row);
+    mix(out, "player 2", &stage.equilibrium.column);
+    if stage.dominated_player1.is_empty() && stage.dominated_player2.is_empty() {
+        out.push_str("No dominated actions\n");
+    } else {
+        out.push_str("Dominated actions:\n");
+        dominated(out, "player 1", &stage.dominated_player1);
+        dominated(out, "player 2", &stage.dominated_player2);
+    }
+}
+
+fn render_text(analysis: &Analysis) -> String {
+    let c = &analysis.constants;
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Stellar Dynasties balance: {} rounds, starting prestige {}, assassination +{}, \
+         bribery +{}, rebellion +{}, failed plot -{}, draw +{}, counter x{}",
+        c.max_rounds,
+        c.starting_prestige,
+        c.assassination_prestige,
+        c.bribery_prestige,
+        c.rebellion_prestige,
+        c.failed_plot_penalty,
+        c.draw_prestige,
+        c.counter_multiplier
+    );
+
+    out.push_str("\nExpected prestige change in the opening round (player 1 / player 2)\n");
+    let cells: Vec<Vec<String>> = analysis
+        .expected_deltas
+        .iter()
+        .map(|row| row.iter().map(|[d1, d2]| format!("{d1:.2}/{d2:.2}")).collect())
+        .collect();
+    table(&mut out, &cells);
+
+    stage(&mut out, "One round, payoff = player 1's prestige lead", &analysis.one_round);
+    stage(
+        &mut out,
+        "Full game, payoff = player 1's winning probability after the opening round",
+        &analysis.full_game,
+    );
+    out
+}
+
+fn main() -> ExitCode {
+    let (config, format) = match parse_args(std::env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let analysis = analysis::analyze(&config);
+    match format {
+        Format::Text => print!("{}", render_text(&analysis)),
+        Format::Json => println!(
+            "{}",
+            serde_json::to_string_pretty(&analysis).expect("analysis serializes to JSON")
+        ),
+    }
+    ExitCode::SUCCESS
+}
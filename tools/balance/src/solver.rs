@@ -0,0 +1,168 @@
+//! Two-player zero-sum matrix games: optimal mixed strategies by linear
+//! programming, and pure strategies dominated by another pure strategy.
+//!
+//! The row player maximizes the payoff and the column player minimizes it.
+
+use serde::Serialize;
+
+/// Payoffs to the row player, `matrix[row][column]`
+pub type Matrix = Vec<Vec<f64>>;
+
+/// Tolerance for comparing payoffs and pivots
+const EPSILON: f64 = 1e-9;
+
+/// A mixed-strategy Nash equilibrium of a zero-sum game
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct Equilibrium {
+    /// Payoff the row player can guarantee and the column player can hold them to
+    pub value: f64,
+    /// Probability of each row
+    pub row: Vec<f64>,
+    /// Probability of each column
+    pub column: Vec<f64>,
+}
+
+/// A pure strategy that another pure strategy does at least as well as
+/// against every strategy of the opponent
+#[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+pub struct Dominated {
+    pub strategy: usize,
+    pub by: usize,
+    /// Strictly better against everything, rather than never worse
+    pub strictly: bool,
+}
+
+/// Solve a zero-sum game.
+///
+/// Shifting every payoff by the same amount does not change the optimal
+/// strategies, so the matrix is made positive and the column player's problem
+/// becomes `max sum(y)` subject to `A y <= 1`, `y >= 0`. Its optimum is
+/// `1 / value`; the row player's strategy comes out of the same tableau as the
+/// dual solution.
+///
+/// # Arguments
+/// * `matrix` - Non-empty payoffs to the row player, every row the same length
+pub fn solve(matrix: &[Vec<f64>]) -> Equilibrium {
+    let rows = matrix.len();
+    let columns = matrix[0].len();
+    let min = matrix.iter().flatten().fold(f64::INFINITY, |min, &x| min.min(x));
+    let shift = 1.0 - min;
+
+    // Tableau: constraint rows, then the objective row; columns are the
+    // variables y, the slacks and the right-hand side
+    let width = columns + rows + 1;
+    let mut tableau = vec![vec![0.0; width]; rows + 1];
+    for (i, row) in matrix.iter().enumerate() {
+        for (j, payoff) in row.iter().enumerate() {
+            tableau[i][j] = payoff + shift;
+        }
+        tableau[i][columns + i] = 1.0;
+        tableau[i][width - 1] = 1.0;
+    }
+    for cell in tableau[rows].iter_mut().take(columns) {
+        *cell = -1.0;
+    }
+    let mut basis: Vec<usize> = (columns..columns + rows).collect();
+
+    // Bland's rule (lowest index enters and leaves) cannot cycle
+    while let Some(entering) = (0..width - 1).find(|&j| tableau[rows][j] < -EPSILON) {
+        let mut leaving: Option<usize> = None;
+        for i in 0..rows {
+            if tableau[i][entering] <= EPSILON {
+                continue;
+            }
+            let ratio = tableau[i][width - 1] / tableau[i][entering];
+            leaving = match leaving {
+                Some(best) => {
+                    let best_ratio = tableau[best][width - 1] / tableau[best][entering];
+                    let better = ratio < best_ratio - EPSILON
+                        || (ratio <= best_ratio + EPSILON && basis[i] < basis[best]);
+                    Some(if better { i } else { best })
+                }
+                None => Some(i),
+            };
+        }
+        // The shifted payoffs are positive, so the problem is bounded
+        let pivot_row = leaving.expect("bounded linear program");
+        pivot(&mut tableau, pivot_row, entering);
+        basis[pivot_row] = entering;
+    }
+
+    let total = tableau[rows][width - 1];
+    let mut column = vec![0.0; columns];
+    for (i, &variable) in basis.iter().enumerate() {
+        if variable < columns {
+            column[variable] = tableau[i][width - 1] / total;
+        }
+    }
+    let row = (0..rows).map(|i| tableau[rows][columns + i] / total).collect();
+    Equilibrium {
+        value: 1.0 / total - shift,
+        row,
+        column,
+    }
+}
+
+/// Scale the pivot row to a unit pivot and clear the column everywhere else
+fn pivot(tableau: &mut [Vec<f64>], pivot_row: usize, column: usize) {
+    let scale = tableau[pivot_row][column];
+    for cell in tableau[pivot_row].iter_mut() {
+        *cell /= scale;
+    }
+    let pivot = tableau[pivot_row].clone();
+    for (i, row) in tableau.iter_mut().enumerate() {
+        let factor = row[column];
+        if i == pivot_row || factor == 0.0 {
+            continue;
+        }
+        for (cell, p) in row.iter_mut().zip(&pivot) {
+            *cell -= factor * p;
+        }
+    }
+}
+
+/// Rows dominated by another row. Each dominated row is reported once, with a
+/// strictly dominating row when there is one. Identical rows do not dominate
+/// each other.
+pub fn dominated_rows(matrix: &[Vec<f64>]) -> Vec<Dominated> {
+    let mut dominated = Vec::new();
+    for (strategy, row) in matrix.iter().enumerate() {
+        let mut found: Option<Dominated> = None;
+        for (by, other) in matrix.iter().enumerate() {
+            if by == strategy {
+                continue;
+            }
+            let pairs = || other.iter().zip(row);
+            let strictly = pairs().all(|(o, r)| o > &(r + EPSILON));
+            let weakly = pairs().all(|(o, r)| o >= &(r - EPSILON))
+                && pairs().any(|(o, r)| o > &(r + EPSILON));
+            if strictly {
+                found = Some(Dominated {
+                    strategy,
+                    by,
+                    strictly,
+                });
+                break;
+            }
+            if weakly && found.is_none() {
+                found = Some(Dominated {
+                    strategy,
+                    by,
+                    strictly,
+                });
+            }
+        }
+        dominated.extend(found);
+    }
+    dominated
+}
+
+/// Columns dominated by another column, from the minimizing column player's
+/// point of view
+pub fn dominated_columns(matrix: &[Vec<f64>]) -> Vec<Dominated> {
+    let columns = matrix[0].len();
+    let negated: Matrix = (0..columns)
+        .map(|j| matrix.iter().map(|row| -row[j]).collect())
+        .collect();
+    dominated_rows(&negated)
+}
@@ -0,0 +1,117 @@
+#![cfg(test)]
+
+//! Unit tests for the zero-sum game solver.
+
+use crate::solver::{dominated_columns, dominated_rows, solve, Dominated, Equilibrium};
+
+fn assert_close(actual: f64, expected: f64) {
+    assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+}
+
+fn assert_mix(actual: &[f64], expected: &[f64]) {
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(expected) {
+        assert_close(*a, *e);
+    }
+}
+
+/// Neither player can do better than the value by deviating to a pure strategy
+fn assert_equilibrium(matrix: &[Vec<f64>], equilibrium: &Equilibrium) {
+    for row in matrix {
+        let payoff: f64 = row.iter().zip(&equilibrium.column).map(|(a, q)| a * q).sum();
+        assert!(payoff <= equilibrium.value + 1e-9);
+    }
+    for j in 0..matrix[0].len() {
+        let payoff: f64 = matrix.iter().zip(&equilibrium.row).map(|(row, p)| row[j] * p).sum();
+        assert!(payoff >= equilibrium.value - 1e-9);
+    }
+}
+
+#[test]
+fn test_rock_paper_scissors_is_uniform() {
+    let matrix = vec![
+        vec![0.0, 1.0, -1.0],
+        vec![-1.0, 0.0, 1.0],
+        vec![1.0, -1.0, 0.0],
+    ];
+    let equilibrium = solve(&matrix);
+    assert_close(equilibrium.value, 0.0);
+    assert_mix(&equilibrium.row, &[1.0 / 3.0; 3]);
+    assert_mix(&equilibrium.column, &[1.0 / 3.0; 3]);
+    assert_equilibrium(&matrix, &equilibrium);
+}
+
+#[test]
+fn test_uneven_pennies_mix() {
+    // Row mixes 2/5 : 3/5 to make the column player indifferent, and vice versa
+    let matrix = vec![vec![2.0, -1.0], vec![-1.0, 1.0]];
+    let equilibrium = solve(&matrix);
+    assert_close(equilibrium.value, 0.2);
+    assert_mix(&equilibrium.row, &[0.4, 0.6]);
+    assert_mix(&equilibrium.column, &[0.4, 0.6]);
+}
+
+#[test]
+fn test_saddle_point_is_pure() {
+    let matrix = vec![vec![3.0, 1.0], vec![4.0, 2.0]];
+    let equilibrium = solve(&matrix);
+    assert_close(equilibrium.value, 2.0);
+    assert_mix(&equilibrium.row, &[0.0, 1.0]);
+    assert_mix(&equilibrium.column, &[0.0, 1.0]);
+}
+
+#[test]
+fn test_rectangular_game() {
+    let matrix = vec![vec![1.0, -2.0, 3.0], vec![-1.0, 2.0, -3.0]];
+    let equilibrium = solve(&matrix);
+    assert_close(equilibrium.row.iter().sum(), 1.0);
+    assert_close(equilibrium.column.iter().sum(), 1.0);
+    assert_equilibrium(&matrix, &equilibrium);
+}
+
+#[test]
+fn test_dominated_rows() {
+    let matrix = vec![
+        vec![1.0, 1.0, 1.0],
+        vec![2.0, 2.0, 2.0],
+        vec![2.0, 1.0, 2.0],
+        vec![2.0, 1.0, 2.0],
+    ];
+    // Row 0 loses to row 1 everywhere; rows 2 and 3 are only weakly worse,
+    // and do not dominate each other
+    assert_eq!(
+        dominated_rows(&matrix),
+        vec![
+            Dominated {
+                strategy: 0,
+                by: 1,
+                strictly: true
+            },
+            Dominated {
+                strategy: 2,
+                by: 1,
+                strictly: false
+            },
+            Dominated {
+                strategy: 3,
+                by: 1,
+                strictly: false
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_dominated_columns_minimize() {
+    // Column 1 always concedes more to the row player than column 0
+    let matrix = vec![vec![1.0, 3.0], vec![0.0, 2.0]];
+    assert_eq!(
+        dominated_columns(&matrix),
+        vec![Dominated {
+            strategy: 1,
+            by: 0,
+            strictly: true
+        }]
+    );
+    assert!(dominated_rows(&[vec![1.0, 0.0], vec![0.0, 1.0]]).is_empty());
+}
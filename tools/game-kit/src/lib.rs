@@ -0,0 +1,64 @@
+#![no_std]
+
+//! # Game Kit
+//!
+//! Features every game contract offers the same way, kept here so they cannot
+//! drift apart between games:
+//!
+//! - `ttl`: admin-configurable storage TTLs for games and their history
+//! - `session_keys`: ephemeral keys that sign a player's moves in one session
+//! - `rematch`: rematch offers between the players of an ended session, and
+//!   the series they form
+//! - `stakes`: admin stake limits and the matched-stakes payout rule
+//! - `settlement`: who may settle a session, and the keeper rewards it books
+//!
+//! Each game keeps its own state, checks and entry points, and calls in here
+//! for the shared part. Failures are reported as this crate's `Error`, which
+//! every game converts into its own error codes. Storage keys used here are
+//! private to this crate and encode like the `DataKey` variants of the same
+//! name they replace, so stored data is kept.
+
+pub mod rematch;
+pub mod session_keys;
+pub mod settlement;
+pub mod stakes;
+pub mod ttl;
+
+/// Failures of the shared features. Games map each one to their own
+/// `#[contracterror]` variant of the same name.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Error {
+    /// A TTL threshold exceeds its extension target, or a target exceeds the
+    /// network's maximum TTL
+    InvalidTtlConfig,
+    /// A session key is a player of the session, expires in the past, is
+    /// scoped to an unknown entry point or already signs for another player
+    InvalidSessionKey,
+    /// The session key's `expires_at` ledger has been reached
+    SessionKeyExpired,
+    /// The session key is not scoped to this entry point
+    SessionKeyNotAllowed,
+    /// No rematch was offered to the player for the session
+    RematchNotFound,
+    /// The session was already rematched; only the latest session of a series can be
+    RematchAlreadyPlayed,
+    /// Doubling the stakes overflows
+    StakeOverflow,
+    /// A player committed zero or negative points
+    InvalidStake,
+    /// A player's points are outside the admin's stake limits
+    StakeOutOfRange,
+    /// The minimum stake is below 1 or above the maximum, or the maximum is
+    /// above the game's `MAX_STAKE`
+    InvalidStakeLimits,
+    /// A third party tried to settle in participants-only mode, or a session
+    /// whose stakes no matchmaker escrows
+    NotPlayer,
+    /// A keeper tried to settle before `keeper_delay` ledgers passed
+    SettlementTooEarly,
+    /// The keeper reward exceeds 500 basis points
+    InvalidSettlementConfig,
+}
+
+#[cfg(test)]
+mod ttl_test;
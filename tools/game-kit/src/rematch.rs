@@ -0,0 +1,132 @@
+//! Rematches and series.
+//!
+//! Once a two-player session has ended either player may offer a rematch,
+//! which the other accepts without a fresh two-party start. Offers live in
+//! temporary storage next to the ended game. Accepted rematches link their
+//! sessions into a series whose cumulative score is kept in persistent storage
+//! next to the match history. The game checks seats, stakes and session IDs,
+//! and starts the rematch itself.
+
+use soroban_sdk::{Address, Env, Vec, contracttype, vec};
+
+use crate::ttl::{extend_game_ttl, save_history};
+use crate::Error;
+
+/// A rematch one player of an ended session offered the other
+/// (see `propose_rematch`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RematchOffer {
+    pub proposer: Address,
+    /// Session ID the rematch is played under
+    pub new_session_id: u32,
+    /// Both stakes are doubled instead of carried over
+    pub double_stakes: bool,
+}
+
+/// Sessions linked by accepted rematches and their cumulative score
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Series {
+    pub player1: Address,
+    pub player2: Address,
+    /// Sessions of the series, oldest first; the first is the series ID
+    pub sessions: Vec<u32>,
+    /// Finished sessions won by each player
+    pub player1_wins: u32,
+    pub player2_wins: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Rematch(u32),                  // session_id -> RematchOffer
+    Series(u32),                   // series_id (first session) -> Series
+    SessionSeries(u32),            // session_id -> series_id
+}
+
+/// Check that `session_id` may still be rematched: only the latest session of
+/// a series can be
+pub fn check_rematchable(env: &Env, session_id: u32) -> Result<(), Error> {
+    if session_series(env, session_id)
+        .is_some_and(|(_, series)| series.sessions.last() != Some(session_id))
+    {
+        return Err(Error::RematchAlreadyPlayed);
+    }
+    Ok(())
+}
+
+/// Store `offer` for an ended session, replacing any offer pending for it
+pub fn offer_rematch(env: &Env, session_id: u32, offer: &RematchOffer) {
+    let key = DataKey::Rematch(session_id);
+    env.storage().temporary().set(&key, offer);
+    extend_game_ttl(env, &key);
+}
+
+/// The rematch offered for an ended session, if one is pending
+pub fn rematch_offer(env: &Env, session_id: u32) -> Option<RematchOffer> {
+    env.storage().temporary().get(&DataKey::Rematch(session_id))
+}
+
+/// Remove and return the rematch the other player offered `player`
+pub fn take_rematch(env: &Env, session_id: u32, player: &Address) -> Result<RematchOffer, Error> {
+    let offer = rematch_offer(env, session_id)
+        .filter(|offer| offer.proposer != *player)
+        .ok_or(Error::RematchNotFound)?;
+    env.storage().temporary().remove(&DataKey::Rematch(session_id));
+    Ok(offer)
+}
+
+/// The series a session belongs to, with its ID, if it was ever rematched
+pub fn session_series(env: &Env, session_id: u32) -> Option<(u32, Series)> {
+    let series_id: u32 = env
+        .storage()
+        .persistent()
+        .get(&DataKey::SessionSeries(session_id))?;
+    let series = env.storage().persistent().get(&DataKey::Series(series_id))?;
+    Some((series_id, series))
+}
+
+/// Store a series and index `session_id` under it
+fn save_series(env: &Env, series_id: u32, series: &Series, session_id: u32) {
+    save_history(env, &DataKey::Series(series_id), series);
+    save_history(env, &DataKey::SessionSeries(session_id), &series_id);
+}
+
+/// Append a rematch to the series of the ended session it follows, starting
+/// the series (scored with that session's result) on the first rematch
+pub fn link_rematch(
+    env: &Env,
+    session_id: u32,
+    new_session_id: u32,
+    player1: &Address,
+    player2: &Address,
+    player1_won: bool,
+) {
+    let (series_id, mut series) = session_series(env, session_id).unwrap_or_else(|| {
+        let series = Series {
+            player1: player1.clone(),
+            player2: player2.clone(),
+            sessions: vec![env, session_id],
+            player1_wins: u32::from(player1_won),
+            player2_wins: u32::from(!player1_won),
+        };
+        save_series(env, session_id, &series, session_id);
+        (session_id, series)
+    });
+    series.sessions.push_back(new_session_id);
+    save_series(env, series_id, &series, new_session_id);
+}
+
+/// Count a finished session towards the score of its series, if any
+pub fn record_series_result(env: &Env, session_id: u32, player1_won: bool) {
+    let Some((series_id, mut series)) = session_series(env, session_id) else {
+        return;
+    };
+    if player1_won {
+        series.player1_wins += 1;
+    } else {
+        series.player2_wins += 1;
+    }
+    save_series(env, series_id, &series, session_id);
+}
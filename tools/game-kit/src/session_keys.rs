@@ -0,0 +1,126 @@
+//! Session keys.
+//!
+//! A player may register an ephemeral key for one session so their frontend
+//! can sign moves without a wallet prompt each time. Move entry points take
+//! either the player's address or the key's: a key is resolved to its player,
+//! and must be unexpired and scoped to the entry point. Keys live in temporary
+//! storage next to the game.
+
+use soroban_sdk::{Address, Env, Symbol, Vec, contracttype};
+
+use crate::ttl::extend_game_ttl;
+use crate::Error;
+
+/// An ephemeral key a player registered to sign their moves in one session
+/// (see `register_session_key`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionKey {
+    pub player: Address,
+    /// Address of the key, passed in place of the player's to move entry points
+    pub key: Address,
+    /// Entry points the key may call; empty for every move
+    pub functions: Vec<Symbol>,
+    /// Ledger sequence from which the key is refused; `None` keeps it for the session
+    pub expires_at: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    SessionKey(u32, Address),      // (session_id, key) -> SessionKey
+    PlayerSessionKey(u32, Address), // (session_id, player) -> key
+}
+
+/// Require the authorization of `caller`, a player or one of their session
+/// keys, for the move `function`. Returns the player the move is made for.
+pub fn authorize_move(
+    env: &Env,
+    session_id: u32,
+    caller: Address,
+    function: &str,
+) -> Result<Address, Error> {
+    caller.require_auth();
+    let key = DataKey::SessionKey(session_id, caller.clone());
+    let Some(session_key) = env.storage().temporary().get::<_, SessionKey>(&key) else {
+        return Ok(caller);
+    };
+    if session_key
+        .expires_at
+        .is_some_and(|ledger| env.ledger().sequence() >= ledger)
+    {
+        return Err(Error::SessionKeyExpired);
+    }
+    if !session_key.functions.is_empty()
+        && !session_key.functions.contains(Symbol::new(env, function))
+    {
+        return Err(Error::SessionKeyNotAllowed);
+    }
+    extend_game_ttl(env, &key);
+    extend_game_ttl(env, &DataKey::PlayerSessionKey(session_id, session_key.player.clone()));
+    Ok(session_key.player)
+}
+
+/// Register `key` to sign `player`'s moves, replacing any key they registered
+/// before. The game checks the player's authorization and seat in a live
+/// session, and whether `key` holds a seat itself (`key_is_player`); `moves`
+/// are the entry points a key may be scoped to.
+#[allow(clippy::too_many_arguments)]
+pub fn register_session_key(
+    env: &Env,
+    session_id: u32,
+    player: Address,
+    key: Address,
+    functions: Vec<Symbol>,
+    expires_at: Option<u32>,
+    key_is_player: bool,
+    moves: &[&str],
+) -> Result<(), Error> {
+    let unknown_function = functions
+        .iter()
+        .any(|function| !moves.iter().any(|m| Symbol::new(env, m) == function));
+    let expired = expires_at.is_some_and(|ledger| ledger <= env.ledger().sequence());
+    let entry = DataKey::SessionKey(session_id, key.clone());
+    let other_player = env
+        .storage()
+        .temporary()
+        .get::<_, SessionKey>(&entry)
+        .is_some_and(|existing| existing.player != player);
+    if key_is_player || unknown_function || expired || other_player {
+        return Err(Error::InvalidSessionKey);
+    }
+
+    remove_session_key(env, session_id, &player);
+    let index = DataKey::PlayerSessionKey(session_id, player.clone());
+    let session_key = SessionKey {
+        player,
+        key: key.clone(),
+        functions,
+        expires_at,
+    };
+    env.storage().temporary().set(&entry, &session_key);
+    env.storage().temporary().set(&index, &key);
+    extend_game_ttl(env, &entry);
+    extend_game_ttl(env, &index);
+    Ok(())
+}
+
+/// Remove `player`'s session key, returning whether one was registered
+pub fn remove_session_key(env: &Env, session_id: u32, player: &Address) -> bool {
+    let index = DataKey::PlayerSessionKey(session_id, player.clone());
+    let Some(key) = env.storage().temporary().get::<_, Address>(&index) else {
+        return false;
+    };
+    env.storage().temporary().remove(&index);
+    env.storage().temporary().remove(&DataKey::SessionKey(session_id, key));
+    true
+}
+
+/// The session key `player` registered for a session, if any
+pub fn session_key(env: &Env, session_id: u32, player: Address) -> Option<SessionKey> {
+    let key: Address = env
+        .storage()
+        .temporary()
+        .get(&DataKey::PlayerSessionKey(session_id, player))?;
+    env.storage().temporary().get(&DataKey::SessionKey(session_id, key))
+}
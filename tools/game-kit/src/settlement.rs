@@ -0,0 +1,150 @@
+//! Settlement.
+//!
+//! Players can always settle their own session. In keeper mode anyone else can
+//! settle it too, once the players have had `keeper_delay` ledgers to do it
+//! themselves, and earns a small share of the matched pot for the service.
+//!
+//! Keeper rewards come out of the winnings (see `stakes::settle_stakes`). The
+//! game only books them: each session records what every keeper was credited,
+//! and the contract escrowing the stakes (a lobby or a tournament) pays them
+//! out in tokens from the session's settlement. Sessions started straight
+//! through the Game Hub have no such escrow, so keepers cannot settle them.
+
+use matchmaking::KeeperReward;
+use soroban_sdk::{Address, Env, Vec, contracttype};
+
+use crate::ttl::{extend_instance_ttl, save_history};
+use crate::Error;
+
+/// Who may settle a session
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SettlementMode {
+    /// Only the session's players can settle it
+    Participants = 0,
+    /// Anyone can settle, and third parties earn a keeper reward
+    Keeper = 1,
+}
+
+/// Settlement rules, set by the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SettlementConfig {
+    pub mode: SettlementMode,
+    /// Keeper reward in basis points of the matched pot over a full session
+    /// (capped at 5%), spread over its rounds
+    pub keeper_reward_bps: u32,
+    /// Ledgers after the last move during which only the players may settle
+    pub keeper_delay: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    SettlementConfig,
+    KeeperEarnings(Address),       // keeper -> total rewards earned
+    SessionKeepers(u32),           // session_id -> Vec<KeeperReward>
+}
+
+/// Default keeper reward: 0.5% of the matched pot
+pub const DEFAULT_KEEPER_REWARD_BPS: u32 = 50;
+
+/// Largest keeper reward the admin can configure: 5% of the matched pot
+pub const MAX_KEEPER_REWARD_BPS: u32 = 500;
+
+/// Default head start for the players (1 hour in ledgers)
+pub const DEFAULT_KEEPER_DELAY: u32 = 720;
+
+/// Read the settlement rules, falling back to participants-only settlement
+pub fn settlement_config(env: &Env) -> SettlementConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey::SettlementConfig)
+        .unwrap_or(SettlementConfig {
+            mode: SettlementMode::Participants,
+            keeper_reward_bps: DEFAULT_KEEPER_REWARD_BPS,
+            keeper_delay: DEFAULT_KEEPER_DELAY,
+        })
+}
+
+/// Store new settlement rules. The caller checks the admin's authorization.
+pub fn set_settlement_config(env: &Env, config: &SettlementConfig) -> Result<(), Error> {
+    if config.keeper_reward_bps > MAX_KEEPER_REWARD_BPS {
+        return Err(Error::InvalidSettlementConfig);
+    }
+    env.storage().instance().set(&DataKey::SettlementConfig, config);
+    extend_instance_ttl(env);
+    Ok(())
+}
+
+/// Check that `caller` may settle a session now; `is_player` tells whether
+/// they hold a seat in it and `escrowed` whether a matchmaker escrows its
+/// stakes, and so pays out keeper rewards. Returns the keeper to reward, or
+/// `None` when one of the players settles.
+pub fn authorize_settlement(
+    env: &Env,
+    config: &SettlementConfig,
+    caller: &Address,
+    is_player: bool,
+    escrowed: bool,
+    last_move_ledger: u32,
+) -> Result<Option<Address>, Error> {
+    caller.require_auth();
+    if is_player {
+        return Ok(None);
+    }
+    if config.mode == SettlementMode::Participants || !escrowed {
+        return Err(Error::NotPlayer);
+    }
+    if env.ledger().sequence() < last_move_ledger.saturating_add(config.keeper_delay) {
+        return Err(Error::SettlementTooEarly);
+    }
+    Ok(Some(caller.clone()))
+}
+
+/// Keeper reward for settling one of `rounds` rounds: the configured share of
+/// the matched pot, spread over the rounds
+pub fn keeper_reward(config: &SettlementConfig, matched_pot: i128, rounds: u32) -> i128 {
+    matched_pot.saturating_mul(i128::from(config.keeper_reward_bps))
+        / 10_000
+        / i128::from(rounds.max(1))
+}
+
+/// Credit a keeper's reward for settling (a round of) a session
+pub fn credit_keeper(env: &Env, session_id: u32, keeper: &Address, reward: i128) {
+    let key = DataKey::KeeperEarnings(keeper.clone());
+    let earned: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+    save_history(env, &key, &(earned + reward));
+
+    let mut rewards = session_keepers(env, session_id);
+    let entry = rewards.iter().position(|entry| entry.keeper == *keeper);
+    match entry {
+        Some(index) => {
+            let mut entry = rewards.get_unchecked(index as u32);
+            entry.amount += reward;
+            rewards.set(index as u32, entry);
+        }
+        None => rewards.push_back(KeeperReward {
+            keeper: keeper.clone(),
+            amount: reward,
+        }),
+    }
+    save_history(env, &DataKey::SessionKeepers(session_id), &rewards);
+}
+
+/// Total rewards a keeper was credited across sessions
+pub fn keeper_earnings(env: &Env, keeper: Address) -> i128 {
+    env.storage()
+        .persistent()
+        .get(&DataKey::KeeperEarnings(keeper))
+        .unwrap_or(0)
+}
+
+/// Keepers credited for settling a session, each once, with their total reward
+pub fn session_keepers(env: &Env, session_id: u32) -> Vec<KeeperReward> {
+    env.storage()
+        .persistent()
+        .get(&DataKey::SessionKeepers(session_id))
+        .unwrap_or(Vec::new(env))
+}
@@ -0,0 +1,89 @@
+//! Stakes.
+//!
+//! Every player must commit a positive number of points within the admin's
+//! limits. Unequal stakes are matched at the smallest one: only that much of
+//! each stake is at play, and the excess goes back to its owner whatever the
+//! result. Keeper rewards are paid out of the matched pot, so they come off
+//! the winnings.
+
+use soroban_sdk::{Env, contracttype};
+
+use crate::ttl::extend_instance_ttl;
+use crate::Error;
+
+/// Stake limits for new games, set by the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeLimits {
+    /// Smallest points amount a player may commit (at least 1)
+    pub min_stake: i128,
+    /// Largest points amount a player may commit (at most the game's `MAX_STAKE`)
+    pub max_stake: i128,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    StakeLimits,
+}
+
+/// Read the stake limits, falling back to any positive amount up to `max_stake`
+pub fn stake_limits(env: &Env, max_stake: i128) -> StakeLimits {
+    env.storage()
+        .instance()
+        .get(&DataKey::StakeLimits)
+        .unwrap_or(StakeLimits {
+            min_stake: 1,
+            max_stake,
+        })
+}
+
+/// Store new stake limits, capped at `max_stake`. The caller checks the
+/// admin's authorization.
+pub fn set_stake_limits(env: &Env, limits: &StakeLimits, max_stake: i128) -> Result<(), Error> {
+    if limits.min_stake < 1 || limits.min_stake > limits.max_stake || limits.max_stake > max_stake
+    {
+        return Err(Error::InvalidStakeLimits);
+    }
+    env.storage().instance().set(&DataKey::StakeLimits, limits);
+    extend_instance_ttl(env);
+    Ok(())
+}
+
+/// Check a player's points against the stake limits
+pub fn validate_stake(limits: &StakeLimits, points: i128) -> Result<(), Error> {
+    if points <= 0 {
+        return Err(Error::InvalidStake);
+    }
+    if points < limits.min_stake || points > limits.max_stake {
+        return Err(Error::StakeOutOfRange);
+    }
+    Ok(())
+}
+
+/// A stake carried over to a rematch, doubled if `double_stakes`
+pub fn rematch_stake(points: i128, double_stakes: bool) -> Result<i128, Error> {
+    if double_stakes {
+        points.checked_mul(2).ok_or(Error::StakeOverflow)
+    } else {
+        Ok(points)
+    }
+}
+
+/// Points paid back to `(player1, player2)` when a two-player game ends: the
+/// winner gets their stake plus the matched amount (the smaller stake) less
+/// the keeper rewards, the loser gets the excess of their stake over it, if
+/// any. With the keeper rewards they add up to both stakes.
+pub fn settle_stakes(
+    player1_points: i128,
+    player2_points: i128,
+    player1_won: bool,
+    keeper_rewards: i128,
+) -> (i128, i128) {
+    let matched = player1_points.min(player2_points);
+    if player1_won {
+        (player1_points + matched - keeper_rewards, player2_points - matched)
+    } else {
+        (player1_points - matched, player2_points + matched - keeper_rewards)
+    }
+}
@@ -0,0 +1,102 @@
+//! Storage TTL management.
+//!
+//! TTL (Time To Live) ensures game data doesn't expire unexpectedly. Games
+//! are stored in temporary storage with a minimum 30-day retention; finished
+//! games and everything that outlives them are kept in persistent storage.
+
+use soroban_sdk::{Env, IntoVal, Val, contracttype};
+
+use crate::Error;
+
+/// Storage TTL thresholds (in ledgers), set by the admin
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TtlConfig {
+    /// Instance storage is bumped when its TTL drops below this
+    pub instance_threshold: u32,
+    /// Instance storage TTL after a bump
+    pub instance_extend_to: u32,
+    /// Game storage is bumped when its TTL drops below this
+    pub game_threshold: u32,
+    /// Game storage TTL after a bump
+    pub game_extend_to: u32,
+}
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    TtlConfig,
+}
+
+/// TTL for game storage (30 days in ledgers, ~5 seconds per ledger)
+/// 30 days = 30 * 24 * 60 * 60 / 5 = 518,400 ledgers
+pub const GAME_TTL_LEDGERS: u32 = 518_400;
+
+/// Default threshold below which instance storage (Admin, GameHubAddress) is bumped
+/// 1 day = 24 * 60 * 60 / 5 = 17,280 ledgers
+pub const INSTANCE_TTL_THRESHOLD: u32 = 17_280;
+
+/// Default TTL for instance storage after a bump (30 days)
+pub const INSTANCE_TTL_LEDGERS: u32 = 518_400;
+
+/// TTL for archived match results and other history (180 days in ledgers)
+pub const HISTORY_TTL_LEDGERS: u32 = 3_110_400;
+
+/// Read the TTL thresholds, falling back to the defaults above
+pub fn ttl_config(env: &Env) -> TtlConfig {
+    env.storage()
+        .instance()
+        .get(&DataKey::TtlConfig)
+        .unwrap_or(TtlConfig {
+            instance_threshold: INSTANCE_TTL_THRESHOLD,
+            instance_extend_to: INSTANCE_TTL_LEDGERS,
+            game_threshold: GAME_TTL_LEDGERS,
+            game_extend_to: GAME_TTL_LEDGERS,
+        })
+}
+
+/// Store new TTL thresholds. The caller checks the admin's authorization.
+///
+/// Extending past the network's maximum TTL fails, so a target above it
+/// would make every call that extends storage fail.
+pub fn set_ttl_config(env: &Env, config: &TtlConfig) -> Result<(), Error> {
+    let max_ttl = env.storage().max_ttl();
+    if config.instance_threshold > config.instance_extend_to
+        || config.game_threshold > config.game_extend_to
+        || config.instance_extend_to > max_ttl
+        || config.game_extend_to > max_ttl
+    {
+        return Err(Error::InvalidTtlConfig);
+    }
+    env.storage().instance().set(&DataKey::TtlConfig, config);
+    extend_instance_ttl(env);
+    Ok(())
+}
+
+/// Extend the contract instance TTL (called on every entry point)
+pub fn extend_instance_ttl(env: &Env) {
+    let config = ttl_config(env);
+    env.storage()
+        .instance()
+        .extend_ttl(config.instance_threshold, config.instance_extend_to);
+}
+
+/// Extend the TTL of a game entry in temporary storage (called on every move)
+pub fn extend_game_ttl<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    let config = ttl_config(env);
+    env.storage()
+        .temporary()
+        .extend_ttl(key, config.game_threshold, config.game_extend_to);
+}
+
+/// Store a history entry in persistent storage and keep it for `HISTORY_TTL_LEDGERS`
+pub fn save_history<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    env.storage()
+        .persistent()
+        .extend_ttl(key, HISTORY_TTL_LEDGERS, HISTORY_TTL_LEDGERS);
+}
@@ -0,0 +1,175 @@
+#![cfg(test)]
+
+// Unit tests for the shared storage TTL handling. Every game calls these
+// helpers, so each game only checks that its moves reach them.
+
+use crate::ttl::{
+    extend_game_ttl, extend_instance_ttl, set_ttl_config, ttl_config, TtlConfig,
+    GAME_TTL_LEDGERS, INSTANCE_TTL_LEDGERS, INSTANCE_TTL_THRESHOLD,
+};
+use crate::Error;
+use soroban_sdk::testutils::storage::{Instance as _, Temporary as _};
+use soroban_sdk::testutils::Ledger as _;
+use soroban_sdk::{contract, symbol_short, Address, Env, Symbol};
+
+/// Contract the helpers run as; it needs no entry points of its own
+#[contract]
+struct TtlContract;
+
+/// Temporary key standing in for a game
+const GAME: Symbol = symbol_short!("GAME");
+
+// ============================================================================
+// Test Helpers
+// ============================================================================
+
+/// New storage entries start with 100 ledgers so tests can observe extension
+/// and expiry
+fn setup() -> (Env, Address) {
+    let env = Env::default();
+    env.ledger().set(soroban_sdk::testutils::LedgerInfo {
+        timestamp: 1441065600,
+        protocol_version: 25,
+        sequence_number: 100,
+        network_id: Default::default(),
+        base_reserve: 10,
+        min_temp_entry_ttl: 100,
+        min_persistent_entry_ttl: 100,
+        max_entry_ttl: 6_312_000,
+    });
+    let contract_id = env.register(TtlContract, ());
+    (env, contract_id)
+}
+
+/// Advance the ledger sequence by `ledgers`
+fn advance_ledgers(env: &Env, ledgers: u32) {
+    env.ledger().with_mut(|li| li.sequence_number += ledgers);
+}
+
+/// Store a game entry and give it the default game TTL
+fn create_game(env: &Env, contract_id: &Address) {
+    env.as_contract(contract_id, || {
+        env.storage().temporary().set(&GAME, &1u32);
+        extend_game_ttl(env, &GAME);
+    });
+}
+
+/// Whether the game entry is still stored
+fn game_exists(env: &Env, contract_id: &Address) -> bool {
+    env.as_contract(contract_id, || env.storage().temporary().has(&GAME))
+}
+
+// ============================================================================
+// Storage TTL Tests
+// ============================================================================
+
+#[test]
+fn test_default_ttl_config() {
+    let (env, contract_id) = setup();
+
+    let config = env.as_contract(&contract_id, || ttl_config(&env));
+    assert_eq!(config.instance_threshold, INSTANCE_TTL_THRESHOLD);
+    assert_eq!(config.instance_extend_to, INSTANCE_TTL_LEDGERS);
+    assert_eq!(config.game_threshold, GAME_TTL_LEDGERS);
+    assert_eq!(config.game_extend_to, GAME_TTL_LEDGERS);
+}
+
+#[test]
+fn test_extend_keeps_game_alive() {
+    let (env, contract_id) = setup();
+    create_game(&env, &contract_id);
+
+    // Extend close to the end of the original 30-day TTL, then go past it
+    advance_ledgers(&env, GAME_TTL_LEDGERS - 100);
+    env.as_contract(&contract_id, || extend_game_ttl(&env, &GAME));
+    advance_ledgers(&env, 1_000);
+
+    assert!(game_exists(&env, &contract_id));
+}
+
+#[test]
+fn test_game_expires_without_extend() {
+    let (env, contract_id) = setup();
+    create_game(&env, &contract_id);
+
+    advance_ledgers(&env, GAME_TTL_LEDGERS + 1);
+    assert!(!game_exists(&env, &contract_id));
+}
+
+#[test]
+fn test_extend_instance_ttl() {
+    let (env, contract_id) = setup();
+    let instance_ttl = || env.as_contract(&contract_id, || env.storage().instance().get_ttl());
+
+    env.as_contract(&contract_id, || extend_instance_ttl(&env));
+    assert_eq!(instance_ttl(), INSTANCE_TTL_LEDGERS);
+
+    // Above the threshold nothing happens; below it the TTL is topped back up
+    advance_ledgers(&env, 1_000);
+    env.as_contract(&contract_id, || extend_instance_ttl(&env));
+    assert_eq!(instance_ttl(), INSTANCE_TTL_LEDGERS - 1_000);
+
+    advance_ledgers(&env, INSTANCE_TTL_LEDGERS - INSTANCE_TTL_THRESHOLD);
+    env.as_contract(&contract_id, || extend_instance_ttl(&env));
+    assert_eq!(instance_ttl(), INSTANCE_TTL_LEDGERS);
+}
+
+#[test]
+fn test_set_ttl_config() {
+    let (env, contract_id) = setup();
+
+    let config = TtlConfig {
+        instance_threshold: 1_000,
+        instance_extend_to: 100_000,
+        game_threshold: 2_000,
+        game_extend_to: 200_000,
+    };
+    env.as_contract(&contract_id, || {
+        assert_eq!(set_ttl_config(&env, &config), Ok(()));
+        assert_eq!(ttl_config(&env), config);
+    });
+
+    // Games are now extended to the configured TTL
+    create_game(&env, &contract_id);
+    let game_ttl = env.as_contract(&contract_id, || env.storage().temporary().get_ttl(&GAME));
+    assert_eq!(game_ttl, 200_000);
+
+    let invalid = TtlConfig {
+        instance_threshold: 200_000,
+        ..config.clone()
+    };
+    env.as_contract(&contract_id, || {
+        assert_eq!(set_ttl_config(&env, &invalid), Err(Error::InvalidTtlConfig));
+        assert_eq!(ttl_config(&env), config);
+    });
+}
+
+#[test]
+fn test_set_ttl_config_rejects_targets_above_max_ttl() {
+    let (env, contract_id) = setup();
+    let max_ttl = env.as_contract(&contract_id, || env.storage().max_ttl());
+
+    let config = TtlConfig {
+        instance_threshold: 1_000,
+        instance_extend_to: max_ttl,
+        game_threshold: 2_000,
+        game_extend_to: max_ttl,
+    };
+    let too_long_game = TtlConfig {
+        game_extend_to: max_ttl + 1,
+        ..config.clone()
+    };
+    let too_long_instance = TtlConfig {
+        instance_extend_to: max_ttl + 1,
+        ..config.clone()
+    };
+    env.as_contract(&contract_id, || {
+        assert_eq!(set_ttl_config(&env, &too_long_game), Err(Error::InvalidTtlConfig));
+        assert_eq!(set_ttl_config(&env, &too_long_instance), Err(Error::InvalidTtlConfig));
+        assert_eq!(set_ttl_config(&env, &config), Ok(()));
+    });
+
+    // The maximum itself is accepted and games still extend
+    create_game(&env, &contract_id);
+    assert!(game_exists(&env, &contract_id));
+}
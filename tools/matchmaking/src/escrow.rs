@@ -0,0 +1,73 @@
+//! Boilerplate of the contracts that escrow tokens (lobby, tournaments,
+//! side-bet pools): the admin and the token in instance storage, and TTLs.
+//!
+//! Escrowed entries hold tokens, so they live in persistent storage and are
+//! kept alive for as long as they can still be settled. Missing addresses read
+//! as `None`; each contract maps them to its own error codes.
+
+use soroban_sdk::{Address, Env, IntoVal, Val, contracttype, token};
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Admin,
+    Token,
+}
+
+/// Threshold below which instance storage is bumped (1 day)
+const INSTANCE_TTL_THRESHOLD: u32 = 17_280;
+
+/// TTL for instance storage after a bump (30 days)
+const INSTANCE_TTL_LEDGERS: u32 = 518_400;
+
+/// Store the admin and the escrow token (called from the constructor)
+pub fn initialize(env: &Env, admin: &Address, token: &Address) {
+    env.storage().instance().set(&DataKey::Admin, admin);
+    env.storage().instance().set(&DataKey::Token, token);
+    extend_instance_ttl(env);
+}
+
+/// Extend the contract instance TTL (called on every entry point)
+pub fn extend_instance_ttl(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(INSTANCE_TTL_THRESHOLD, INSTANCE_TTL_LEDGERS);
+}
+
+/// Store an escrowed entry in persistent storage and keep it for `ttl` ledgers
+pub fn save<K, V>(env: &Env, key: &K, value: &V, ttl: u32)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    env.storage().persistent().extend_ttl(key, ttl, ttl);
+}
+
+/// The current admin
+pub fn admin(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Admin)
+}
+
+/// Require the current admin's authorization, returning the admin
+pub fn require_admin(env: &Env) -> Option<Address> {
+    let admin = admin(env)?;
+    admin.require_auth();
+    Some(admin)
+}
+
+/// Replace the admin. The caller checks the current admin's authorization.
+pub fn set_admin(env: &Env, new_admin: &Address) {
+    env.storage().instance().set(&DataKey::Admin, new_admin);
+    extend_instance_ttl(env);
+}
+
+/// Address of the token contract escrowed amounts are held in
+pub fn token_address(env: &Env) -> Option<Address> {
+    env.storage().instance().get(&DataKey::Token)
+}
+
+/// Client of the escrow token
+pub fn token(env: &Env) -> Option<token::Client<'_>> {
+    token_address(env).map(|address| token::Client::new(env, &address))
+}
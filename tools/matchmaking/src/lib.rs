@@ -0,0 +1,98 @@
+#![no_std]
+
+//! # Matchmaking
+//!
+//! What games and the contracts that escrow tokens around them (the lobby,
+//! tournaments, side-bet pools) share, so both sides agree on one definition:
+//!
+//! - `MatchedGame`: the interface every game exposes to matchmakers
+//! - `SessionGame`: the read-only view every game exposes to spectators
+//! - `registry`: the game side, the matchmakers a game's admin registered
+//! - `escrow`: the escrow side, admin, stake token and TTL boilerplate
+//!
+//! Storage keys used here are private to this crate and encode like the
+//! `DataKey` variants of the same name they replace, so stored data is kept.
+
+use soroban_sdk::{Address, Env, Vec, contractclient, contracttype};
+
+pub mod escrow;
+pub mod registry;
+
+/// Points a keeper was credited for settling (part of) a session
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct KeeperReward {
+    pub keeper: Address,
+    pub amount: i128,
+}
+
+/// How a finished session's stakes are split (see `MatchedGame::get_settlement`).
+/// The payouts and keeper rewards add up to both stakes; the contract escrowing
+/// the stakes pays them out.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Settlement {
+    pub winner: Address,
+    pub player1_payout: i128,
+    pub player2_payout: i128,
+    /// Keepers that settled the session, each once, with their total reward
+    pub keeper_rewards: Vec<KeeperReward>,
+}
+
+impl Settlement {
+    /// Sum of the payouts and keeper rewards, or `None` if one is negative or
+    /// the sum overflows. Escrows check it against the stakes they hold.
+    pub fn total(&self) -> Option<i128> {
+        let mut total: i128 = 0;
+        let payouts = [self.player1_payout, self.player2_payout];
+        let rewards = self.keeper_rewards.iter().map(|reward| reward.amount);
+        for amount in payouts.into_iter().chain(rewards) {
+            if amount < 0 {
+                return None;
+            }
+            total = total.checked_add(amount)?;
+        }
+        Some(total)
+    }
+}
+
+/// What spectators and side-bet pools need to know about a two-player session
+/// (see `SessionGame::get_session_info`)
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SessionInfo {
+    pub player1: Address,
+    pub player2: Address,
+    /// No player has moved yet, so nothing hidden has been revealed
+    pub awaiting_first_move: bool,
+    /// The game's randomness is derived from public seed material (see its
+    /// `get_seed_material`), so anyone can compute the outcome, or the draws
+    /// it hinges on, before the first move
+    pub predetermined: bool,
+    /// Set once the session has ended
+    pub winner: Option<Address>,
+}
+
+// Interface every game exposes to matchmakers (lobby, tournaments)
+#[contractclient(name = "MatchedGameClient")]
+pub trait MatchedGame {
+    fn start_matched_game(
+        env: Env,
+        matchmaker: Address,
+        session_id: u32,
+        player1: Address,
+        player2: Address,
+        player1_points: i128,
+        player2_points: i128,
+    );
+
+    fn get_winner(env: Env, session_id: u32) -> Option<Address>;
+
+    fn get_settlement(env: Env, session_id: u32) -> Option<Settlement>;
+}
+
+// Read-only view every game exposes to spectators (side-bet pools)
+#[contractclient(name = "SessionGameClient")]
+pub trait SessionGame {
+    fn get_session_info(env: Env, session_id: u32) -> SessionInfo;
+}
@@ -0,0 +1,37 @@
+//! Matchmakers a game accepts `start_matched_game` calls from.
+//!
+//! Contracts registered by the game's admin (a lobby, a tournament) may start
+//! games for players who gave their consent to the matchmaker instead of
+//! signing the start themselves. Registrations live in instance storage.
+
+use soroban_sdk::{Address, Env, contracttype};
+
+#[contracttype]
+#[derive(Clone)]
+enum DataKey {
+    Matchmaker(Address),           // matchmaker -> registered by the admin
+}
+
+/// Whether the admin registered `matchmaker`
+pub fn is_matchmaker(env: &Env, matchmaker: &Address) -> bool {
+    env.storage()
+        .instance()
+        .get(&DataKey::Matchmaker(matchmaker.clone()))
+        .unwrap_or(false)
+}
+
+/// Register or remove a matchmaker. The caller checks the admin's authorization.
+pub fn set_matchmaker(env: &Env, matchmaker: Address, allowed: bool) {
+    let key = DataKey::Matchmaker(matchmaker);
+    if allowed {
+        env.storage().instance().set(&key, &true);
+    } else {
+        env.storage().instance().remove(&key);
+    }
+}
+
+/// Require `matchmaker`'s authorization and return whether it is registered
+pub fn authorize_matchmaker(env: &Env, matchmaker: &Address) -> bool {
+    matchmaker.require_auth();
+    is_matchmaker(env, matchmaker)
+}
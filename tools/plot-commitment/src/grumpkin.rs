@@ -0,0 +1,129 @@
+//! The Grumpkin curve `y^2 = x^3 - 17`, defined over the BN254 scalar field
+//! (Noir's `Field`), and the hash-to-curve generator derivation Noir and
+//! barretenberg share.
+
+use ark_bn254::Fr;
+use ark_ff::{BigInteger, Field, PrimeField, Zero};
+
+/// Curve constant `b`
+fn coefficient_b() -> Fr {
+    -Fr::from(17u64)
+}
+
+/// A point in affine coordinates
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Point {
+    Infinity,
+    Affine { x: Fr, y: Fr },
+}
+
+impl Point {
+    /// The point with this `x`, picking the `y` whose parity matches `odd`, if
+    /// `x` is on the curve at all
+    pub fn from_x(x: Fr, odd: bool) -> Option<Point> {
+        let y = (x.square() * x + coefficient_b()).sqrt()?;
+        let y = if y.into_bigint().is_odd() == odd {
+            y
+        } else {
+            -y
+        };
+        Some(Point::Affine { x, y })
+    }
+
+    pub fn is_on_curve(&self) -> bool {
+        match self {
+            Point::Infinity => true,
+            Point::Affine { x, y } => y.square() == x.square() * x + coefficient_b(),
+        }
+    }
+
+    pub fn double(&self) -> Point {
+        match *self {
+            Point::Affine { x, y } if !y.is_zero() => {
+                // The curve has a = 0
+                let lambda = (x.square() * Fr::from(3u64)) / y.double();
+                let x3 = lambda.square() - x.double();
+                Point::Affine {
+                    x: x3,
+                    y: lambda * (x - x3) - y,
+                }
+            }
+            _ => Point::Infinity,
+        }
+    }
+
+    pub fn add(&self, other: &Point) -> Point {
+        match (*self, *other) {
+            (Point::Infinity, point) | (point, Point::Infinity) => point,
+            (Point::Affine { x: x1, y: y1 }, Point::Affine { x: x2, y: y2 }) => {
+                if x1 == x2 {
+                    return if y1 == y2 {
+                        self.double()
+                    } else {
+                        Point::Infinity
+                    };
+                }
+                let lambda = (y2 - y1) / (x2 - x1);
+                let x3 = lambda.square() - x1 - x2;
+                Point::Affine {
+                    x: x3,
+                    y: lambda * (x1 - x3) - y1,
+                }
+            }
+        }
+    }
+
+    /// `scalar` times this point, reading the field element as an integer
+    pub fn mul(&self, scalar: &Fr) -> Point {
+        let bits = scalar.into_bigint().to_bits_be();
+        bits.iter().fold(Point::Infinity, |acc, &bit| {
+            let doubled = acc.double();
+            if bit {
+                doubled.add(self)
+            } else {
+                doubled
+            }
+        })
+    }
+
+    /// The x coordinate, 0 for the point at infinity (as in Noir)
+    pub fn x(&self) -> Fr {
+        match self {
+            Point::Infinity => Fr::zero(),
+            Point::Affine { x, .. } => *x,
+        }
+    }
+}
+
+/// Hash a seed onto the curve: two BLAKE3 hashes form a 512-bit integer that
+/// is reduced into `x`; the top bit of the first hash picks the parity of `y`.
+/// Seeds whose `x` is not on the curve are retried with the next attempt count.
+fn hash_to_curve(seed: &[u8], attempt: u8) -> Point {
+    let mut target = seed.to_vec();
+    target.extend_from_slice(&[attempt, 0]);
+    let hash_hi = *blake3::hash(&target).as_bytes();
+    *target.last_mut().expect("seed was extended") = 1;
+    let hash_lo = *blake3::hash(&target).as_bytes();
+
+    let mut wide = hash_hi.to_vec();
+    wide.extend_from_slice(&hash_lo);
+    let x = Fr::from_be_bytes_mod_order(&wide);
+    match Point::from_x(x, hash_hi[0] > 127) {
+        Some(point) => point,
+        None => hash_to_curve(seed, attempt + 1),
+    }
+}
+
+/// `count` generators for a domain, starting at generator `start`: each is
+/// hashed to the curve from the BLAKE3 hash of the domain separator followed by
+/// the big-endian generator index, zero-padded to 64 bytes
+pub fn derive_generators(domain_separator: &[u8], count: u32, start: u32) -> Vec<Point> {
+    let mut preimage = [0u8; 64];
+    preimage[..32].copy_from_slice(blake3::hash(domain_separator).as_bytes());
+    (start..start + count)
+        .map(|index| {
+            preimage[32..36].copy_from_slice(&index.to_be_bytes());
+            hash_to_curve(&preimage, 0)
+        })
+        .collect()
+}
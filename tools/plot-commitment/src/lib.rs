@@ -0,0 +1,47 @@
+//! Native plot commitments for Stellar Dynasties.
+//!
+//! Computes `std::hash::pedersen_hash` over the BN254 scalar field exactly as
+//! the Noir circuit in `circuits/src/main.nr` does, so clients can produce the
+//! `plot_hash` for `commit_plot` and the `Prover.toml` witness for the proof
+//! without running Noir.
+
+pub mod grumpkin;
+pub mod pedersen;
+pub mod plot;
+
+#[cfg(test)]
+mod pedersen_test;
+
+#[cfg(test)]
+mod plot_test;
+
+use ark_ff::{BigInteger, PrimeField};
+use num_bigint::BigUint;
+
+/// Noir's `Field`: the BN254 scalar field
+pub use ark_bn254::Fr as Field;
+
+pub use pedersen::pedersen_hash;
+pub use plot::{Plot, PlotError};
+
+/// `0x`-prefixed, zero-padded big-endian hex, as Noir prints fields
+pub fn to_hex(field: &Field) -> String {
+    let digits: String = field
+        .into_bigint()
+        .to_bytes_be()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("0x{digits}")
+}
+
+/// Parse a field element from decimal or `0x`-prefixed hex. Values at or above
+/// the field modulus are rejected rather than reduced.
+pub fn parse_field(text: &str) -> Option<Field> {
+    let (digits, radix) = match text.strip_prefix("0x") {
+        Some(hex) => (hex, 16),
+        None => (text, 10),
+    };
+    let value = BigUint::parse_bytes(digits.as_bytes(), radix)?;
+    (value < Field::MODULUS.into()).then(|| Field::from(value))
+}
@@ -0,0 +1,104 @@
+//! Command line for plot commitments: prints the `Prover.toml` witness for a
+//! plot, or just the commitment to pass to `commit_plot`.
+
+use std::process::ExitCode;
+
+use plot_commitment::{parse_field, to_hex, Plot};
+
+const USAGE: &str = "\
+usage: plot-commitment --target T --secret S --action A --guard G --spend N --dynasty D
+                       [--betrayal 0|1] [--format toml|hex]
+
+  --target T     holding plotted against or defended (1=Heir .. 4=Army)
+  --secret S     secret key, decimal or 0x-prefixed hex (non-zero)
+  --action A     0=Assassination, 1=Bribery, 2=Rebellion, 3=Defend
+  --guard G      own holding guarded this round (the target for Defend)
+  --spend N      resources committed to the action
+  --dynasty D    seat plotted against (own seat for Defend)
+  --betrayal B   1 if the plot betrays an ally (default 0)
+  --format F     toml prints the Prover.toml witness (default), hex only the commitment";
+
+/// Output format selected with `--format`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Format {
+    Toml,
+    Hex,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<(Plot, Format), String> {
+    let mut values: [Option<u32>; 6] = [None; 6];
+    let mut secret_key = None;
+    let mut format = Format::Toml;
+    const NAMES: [&str; 6] = [
+        "--target",
+        "--action",
+        "--guard",
+        "--spend",
+        "--dynasty",
+        "--betrayal",
+    ];
+
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(format!("missing value for {flag}"))?;
+        if let Some(index) = NAMES.iter().position(|name| *name == flag) {
+            let number = value
+                .parse()
+                .map_err(|_| format!("invalid {flag}: {value}"))?;
+            values[index] = Some(number);
+            continue;
+        }
+        match flag.as_str() {
+            "--secret" => {
+                secret_key = Some(parse_field(&value).ok_or(format!("invalid --secret: {value}"))?)
+            }
+            "--format" => {
+                format = match value.as_str() {
+                    "toml" => Format::Toml,
+                    "hex" => Format::Hex,
+                    _ => return Err(format!("unknown format: {value}")),
+                }
+            }
+            _ => return Err(format!("unknown option: {flag}")),
+        }
+    }
+
+    let required = |index: usize| values[index].ok_or(format!("missing {}", NAMES[index]));
+    let plot = Plot {
+        target_id: required(0)?,
+        secret_key: secret_key.ok_or("missing --secret")?,
+        action_type: required(1)?,
+        guard_id: required(2)?,
+        spend: required(3)?,
+        target_dynasty: required(4)?,
+        betrayal: match values[5].unwrap_or(0) {
+            0 => false,
+            1 => true,
+            _ => return Err("invalid --betrayal: must be 0 or 1".to_string()),
+        },
+    };
+    Ok((plot, format))
+}
+
+fn main() -> ExitCode {
+    let (plot, format) = match parse_args(std::env::args().skip(1)) {
+        Ok(parsed) => parsed,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+    // Either way the plot must be provable, or the commitment is useless
+    match plot.prover_toml() {
+        Ok(toml) => {
+            match format {
+                Format::Toml => print!("{toml}"),
+                Format::Hex => println!("{}", to_hex(&plot.commitment())),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("invalid plot: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
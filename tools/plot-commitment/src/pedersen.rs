@@ -0,0 +1,34 @@
+//! Noir's `std::hash::pedersen_hash`.
+
+use ark_bn254::Fr;
+
+use crate::grumpkin::{derive_generators, Point};
+
+/// Domain separator of the input generators
+const DEFAULT_DOMAIN_SEPARATOR: &[u8] = b"DEFAULT_DOMAIN_SEPARATOR";
+
+/// Domain separator of the generator that commits to the input length
+const LENGTH_DOMAIN_SEPARATOR: &[u8] = b"pedersen_hash_length";
+
+/// Pedersen hash of `inputs`, as `std::hash::pedersen_hash` computes it in a
+/// circuit
+pub fn pedersen_hash(inputs: &[Fr]) -> Fr {
+    pedersen_hash_with_separator(inputs, 0)
+}
+
+/// Pedersen hash with the input generators starting at index `separator`, as
+/// `std::hash::pedersen_hash_with_separator` computes it: the x coordinate of
+/// `sum(inputs[i] * G[separator + i]) + len * H`, where `G` are the default
+/// domain generators and `H` is the length generator
+pub fn pedersen_hash_with_separator(inputs: &[Fr], separator: u32) -> Fr {
+    let generators = derive_generators(DEFAULT_DOMAIN_SEPARATOR, inputs.len() as u32, separator);
+    let length: Point = derive_generators(LENGTH_DOMAIN_SEPARATOR, 1, 0)[0];
+    generators
+        .iter()
+        .zip(inputs)
+        .fold(
+            length.mul(&Fr::from(inputs.len() as u64)),
+            |acc, (generator, input)| acc.add(&generator.mul(input)),
+        )
+        .x()
+}
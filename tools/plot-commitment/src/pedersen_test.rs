@@ -0,0 +1,99 @@
+#![cfg(test)]
+
+//! Known-answer tests: BLAKE3 vectors from its specification, generators and
+//! hashes from barretenberg's and Noir's own test suites.
+
+use crate::grumpkin::{derive_generators, Point};
+use crate::pedersen::{pedersen_hash, pedersen_hash_with_separator};
+use crate::{parse_field, Field};
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn field(hex: &str) -> Field {
+    parse_field(hex).unwrap()
+}
+
+fn point(x: &str, y: &str) -> Point {
+    Point::Affine {
+        x: field(x),
+        y: field(y),
+    }
+}
+
+#[test]
+fn test_blake3_vectors() {
+    assert_eq!(
+        hex(blake3::hash(b"").as_bytes()),
+        "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+    assert_eq!(
+        hex(blake3::hash(b"abc").as_bytes()),
+        "6437b3ac38465133ffb63b75273a8db548c558465d79db03fd359c6cd5bd9d85"
+    );
+}
+
+#[test]
+fn test_default_generators() {
+    let generators = derive_generators(b"DEFAULT_DOMAIN_SEPARATOR", 2, 0);
+    assert_eq!(
+        generators,
+        vec![
+            point(
+                "0x083e7911d835097629f0067531fc15cafd79a89beecb39903f69572c636f4a5a",
+                "0x1a7f5efaad7f315c25a918f30cc8d7333fccab7ad7c90f14de81bcc528f9935d",
+            ),
+            point(
+                "0x054aa86a73cb8a34525e5bbed6e43ba1198e860f5f3950268f71df4591bde402",
+                "0x209dcfbf2cfb57f9f6046f44d71ac6faf87254afc7407c04eb621a6287cac126",
+            ),
+        ]
+    );
+    assert!(generators.iter().all(Point::is_on_curve));
+    // Starting later in the domain continues the same sequence
+    assert_eq!(
+        derive_generators(b"DEFAULT_DOMAIN_SEPARATOR", 1, 1)[0],
+        generators[1]
+    );
+}
+
+#[test]
+fn test_length_generator() {
+    assert_eq!(
+        derive_generators(b"pedersen_hash_length", 1, 0)[0],
+        point(
+            "0x2df8b940e5890e4e1377e05373fae69a1d754f6935e6a780b666947431f2cdcd",
+            "0x2ecd88d15967bc53b885912e0d16866154acb6aac2d3f85e27ca7eefb2c19083",
+        )
+    );
+}
+
+#[test]
+fn test_pedersen_hash_vectors() {
+    let one = Field::from(1u64);
+    assert_eq!(
+        pedersen_hash(&[one, one]),
+        field("0x07ebfbf4df29888c6cd6dca13d4bb9d1a923013ddbbcbdc3378ab8845463297b")
+    );
+    assert_eq!(
+        pedersen_hash_with_separator(&[one, one], 5),
+        field("0x1c446df60816b897cda124524e6b03f36df0cec333fad87617aab70d7861daa6")
+    );
+}
+
+#[test]
+fn test_point_arithmetic() {
+    let g = derive_generators(b"DEFAULT_DOMAIN_SEPARATOR", 1, 0)[0];
+    let three = Field::from(3u64);
+    assert_eq!(g.mul(&three), g.double().add(&g));
+    assert_eq!(g.add(&g), g.double());
+    assert_eq!(g.mul(&Field::from(0u64)), Point::Infinity);
+    assert!(g.mul(&three).is_on_curve());
+
+    // A point plus its negation is the point at infinity
+    let Point::Affine { x, y } = g else {
+        panic!("generator is finite")
+    };
+    assert_eq!(g.add(&Point::Affine { x, y: -y }), Point::Infinity);
+}
@@ -0,0 +1,162 @@
+//! Plots as the circuit in `circuits/src/main.nr` sees them: the private
+//! inputs, their constraints, the commitment and the `Prover.toml` witness.
+
+use core::fmt;
+
+use ark_ff::{BigInteger, PrimeField, Zero};
+
+use crate::pedersen::pedersen_hash;
+use crate::{to_hex, Field};
+
+/// `PlotAction::Defend`
+const DEFEND: u32 = 3;
+
+/// Holdings are numbered 1 (Heir) to 4 (Army)
+const HOLDINGS: core::ops::RangeInclusive<u32> = 1..=4;
+
+/// Seats are numbered 0 to 5
+const SEATS: core::ops::RangeInclusive<u32> = 0..=5;
+
+/// The private inputs of one plot
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Plot {
+    /// Rival holding plotted against, or the own holding defended
+    pub target_id: u32,
+    /// The player's secret, which keeps the commitment from being brute-forced
+    pub secret_key: Field,
+    /// 0=Assassination, 1=Bribery, 2=Rebellion, 3=Defend
+    pub action_type: u32,
+    /// Own holding guarded this round
+    pub guard_id: u32,
+    /// Resources committed to the action
+    pub spend: u32,
+    /// Seat plotted against (own seat for Defend)
+    pub target_dynasty: u32,
+    /// The plot betrays an ally
+    pub betrayal: bool,
+}
+
+/// A plot the circuit would refuse to prove, by failed constraint
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum PlotError {
+    InvalidAction,
+    ZeroTarget,
+    InvalidTarget,
+    InvalidGuard,
+    DefendGuardMismatch,
+    ZeroSecret,
+    InvalidDynasty,
+    DefendBetrayal,
+}
+
+impl fmt::Display for PlotError {
+    /// The circuit's own assertion messages
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            PlotError::InvalidAction => {
+                "Invalid action type: must be 0 (Assassination), 1 (Bribery), 2 (Rebellion), \
+                 or 3 (Defend)"
+            }
+            PlotError::ZeroTarget => "Target ID cannot be zero",
+            PlotError::InvalidTarget => {
+                "Invalid target: must be 1 (Heir), 2 (Treasury), 3 (Castle), or 4 (Army)"
+            }
+            PlotError::InvalidGuard => {
+                "Invalid guard: must be 1 (Heir), 2 (Treasury), 3 (Castle), or 4 (Army)"
+            }
+            PlotError::DefendGuardMismatch => "Defend must guard its own target",
+            PlotError::ZeroSecret => "Secret key cannot be zero",
+            PlotError::InvalidDynasty => "Invalid dynasty: must be a seat from 0 to 5",
+            PlotError::DefendBetrayal => "Defend cannot be a betrayal",
+        };
+        f.write_str(message)
+    }
+}
+
+impl std::error::Error for PlotError {}
+
+impl Plot {
+    /// Check the circuit's constraints, in the circuit's order. The
+    /// commitment constraint holds by construction and the spend always fits
+    /// in a u32.
+    pub fn validate(&self) -> Result<(), PlotError> {
+        let defend = self.action_type == DEFEND;
+        if self.action_type > DEFEND {
+            return Err(PlotError::InvalidAction);
+        }
+        if self.target_id == 0 {
+            return Err(PlotError::ZeroTarget);
+        }
+        if !HOLDINGS.contains(&self.target_id) {
+            return Err(PlotError::InvalidTarget);
+        }
+        if !HOLDINGS.contains(&self.guard_id) {
+            return Err(PlotError::InvalidGuard);
+        }
+        if defend && self.guard_id != self.target_id {
+            return Err(PlotError::DefendGuardMismatch);
+        }
+        if self.secret_key.is_zero() {
+            return Err(PlotError::ZeroSecret);
+        }
+        if !SEATS.contains(&self.target_dynasty) {
+            return Err(PlotError::InvalidDynasty);
+        }
+        if defend && self.betrayal {
+            return Err(PlotError::DefendBetrayal);
+        }
+        Ok(())
+    }
+
+    /// The private inputs in the order the circuit hashes them
+    pub fn inputs(&self) -> [Field; 7] {
+        [
+            Field::from(self.target_id),
+            self.secret_key,
+            Field::from(self.action_type),
+            Field::from(self.guard_id),
+            Field::from(self.spend),
+            Field::from(self.target_dynasty),
+            Field::from(self.betrayal),
+        ]
+    }
+
+    /// `pedersen_hash([target_id, secret_key, action_type, guard_id, spend,
+    /// target_dynasty, betrayal])`, the circuit's public `action_commitment`
+    pub fn commitment(&self) -> Field {
+        pedersen_hash(&self.inputs())
+    }
+
+    /// The commitment as the 32 big-endian bytes `commit_plot` takes as
+    /// `BytesN<32>`
+    pub fn commitment_bytes(&self) -> [u8; 32] {
+        let bytes = self.commitment().into_bigint().to_bytes_be();
+        let mut out = [0u8; 32];
+        out.copy_from_slice(&bytes);
+        out
+    }
+
+    /// The `Prover.toml` witness for `nargo execute` / `nargo prove`, after
+    /// checking the plot against the circuit's constraints
+    pub fn prover_toml(&self) -> Result<String, PlotError> {
+        self.validate()?;
+        Ok(format!(
+            "target_id = \"{}\"\n\
+             secret_key = \"{}\"\n\
+             action_type = \"{}\"\n\
+             guard_id = \"{}\"\n\
+             spend = \"{}\"\n\
+             target_dynasty = \"{}\"\n\
+             betrayal = \"{}\"\n\
+             action_commitment = \"{}\"\n",
+            self.target_id,
+            to_hex(&self.secret_key),
+            self.action_type,
+            self.guard_id,
+            self.spend,
+            self.target_dynasty,
+            u8::from(self.betrayal),
+            to_hex(&self.commitment()),
+        ))
+    }
+}
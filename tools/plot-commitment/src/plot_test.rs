@@ -0,0 +1,226 @@
+#![cfg(test)]
+
+//! Plot commitments against the circuit's own test plots.
+
+use crate::{parse_field, to_hex, Field, Plot, PlotError};
+
+/// `test_valid_assassination_plot` in `circuits/src/main.nr`
+fn assassination() -> Plot {
+    Plot {
+        target_id: 1,
+        secret_key: Field::from(12345u64),
+        action_type: 0,
+        guard_id: 4,
+        spend: 3,
+        target_dynasty: 1,
+        betrayal: false,
+    }
+}
+
+#[test]
+fn test_circuit_test_vectors() {
+    // Also asserted by the circuit tests, so `nargo test` cross-checks them
+    let plots = [
+        (
+            assassination(),
+            "0x12266d1eba5f91aaf480b9c8b4831ba67c40525c0dca556a887fe76a5cd1c24e",
+        ),
+        (
+            Plot {
+                target_id: 2,
+                secret_key: Field::from(99999u64),
+                action_type: 1,
+                guard_id: 2,
+                ..assassination()
+            },
+            "0x1c4139e851aa42e8a8f40ceb0017c1b3693974f24758677f7dcf26af161f256f",
+        ),
+        (
+            Plot {
+                target_id: 4,
+                secret_key: Field::from(55555u64),
+                action_type: 2,
+                guard_id: 3,
+                ..assassination()
+            },
+            "0x1ae4b43e796b9f2c4b0e3e1139a317a32ff302d443ab375a18a7b1f8c47a1b7a",
+        ),
+        (
+            Plot {
+                target_id: 3,
+                secret_key: Field::from(77777u64),
+                action_type: 3,
+                guard_id: 3,
+                ..assassination()
+            },
+            "0x0ebc98f9697684ebddbad69a0f66c981935b6c65337d17b9565e2acac2fba6e7",
+        ),
+        (
+            Plot {
+                target_id: 2,
+                secret_key: Field::from(24680u64),
+                action_type: 1,
+                betrayal: true,
+                ..assassination()
+            },
+            "0x17d5c37108764c8d357aa07ccad16d9d23fcba845c5f5ac7eea2538ea2b2016f",
+        ),
+    ];
+    for (plot, commitment) in plots {
+        assert_eq!(plot.validate(), Ok(()));
+        assert_eq!(to_hex(&plot.commitment()), commitment);
+    }
+}
+
+#[test]
+fn test_every_input_changes_the_commitment() {
+    let base = assassination().commitment();
+    let variants = [
+        Plot {
+            target_id: 2,
+            ..assassination()
+        },
+        Plot {
+            secret_key: Field::from(12346u64),
+            ..assassination()
+        },
+        Plot {
+            action_type: 1,
+            ..assassination()
+        },
+        Plot {
+            guard_id: 3,
+            ..assassination()
+        },
+        Plot {
+            spend: 7,
+            ..assassination()
+        },
+        Plot {
+            target_dynasty: 2,
+            ..assassination()
+        },
+        Plot {
+            betrayal: true,
+            ..assassination()
+        },
+    ];
+    for plot in variants {
+        assert_ne!(plot.commitment(), base);
+    }
+}
+
+#[test]
+fn test_commitment_bytes_are_big_endian() {
+    let plot = assassination();
+    let bytes = plot.commitment_bytes();
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    assert_eq!(format!("0x{hex}"), to_hex(&plot.commitment()));
+}
+
+#[test]
+fn test_validate_matches_circuit_constraints() {
+    let defend = Plot {
+        action_type: 3,
+        target_id: 3,
+        guard_id: 3,
+        ..assassination()
+    };
+    let cases = [
+        (
+            Plot {
+                action_type: 5,
+                ..assassination()
+            },
+            PlotError::InvalidAction,
+        ),
+        (
+            Plot {
+                target_id: 0,
+                ..assassination()
+            },
+            PlotError::ZeroTarget,
+        ),
+        (
+            Plot {
+                target_id: 42,
+                ..assassination()
+            },
+            PlotError::InvalidTarget,
+        ),
+        (
+            Plot {
+                guard_id: 0,
+                ..assassination()
+            },
+            PlotError::InvalidGuard,
+        ),
+        (
+            Plot {
+                guard_id: 4,
+                ..defend
+            },
+            PlotError::DefendGuardMismatch,
+        ),
+        (
+            Plot {
+                secret_key: Field::from(0u64),
+                ..assassination()
+            },
+            PlotError::ZeroSecret,
+        ),
+        (
+            Plot {
+                target_dynasty: 6,
+                ..assassination()
+            },
+            PlotError::InvalidDynasty,
+        ),
+        (
+            Plot {
+                betrayal: true,
+                ..defend
+            },
+            PlotError::DefendBetrayal,
+        ),
+    ];
+    for (plot, error) in cases {
+        assert_eq!(plot.validate(), Err(error));
+        assert_eq!(plot.prover_toml(), Err(error));
+    }
+    assert_eq!(
+        PlotError::DefendGuardMismatch.to_string(),
+        "Defend must guard its own target"
+    );
+}
+
+#[test]
+fn test_prover_toml() {
+    assert_eq!(
+        assassination().prover_toml().unwrap(),
+        "target_id = \"1\"\n\
+         secret_key = \"0x0000000000000000000000000000000000000000000000000000000000003039\"\n\
+         action_type = \"0\"\n\
+         guard_id = \"4\"\n\
+         spend = \"3\"\n\
+         target_dynasty = \"1\"\n\
+         betrayal = \"0\"\n\
+         action_commitment = \
+         \"0x12266d1eba5f91aaf480b9c8b4831ba67c40525c0dca556a887fe76a5cd1c24e\"\n"
+    );
+}
+
+#[test]
+fn test_parse_field() {
+    assert_eq!(parse_field("12345"), Some(Field::from(12345u64)));
+    assert_eq!(parse_field("0x3039"), Some(Field::from(12345u64)));
+    assert_eq!(parse_field(""), None);
+    assert_eq!(parse_field("0x"), None);
+    assert_eq!(parse_field("12ab"), None);
+
+    // The modulus itself is out of range, one below it is the largest field element
+    let modulus = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000001";
+    let largest = "0x30644e72e131a029b85045b68181585d2833e84879b9709143e1f593f0000000";
+    assert_eq!(parse_field(modulus), None);
+    assert_eq!(parse_field(largest), Some(-Field::from(1u64)));
+}
@@ -0,0 +1,47 @@
+//! Dice Duel rolls.
+//!
+//! Both players' dice in a round are drawn from the round seed, the session
+//! material and the round with nothing appended. `Scoring::Pig` resolves every
+//! roll on its own, so each roll seed appends the number of rolls made so far
+//! this round, this one included, then the secrets player 1 and player 2
+//! revealed for the roll. Die `index` of `player` (both counted from 1) is
+//! drawn from `keccak256(seed || [player, index])`.
+
+use crate::{draw, sub_seed, Keccak256, Session};
+
+/// Seed of a round's simultaneous rolls
+pub fn round_seed(keccak: &impl Keccak256, session: &Session, round: u32) -> [u8; 32] {
+    session.seed(keccak, round, &[])
+}
+
+/// Seed of the `roll`-th `Scoring::Pig` roll of a round, counting from 1,
+/// from the secrets both players revealed for it (player 1's first)
+pub fn pig_roll_seed(
+    keccak: &impl Keccak256,
+    session: &Session,
+    round: u32,
+    roll: u32,
+    secrets: [&[u8; 32]; 2],
+) -> [u8; 32] {
+    let mut extra = [0u8; 68];
+    extra[..4].copy_from_slice(&roll.to_be_bytes());
+    extra[4..36].copy_from_slice(secrets[0]);
+    extra[36..].copy_from_slice(secrets[1]);
+    session.seed(keccak, round, &extra)
+}
+
+/// One die, `1..=sides`
+pub fn die(keccak: &impl Keccak256, seed: &[u8; 32], player: u8, index: u8, sides: u32) -> u32 {
+    draw(&sub_seed(keccak, seed, [player, index]), 1, sides)
+}
+
+/// All `dice` dice `player` (1 or 2) rolls from a seed, in order
+pub fn roll<'a, K: Keccak256>(
+    keccak: &'a K,
+    seed: &'a [u8; 32],
+    player: u8,
+    dice: u32,
+    sides: u32,
+) -> impl Iterator<Item = u32> + 'a {
+    (1..=dice).map(move |index| die(keccak, seed, player, index as u8, sides))
+}
@@ -0,0 +1,155 @@
+#![cfg(test)]
+
+//! Draws, seed preimages and the per-game derivations. The contracts' own tests
+//! check their outcomes against this crate across many sessions.
+
+extern crate std;
+
+use std::vec::Vec;
+
+use crate::{dice_duel, draw, number_guess, twenty_one, Keccak256, NativeKeccak, Session};
+
+const PLAYER1: &[u8] = b"GBZXN7PIRZGNMHGA7MUUUF4GWPY5AYPV6LY4UV2GL6VJGIQRXFDNMADI";
+const PLAYER2: &[u8] = b"GCEZWKCA5VLDNRLN3RPRJMRZOX3Z6G5CHCGSNFHEYVXM3XOJMDS674JZ";
+
+fn session() -> Session<'static> {
+    Session {
+        session_id: 7,
+        player1: PLAYER1,
+        player2: PLAYER2,
+    }
+}
+
+fn hex(bytes: &[u8]) -> std::string::String {
+    bytes
+        .iter()
+        .map(|byte| std::format!("{byte:02x}"))
+        .collect()
+}
+
+#[test]
+fn test_native_keccak() {
+    assert_eq!(
+        hex(&NativeKeccak.hash(&[])),
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+    );
+    // Parts are hashed as one concatenated message
+    assert_eq!(
+        NativeKeccak.hash(&[b"ab", b"", b"c"]),
+        NativeKeccak.hash(&[b"abc"])
+    );
+    assert_eq!(
+        hex(&NativeKeccak.hash(&[b"abc"])),
+        "4e03657aea45a94fc7d47ba826c8d667c0d1e6e33a64a036ec44f58fa12d6c45"
+    );
+}
+
+#[test]
+fn test_draw_bounds() {
+    assert_eq!(draw(&[0; 32], 1, 6), 1);
+    assert_eq!(draw(&[0xff; 32], 1, 6), 6);
+    assert_eq!(draw(&[0xab; 32], 9, 9), 9);
+    assert_eq!(draw(&[0xff; 32], 0, u32::MAX), u32::MAX);
+    // Only the first eight bytes count
+    let mut seed = [0u8; 32];
+    seed[8..].fill(0xff);
+    assert_eq!(draw(&seed, 1, 100), 1);
+}
+
+#[test]
+#[should_panic(expected = "empty draw range")]
+fn test_draw_rejects_empty_range() {
+    draw(&[0; 32], 2, 1);
+}
+
+#[test]
+fn test_draw_is_uniform() {
+    let mut counts = [0u32; 6];
+    for counter in 0u32..6_000 {
+        let seed = NativeKeccak.hash(&[&counter.to_be_bytes()]);
+        counts[draw(&seed, 1, 6) as usize - 1] += 1;
+    }
+    for count in counts {
+        assert!((850..=1_150).contains(&count), "{counts:?}");
+    }
+}
+
+#[test]
+fn test_session_seed_preimage() {
+    let mut preimage = Vec::new();
+    preimage.extend_from_slice(&7u32.to_be_bytes());
+    preimage.extend_from_slice(PLAYER1);
+    preimage.extend_from_slice(PLAYER2);
+    preimage.extend_from_slice(&3u32.to_be_bytes());
+    preimage.extend_from_slice(&[1, 2]);
+    assert_eq!(
+        session().seed(&NativeKeccak, 3, &[1, 2]),
+        NativeKeccak.hash(&[&preimage])
+    );
+
+    // Every input moves the seed, including which player is which
+    let base = session().seed(&NativeKeccak, 3, &[]);
+    let swapped = Session {
+        player1: PLAYER2,
+        player2: PLAYER1,
+        ..session()
+    };
+    let next = Session {
+        session_id: 8,
+        ..session()
+    };
+    for other in [
+        swapped.seed(&NativeKeccak, 3, &[]),
+        next.seed(&NativeKeccak, 3, &[]),
+        session().seed(&NativeKeccak, 4, &[]),
+        session().seed(&NativeKeccak, 3, &[0]),
+    ] {
+        assert_ne!(base, other);
+    }
+}
+
+#[test]
+fn test_dice_duel_rolls() {
+    let seed = dice_duel::round_seed(&NativeKeccak, &session(), 1);
+    assert_eq!(seed, session().seed(&NativeKeccak, 1, &[]));
+    let pig_seed = |secrets| dice_duel::pig_roll_seed(&NativeKeccak, &session(), 1, 1, secrets);
+    assert_ne!(seed, pig_seed([&[1; 32], &[2; 32]]));
+    assert_ne!(pig_seed([&[1; 32], &[2; 32]]), pig_seed([&[2; 32], &[1; 32]]));
+    assert_ne!(pig_seed([&[1; 32], &[2; 32]]), pig_seed([&[1; 32], &[3; 32]]));
+
+    let player1: Vec<u32> = dice_duel::roll(&NativeKeccak, &seed, 1, 10, 100).collect();
+    let player2: Vec<u32> = dice_duel::roll(&NativeKeccak, &seed, 2, 10, 100).collect();
+    assert_eq!(player1.len(), 10);
+    assert!(player1
+        .iter()
+        .chain(&player2)
+        .all(|face| (1..=100).contains(face)));
+    assert_ne!(player1, player2);
+    assert_eq!(player1[2], dice_duel::die(&NativeKeccak, &seed, 1, 3, 100));
+}
+
+#[test]
+fn test_number_guess_depends_on_both_guesses() {
+    let seed = number_guess::round_seed(&NativeKeccak, &session(), 1, 3, 8);
+    assert_ne!(
+        seed,
+        number_guess::round_seed(&NativeKeccak, &session(), 1, 8, 3)
+    );
+    assert_ne!(
+        seed,
+        number_guess::round_seed(&NativeKeccak, &session(), 1, 3, 9)
+    );
+    assert!((1..=10).contains(&number_guess::winning_number(&seed, 1, 10)));
+}
+
+#[test]
+fn test_twenty_one_hits_extend_the_deal() {
+    let seed = twenty_one::deal_seed(&NativeKeccak, &session(), 1);
+    let dealt: Vec<u8> =
+        twenty_one::hand(&NativeKeccak, &seed, 1, twenty_one::OPENING_CARDS).collect();
+    let hit: Vec<u8> = twenty_one::hand(&NativeKeccak, &seed, 1, 5).collect();
+    assert_eq!(dealt[..], hit[..2]);
+    assert_eq!(hit[4], twenty_one::card(&NativeKeccak, &seed, 1, 4));
+    assert!(hit.iter().all(|card| (1..=13).contains(card)));
+    assert_ne!(seed, twenty_one::deal_seed(&NativeKeccak, &session(), 2));
+}
@@ -0,0 +1,94 @@
+#![no_std]
+
+//! Provably-fair outcomes for Dice Duel, Number Guess and Twenty-One.
+//!
+//! Every random outcome of those games is a pure function of public seed
+//! material: the session id, both players' addresses, the round and a few
+//! game-specific values, hashed with keccak256. Dice Duel's `Pig` rolls also
+//! mix in a secret from each player, public once revealed (see `dice_duel`).
+//! The contracts derive their dice, winning numbers and cards through this
+//! crate, hashing on the host; auditors build it natively (the default `native`
+//! feature hashes with `sha3`) and recompute the same outcomes from each
+//! contract's `get_seed_material`.
+//!
+//! Seed preimages concatenate, in order: the session id, player 1's and player
+//! 2's addresses as strkey text, the round, then the game-specific values.
+//! Integers are big-endian.
+
+pub mod dice_duel;
+pub mod number_guess;
+pub mod twenty_one;
+
+#[cfg(all(test, feature = "native"))]
+mod fair_test;
+
+/// Longest strkey text of any Stellar address (a muxed account)
+pub const MAX_STRKEY_LEN: usize = 69;
+
+/// A keccak256 implementation: the host's in a contract, `NativeKeccak` outside
+pub trait Keccak256 {
+    /// keccak256 of the concatenation of `parts`
+    fn hash(&self, parts: &[&[u8]]) -> [u8; 32];
+}
+
+/// keccak256 from the `sha3` crate
+#[cfg(feature = "native")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NativeKeccak;
+
+#[cfg(feature = "native")]
+impl Keccak256 for NativeKeccak {
+    fn hash(&self, parts: &[&[u8]]) -> [u8; 32] {
+        use sha3::Digest;
+
+        let mut hasher = sha3::Keccak256::new();
+        for part in parts {
+            hasher.update(part);
+        }
+        hasher.finalize().into()
+    }
+}
+
+/// The seed material shared by every outcome of a session
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Session<'a> {
+    pub session_id: u32,
+    /// Player 1's address as strkey text (`G...` or `C...`)
+    pub player1: &'a [u8],
+    /// Player 2's address as strkey text
+    pub player2: &'a [u8],
+}
+
+impl Session<'_> {
+    /// keccak256 of the session material, the round and `extra`
+    pub fn seed(&self, keccak: &impl Keccak256, round: u32, extra: &[u8]) -> [u8; 32] {
+        keccak.hash(&[
+            &self.session_id.to_be_bytes(),
+            self.player1,
+            self.player2,
+            &round.to_be_bytes(),
+            extra,
+        ])
+    }
+}
+
+/// A value in `low..=high` drawn from a seed: its first eight bytes, read as a
+/// big-endian integer, are scaled onto the range. The bias is below
+/// `(high - low + 1) / 2^64`, negligible for any range of u32 values.
+///
+/// # Panics
+/// If `low > high`
+pub fn draw(seed: &[u8; 32], low: u32, high: u32) -> u32 {
+    assert!(low <= high, "empty draw range");
+    let span = u128::from(high - low) + 1;
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&seed[..8]);
+    let value = u128::from(u64::from_be_bytes(word));
+    low + ((value * span) >> 64) as u32
+}
+
+/// keccak256 of a seed followed by two index bytes, for outcomes drawn several
+/// times from one seed (each die of a roll, each card of a hand)
+fn sub_seed(keccak: &impl Keccak256, seed: &[u8; 32], indices: [u8; 2]) -> [u8; 32] {
+    keccak.hash(&[seed, &indices])
+}
@@ -0,0 +1,26 @@
+//! Number Guess winning numbers.
+//!
+//! The number is drawn only once both guesses are in, and the round seed appends
+//! both guesses to the session material and the round, so neither player can
+//! steer it alone.
+
+use crate::{draw, Keccak256, Session};
+
+/// Seed of a round, given both guesses
+pub fn round_seed(
+    keccak: &impl Keccak256,
+    session: &Session,
+    round: u32,
+    guess1: u32,
+    guess2: u32,
+) -> [u8; 32] {
+    let mut guesses = [0u8; 8];
+    guesses[..4].copy_from_slice(&guess1.to_be_bytes());
+    guesses[4..].copy_from_slice(&guess2.to_be_bytes());
+    session.seed(keccak, round, &guesses)
+}
+
+/// The round's winning number in the session's range `min..=max`
+pub fn winning_number(seed: &[u8; 32], min: u32, max: u32) -> u32 {
+    draw(seed, min, max)
+}
@@ -0,0 +1,32 @@
+//! Twenty-One cards.
+//!
+//! Each round deals from its own deck seed, the session material and the round
+//! with nothing appended. Card `index` of `player`'s hand (index from 0, player 1
+//! or 2) is drawn from `keccak256(seed || [index, player])`: the opening deal is
+//! cards 0 and 1, and every hit takes the next index. A hand is therefore fixed
+//! by the round and its length.
+
+use crate::{draw, sub_seed, Keccak256, Session};
+
+/// Cards in a hand at the deal
+pub const OPENING_CARDS: u8 = 2;
+
+/// Deck seed of a round
+pub fn deal_seed(keccak: &impl Keccak256, session: &Session, round: u32) -> [u8; 32] {
+    session.seed(keccak, round, &[])
+}
+
+/// Card `index` of `player`'s hand, from 1 (Ace) to 13 (King)
+pub fn card(keccak: &impl Keccak256, seed: &[u8; 32], player: u8, index: u8) -> u8 {
+    draw(&sub_seed(keccak, seed, [index, player]), 1, 13) as u8
+}
+
+/// The first `len` cards of `player`'s hand, in the order they were dealt
+pub fn hand<'a, K: Keccak256>(
+    keccak: &'a K,
+    seed: &'a [u8; 32],
+    player: u8,
+    len: u8,
+) -> impl Iterator<Item = u8> + 'a {
+    (0..len).map(move |index| card(keccak, seed, player, index))
+}
@@ -0,0 +1,141 @@
+//! Dice Duel in press-your-luck (`Pig`) mode, the only mode with decisions:
+//! on each turn a player rolls until they bank or roll a 1. Every roll is a
+//! commit-reveal: both players commit to a random secret, then reveal it.
+
+use std::fmt;
+
+use dice_duel::{DiceDuelContract, DiceDuelContractClient, DiceRules, Scoring};
+use soroban_sdk::{Address, BytesN};
+
+use crate::sim::{Options, Outcome, Simulation, Table};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// Keep rolling until the turn total reaches the threshold, then bank
+    HoldAt(u32),
+    /// Bank after each scoring roll with a one-in-three chance
+    Random,
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::HoldAt(threshold) => write!(f, "hold-at-{threshold}"),
+            Strategy::Random => write!(f, "random"),
+        }
+    }
+}
+
+impl Strategy {
+    /// Whether to bank with `turn_total` on the table and `banked` points
+    /// already safe; banking enough to win is always taken
+    fn banks(&self, turn_total: u32, banked: u32, target: u32, table: &mut Table) -> bool {
+        if banked + turn_total >= target {
+            return true;
+        }
+        match self {
+            Strategy::HoldAt(threshold) => turn_total >= *threshold,
+            Strategy::Random => table.rng.below(3) == 0,
+        }
+    }
+}
+
+/// Make the next roll: both players commit to a fresh secret, then reveal it
+fn roll(table: &mut Table, client: &DiceDuelContractClient, session_id: u32) {
+    let env = table.env.clone();
+    let players: [Address; 2] = [table.player1.clone(), table.player2.clone()];
+    let secrets = [(); 2].map(|_| {
+        let mut secret = [0u8; 32];
+        for chunk in secret.chunks_mut(8) {
+            chunk.copy_from_slice(&table.rng.next_u64().to_be_bytes());
+        }
+        BytesN::from_array(&env, &secret)
+    });
+    for (player, secret) in players.iter().zip(&secrets) {
+        let hash: BytesN<32> = env.crypto().keccak256(&secret.clone().into()).into();
+        table.call(|| client.commit_roll(&session_id, player, &hash));
+    }
+    for (player, secret) in players.iter().zip(&secrets) {
+        table.call(|| client.reveal_roll(&session_id, player, secret));
+    }
+}
+
+pub struct DiceDuel;
+
+impl Simulation for DiceDuel {
+    const NAME: &'static str = "dice-duel";
+
+    type Strategy = Strategy;
+
+    fn default_strategies() -> Vec<Strategy> {
+        vec![
+            Strategy::HoldAt(10),
+            Strategy::HoldAt(15),
+            Strategy::HoldAt(20),
+            Strategy::HoldAt(25),
+            Strategy::Random,
+        ]
+    }
+
+    fn parse_strategy(name: &str) -> Option<Strategy> {
+        match name {
+            "random" => Some(Strategy::Random),
+            _ => {
+                let threshold = name.strip_prefix("hold-at-")?.parse().ok()?;
+                (threshold > 0).then_some(Strategy::HoldAt(threshold))
+            }
+        }
+    }
+
+    fn play(
+        table: &mut Table,
+        session_id: u32,
+        strategies: [Strategy; 2],
+        options: &Options,
+    ) -> Outcome {
+        let env = table.env.clone();
+        let game_addr = env.register(DiceDuelContract, (&table.admin, &table.hub));
+        let client = DiceDuelContractClient::new(&env, &game_addr);
+        let (player1, player2) = (table.player1.clone(), table.player2.clone());
+        let points = 100_0000000;
+        let best_of = Some(options.best_of);
+        let rules = Some(DiceRules {
+            dice: 1,
+            sides: 6,
+            scoring: Scoring::Pig,
+            target: options.pig_target,
+        });
+        table.call(|| {
+            client.start_game(&session_id, &player1, &player2, &points, &points, &best_of, &rules)
+        });
+
+        let mut turns = 0;
+        loop {
+            let game = client.get_game(&session_id);
+            if let Some(winner) = game.winner {
+                return Outcome {
+                    winner: Some(if winner == player1 { 0 } else { 1 }),
+                    tied: false,
+                    rounds: turns,
+                };
+            }
+
+            let seat = if game.player1_turn { 0 } else { 1 };
+            let player = table.player(seat).clone();
+            turns += 1;
+            // Roll until the turn passes, by a bust or a bank
+            loop {
+                roll(table, &client, session_id);
+                let game = client.get_game(&session_id);
+                if game.turn_total == 0 {
+                    break;
+                }
+                let banked = if seat == 0 { game.player1_banked } else { game.player2_banked };
+                if strategies[seat].banks(game.turn_total, banked, game.rules.target, table) {
+                    table.call(|| client.bank(&session_id, &player));
+                    break;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,6 @@
+//! Game drivers and their strategies, one module per contract.
+
+pub mod dice_duel;
+pub mod number_guess;
+pub mod stellar_dynasties;
+pub mod twenty_one;
@@ -0,0 +1,108 @@
+//! Number Guess: each round both players guess, then the number is drawn.
+
+use std::fmt;
+
+use number_guess::{NumberGuessContract, NumberGuessContractClient};
+
+use crate::sim::{Options, Outcome, Simulation, Table};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// Always guess the lowest number of the range
+    Low,
+    /// Always guess the middle of the range
+    Middle,
+    /// Always guess the highest number of the range
+    High,
+    /// Always guess the same number (clamped into the range)
+    Fixed(u32),
+    /// Guess uniformly at random
+    Random,
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Low => write!(f, "low"),
+            Strategy::Middle => write!(f, "middle"),
+            Strategy::High => write!(f, "high"),
+            Strategy::Fixed(guess) => write!(f, "fixed-{guess}"),
+            Strategy::Random => write!(f, "random"),
+        }
+    }
+}
+
+impl Strategy {
+    fn guess(&self, min: u32, max: u32, table: &mut Table) -> u32 {
+        match self {
+            Strategy::Low => min,
+            Strategy::Middle => min + (max - min) / 2,
+            Strategy::High => max,
+            Strategy::Fixed(guess) => (*guess).clamp(min, max),
+            Strategy::Random => table.rng.range(min, max),
+        }
+    }
+}
+
+pub struct NumberGuess;
+
+impl Simulation for NumberGuess {
+    const NAME: &'static str = "number-guess";
+
+    type Strategy = Strategy;
+
+    fn default_strategies() -> Vec<Strategy> {
+        vec![Strategy::Low, Strategy::Middle, Strategy::High, Strategy::Random]
+    }
+
+    fn parse_strategy(name: &str) -> Option<Strategy> {
+        match name {
+            "low" => Some(Strategy::Low),
+            "middle" => Some(Strategy::Middle),
+            "high" => Some(Strategy::High),
+            "random" => Some(Strategy::Random),
+            _ => Some(Strategy::Fixed(name.strip_prefix("fixed-")?.parse().ok()?)),
+        }
+    }
+
+    fn play(
+        table: &mut Table,
+        session_id: u32,
+        strategies: [Strategy; 2],
+        options: &Options,
+    ) -> Outcome {
+        let env = table.env.clone();
+        let game_addr = env.register(NumberGuessContract, (&table.admin, &table.hub));
+        let client = NumberGuessContractClient::new(&env, &game_addr);
+        let (player1, player2) = (table.player1.clone(), table.player2.clone());
+        let points = 100_0000000;
+        let best_of = Some(options.best_of);
+        table.call(|| {
+            client.start_game(&session_id, &player1, &player2, &points, &points, &best_of, &None)
+        });
+
+        let rules = client.get_game(&session_id).rules;
+        loop {
+            for (seat, strategy) in strategies.iter().enumerate() {
+                let player = table.player(seat).clone();
+                let guess = strategy.guess(rules.min, rules.max, table);
+                table.call(|| client.make_guess(&session_id, &player, &guess));
+            }
+            table.call(|| client.reveal_winner(&session_id, &player1));
+
+            let game = client.get_game(&session_id);
+            if let Some(winner) = game.winner {
+                // Equally close guesses go to player 1
+                let tied = game.rounds.iter().any(|round| {
+                    round.player1_guess.abs_diff(round.winning_number)
+                        == round.player2_guess.abs_diff(round.winning_number)
+                });
+                return Outcome {
+                    winner: Some(if winner == player1 { 0 } else { 1 }),
+                    tied,
+                    rounds: game.rounds.len(),
+                };
+            }
+        }
+    }
+}
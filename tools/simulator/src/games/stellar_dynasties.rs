@@ -0,0 +1,165 @@
+//! Stellar Dynasties: each round both dynasties commit and reveal a plot, then
+//! the round is resolved. Plots are committed with the same Pedersen hash the
+//! contract checks them against at reveal.
+
+use std::fmt;
+
+use soroban_sdk::BytesN;
+use stellar_dynasties::{
+    pedersen::plot_commitment,
+    Dynasty, GameConfig, PlotAction, RevealedPlot, StellarDynasties as StellarDynastiesContract,
+    StellarDynastiesClient,
+};
+
+use crate::sim::{Options, Outcome, Simulation, Table};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// Play the same action every round
+    Always(u32),
+    /// Pick an action uniformly at random each round
+    Random,
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Always(action) => write!(f, "{}", ACTION_NAMES[*action as usize]),
+            Strategy::Random => write!(f, "random"),
+        }
+    }
+}
+
+/// Strategy names of the actions, by `PlotAction` value
+const ACTION_NAMES: [&str; 4] = ["assassination", "bribery", "rebellion", "defend"];
+
+impl Strategy {
+    fn action(&self, table: &mut Table) -> u32 {
+        match self {
+            Strategy::Always(action) => *action,
+            Strategy::Random => table.rng.below(ACTION_NAMES.len() as u32),
+        }
+    }
+}
+
+/// A uniformly chosen intact holding of `dynasty`; dynasties without one are
+/// eliminated before they could plot again
+fn intact_holding(dynasty: &Dynasty, table: &mut Table) -> u32 {
+    let intact: Vec<u32> = dynasty
+        .holdings
+        .iter()
+        .filter(|holding| holding.intact)
+        .map(|holding| holding.target)
+        .collect();
+    intact[table.rng.below(intact.len() as u32) as usize]
+}
+
+pub struct StellarDynasties;
+
+impl Simulation for StellarDynasties {
+    const NAME: &'static str = "stellar-dynasties";
+
+    type Strategy = Strategy;
+
+    fn default_strategies() -> Vec<Strategy> {
+        vec![
+            Strategy::Always(PlotAction::Assassination as u32),
+            Strategy::Always(PlotAction::Bribery as u32),
+            Strategy::Always(PlotAction::Rebellion as u32),
+            Strategy::Always(PlotAction::Defend as u32),
+            Strategy::Random,
+        ]
+    }
+
+    fn parse_strategy(name: &str) -> Option<Strategy> {
+        if name == "random" {
+            return Some(Strategy::Random);
+        }
+        let action = ACTION_NAMES.iter().position(|action| *action == name)?;
+        Some(Strategy::Always(action as u32))
+    }
+
+    fn play(
+        table: &mut Table,
+        session_id: u32,
+        strategies: [Strategy; 2],
+        _options: &Options,
+    ) -> Outcome {
+        let env = table.env.clone();
+        let game_addr = env.register(
+            StellarDynastiesContract,
+            (&table.admin, &table.hub, None::<GameConfig>),
+        );
+        let client = StellarDynastiesClient::new(&env, &game_addr);
+        let (player1, player2) = (table.player1.clone(), table.player2.clone());
+        let points = 100_0000000;
+        table.call(|| {
+            client.start_session(&session_id, &player1, &player2, &points, &points, &None)
+        });
+
+        // Each seat commits with its own fixed secret key
+        let secrets = [[1u8; 32], [2u8; 32]];
+        loop {
+            let game = client.get_game(&session_id);
+            if game.ended {
+                let first = game.players.get_unchecked(0);
+                let second = game.players.get_unchecked(1);
+                // Ranking falls back to seat order when survival and prestige
+                // are level
+                let tied = first.eliminated_in == second.eliminated_in
+                    && first.prestige == second.prestige;
+                let winner = game.winner.expect("ended game has a winner");
+                return Outcome {
+                    winner: Some(if winner == player1 { 0 } else { 1 }),
+                    tied,
+                    rounds: game.rounds.len(),
+                };
+            }
+
+            // Both plots are committed before either is revealed
+            let mut reveals = Vec::with_capacity(strategies.len());
+            for (seat, strategy) in strategies.iter().enumerate() {
+                let player = table.player(seat).clone();
+                let own = game.players.get_unchecked(seat as u32);
+                let rival = game.players.get_unchecked(1 - seat as u32);
+                let action = strategy.action(table);
+                let (target_dynasty, target, guard) = if action == PlotAction::Defend as u32 {
+                    let holding = intact_holding(&own, table);
+                    (seat as u32, holding, holding)
+                } else {
+                    (1 - seat as u32, intact_holding(&rival, table), intact_holding(&own, table))
+                };
+                // Commit half of the action's resource, at least the minimum
+                let resources = &own.resources;
+                let stock = match action {
+                    1 => resources.gold,
+                    2 => resources.troops,
+                    _ => resources.influence,
+                };
+                let spend = (stock / 2).max(game.config.min_plot_spend);
+
+                let secret = secrets[seat];
+                let hash = plot_commitment(
+                    target, &secret, action, guard, spend, target_dynasty, false,
+                )
+                .expect("secret keys are field elements");
+                let (hash, secret) =
+                    (BytesN::from_array(&env, &hash), BytesN::from_array(&env, &secret));
+                table.call(|| client.commit_plot(&session_id, &player, &hash));
+                let plot = RevealedPlot {
+                    action,
+                    target_dynasty,
+                    target,
+                    guard,
+                    spend,
+                    betrayal: false,
+                };
+                reveals.push((player, plot, secret));
+            }
+            for (player, plot, secret) in &reveals {
+                table.call(|| client.verify_plot(&session_id, player, plot, secret));
+            }
+            table.call(|| client.resolve_round(&session_id, &player1));
+        }
+    }
+}
@@ -0,0 +1,129 @@
+//! Twenty-One: each player draws until their strategy sticks or they bust.
+
+use std::fmt;
+
+use twenty_one::{TwentyOneContract, TwentyOneContractClient};
+
+use crate::sim::{Options, Outcome, Simulation, Table};
+
+/// Hands dealt before the simulator gives up on repeated draws
+const MAX_DEALS: u32 = 50;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Strategy {
+    /// Hit while the hand is worth less than the threshold, then stick
+    HitUnder(u32),
+    /// Hit or stick on a coin flip while under 21
+    Random,
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::HitUnder(0) => write!(f, "stick"),
+            Strategy::HitUnder(threshold) => write!(f, "hit-under-{threshold}"),
+            Strategy::Random => write!(f, "random"),
+        }
+    }
+}
+
+impl Strategy {
+    fn hits(&self, value: u32, table: &mut Table) -> bool {
+        match self {
+            Strategy::HitUnder(threshold) => value < *threshold,
+            Strategy::Random => value < 21 && table.rng.coin(),
+        }
+    }
+}
+
+pub struct TwentyOne;
+
+impl Simulation for TwentyOne {
+    const NAME: &'static str = "twenty-one";
+
+    type Strategy = Strategy;
+
+    fn default_strategies() -> Vec<Strategy> {
+        vec![
+            Strategy::HitUnder(0),
+            Strategy::HitUnder(12),
+            Strategy::HitUnder(15),
+            Strategy::HitUnder(17),
+            Strategy::HitUnder(19),
+            Strategy::Random,
+        ]
+    }
+
+    fn parse_strategy(name: &str) -> Option<Strategy> {
+        match name {
+            "stick" => Some(Strategy::HitUnder(0)),
+            "random" => Some(Strategy::Random),
+            _ => {
+                let threshold = name.strip_prefix("hit-under-")?.parse().ok()?;
+                (1..=22).contains(&threshold).then_some(Strategy::HitUnder(threshold))
+            }
+        }
+    }
+
+    fn play(
+        table: &mut Table,
+        session_id: u32,
+        strategies: [Strategy; 2],
+        _options: &Options,
+    ) -> Outcome {
+        let env = table.env.clone();
+        let game_addr = env.register(TwentyOneContract, (&table.admin, &table.hub));
+        let client = TwentyOneContractClient::new(&env, &game_addr);
+        let (player1, player2) = (table.player1.clone(), table.player2.clone());
+        let points = 100_0000000;
+        table.call(|| client.start_game(&session_id, &player1, &player2, &points, &points));
+
+        let mut draws = 0;
+        loop {
+            // Player 1 plays out their hand first, then player 2
+            for (seat, strategy) in strategies.iter().enumerate() {
+                let player = table.player(seat).clone();
+                loop {
+                    let value = client.get_hand_value(&session_id, &player);
+                    if !strategy.hits(value, table) {
+                        table.call(|| client.stick(&session_id, &player));
+                        break;
+                    }
+                    table.call(|| client.hit(&session_id, &player));
+                    if let Some(winner) = client.get_game(&session_id).winner {
+                        // A bust ends the match on the spot
+                        let round = client.get_game(&session_id).round;
+                        return outcome(winner == player1, round);
+                    }
+                }
+            }
+
+            let round = client.get_game(&session_id).round;
+            match table.call(|| client.try_reveal_winner(&session_id, &player1)) {
+                Ok(Ok(Some(winner))) => return outcome(winner == player1, round),
+                Ok(Ok(None)) => {
+                    // A draw redeals both hands in a new round
+                    draws += 1;
+                    if draws >= MAX_DEALS {
+                        return Outcome {
+                            winner: None,
+                            tied: true,
+                            rounds: round,
+                        };
+                    }
+                }
+                other => panic!("twenty-one reveal failed: {other:?}"),
+            }
+        }
+    }
+}
+
+/// A decided match; equal hands are redealt rather than broken in anyone's
+/// favour, so only undecided matches count as ties
+fn outcome(player1_won: bool, rounds: u32) -> Outcome {
+    Outcome {
+        winner: Some(if player1_won { 0 } else { 1 }),
+        tied: false,
+        rounds,
+    }
+}
@@ -0,0 +1,134 @@
+//! Offline Monte-Carlo match simulator.
+//!
+//! Plays thousands of seeded matches between player strategies against the
+//! real game contracts, run in-process on the soroban-sdk test ledger, and
+//! reports win rates, tie rates, match length and resource use per pairing.
+
+mod games;
+mod report;
+mod sim;
+
+#[cfg(test)]
+mod test;
+
+use std::process::ExitCode;
+
+use games::{
+    dice_duel::DiceDuel, number_guess::NumberGuess, stellar_dynasties::StellarDynasties,
+    twenty_one::TwentyOne,
+};
+use report::{Format, Report};
+use sim::{Options, Simulation};
+
+const USAGE: &str = "\
+usage: simulator <game> [options]
+
+games:
+  twenty-one          strategies: stick, hit-under-N, random
+  number-guess        strategies: low, middle, high, fixed-N, random
+  dice-duel           strategies: hold-at-N, random (press-your-luck mode)
+  stellar-dynasties   strategies: assassination, bribery, rebellion, defend, random
+
+options:
+  --matches N         matches per strategy pairing (default 1000)
+  --seed S            seed for sessions and random strategies (default 1)
+  --strategies A,B    strategies to pit against each other (default: all listed)
+  --format F          json or csv (default json)
+  --best-of N         match length for number-guess and dice-duel (default 1)
+  --pig-target N      points to bank in dice-duel (default 50)";
+
+/// Parsed command line
+struct Args {
+    game: String,
+    strategies: Option<Vec<String>>,
+    format: Format,
+    options: Options,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+    let game = args.next().ok_or("missing game")?;
+    let mut parsed = Args {
+        game,
+        strategies: None,
+        format: Format::Json,
+        options: Options {
+            matches: 1000,
+            seed: 1,
+            best_of: 1,
+            pig_target: 50,
+        },
+    };
+
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or(format!("missing value for {flag}"))?;
+        let number = || value.parse::<u64>().map_err(|_| format!("invalid {flag}: {value}"));
+        match flag.as_str() {
+            "--matches" => parsed.options.matches = number()? as u32,
+            "--seed" => parsed.options.seed = number()?,
+            "--best-of" => parsed.options.best_of = number()? as u32,
+            "--pig-target" => parsed.options.pig_target = number()? as u32,
+            "--strategies" => {
+                parsed.strategies = Some(value.split(',').map(str::to_string).collect())
+            }
+            "--format" => {
+                parsed.format = match value.as_str() {
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    _ => return Err(format!("unknown format: {value}")),
+                }
+            }
+            _ => return Err(format!("unknown option: {flag}")),
+        }
+    }
+    Ok(parsed)
+}
+
+/// Run every pairing of the selected strategies of one game
+fn simulate<G: Simulation>(
+    strategies: Option<&[String]>,
+    options: &Options,
+) -> Result<Report, String> {
+    let strategies = match strategies {
+        Some(names) => names
+            .iter()
+            .map(|name| G::parse_strategy(name).ok_or(format!("unknown strategy: {name}")))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => G::default_strategies(),
+    };
+    Ok(Report {
+        game: G::NAME.to_string(),
+        matches_per_pairing: options.matches,
+        seed: options.seed,
+        pairings: sim::run::<G>(&strategies, options),
+    })
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let strategies = args.strategies.as_deref();
+    let options = &args.options;
+    let report = match args.game.as_str() {
+        TwentyOne::NAME => simulate::<TwentyOne>(strategies, options),
+        NumberGuess::NAME => simulate::<NumberGuess>(strategies, options),
+        DiceDuel::NAME => simulate::<DiceDuel>(strategies, options),
+        StellarDynasties::NAME => simulate::<StellarDynasties>(strategies, options),
+        game => Err(format!("unknown game: {game}")),
+    };
+    match report {
+        Ok(report) => {
+            print!("{}", report.render(args.format));
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{message}\n\n{USAGE}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,148 @@
+//! Per-pairing statistics and the JSON / CSV report formats.
+
+use serde::Serialize;
+
+use crate::sim::{Meter, Outcome};
+
+/// Results of one ordered strategy pairing
+#[derive(Clone, Debug, Default, PartialEq, Serialize)]
+pub struct PairingStats {
+    pub player1_strategy: String,
+    pub player2_strategy: String,
+    pub matches: u32,
+    pub player1_wins: u32,
+    pub player2_wins: u32,
+    /// Matches settled by a tie-break or left undecided
+    pub ties: u32,
+    pub player1_win_rate: f64,
+    pub player2_win_rate: f64,
+    pub tie_rate: f64,
+    pub avg_rounds: f64,
+    /// Contract calls per match
+    pub avg_calls: f64,
+    /// CPU instructions per match, over all its calls
+    pub avg_instructions: f64,
+    /// Memory bytes per match, over all its calls
+    pub avg_mem_bytes: f64,
+    /// Estimated fee per match, in stroops
+    pub avg_fee_stroops: f64,
+    /// Most expensive single call seen in any match
+    pub max_call_instructions: u64,
+    pub max_call_mem_bytes: u64,
+    #[serde(skip)]
+    totals: Totals,
+}
+
+/// Running sums, turned into averages by `finish`
+#[derive(Clone, Debug, Default, PartialEq)]
+struct Totals {
+    rounds: u64,
+    calls: u64,
+    instructions: u64,
+    mem_bytes: u64,
+    fee_stroops: i64,
+}
+
+impl PairingStats {
+    pub fn new(player1_strategy: String, player2_strategy: String) -> Self {
+        PairingStats {
+            player1_strategy,
+            player2_strategy,
+            ..Default::default()
+        }
+    }
+
+    /// Count one finished match
+    pub fn add(&mut self, outcome: &Outcome, meter: &Meter) {
+        self.matches += 1;
+        match outcome.winner {
+            Some(0) => self.player1_wins += 1,
+            Some(_) => self.player2_wins += 1,
+            None => {}
+        }
+        if outcome.tied {
+            self.ties += 1;
+        }
+        self.totals.rounds += u64::from(outcome.rounds);
+        self.totals.calls += u64::from(meter.calls);
+        self.totals.instructions += meter.instructions;
+        self.totals.mem_bytes += meter.mem_bytes;
+        self.totals.fee_stroops += meter.fee_stroops;
+        self.max_call_instructions = self.max_call_instructions.max(meter.max_call_instructions);
+        self.max_call_mem_bytes = self.max_call_mem_bytes.max(meter.max_call_mem_bytes);
+    }
+
+    /// Compute the rates and averages once every match was added
+    pub fn finish(mut self) -> Self {
+        let matches = f64::from(self.matches.max(1));
+        self.player1_win_rate = f64::from(self.player1_wins) / matches;
+        self.player2_win_rate = f64::from(self.player2_wins) / matches;
+        self.tie_rate = f64::from(self.ties) / matches;
+        self.avg_rounds = self.totals.rounds as f64 / matches;
+        self.avg_calls = self.totals.calls as f64 / matches;
+        self.avg_instructions = self.totals.instructions as f64 / matches;
+        self.avg_mem_bytes = self.totals.mem_bytes as f64 / matches;
+        self.avg_fee_stroops = self.totals.fee_stroops as f64 / matches;
+        self
+    }
+}
+
+/// A whole simulation run
+#[derive(Clone, Debug, Serialize)]
+pub struct Report {
+    pub game: String,
+    pub matches_per_pairing: u32,
+    pub seed: u64,
+    pub pairings: Vec<PairingStats>,
+}
+
+/// Output format selected with `--format`
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+const CSV_HEADER: &str = "game,player1_strategy,player2_strategy,matches,player1_wins,\
+player2_wins,ties,player1_win_rate,player2_win_rate,tie_rate,avg_rounds,avg_calls,\
+avg_instructions,avg_mem_bytes,avg_fee_stroops,max_call_instructions,max_call_mem_bytes";
+
+impl Report {
+    pub fn render(&self, format: Format) -> String {
+        match format {
+            Format::Json => {
+                serde_json::to_string_pretty(self).expect("report serializes to JSON") + "\n"
+            }
+            Format::Csv => self.to_csv(),
+        }
+    }
+
+    /// One row per pairing; strategy names never contain commas or quotes
+    fn to_csv(&self) -> String {
+        let mut csv = String::from(CSV_HEADER);
+        csv.push('\n');
+        for p in &self.pairings {
+            csv.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.4},{:.4},{:.4},{:.2},{:.2},{:.0},{:.0},{:.0},{},{}\n",
+                self.game,
+                p.player1_strategy,
+                p.player2_strategy,
+                p.matches,
+                p.player1_wins,
+                p.player2_wins,
+                p.ties,
+                p.player1_win_rate,
+                p.player2_win_rate,
+                p.tie_rate,
+                p.avg_rounds,
+                p.avg_calls,
+                p.avg_instructions,
+                p.avg_mem_bytes,
+                p.avg_fee_stroops,
+                p.max_call_instructions,
+                p.max_call_mem_bytes,
+            ));
+        }
+        csv
+    }
+}
@@ -0,0 +1,254 @@
+//! Match harness: a fresh in-process ledger per match, a mock GameHub, the
+//! resource meter and the seeded random source strategies draw from.
+
+use soroban_sdk::testutils::{Address as _, EnvTestConfig};
+use soroban_sdk::{contract, contractimpl, Address, Env};
+
+use crate::report::PairingStats;
+
+// ============================================================================
+// Mock GameHub
+// ============================================================================
+
+/// GameHub stand-in that accepts every session; the games only need the calls
+/// to succeed
+#[contract]
+pub struct MockGameHub;
+
+#[contractimpl]
+impl MockGameHub {
+    pub fn start_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _player1: Address,
+        _player2: Address,
+        _player1_points: i128,
+        _player2_points: i128,
+    ) {
+    }
+
+    pub fn end_game(_env: Env, _session_id: u32, _player1_won: bool) {}
+
+    pub fn start_multi_game(
+        _env: Env,
+        _game_id: Address,
+        _session_id: u32,
+        _players: soroban_sdk::Vec<Address>,
+        _points: soroban_sdk::Vec<i128>,
+    ) {
+    }
+
+    pub fn end_multi_game(
+        _env: Env,
+        _session_id: u32,
+        _ranking: soroban_sdk::Vec<Address>,
+        _payouts: soroban_sdk::Vec<i128>,
+    ) {
+    }
+}
+
+// ============================================================================
+// Seeded Randomness
+// ============================================================================
+
+/// SplitMix64: small, fast and fully determined by its seed, so a run can be
+/// repeated exactly from the command line
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `0..n` (`n` > 0)
+    pub fn below(&mut self, n: u32) -> u32 {
+        (self.next_u64() % u64::from(n)) as u32
+    }
+
+    /// Uniform value in `min..=max`
+    pub fn range(&mut self, min: u32, max: u32) -> u32 {
+        min + self.below(max - min + 1)
+    }
+
+    pub fn coin(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+}
+
+// ============================================================================
+// Resource Meter
+// ============================================================================
+
+/// Resources of every top-level contract call made during one match
+#[derive(Clone, Debug, Default)]
+pub struct Meter {
+    pub calls: u32,
+    pub instructions: u64,
+    pub mem_bytes: u64,
+    pub fee_stroops: i64,
+    /// Most expensive single call, which is what a transaction must fit
+    pub max_call_instructions: u64,
+    pub max_call_mem_bytes: u64,
+}
+
+impl Meter {
+    /// Add the resources of the call that just returned
+    pub fn record(&mut self, env: &Env) {
+        let estimate = env.cost_estimate();
+        let resources = estimate.resources();
+        let instructions = resources.instructions.max(0) as u64;
+        let mem_bytes = resources.mem_bytes.max(0) as u64;
+        self.calls += 1;
+        self.instructions += instructions;
+        self.mem_bytes += mem_bytes;
+        self.fee_stroops += estimate.fee().total;
+        self.max_call_instructions = self.max_call_instructions.max(instructions);
+        self.max_call_mem_bytes = self.max_call_mem_bytes.max(mem_bytes);
+    }
+}
+
+// ============================================================================
+// Match Table
+// ============================================================================
+
+/// Everything one match is played with
+pub struct Table {
+    pub env: Env,
+    pub hub: Address,
+    pub admin: Address,
+    pub player1: Address,
+    pub player2: Address,
+    pub meter: Meter,
+    pub rng: Rng,
+}
+
+impl Table {
+    /// A fresh ledger for one match. Addresses are generated the same way in
+    /// every fresh `Env`, so the players are identical from match to match.
+    pub fn new(rng_seed: u64) -> Self {
+        let env = Env::new_with_config(EnvTestConfig {
+            capture_snapshot_at_drop: false,
+        });
+        env.mock_all_auths();
+
+        let hub = env.register(MockGameHub, ());
+        let admin = Address::generate(&env);
+        let player1 = Address::generate(&env);
+        let player2 = Address::generate(&env);
+
+        Table {
+            env,
+            hub,
+            admin,
+            player1,
+            player2,
+            meter: Meter::default(),
+            rng: Rng::new(rng_seed),
+        }
+    }
+
+    /// Run one game call and meter it
+    pub fn call<T>(&mut self, call: impl FnOnce() -> T) -> T {
+        let result = call();
+        self.meter.record(&self.env);
+        result
+    }
+
+    /// Address playing in `seat` (0 = player 1)
+    pub fn player(&self, seat: usize) -> &Address {
+        if seat == 0 {
+            &self.player1
+        } else {
+            &self.player2
+        }
+    }
+}
+
+/// How a match ended
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Outcome {
+    /// Seat of the match winner (0 = player 1), `None` if the match could not
+    /// be decided
+    pub winner: Option<usize>,
+    /// The deciding comparison was level and the game's tie-break (or no
+    /// decision at all) settled the match
+    pub tied: bool,
+    /// Rounds or turns played, as the game counts them
+    pub rounds: u32,
+}
+
+// ============================================================================
+// Simulations
+// ============================================================================
+
+/// Settings shared by every game; each game uses the ones that apply to it
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub matches: u32,
+    pub seed: u64,
+    /// Match length for games with best-of-N matches
+    pub best_of: u32,
+    /// Points to bank in Dice Duel's press-your-luck mode
+    pub pig_target: u32,
+}
+
+/// A game the simulator can drive, with its player strategies
+pub trait Simulation {
+    /// Game name used on the command line
+    const NAME: &'static str;
+
+    type Strategy: Copy + std::fmt::Display;
+
+    /// Strategies pitted against each other when none are given
+    fn default_strategies() -> Vec<Self::Strategy>;
+
+    /// Parse a strategy name as printed by `Display`
+    fn parse_strategy(name: &str) -> Option<Self::Strategy>;
+
+    /// Play one match between two strategies (player 1 first)
+    fn play(
+        table: &mut Table,
+        session_id: u32,
+        strategies: [Self::Strategy; 2],
+        options: &Options,
+    ) -> Outcome;
+}
+
+/// Session id of the `index`th match of every pairing. Pairings reuse the same
+/// ids, so each strategy faces the same dealt cards, dice and draws.
+pub fn session_id(seed: u64, index: u32) -> u32 {
+    (seed as u32).wrapping_add(index)
+}
+
+/// Play `options.matches` matches for every ordered pairing of `strategies`,
+/// seating each strategy as player 1 and as player 2
+pub fn run<G: Simulation>(
+    strategies: &[G::Strategy],
+    options: &Options,
+) -> Vec<PairingStats> {
+    let mut pairings = Vec::new();
+    for (first, strategy1) in strategies.iter().enumerate() {
+        for (second, strategy2) in strategies.iter().enumerate() {
+            let mut stats = PairingStats::new(strategy1.to_string(), strategy2.to_string());
+            for index in 0..options.matches {
+                let pairing = (first * strategies.len() + second) as u64;
+                let rng_seed = options.seed ^ (pairing << 32) ^ u64::from(index);
+                let mut table = Table::new(rng_seed);
+                let session_id = session_id(options.seed, index);
+                let outcome = G::play(&mut table, session_id, [*strategy1, *strategy2], options);
+                stats.add(&outcome, &table.meter);
+            }
+            pairings.push(stats.finish());
+        }
+    }
+    pairings
+}
@@ -0,0 +1,199 @@
+#![cfg(test)]
+
+use crate::games::{
+    dice_duel::{self, DiceDuel},
+    number_guess::{self, NumberGuess},
+    stellar_dynasties::StellarDynasties,
+    twenty_one::{self, TwentyOne},
+};
+use crate::report::Format;
+use crate::sim::{Options, Simulation};
+use crate::{parse_args, simulate};
+
+fn options(matches: u32) -> Options {
+    Options {
+        matches,
+        seed: 7,
+        best_of: 1,
+        pig_target: 20,
+    }
+}
+
+fn args(line: &str) -> impl Iterator<Item = String> + '_ {
+    line.split_whitespace().map(str::to_string)
+}
+
+/// Every default strategy parses back from its printed name
+fn assert_names_round_trip<G: Simulation>()
+where
+    G::Strategy: PartialEq + std::fmt::Debug,
+{
+    for strategy in G::default_strategies() {
+        assert_eq!(G::parse_strategy(&strategy.to_string()), Some(strategy));
+    }
+    assert!(G::parse_strategy("no-such-strategy").is_none());
+}
+
+// ============================================================================
+// Strategy Tests
+// ============================================================================
+
+#[test]
+fn test_strategy_names_round_trip() {
+    assert_names_round_trip::<TwentyOne>();
+    assert_names_round_trip::<NumberGuess>();
+    assert_names_round_trip::<DiceDuel>();
+    assert_names_round_trip::<StellarDynasties>();
+
+    assert_eq!(
+        TwentyOne::parse_strategy("hit-under-16"),
+        Some(twenty_one::Strategy::HitUnder(16))
+    );
+    assert_eq!(TwentyOne::parse_strategy("hit-under-30"), None);
+    assert_eq!(NumberGuess::parse_strategy("fixed-42"), Some(number_guess::Strategy::Fixed(42)));
+    assert_eq!(DiceDuel::parse_strategy("hold-at-0"), None);
+    assert_eq!(DiceDuel::parse_strategy("hold-at-8"), Some(dice_duel::Strategy::HoldAt(8)));
+}
+
+// ============================================================================
+// Simulation Tests
+// ============================================================================
+
+/// Every pairing is played, and every decided match has exactly one winner
+fn assert_runs<G: Simulation>(strategies: &[&str], always_decided: bool) {
+    let names: Vec<String> = strategies.iter().map(|name| name.to_string()).collect();
+    let report = simulate::<G>(Some(&names), &options(3)).unwrap();
+
+    assert_eq!(report.game, G::NAME);
+    assert_eq!(report.pairings.len(), strategies.len() * strategies.len());
+    for pairing in &report.pairings {
+        assert_eq!(pairing.matches, 3);
+        let decided = pairing.player1_wins + pairing.player2_wins;
+        assert!(decided <= pairing.matches);
+        if always_decided {
+            assert_eq!(decided, pairing.matches);
+        }
+        assert!(pairing.avg_rounds >= 1.0);
+        assert!(pairing.avg_calls >= 1.0);
+        assert!(pairing.avg_instructions > 0.0);
+        assert!(pairing.max_call_instructions > 0);
+    }
+}
+
+#[test]
+fn test_every_game_runs() {
+    // Twenty-One can leave a match undecided when both hands are level
+    assert_runs::<TwentyOne>(&["stick", "hit-under-17"], false);
+    assert_runs::<NumberGuess>(&["middle", "random"], true);
+    assert_runs::<DiceDuel>(&["hold-at-5", "random"], true);
+    assert_runs::<StellarDynasties>(&["rebellion", "random"], true);
+}
+
+#[test]
+fn test_mirror_match_always_ties() {
+    // Identical guesses are always equally close
+    let names = vec!["low".to_string()];
+    let report = simulate::<NumberGuess>(Some(&names), &options(5)).unwrap();
+    let pairing = &report.pairings[0];
+    assert_eq!(pairing.ties, 5);
+    assert_eq!(pairing.tie_rate, 1.0);
+    // The tie-break favours player 1
+    assert_eq!(pairing.player1_wins, 5);
+}
+
+#[test]
+fn test_same_seed_same_report() {
+    let names = vec!["random".to_string(), "hit-under-15".to_string()];
+    let first = simulate::<TwentyOne>(Some(&names), &options(4)).unwrap();
+    let second = simulate::<TwentyOne>(Some(&names), &options(4)).unwrap();
+    assert_eq!(first.render(Format::Json), second.render(Format::Json));
+}
+
+#[test]
+fn test_unknown_strategy_rejected() {
+    let names = vec!["middle".to_string(), "psychic".to_string()];
+    let result = simulate::<NumberGuess>(Some(&names), &options(1));
+    assert_eq!(result.err(), Some("unknown strategy: psychic".to_string()));
+}
+
+// ============================================================================
+// Report Tests
+// ============================================================================
+
+#[test]
+fn test_csv_has_row_per_pairing() {
+    let names = vec!["high".to_string(), "middle".to_string()];
+    let report = simulate::<NumberGuess>(Some(&names), &options(2)).unwrap();
+    let csv = report.render(Format::Csv);
+    let lines: Vec<&str> = csv.lines().collect();
+
+    assert_eq!(lines.len(), 1 + 4);
+    let columns = lines[0].split(',').count();
+    assert!(lines.iter().all(|line| line.split(',').count() == columns));
+    assert!(lines[1].starts_with("number-guess,high,high,2,"));
+}
+
+#[test]
+fn test_json_report_fields() {
+    let names = vec!["middle".to_string()];
+    let report = simulate::<NumberGuess>(Some(&names), &options(2)).unwrap();
+    let json: serde_json::Value = serde_json::from_str(&report.render(Format::Json)).unwrap();
+
+    assert_eq!(json["game"], "number-guess");
+    assert_eq!(json["matches_per_pairing"], 2);
+    assert_eq!(json["seed"], 7);
+    let pairing = &json["pairings"][0];
+    assert_eq!(pairing["player1_strategy"], "middle");
+    assert_eq!(pairing["matches"], 2);
+    assert!(pairing.get("totals").is_none());
+}
+
+// ============================================================================
+// Command Line Tests
+// ============================================================================
+
+#[test]
+fn test_parse_args() {
+    let parsed = parse_args(args(
+        "dice-duel --matches 50 --seed 9 --strategies hold-at-20,random --format csv \
+         --best-of 3 --pig-target 100",
+    ))
+    .unwrap();
+    assert_eq!(parsed.game, "dice-duel");
+    assert_eq!(parsed.format, Format::Csv);
+    assert_eq!(
+        parsed.strategies,
+        Some(vec!["hold-at-20".to_string(), "random".to_string()])
+    );
+    assert_eq!(parsed.options.matches, 50);
+    assert_eq!(parsed.options.seed, 9);
+    assert_eq!(parsed.options.best_of, 3);
+    assert_eq!(parsed.options.pig_target, 100);
+
+    let defaults = parse_args(args("twenty-one")).unwrap();
+    assert_eq!(defaults.format, Format::Json);
+    assert_eq!(defaults.strategies, None);
+    assert_eq!(defaults.options.matches, 1000);
+    assert_eq!(defaults.options.seed, 1);
+}
+
+#[test]
+fn test_parse_args_errors() {
+    assert_eq!(parse_args(args("")).err(), Some("missing game".to_string()));
+    assert_eq!(
+        parse_args(args("twenty-one --matches")).err(),
+        Some("missing value for --matches".to_string())
+    );
+    assert_eq!(
+        parse_args(args("twenty-one --matches many")).err(),
+        Some("invalid --matches: many".to_string())
+    );
+    assert_eq!(
+        parse_args(args("twenty-one --format xml")).err(),
+        Some("unknown format: xml".to_string())
+    );
+    assert_eq!(
+        parse_args(args("twenty-one --verbose yes")).err(),
+        Some("unknown option: --verbose".to_string())
+    );
+}